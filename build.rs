@@ -1,4 +1,5 @@
-
 fn main() {
-    println!("cargo:rustc-link-lib=framework=CoreVideo");
+    if std::env::var("CARGO_CFG_TARGET_VENDOR").as_deref() == Ok("apple") {
+        println!("cargo:rustc-link-lib=framework=CoreVideo");
+    }
 }
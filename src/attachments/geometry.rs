@@ -0,0 +1,151 @@
+use crate::{cv_pixel_buffer::PixelRect, types::Rational};
+
+/// Width and height in pixels, which may be fractional. The pure Rust counterpart of the
+/// `CGSize` CoreVideo returns.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Size {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Size {
+    pub const fn new(width: f64, height: f64) -> Self {
+        Self { width, height }
+    }
+}
+
+/// Rectangle in pixels with the origin at the top-left corner of the image. The pure Rust
+/// counterpart of the `CGRect` CoreVideo returns.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub const fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+    /// The overlap of two rects, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let (x, y) = (self.x.max(other.x), self.y.max(other.y));
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+        (right > x && bottom > y).then(|| Rect::new(x, y, right - x, bottom - y))
+    }
+    /// Smallest whole-pixel rect containing this one, clamped to `width` x `height`.
+    pub fn to_pixel_rect(&self, width: usize, height: usize) -> PixelRect {
+        let edges = |start: f64, length: f64, size: usize| {
+            let end = (start + length).ceil().clamp(0.0, size as f64) as usize;
+            let start = start.floor().clamp(0.0, size as f64) as usize;
+            (start, end.saturating_sub(start))
+        };
+        let (x, rect_width) = edges(self.x, self.width, width);
+        let (y, rect_height) = edges(self.y, self.height, height);
+        PixelRect::new(x, y, rect_width, rect_height)
+    }
+}
+
+/// The clean aperture of an image, in pixels, with offsets of its center from the center of
+/// the encoded image. Positive offsets move it right and down.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CleanAperture {
+    pub width: Rational,
+    pub height: Rational,
+    pub horizontal_offset: Rational,
+    pub vertical_offset: Rational,
+}
+
+impl CleanAperture {
+    /// A `width` x `height` aperture centered in the encoded image.
+    pub fn centered(width: i32, height: i32) -> Self {
+        Self {
+            width: width.into(),
+            height: height.into(),
+            ..Default::default()
+        }
+    }
+    /// The aperture within an image of `encoded_size`, as `CVImageBufferGetCleanRect`
+    /// computes it.
+    pub fn clean_rect(&self, encoded_size: Size) -> Rect {
+        let (width, height) = (self.width.to_f64(), self.height.to_f64());
+        Rect::new(
+            (encoded_size.width - width) / 2.0 + self.horizontal_offset.to_f64(),
+            (encoded_size.height - height) / 2.0 + self.vertical_offset.to_f64(),
+            width,
+            height,
+        )
+    }
+    /// The aperture covering `rect` within an image of `encoded_size`; the inverse of
+    /// [`clean_rect`](Self::clean_rect).
+    pub fn from_clean_rect(rect: Rect, encoded_size: Size) -> Self {
+        Self {
+            width: Rational::from_f64(rect.width),
+            height: Rational::from_f64(rect.height),
+            horizontal_offset: Rational::from_f64(rect.x + (rect.width - encoded_size.width) / 2.0),
+            vertical_offset: Rational::from_f64(rect.y + (rect.height - encoded_size.height) / 2.0),
+        }
+    }
+    /// Smallest whole-pixel crop of a `width` x `height` image that contains the aperture.
+    pub fn crop_rect(&self, width: usize, height: usize) -> PixelRect {
+        self.clean_rect(Size::new(width as f64, height as f64))
+            .to_pixel_rect(width, height)
+    }
+}
+
+/// Pixel aspect ratio as horizontal:vertical spacing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PixelAspectRatio {
+    pub horizontal_spacing: u32,
+    pub vertical_spacing: u32,
+}
+
+impl PixelAspectRatio {
+    pub const SQUARE: Self = Self {
+        horizontal_spacing: 1,
+        vertical_spacing: 1,
+    };
+
+    pub fn is_square(&self) -> bool {
+        self.horizontal_spacing == self.vertical_spacing
+    }
+    /// Size of `size` in square pixels, stretching horizontally by the ratio.
+    pub fn display_size(&self, size: Size) -> Size {
+        if self.horizontal_spacing == 0 || self.vertical_spacing == 0 {
+            return size;
+        }
+        Size::new(
+            size.width * self.horizontal_spacing as f64 / self.vertical_spacing as f64,
+            size.height,
+        )
+    }
+}
+
+impl Default for PixelAspectRatio {
+    fn default() -> Self {
+        Self::SQUARE
+    }
+}
+
+/// Size the image should be displayed at, in square pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisplayDimensions {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl DisplayDimensions {
+    pub fn size(&self) -> Size {
+        Size::new(self.width as f64, self.height as f64)
+    }
+}
@@ -0,0 +1,265 @@
+use crate::cv_pixel_buffer::error::CVPixelBufferError;
+
+type Result<T, E = CVPixelBufferError> = std::result::Result<T, E>;
+
+/// Content light level information (CEA-861.3), in cd/m².
+///
+/// The CoreVideo attachment and the HEVC `content_light_level_info` SEI share the same
+/// 4-byte big-endian layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ContentLightLevelInfo {
+    pub max_cll: u16,
+    pub max_fall: u16,
+}
+
+impl ContentLightLevelInfo {
+    pub const SIZE: usize = 4;
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..2].copy_from_slice(&self.max_cll.to_be_bytes());
+        bytes[2..4].copy_from_slice(&self.max_fall.to_be_bytes());
+        bytes
+    }
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != Self::SIZE {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let mut reader = Reader(bytes);
+        Ok(Self {
+            max_cll: reader.u16(),
+            max_fall: reader.u16(),
+        })
+    }
+    /// The HEVC `content_light_level_info` SEI payload.
+    pub fn to_hevc_sei_payload(&self) -> [u8; Self::SIZE] {
+        self.to_bytes()
+    }
+    pub fn from_hevc_sei_payload(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+    /// The AV1 `metadata_hdr_cll()` payload, excluding `metadata_type`.
+    pub fn to_av1_metadata_payload(&self) -> [u8; Self::SIZE] {
+        self.to_bytes()
+    }
+    pub fn from_av1_metadata_payload(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+}
+
+/// A CIE 1931 chromaticity coordinate in increments of 0.00002, as used by SMPTE ST 2086
+/// and HEVC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Chromaticity {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl Chromaticity {
+    const SCALE: f64 = 50_000.0;
+    const AV1_SCALE: f64 = 65_536.0;
+
+    /// Rounds `(x, y)` to the nearest representable coordinate.
+    pub fn from_xy(x: f64, y: f64) -> Self {
+        Self {
+            x: to_fixed(x, Self::SCALE) as u16,
+            y: to_fixed(y, Self::SCALE) as u16,
+        }
+    }
+    pub fn xy(&self) -> (f64, f64) {
+        (self.x as f64 / Self::SCALE, self.y as f64 / Self::SCALE)
+    }
+    fn to_av1(self) -> (u16, u16) {
+        let (x, y) = self.xy();
+        (
+            to_fixed(x, Self::AV1_SCALE) as u16,
+            to_fixed(y, Self::AV1_SCALE) as u16,
+        )
+    }
+    fn from_av1(x: u16, y: u16) -> Self {
+        Self::from_xy(x as f64 / Self::AV1_SCALE, y as f64 / Self::AV1_SCALE)
+    }
+}
+
+/// Mastering display color volume (SMPTE ST 2086). Luminance is in increments of
+/// 0.0001 cd/m².
+///
+/// The CoreVideo attachment and the HEVC `mastering_display_colour_volume` SEI share the same
+/// 24-byte big-endian layout, which stores the primaries in green, blue, red order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MasteringDisplayColorVolume {
+    pub red: Chromaticity,
+    pub green: Chromaticity,
+    pub blue: Chromaticity,
+    pub white_point: Chromaticity,
+    pub max_luminance: u32,
+    pub min_luminance: u32,
+}
+
+/// [`MasteringDisplayColorVolume`] in floating-point units: chromaticity as CIE 1931 `(x, y)`
+/// and luminance in cd/m².
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct St2086 {
+    pub red: (f64, f64),
+    pub green: (f64, f64),
+    pub blue: (f64, f64),
+    pub white_point: (f64, f64),
+    pub max_luminance: f64,
+    pub min_luminance: f64,
+}
+
+impl MasteringDisplayColorVolume {
+    pub const SIZE: usize = 24;
+    const LUMINANCE_SCALE: f64 = 10_000.0;
+    const AV1_MAX_LUMINANCE_SCALE: f64 = 256.0;
+    const AV1_MIN_LUMINANCE_SCALE: f64 = 16_384.0;
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        let mut writer = Writer(&mut bytes[..]);
+        for primary in [self.green, self.blue, self.red, self.white_point] {
+            writer.u16(primary.x);
+            writer.u16(primary.y);
+        }
+        writer.u32(self.max_luminance);
+        writer.u32(self.min_luminance);
+        bytes
+    }
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != Self::SIZE {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let mut reader = Reader(bytes);
+        let mut primary = || Chromaticity {
+            x: reader.u16(),
+            y: reader.u16(),
+        };
+        let green = primary();
+        let blue = primary();
+        let red = primary();
+        let white_point = primary();
+        Ok(Self {
+            red,
+            green,
+            blue,
+            white_point,
+            max_luminance: reader.u32(),
+            min_luminance: reader.u32(),
+        })
+    }
+    /// The HEVC `mastering_display_colour_volume` SEI payload.
+    pub fn to_hevc_sei_payload(&self) -> [u8; Self::SIZE] {
+        self.to_bytes()
+    }
+    pub fn from_hevc_sei_payload(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+    /// The AV1 `metadata_hdr_mdcv()` payload, excluding `metadata_type`.
+    ///
+    /// AV1 stores the primaries in red, green, blue order as 0.16 fixed point, the maximum
+    /// luminance as 24.8 and the minimum luminance as 18.14 fixed point, so values are rounded
+    /// to the nearest representable step.
+    pub fn to_av1_metadata_payload(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        let mut writer = Writer(&mut bytes[..]);
+        for primary in [self.red, self.green, self.blue, self.white_point] {
+            let (x, y) = primary.to_av1();
+            writer.u16(x);
+            writer.u16(y);
+        }
+        let st2086 = self.to_st2086();
+        writer.u32(to_fixed(st2086.max_luminance, Self::AV1_MAX_LUMINANCE_SCALE) as u32);
+        writer.u32(to_fixed(st2086.min_luminance, Self::AV1_MIN_LUMINANCE_SCALE) as u32);
+        bytes
+    }
+    pub fn from_av1_metadata_payload(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != Self::SIZE {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let mut reader = Reader(bytes);
+        let mut primary = || Chromaticity::from_av1(reader.u16(), reader.u16());
+        let red = primary();
+        let green = primary();
+        let blue = primary();
+        let white_point = primary();
+        let max_luminance = reader.u32() as f64 / Self::AV1_MAX_LUMINANCE_SCALE;
+        let min_luminance = reader.u32() as f64 / Self::AV1_MIN_LUMINANCE_SCALE;
+        Ok(Self {
+            red,
+            green,
+            blue,
+            white_point,
+            max_luminance: to_fixed(max_luminance, Self::LUMINANCE_SCALE) as u32,
+            min_luminance: to_fixed(min_luminance, Self::LUMINANCE_SCALE) as u32,
+        })
+    }
+    pub fn to_st2086(&self) -> St2086 {
+        St2086 {
+            red: self.red.xy(),
+            green: self.green.xy(),
+            blue: self.blue.xy(),
+            white_point: self.white_point.xy(),
+            max_luminance: self.max_luminance as f64 / Self::LUMINANCE_SCALE,
+            min_luminance: self.min_luminance as f64 / Self::LUMINANCE_SCALE,
+        }
+    }
+    /// Rounds each value to the nearest representable step.
+    pub fn from_st2086(st2086: &St2086) -> Self {
+        let chromaticity = |(x, y)| Chromaticity::from_xy(x, y);
+        Self {
+            red: chromaticity(st2086.red),
+            green: chromaticity(st2086.green),
+            blue: chromaticity(st2086.blue),
+            white_point: chromaticity(st2086.white_point),
+            max_luminance: to_fixed(st2086.max_luminance, Self::LUMINANCE_SCALE) as u32,
+            min_luminance: to_fixed(st2086.min_luminance, Self::LUMINANCE_SCALE) as u32,
+        }
+    }
+}
+
+impl From<St2086> for MasteringDisplayColorVolume {
+    fn from(val: St2086) -> Self {
+        Self::from_st2086(&val)
+    }
+}
+
+impl From<MasteringDisplayColorVolume> for St2086 {
+    fn from(val: MasteringDisplayColorVolume) -> Self {
+        val.to_st2086()
+    }
+}
+
+/// Scales and rounds `value`; the float-to-int casts at the call sites saturate.
+fn to_fixed(value: f64, scale: f64) -> f64 {
+    (value * scale).round()
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn u16(&mut self) -> u16 {
+        let (head, tail) = self.0.split_at(2);
+        self.0 = tail;
+        u16::from_be_bytes([head[0], head[1]])
+    }
+    fn u32(&mut self) -> u32 {
+        let (head, tail) = self.0.split_at(4);
+        self.0 = tail;
+        u32::from_be_bytes([head[0], head[1], head[2], head[3]])
+    }
+}
+
+struct Writer<'a>(&'a mut [u8]);
+
+impl Writer<'_> {
+    fn u16(&mut self, value: u16) {
+        let (head, tail) = std::mem::take(&mut self.0).split_at_mut(2);
+        head.copy_from_slice(&value.to_be_bytes());
+        self.0 = tail;
+    }
+    fn u32(&mut self, value: u32) {
+        let (head, tail) = std::mem::take(&mut self.0).split_at_mut(4);
+        head.copy_from_slice(&value.to_be_bytes());
+        self.0 = tail;
+    }
+}
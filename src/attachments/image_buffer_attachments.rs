@@ -0,0 +1,167 @@
+use super::{
+    geometry::{CleanAperture, DisplayDimensions, PixelAspectRatio},
+    hdr_metadata::{ContentLightLevelInfo, MasteringDisplayColorVolume},
+};
+use crate::constants::{
+    ChromaLocation, ChromaSubsampling, ColorPrimaries, FieldDetail, TransferFunction, YCbCrMatrix,
+};
+
+/// Whether an attachment is copied to derived buffers by `propagate_attachments`.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CVAttachmentMode {
+    ShouldNotPropagate = 0,
+    ShouldPropagate = 1,
+}
+
+/// Identifies an [`ImageBufferAttachment`] independent of its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageBufferAttachmentKey {
+    ColorPrimaries,
+    TransferFunction,
+    YCbCrMatrix,
+    ChromaLocationTopField,
+    ChromaLocationBottomField,
+    ChromaSubsampling,
+    FieldCount,
+    FieldDetail,
+    CleanAperture,
+    PreferredCleanAperture,
+    PixelAspectRatio,
+    DisplayDimensions,
+    GammaLevel,
+    ICCProfile,
+    ContentLightLevelInfo,
+    MasteringDisplayColorVolume,
+    AlphaChannelIsOpaque,
+}
+
+impl ImageBufferAttachmentKey {
+    pub const ALL: &'static [ImageBufferAttachmentKey] = &[
+        ImageBufferAttachmentKey::ColorPrimaries,
+        ImageBufferAttachmentKey::TransferFunction,
+        ImageBufferAttachmentKey::YCbCrMatrix,
+        ImageBufferAttachmentKey::ChromaLocationTopField,
+        ImageBufferAttachmentKey::ChromaLocationBottomField,
+        ImageBufferAttachmentKey::ChromaSubsampling,
+        ImageBufferAttachmentKey::FieldCount,
+        ImageBufferAttachmentKey::FieldDetail,
+        ImageBufferAttachmentKey::CleanAperture,
+        ImageBufferAttachmentKey::PreferredCleanAperture,
+        ImageBufferAttachmentKey::PixelAspectRatio,
+        ImageBufferAttachmentKey::DisplayDimensions,
+        ImageBufferAttachmentKey::GammaLevel,
+        ImageBufferAttachmentKey::ICCProfile,
+        ImageBufferAttachmentKey::ContentLightLevelInfo,
+        ImageBufferAttachmentKey::MasteringDisplayColorVolume,
+        ImageBufferAttachmentKey::AlphaChannelIsOpaque,
+    ];
+}
+
+/// A typed `kCVImageBuffer*Key` attachment.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageBufferAttachment {
+    ColorPrimaries(ColorPrimaries),
+    TransferFunction(TransferFunction),
+    YCbCrMatrix(YCbCrMatrix),
+    ChromaLocationTopField(ChromaLocation),
+    ChromaLocationBottomField(ChromaLocation),
+    ChromaSubsampling(ChromaSubsampling),
+    FieldCount(u32),
+    FieldDetail(FieldDetail),
+    CleanAperture(CleanAperture),
+    PreferredCleanAperture(CleanAperture),
+    PixelAspectRatio(PixelAspectRatio),
+    DisplayDimensions(DisplayDimensions),
+    GammaLevel(f64),
+    ICCProfile(Vec<u8>),
+    ContentLightLevelInfo(ContentLightLevelInfo),
+    MasteringDisplayColorVolume(MasteringDisplayColorVolume),
+    AlphaChannelIsOpaque(bool),
+}
+
+impl ImageBufferAttachment {
+    pub fn key(&self) -> ImageBufferAttachmentKey {
+        match self {
+            Self::ColorPrimaries(_) => ImageBufferAttachmentKey::ColorPrimaries,
+            Self::TransferFunction(_) => ImageBufferAttachmentKey::TransferFunction,
+            Self::YCbCrMatrix(_) => ImageBufferAttachmentKey::YCbCrMatrix,
+            Self::ChromaLocationTopField(_) => ImageBufferAttachmentKey::ChromaLocationTopField,
+            Self::ChromaLocationBottomField(_) => {
+                ImageBufferAttachmentKey::ChromaLocationBottomField
+            }
+            Self::ChromaSubsampling(_) => ImageBufferAttachmentKey::ChromaSubsampling,
+            Self::FieldCount(_) => ImageBufferAttachmentKey::FieldCount,
+            Self::FieldDetail(_) => ImageBufferAttachmentKey::FieldDetail,
+            Self::CleanAperture(_) => ImageBufferAttachmentKey::CleanAperture,
+            Self::PreferredCleanAperture(_) => ImageBufferAttachmentKey::PreferredCleanAperture,
+            Self::PixelAspectRatio(_) => ImageBufferAttachmentKey::PixelAspectRatio,
+            Self::DisplayDimensions(_) => ImageBufferAttachmentKey::DisplayDimensions,
+            Self::GammaLevel(_) => ImageBufferAttachmentKey::GammaLevel,
+            Self::ICCProfile(_) => ImageBufferAttachmentKey::ICCProfile,
+            Self::ContentLightLevelInfo(_) => ImageBufferAttachmentKey::ContentLightLevelInfo,
+            Self::MasteringDisplayColorVolume(_) => {
+                ImageBufferAttachmentKey::MasteringDisplayColorVolume
+            }
+            Self::AlphaChannelIsOpaque(_) => ImageBufferAttachmentKey::AlphaChannelIsOpaque,
+        }
+    }
+}
+
+/// A set of image buffer attachments with at most one value per key.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageBufferAttachments(Vec<ImageBufferAttachment>);
+
+impl ImageBufferAttachments {
+    /// Later attachments replace earlier ones with the same key.
+    pub fn new(attachments: &[ImageBufferAttachment]) -> Self {
+        let mut result = Self::default();
+        for attachment in attachments {
+            result.add(attachment.clone());
+        }
+        result
+    }
+    /// Adds `attachment`, returning the value it replaced.
+    pub fn add(&mut self, attachment: ImageBufferAttachment) -> Option<ImageBufferAttachment> {
+        let key = attachment.key();
+        match self.0.iter_mut().find(|a| a.key() == key) {
+            Some(existing) => Some(std::mem::replace(existing, attachment)),
+            None => {
+                self.0.push(attachment);
+                None
+            }
+        }
+    }
+    pub fn get(&self, key: ImageBufferAttachmentKey) -> Option<&ImageBufferAttachment> {
+        self.0.iter().find(|a| a.key() == key)
+    }
+    pub fn remove(&mut self, key: ImageBufferAttachmentKey) -> Option<ImageBufferAttachment> {
+        let index = self.0.iter().position(|a| a.key() == key)?;
+        Some(self.0.remove(index))
+    }
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn as_slice(&self) -> &[ImageBufferAttachment] {
+        &self.0
+    }
+    pub fn into_vec(self) -> Vec<ImageBufferAttachment> {
+        self.0
+    }
+}
+
+impl FromIterator<ImageBufferAttachment> for ImageBufferAttachments {
+    fn from_iter<T: IntoIterator<Item = ImageBufferAttachment>>(iter: T) -> Self {
+        let mut result = Self::default();
+        for attachment in iter {
+            result.add(attachment);
+        }
+        result
+    }
+}
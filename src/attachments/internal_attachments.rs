@@ -0,0 +1,424 @@
+use core_foundation::{
+    array::CFArray,
+    base::{CFType, CFTypeRef, TCFType},
+    boolean::CFBoolean,
+    data::CFData,
+    dictionary::{CFDictionary as CFUntypedDictionary, CFDictionaryRef},
+    number::CFNumber,
+    string::{CFString, CFStringRef},
+};
+use core_graphics::display::CFDictionary;
+
+use super::{
+    aspect_ratio::{
+        kCVImageBufferPixelAspectRatioHorizontalSpacingKey,
+        kCVImageBufferPixelAspectRatioVerticalSpacingKey,
+    },
+    clean_aperture::{
+        kCVImageBufferCleanApertureHeightKey, kCVImageBufferCleanApertureHorizontalOffsetKey,
+        kCVImageBufferCleanApertureVerticalOffsetKey, kCVImageBufferCleanApertureWidthKey,
+    },
+    display_dimensions::{kCVImageBufferDisplayHeightKey, kCVImageBufferDisplayWidthKey},
+    geometry::{CleanAperture, DisplayDimensions, PixelAspectRatio},
+    hdr_metadata::{ContentLightLevelInfo, MasteringDisplayColorVolume},
+    image_buffer_attachments::{
+        CVAttachmentMode, ImageBufferAttachment, ImageBufferAttachmentKey, ImageBufferAttachments,
+    },
+    kCVImageBufferAlphaChannelIsOpaque, kCVImageBufferChromaLocationBottomFieldKey,
+    kCVImageBufferChromaLocationTopFieldKey, kCVImageBufferChromaSubsamplingKey,
+    kCVImageBufferCleanApertureKey, kCVImageBufferColorPrimariesKey,
+    kCVImageBufferContentLightLevelInfoKey, kCVImageBufferDisplayDimensionsKey,
+    kCVImageBufferFieldCountKey, kCVImageBufferFieldDetailKey, kCVImageBufferGammaLevelKey,
+    kCVImageBufferICCProfileKey, kCVImageBufferMasteringDisplayColorVolumeKey,
+    kCVImageBufferPixelAspectRatioKey, kCVImageBufferPreferredCleanApertureKey,
+    kCVImageBufferTransferFunctionKey, kCVImageBufferYCbCrMatrixKey,
+};
+use crate::types::Rational;
+
+pub(crate) type CVBufferRef = CFTypeRef;
+
+impl ImageBufferAttachmentKey {
+    pub(crate) fn as_cf_string(self) -> CFString {
+        let key = unsafe {
+            match self {
+                Self::ColorPrimaries => kCVImageBufferColorPrimariesKey,
+                Self::TransferFunction => kCVImageBufferTransferFunctionKey,
+                Self::YCbCrMatrix => kCVImageBufferYCbCrMatrixKey,
+                Self::ChromaLocationTopField => kCVImageBufferChromaLocationTopFieldKey,
+                Self::ChromaLocationBottomField => kCVImageBufferChromaLocationBottomFieldKey,
+                Self::ChromaSubsampling => kCVImageBufferChromaSubsamplingKey,
+                Self::FieldCount => kCVImageBufferFieldCountKey,
+                Self::FieldDetail => kCVImageBufferFieldDetailKey,
+                Self::CleanAperture => kCVImageBufferCleanApertureKey,
+                Self::PreferredCleanAperture => kCVImageBufferPreferredCleanApertureKey,
+                Self::PixelAspectRatio => kCVImageBufferPixelAspectRatioKey,
+                Self::DisplayDimensions => kCVImageBufferDisplayDimensionsKey,
+                Self::GammaLevel => kCVImageBufferGammaLevelKey,
+                Self::ICCProfile => kCVImageBufferICCProfileKey,
+                Self::ContentLightLevelInfo => kCVImageBufferContentLightLevelInfoKey,
+                Self::MasteringDisplayColorVolume => kCVImageBufferMasteringDisplayColorVolumeKey,
+                Self::AlphaChannelIsOpaque => kCVImageBufferAlphaChannelIsOpaque,
+            }
+        };
+        unsafe { CFString::wrap_under_get_rule(key) }
+    }
+}
+
+fn key(key: CFStringRef) -> CFString {
+    unsafe { CFString::wrap_under_get_rule(key) }
+}
+
+fn number_dictionary(pairs: &[(CFStringRef, CFType)]) -> CFType {
+    let pairs: Vec<(CFString, CFType)> = pairs.iter().map(|(k, v)| (key(*k), v.clone())).collect();
+    CFDictionary::from_CFType_pairs(&pairs).into_CFType()
+}
+
+fn dictionary_number(dictionary: &CFDictionary<CFString, CFType>, k: CFStringRef) -> Option<f64> {
+    dictionary.find(key(k))?.downcast::<CFNumber>()?.to_f64()
+}
+
+/// Name of the `[numerator, denominator]` array CoreMedia stores next to a clean aperture
+/// value, such as `WidthRational` for `Width`.
+fn rational_key(k: CFStringRef) -> CFString {
+    CFString::new(&format!("{}Rational", key(k)))
+}
+
+fn rational_array(value: &CFType) -> Option<Rational> {
+    let array = value.downcast::<CFArray>()?;
+    let term = |index: isize| -> Option<i32> {
+        let item = unsafe { CFType::wrap_under_get_rule(*array.get(index)?) };
+        item.downcast::<CFNumber>()?.to_i32()
+    };
+    match array.len() {
+        2 => Some(Rational::new(term(0)?, term(1)?)),
+        _ => None,
+    }
+}
+
+/// Clean aperture dictionary in the layout CoreVideo reads: every value as a `CFNumber`,
+/// and fractions also as an exact `*Rational` array.
+fn clean_aperture_dictionary(pairs: &[(CFStringRef, Rational)]) -> CFType {
+    let mut entries = Vec::with_capacity(pairs.len() * 2);
+    for &(k, value) in pairs {
+        let value = value.reduced();
+        if value.is_integer() {
+            entries.push((
+                key(k),
+                CFNumber::from(value.numerator as i64 / value.denominator as i64).into_CFType(),
+            ));
+        } else {
+            entries.push((key(k), CFNumber::from(value.to_f64()).into_CFType()));
+            entries.push((
+                rational_key(k),
+                CFArray::from_CFTypes(&[
+                    CFNumber::from(value.numerator),
+                    CFNumber::from(value.denominator),
+                ])
+                .into_CFType(),
+            ));
+        }
+    }
+    CFDictionary::from_CFType_pairs(&entries).into_CFType()
+}
+
+/// Reads the exact `*Rational` array if present, else the number. A `[numerator,
+/// denominator]` array under the plain key is accepted too.
+fn dictionary_rational(
+    dictionary: &CFDictionary<CFString, CFType>,
+    k: CFStringRef,
+) -> Option<Rational> {
+    if let Some(value) = dictionary
+        .find(rational_key(k))
+        .and_then(|value| rational_array(&value))
+    {
+        return Some(value);
+    }
+    let value = dictionary.find(key(k))?;
+    if let Some(number) = value.downcast::<CFNumber>() {
+        return match number.to_i32() {
+            Some(integer) => Some(Rational::integer(integer)),
+            None => Some(Rational::from_f64(number.to_f64()?)),
+        };
+    }
+    rational_array(&value)
+}
+
+fn as_dictionary(value: &CFType) -> Option<CFDictionary<CFString, CFType>> {
+    let dictionary = value.downcast::<CFUntypedDictionary>()?;
+    Some(unsafe { CFDictionary::wrap_under_get_rule(dictionary.as_concrete_TypeRef()) })
+}
+
+fn as_string(value: &CFType) -> Option<String> {
+    Some(value.downcast::<CFString>()?.to_string())
+}
+
+fn as_data(value: &CFType) -> Option<Vec<u8>> {
+    Some(value.downcast::<CFData>()?.bytes().to_vec())
+}
+
+impl ImageBufferAttachment {
+    pub(crate) fn to_cf_value(&self) -> CFType {
+        match self {
+            Self::ColorPrimaries(val) => CFString::new(val.as_str()).into_CFType(),
+            Self::TransferFunction(val) => CFString::new(val.as_str()).into_CFType(),
+            Self::YCbCrMatrix(val) => CFString::new(val.as_str()).into_CFType(),
+            Self::ChromaLocationTopField(val) | Self::ChromaLocationBottomField(val) => {
+                CFString::new(val.as_str()).into_CFType()
+            }
+            Self::ChromaSubsampling(val) => CFString::new(val.as_str()).into_CFType(),
+            Self::FieldDetail(val) => CFString::new(val.as_str()).into_CFType(),
+            Self::FieldCount(val) => CFNumber::from(*val as i64).into_CFType(),
+            Self::CleanAperture(val) | Self::PreferredCleanAperture(val) => unsafe {
+                clean_aperture_dictionary(&[
+                    (kCVImageBufferCleanApertureWidthKey, val.width),
+                    (kCVImageBufferCleanApertureHeightKey, val.height),
+                    (
+                        kCVImageBufferCleanApertureHorizontalOffsetKey,
+                        val.horizontal_offset,
+                    ),
+                    (
+                        kCVImageBufferCleanApertureVerticalOffsetKey,
+                        val.vertical_offset,
+                    ),
+                ])
+            },
+            Self::PixelAspectRatio(val) => number_dictionary(&[
+                (
+                    unsafe { kCVImageBufferPixelAspectRatioHorizontalSpacingKey },
+                    CFNumber::from(val.horizontal_spacing as i64).into_CFType(),
+                ),
+                (
+                    unsafe { kCVImageBufferPixelAspectRatioVerticalSpacingKey },
+                    CFNumber::from(val.vertical_spacing as i64).into_CFType(),
+                ),
+            ]),
+            Self::DisplayDimensions(val) => number_dictionary(&[
+                (
+                    unsafe { kCVImageBufferDisplayWidthKey },
+                    CFNumber::from(val.width as i64).into_CFType(),
+                ),
+                (
+                    unsafe { kCVImageBufferDisplayHeightKey },
+                    CFNumber::from(val.height as i64).into_CFType(),
+                ),
+            ]),
+            Self::GammaLevel(val) => CFNumber::from(*val).into_CFType(),
+            Self::ICCProfile(val) => CFData::from_buffer(val).into_CFType(),
+            Self::ContentLightLevelInfo(val) => CFData::from_buffer(&val.to_bytes()).into_CFType(),
+            Self::MasteringDisplayColorVolume(val) => {
+                CFData::from_buffer(&val.to_bytes()).into_CFType()
+            }
+            Self::AlphaChannelIsOpaque(val) => CFBoolean::from(*val).into_CFType(),
+        }
+    }
+
+    /// Parses a CoreVideo attachment value; returns `None` if it has an unexpected type.
+    pub(crate) fn from_cf_value(key: ImageBufferAttachmentKey, value: &CFType) -> Option<Self> {
+        use ImageBufferAttachmentKey as Key;
+        Some(match key {
+            Key::ColorPrimaries => Self::ColorPrimaries(as_string(value)?.as_str().into()),
+            Key::TransferFunction => Self::TransferFunction(as_string(value)?.as_str().into()),
+            Key::YCbCrMatrix => Self::YCbCrMatrix(as_string(value)?.as_str().into()),
+            Key::ChromaLocationTopField => {
+                Self::ChromaLocationTopField(as_string(value)?.as_str().into())
+            }
+            Key::ChromaLocationBottomField => {
+                Self::ChromaLocationBottomField(as_string(value)?.as_str().into())
+            }
+            Key::ChromaSubsampling => Self::ChromaSubsampling(as_string(value)?.as_str().into()),
+            Key::FieldCount => {
+                Self::FieldCount(value.downcast::<CFNumber>()?.to_i64()?.try_into().ok()?)
+            }
+            Key::FieldDetail => Self::FieldDetail(as_string(value)?.as_str().into()),
+            Key::CleanAperture | Key::PreferredCleanAperture => {
+                let dictionary = as_dictionary(value)?;
+                let clean_aperture = unsafe {
+                    CleanAperture {
+                        width: dictionary_rational(
+                            &dictionary,
+                            kCVImageBufferCleanApertureWidthKey,
+                        )?,
+                        height: dictionary_rational(
+                            &dictionary,
+                            kCVImageBufferCleanApertureHeightKey,
+                        )?,
+                        horizontal_offset: dictionary_rational(
+                            &dictionary,
+                            kCVImageBufferCleanApertureHorizontalOffsetKey,
+                        )?,
+                        vertical_offset: dictionary_rational(
+                            &dictionary,
+                            kCVImageBufferCleanApertureVerticalOffsetKey,
+                        )?,
+                    }
+                };
+                if key == Key::CleanAperture {
+                    Self::CleanAperture(clean_aperture)
+                } else {
+                    Self::PreferredCleanAperture(clean_aperture)
+                }
+            }
+            Key::PixelAspectRatio => {
+                let dictionary = as_dictionary(value)?;
+                Self::PixelAspectRatio(unsafe {
+                    PixelAspectRatio {
+                        horizontal_spacing: dictionary_number(
+                            &dictionary,
+                            kCVImageBufferPixelAspectRatioHorizontalSpacingKey,
+                        )? as u32,
+                        vertical_spacing: dictionary_number(
+                            &dictionary,
+                            kCVImageBufferPixelAspectRatioVerticalSpacingKey,
+                        )? as u32,
+                    }
+                })
+            }
+            Key::DisplayDimensions => {
+                let dictionary = as_dictionary(value)?;
+                Self::DisplayDimensions(unsafe {
+                    DisplayDimensions {
+                        width: dictionary_number(&dictionary, kCVImageBufferDisplayWidthKey)?
+                            as u32,
+                        height: dictionary_number(&dictionary, kCVImageBufferDisplayHeightKey)?
+                            as u32,
+                    }
+                })
+            }
+            Key::GammaLevel => Self::GammaLevel(value.downcast::<CFNumber>()?.to_f64()?),
+            Key::ICCProfile => Self::ICCProfile(as_data(value)?),
+            Key::ContentLightLevelInfo => Self::ContentLightLevelInfo(
+                ContentLightLevelInfo::from_bytes(&as_data(value)?).ok()?,
+            ),
+            Key::MasteringDisplayColorVolume => Self::MasteringDisplayColorVolume(
+                MasteringDisplayColorVolume::from_bytes(&as_data(value)?).ok()?,
+            ),
+            Key::AlphaChannelIsOpaque => {
+                Self::AlphaChannelIsOpaque(value.downcast::<CFBoolean>()?.into())
+            }
+        })
+    }
+}
+
+impl From<ImageBufferAttachments> for CFDictionary<CFString, CFType> {
+    fn from(val: ImageBufferAttachments) -> Self {
+        let pairs: Vec<(CFString, CFType)> = val
+            .as_slice()
+            .iter()
+            .map(|attachment| (attachment.key().as_cf_string(), attachment.to_cf_value()))
+            .collect();
+        CFDictionary::from_CFType_pairs(&pairs)
+    }
+}
+
+/// Keeps the attachments this crate models and skips unknown keys and malformed values.
+impl From<&CFDictionary<CFString, CFType>> for ImageBufferAttachments {
+    fn from(val: &CFDictionary<CFString, CFType>) -> Self {
+        ImageBufferAttachmentKey::ALL
+            .iter()
+            .filter_map(|&key| {
+                let value = val.find(key.as_cf_string())?;
+                ImageBufferAttachment::from_cf_value(key, &value)
+            })
+            .collect()
+    }
+}
+
+/// `CVBuffer` attachment calls shared by every CoreVideo buffer type.
+pub(crate) trait InternalAttachments {
+    fn buffer_ref(&self) -> CVBufferRef;
+
+    fn internal_set_attachment(&self, attachment: &ImageBufferAttachment, mode: CVAttachmentMode) {
+        extern "C" {
+            fn CVBufferSetAttachment(
+                buffer: CVBufferRef,
+                key: CFStringRef,
+                value: CFTypeRef,
+                attachmentMode: CVAttachmentMode,
+            );
+        }
+        let key = attachment.key().as_cf_string();
+        let value = attachment.to_cf_value();
+        unsafe {
+            CVBufferSetAttachment(
+                self.buffer_ref(),
+                key.as_concrete_TypeRef(),
+                value.as_CFTypeRef(),
+                mode,
+            )
+        }
+    }
+    fn internal_get_attachment(
+        &self,
+        key: ImageBufferAttachmentKey,
+    ) -> Option<(ImageBufferAttachment, CVAttachmentMode)> {
+        extern "C" {
+            fn CVBufferGetAttachment(
+                buffer: CVBufferRef,
+                key: CFStringRef,
+                attachmentMode: *mut CVAttachmentMode,
+            ) -> CFTypeRef;
+        }
+        let mut mode = CVAttachmentMode::ShouldNotPropagate;
+        let cf_key = key.as_cf_string();
+        let value = unsafe {
+            CVBufferGetAttachment(self.buffer_ref(), cf_key.as_concrete_TypeRef(), &mut mode)
+        };
+        if value.is_null() {
+            return None;
+        }
+        let value = unsafe { CFType::wrap_under_get_rule(value) };
+        Some((ImageBufferAttachment::from_cf_value(key, &value)?, mode))
+    }
+    fn internal_remove_attachment(&self, key: ImageBufferAttachmentKey) {
+        extern "C" {
+            fn CVBufferRemoveAttachment(buffer: CVBufferRef, key: CFStringRef);
+        }
+        let key = key.as_cf_string();
+        unsafe { CVBufferRemoveAttachment(self.buffer_ref(), key.as_concrete_TypeRef()) }
+    }
+    fn internal_remove_all_attachments(&self) {
+        extern "C" {
+            fn CVBufferRemoveAllAttachments(buffer: CVBufferRef);
+        }
+        unsafe { CVBufferRemoveAllAttachments(self.buffer_ref()) }
+    }
+    fn internal_copy_attachments(&self, mode: CVAttachmentMode) -> ImageBufferAttachments {
+        extern "C" {
+            fn CVBufferCopyAttachments(
+                buffer: CVBufferRef,
+                attachmentMode: CVAttachmentMode,
+            ) -> CFDictionaryRef;
+        }
+        let dictionary = unsafe { CVBufferCopyAttachments(self.buffer_ref(), mode) };
+        if dictionary.is_null() {
+            return ImageBufferAttachments::default();
+        }
+        let dictionary: CFDictionary<CFString, CFType> =
+            unsafe { CFDictionary::wrap_under_create_rule(dictionary) };
+        ImageBufferAttachments::from(&dictionary)
+    }
+    fn internal_set_attachments(
+        &self,
+        attachments: ImageBufferAttachments,
+        mode: CVAttachmentMode,
+    ) {
+        extern "C" {
+            fn CVBufferSetAttachments(
+                buffer: CVBufferRef,
+                theAttachments: CFDictionaryRef,
+                attachmentMode: CVAttachmentMode,
+            );
+        }
+        let dictionary: CFDictionary<CFString, CFType> = attachments.into();
+        unsafe { CVBufferSetAttachments(self.buffer_ref(), dictionary.as_concrete_TypeRef(), mode) }
+    }
+    fn internal_propagate_attachments(&self, destination: &impl InternalAttachments) {
+        extern "C" {
+            fn CVBufferPropagateAttachments(
+                sourceBuffer: CVBufferRef,
+                destinationBuffer: CVBufferRef,
+            );
+        }
+        unsafe { CVBufferPropagateAttachments(self.buffer_ref(), destination.buffer_ref()) }
+    }
+}
@@ -1,8 +1,27 @@
+#[cfg(target_vendor = "apple")]
 pub mod aspect_ratio;
+#[cfg(target_vendor = "apple")]
+pub mod clean_aperture;
+#[cfg(target_vendor = "apple")]
 pub mod display_dimensions;
+pub mod geometry;
+pub mod hdr_metadata;
+pub mod image_buffer_attachments;
+#[cfg(target_vendor = "apple")]
+pub(crate) mod internal_attachments;
 
+pub use geometry::{CleanAperture, DisplayDimensions, PixelAspectRatio, Rect, Size};
+pub use hdr_metadata::{Chromaticity, ContentLightLevelInfo, MasteringDisplayColorVolume, St2086};
+pub use image_buffer_attachments::{
+    CVAttachmentMode, ImageBufferAttachment, ImageBufferAttachmentKey, ImageBufferAttachments,
+};
+
+pub use crate::types::Rational;
+
+#[cfg(target_vendor = "apple")]
 use core_foundation::string::CFStringRef;
 
+#[cfg(target_vendor = "apple")]
 extern "C" {
 
     // attachement keys
@@ -1,5 +1,29 @@
+#[cfg(target_vendor = "apple")]
 use core_foundation::string::CFStringRef;
 
+use super::string_constant_enum;
+
+string_constant_enum! {
+    /// Position of chroma samples relative to luma, `kCVImageBufferChromaLocation_*`.
+    pub enum ChromaLocation {
+        /// `kCVImageBufferChromaLocation_Left`
+        Left => "Left",
+        /// `kCVImageBufferChromaLocation_Center`
+        Center => "Center",
+        /// `kCVImageBufferChromaLocation_TopLeft`
+        TopLeft => "TopLeft",
+        /// `kCVImageBufferChromaLocation_Top`
+        Top => "Top",
+        /// `kCVImageBufferChromaLocation_BottomLeft`
+        BottomLeft => "BottomLeft",
+        /// `kCVImageBufferChromaLocation_Bottom`
+        Bottom => "Bottom",
+        /// `kCVImageBufferChromaLocation_DV420`
+        Dv420 => "DV 4:2:0",
+    }
+}
+
+#[cfg(target_vendor = "apple")]
 extern "C" {
     pub static kCVImageBufferChromaLocation_Left: CFStringRef;
     pub static kCVImageBufferChromaLocation_Center: CFStringRef;
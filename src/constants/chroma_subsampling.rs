@@ -1,5 +1,21 @@
+#[cfg(target_vendor = "apple")]
 use core_foundation::string::CFStringRef;
 
+use super::string_constant_enum;
+
+string_constant_enum! {
+    /// Chroma subsampling of the source, `kCVImageBufferChromaSubsampling_*`.
+    pub enum ChromaSubsampling {
+        /// `kCVImageBufferChromaSubsampling_420`
+        Subsampling420 => "4:2:0",
+        /// `kCVImageBufferChromaSubsampling_422`
+        Subsampling422 => "4:2:2",
+        /// `kCVImageBufferChromaSubsampling_411`
+        Subsampling411 => "4:1:1",
+    }
+}
+
+#[cfg(target_vendor = "apple")]
 extern "C" {
     pub static kCVImageBufferChromaSubsampling_420: CFStringRef;
     pub static kCVImageBufferChromaSubsampling_422: CFStringRef;
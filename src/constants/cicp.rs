@@ -0,0 +1,156 @@
+use crate::attachments::{ImageBufferAttachment, ImageBufferAttachmentKey, ImageBufferAttachments};
+
+use super::{ColorPrimaries, TransferFunction, YCbCrMatrix};
+
+/// The H.273 code point for "unspecified", used when a value has no CICP equivalent.
+pub const CICP_UNSPECIFIED: u8 = 2;
+
+impl ColorPrimaries {
+    /// The ITU-T H.273 `colour_primaries` code point.
+    pub fn cicp(&self) -> Option<u8> {
+        match self {
+            Self::ItuR709_2 => Some(1),
+            Self::Ebu3213 => Some(5),
+            Self::SmpteC => Some(6),
+            Self::ItuR2020 => Some(9),
+            Self::DciP3 => Some(11),
+            Self::P3D65 => Some(12),
+            Self::P22 => Some(22),
+            Self::Unknown(_) => None,
+        }
+    }
+    pub fn from_cicp(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::ItuR709_2),
+            5 => Some(Self::Ebu3213),
+            // SMPTE 240M shares its primaries with SMPTE 170M.
+            6 | 7 => Some(Self::SmpteC),
+            9 => Some(Self::ItuR2020),
+            11 => Some(Self::DciP3),
+            12 => Some(Self::P3D65),
+            22 => Some(Self::P22),
+            _ => None,
+        }
+    }
+}
+
+impl TransferFunction {
+    /// The ITU-T H.273 `transfer_characteristics` code point.
+    pub fn cicp(&self) -> Option<u8> {
+        match self {
+            Self::ItuR709_2 => Some(1),
+            Self::SmpteC => Some(6),
+            Self::Smpte240M1995 => Some(7),
+            Self::Linear => Some(8),
+            Self::SRgb => Some(13),
+            Self::ItuR2020 => Some(14),
+            Self::SmpteSt2084Pq => Some(16),
+            Self::SmpteSt428_1 => Some(17),
+            Self::ItuR2100Hlg => Some(18),
+            Self::UseGamma | Self::Ebu3213 | Self::Unknown(_) => None,
+        }
+    }
+    pub fn from_cicp(code: u8) -> Option<Self> {
+        match code {
+            // BT.601 uses the same curve as BT.709.
+            1 | 6 => Some(Self::ItuR709_2),
+            7 => Some(Self::Smpte240M1995),
+            8 => Some(Self::Linear),
+            13 => Some(Self::SRgb),
+            14 | 15 => Some(Self::ItuR2020),
+            16 => Some(Self::SmpteSt2084Pq),
+            17 => Some(Self::SmpteSt428_1),
+            18 => Some(Self::ItuR2100Hlg),
+            _ => None,
+        }
+    }
+}
+
+impl YCbCrMatrix {
+    /// The ITU-T H.273 `matrix_coefficients` code point.
+    pub fn cicp(&self) -> Option<u8> {
+        match self {
+            Self::ItuR709_2 => Some(1),
+            Self::ItuR601_4 => Some(6),
+            Self::Smpte240M1995 => Some(7),
+            Self::ItuR2020 => Some(9),
+            Self::DciP3 | Self::P3D65 | Self::Unknown(_) => None,
+        }
+    }
+    pub fn from_cicp(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::ItuR709_2),
+            5 | 6 => Some(Self::ItuR601_4),
+            7 => Some(Self::Smpte240M1995),
+            9 => Some(Self::ItuR2020),
+            _ => None,
+        }
+    }
+}
+
+/// Color description as ITU-T H.273 code points, as carried in AV1 and HEVC bitstreams and
+/// MP4 `colr` boxes of type `nclx`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cicp {
+    pub colour_primaries: u8,
+    pub transfer_characteristics: u8,
+    pub matrix_coefficients: u8,
+}
+
+impl Default for Cicp {
+    fn default() -> Self {
+        Self {
+            colour_primaries: CICP_UNSPECIFIED,
+            transfer_characteristics: CICP_UNSPECIFIED,
+            matrix_coefficients: CICP_UNSPECIFIED,
+        }
+    }
+}
+
+impl Cicp {
+    /// Reads the color attachments; missing or unmappable values become unspecified.
+    pub fn from_attachments(attachments: &ImageBufferAttachments) -> Self {
+        let mut cicp = Self::default();
+        for attachment in attachments.as_slice() {
+            match attachment {
+                ImageBufferAttachment::ColorPrimaries(val) => {
+                    cicp.colour_primaries = val.cicp().unwrap_or(CICP_UNSPECIFIED)
+                }
+                ImageBufferAttachment::TransferFunction(val) => {
+                    cicp.transfer_characteristics = val.cicp().unwrap_or(CICP_UNSPECIFIED)
+                }
+                ImageBufferAttachment::YCbCrMatrix(val) => {
+                    cicp.matrix_coefficients = val.cicp().unwrap_or(CICP_UNSPECIFIED)
+                }
+                _ => {}
+            }
+        }
+        cicp
+    }
+
+    /// Color attachments for the code points CoreVideo can represent.
+    pub fn to_attachments(&self) -> ImageBufferAttachments {
+        [
+            ColorPrimaries::from_cicp(self.colour_primaries)
+                .map(ImageBufferAttachment::ColorPrimaries),
+            TransferFunction::from_cicp(self.transfer_characteristics)
+                .map(ImageBufferAttachment::TransferFunction),
+            YCbCrMatrix::from_cicp(self.matrix_coefficients)
+                .map(ImageBufferAttachment::YCbCrMatrix),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Replaces the color attachments in `attachments` with this description. Code points
+    /// without a CoreVideo equivalent remove the corresponding attachment.
+    pub fn apply_to(&self, attachments: &mut ImageBufferAttachments) {
+        attachments.remove(ImageBufferAttachmentKey::ColorPrimaries);
+        attachments.remove(ImageBufferAttachmentKey::TransferFunction);
+        attachments.remove(ImageBufferAttachmentKey::YCbCrMatrix);
+        for attachment in self.to_attachments().into_vec() {
+            attachments.add(attachment);
+        }
+    }
+}
@@ -1,5 +1,29 @@
+#[cfg(target_vendor = "apple")]
 use core_foundation::string::CFStringRef;
 
+use super::string_constant_enum;
+
+string_constant_enum! {
+    /// Color primaries of the source, `kCVImageBufferColorPrimaries_*`.
+    pub enum ColorPrimaries {
+        /// `kCVImageBufferColorPrimaries_ITU_R_709_2`
+        ItuR709_2 => "ITU_R_709_2",
+        /// `kCVImageBufferColorPrimaries_EBU_3213`
+        Ebu3213 => "EBU_3213",
+        /// `kCVImageBufferColorPrimaries_SMPTE_C`
+        SmpteC => "SMPTE_C",
+        /// `kCVImageBufferColorPrimaries_DCI_P3`
+        DciP3 => "DCI_P3",
+        /// `kCVImageBufferColorPrimaries_P3_D65`
+        P3D65 => "P3_D65",
+        /// `kCVImageBufferColorPrimaries_ITU_R_2020`
+        ItuR2020 => "ITU_R_2020",
+        /// `kCVImageBufferColorPrimaries_P22`
+        P22 => "P22",
+    }
+}
+
+#[cfg(target_vendor = "apple")]
 extern "C" {
 
     // fill out all constnats
@@ -0,0 +1,25 @@
+#[cfg(target_vendor = "apple")]
+use core_foundation::string::CFStringRef;
+
+use super::string_constant_enum;
+
+string_constant_enum! {
+    /// Range of the component values of a pixel format, `kCVPixelFormatComponentRange_*`.
+    pub enum ComponentRange {
+        /// `kCVPixelFormatComponentRange_VideoRange`
+        VideoRange => "VideoRange",
+        /// `kCVPixelFormatComponentRange_FullRange`
+        FullRange => "FullRange",
+        /// `kCVPixelFormatComponentRange_WideRange`
+        WideRange => "WideRange",
+    }
+}
+
+#[cfg(target_vendor = "apple")]
+extern "C" {
+
+    // constants
+    pub static kCVPixelFormatComponentRange_VideoRange: CFStringRef;
+    pub static kCVPixelFormatComponentRange_FullRange: CFStringRef;
+    pub static kCVPixelFormatComponentRange_WideRange: CFStringRef;
+}
@@ -1,5 +1,23 @@
+#[cfg(target_vendor = "apple")]
 use core_foundation::string::CFStringRef;
 
+use super::string_constant_enum;
+
+string_constant_enum! {
+    /// Field ordering of interlaced content, `kCVImageBufferFieldDetail*`.
+    pub enum FieldDetail {
+        /// `kCVImageBufferFieldDetailTemporalTopFirst`
+        TemporalTopFirst => "TemporalTopFirst",
+        /// `kCVImageBufferFieldDetailTemporalBottomFirst`
+        TemporalBottomFirst => "TemporalBottomFirst",
+        /// `kCVImageBufferFieldDetailSpatialFirstLineEarly`
+        SpatialFirstLineEarly => "SpatialFirstLineEarly",
+        /// `kCVImageBufferFieldDetailSpatialFirstLineLate`
+        SpatialFirstLineLate => "SpatialFirstLineLate",
+    }
+}
+
+#[cfg(target_vendor = "apple")]
 extern "C" {
 
     // constants
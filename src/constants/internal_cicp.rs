@@ -0,0 +1,86 @@
+use core_foundation::{
+    base::TCFType,
+    string::{CFString, CFStringRef},
+};
+
+use super::{ColorPrimaries, TransferFunction, YCbCrMatrix};
+
+fn code_point(code: i32) -> Option<u8> {
+    u8::try_from(code).ok().filter(|&code| code != 0)
+}
+
+fn string_value(value: CFStringRef) -> Option<String> {
+    if value.is_null() {
+        None
+    } else {
+        Some(unsafe { CFString::wrap_under_get_rule(value) }.to_string())
+    }
+}
+
+impl ColorPrimaries {
+    /// The code point CoreVideo reports for this value, for cross-checking [`Self::cicp`].
+    pub fn core_video_cicp(&self) -> Option<u8> {
+        extern "C" {
+            fn CVColorPrimariesGetIntegerCodePointForString(
+                colorPrimariesString: CFStringRef,
+            ) -> i32;
+        }
+        let value = CFString::new(self.as_str());
+        code_point(unsafe {
+            CVColorPrimariesGetIntegerCodePointForString(value.as_concrete_TypeRef())
+        })
+    }
+    pub fn from_core_video_cicp(code: u8) -> Option<Self> {
+        extern "C" {
+            fn CVColorPrimariesGetStringForIntegerCodePoint(
+                colorPrimariesCodePoint: i32,
+            ) -> CFStringRef;
+        }
+        string_value(unsafe { CVColorPrimariesGetStringForIntegerCodePoint(code as i32) })
+            .map(|value| Self::from(value.as_str()))
+    }
+}
+
+impl TransferFunction {
+    /// The code point CoreVideo reports for this value, for cross-checking [`Self::cicp`].
+    pub fn core_video_cicp(&self) -> Option<u8> {
+        extern "C" {
+            fn CVTransferFunctionGetIntegerCodePointForString(
+                transferFunctionString: CFStringRef,
+            ) -> i32;
+        }
+        let value = CFString::new(self.as_str());
+        code_point(unsafe {
+            CVTransferFunctionGetIntegerCodePointForString(value.as_concrete_TypeRef())
+        })
+    }
+    pub fn from_core_video_cicp(code: u8) -> Option<Self> {
+        extern "C" {
+            fn CVTransferFunctionGetStringForIntegerCodePoint(
+                transferFunctionCodePoint: i32,
+            ) -> CFStringRef;
+        }
+        string_value(unsafe { CVTransferFunctionGetStringForIntegerCodePoint(code as i32) })
+            .map(|value| Self::from(value.as_str()))
+    }
+}
+
+impl YCbCrMatrix {
+    /// The code point CoreVideo reports for this value, for cross-checking [`Self::cicp`].
+    pub fn core_video_cicp(&self) -> Option<u8> {
+        extern "C" {
+            fn CVYCbCrMatrixGetIntegerCodePointForString(yCbCrMatrixString: CFStringRef) -> i32;
+        }
+        let value = CFString::new(self.as_str());
+        code_point(unsafe {
+            CVYCbCrMatrixGetIntegerCodePointForString(value.as_concrete_TypeRef())
+        })
+    }
+    pub fn from_core_video_cicp(code: u8) -> Option<Self> {
+        extern "C" {
+            fn CVYCbCrMatrixGetStringForIntegerCodePoint(yCbCrMatrixCodePoint: i32) -> CFStringRef;
+        }
+        string_value(unsafe { CVYCbCrMatrixGetStringForIntegerCodePoint(code as i32) })
+            .map(|value| Self::from(value.as_str()))
+    }
+}
@@ -1,6 +1,77 @@
-pub mod field_detail;
-pub mod transfer_function;
-pub mod ycbcr_matrix;
 pub mod chroma_location;
 pub mod chroma_subsampling;
+pub mod cicp;
 pub mod color_primaries;
+pub mod component_range;
+pub mod field_detail;
+#[cfg(target_vendor = "apple")]
+mod internal_cicp;
+pub mod transfer_function;
+pub mod ycbcr_matrix;
+
+pub use chroma_location::ChromaLocation;
+pub use chroma_subsampling::ChromaSubsampling;
+pub use cicp::Cicp;
+pub use color_primaries::ColorPrimaries;
+pub use component_range::ComponentRange;
+pub use field_detail::FieldDetail;
+pub use transfer_function::TransferFunction;
+pub use ycbcr_matrix::YCbCrMatrix;
+
+/// Declares an enum over the string values of a family of CoreVideo constants.
+///
+/// Values the table does not know map to `Unknown`, so parsing never fails and any value
+/// read from a buffer survives a round trip.
+macro_rules! string_constant_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// A value without a matching variant.
+            Unknown(String),
+        }
+
+        impl $name {
+            /// Every variant except `Unknown`.
+            pub const KNOWN: &'static [$name] = &[$($name::$variant),*];
+
+            /// The CoreVideo string value, e.g. the contents of the matching
+            /// `kCVImageBuffer*` constant.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Ok(Self::from(value))
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    _ => $name::Unknown(value.to_string()),
+                }
+            }
+        }
+    };
+}
+use string_constant_enum;
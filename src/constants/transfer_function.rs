@@ -1,5 +1,37 @@
+#[cfg(target_vendor = "apple")]
 use core_foundation::string::CFStringRef;
 
+use super::string_constant_enum;
+
+string_constant_enum! {
+    /// Transfer function of the source, `kCVImageBufferTransferFunction_*`.
+    pub enum TransferFunction {
+        /// `kCVImageBufferTransferFunction_ITU_R_709_2`
+        ItuR709_2 => "ITU_R_709_2",
+        /// `kCVImageBufferTransferFunction_SMPTE_240M_1995`
+        Smpte240M1995 => "SMPTE_240M_1995",
+        /// `kCVImageBufferTransferFunction_UseGamma`
+        UseGamma => "UseGamma",
+        /// `kCVImageBufferTransferFunction_ITU_R_2020`
+        ItuR2020 => "ITU_R_2020",
+        /// `kCVImageBufferTransferFunction_SMPTE_ST_428_1`
+        SmpteSt428_1 => "SMPTE_ST_428_1",
+        /// `kCVImageBufferTransferFunction_ITU_R_2100_HLG`
+        ItuR2100Hlg => "ITU_R_2100_HLG",
+        /// `kCVImageBufferTransferFunction_SMPTE_ST_2084_PQ`
+        SmpteSt2084Pq => "SMPTE_ST_2084_PQ",
+        /// `kCVImageBufferTransferFunction_Linear`
+        Linear => "Linear",
+        /// `kCVImageBufferTransferFunction_sRGB`
+        SRgb => "IEC_sRGB",
+        /// `kCVImageBufferTransferFunction_EBU_3213`
+        Ebu3213 => "EBU_3213",
+        /// `kCVImageBufferTransferFunction_SMPTE_C`
+        SmpteC => "SMPTE_C",
+    }
+}
+
+#[cfg(target_vendor = "apple")]
 extern "C" {
 
     // fill out all constnats
@@ -12,4 +44,6 @@ extern "C" {
     pub static kCVImageBufferTransferFunction_SMPTE_ST_2084_PQ: CFStringRef;
     pub static kCVImageBufferTransferFunction_EBU_3213: CFStringRef;
     pub static kCVImageBufferTransferFunction_SMPTE_C: CFStringRef;
+    pub static kCVImageBufferTransferFunction_Linear: CFStringRef;
+    pub static kCVImageBufferTransferFunction_sRGB: CFStringRef;
 }
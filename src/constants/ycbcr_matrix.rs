@@ -1,5 +1,27 @@
+#[cfg(target_vendor = "apple")]
 use core_foundation::string::CFStringRef;
 
+use super::string_constant_enum;
+
+string_constant_enum! {
+    /// Matrix used to convert between Y'CbCr and R'G'B', `kCVImageBufferYCbCrMatrix_*`.
+    pub enum YCbCrMatrix {
+        /// `kCVImageBufferYCbCrMatrix_ITU_R_709_2`
+        ItuR709_2 => "ITU_R_709_2",
+        /// `kCVImageBufferYCbCrMatrix_ITU_R_601_4`
+        ItuR601_4 => "ITU_R_601_4",
+        /// `kCVImageBufferYCbCrMatrix_SMPTE_240M_1995`
+        Smpte240M1995 => "SMPTE_240M_1995",
+        /// `kCVImageBufferYCbCrMatrix_DCI_P3`
+        DciP3 => "DCI_P3",
+        /// `kCVImageBufferYCbCrMatrix_P3_D65`
+        P3D65 => "P3_D65",
+        /// `kCVImageBufferYCbCrMatrix_ITU_R_2020`
+        ItuR2020 => "ITU_R_2020",
+    }
+}
+
+#[cfg(target_vendor = "apple")]
 extern "C" {
 
     // constants
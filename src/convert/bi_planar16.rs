@@ -0,0 +1,88 @@
+use crate::cv_pixel_buffer::{
+    error::CVPixelBufferError,
+    plane::{Plane, PlaneMut},
+};
+
+use super::{ChromaFormat, PlanarYCbCr16};
+
+/// Unpacks bi-planar Y'CbCr with little-endian 16-bit containers holding MSB-aligned samples,
+/// such as x420 and P010 (`bit_depth` 10) or sv22 (`bit_depth` 16).
+pub fn unpack_bi_planar_16(
+    y_plane: &Plane<'_>,
+    uv_plane: &Plane<'_>,
+    chroma_format: ChromaFormat,
+    bit_depth: u32,
+) -> Result<PlanarYCbCr16, CVPixelBufferError> {
+    let mut image =
+        PlanarYCbCr16::new(y_plane.width(), y_plane.height(), chroma_format, bit_depth)?;
+    check_planes(&image, y_plane, uv_plane)?;
+    let shift = 16 - bit_depth;
+    let (width, chroma_width) = (image.width, image.chroma_width());
+    for (y, row) in image.y.chunks_exact_mut(width.max(1)).enumerate() {
+        for (sample, bytes) in row.iter_mut().zip(y_plane.row(y).chunks_exact(2)) {
+            *sample = u16::from_le_bytes([bytes[0], bytes[1]]) >> shift;
+        }
+    }
+    let chroma_rows = image
+        .cb
+        .chunks_exact_mut(chroma_width.max(1))
+        .zip(image.cr.chunks_exact_mut(chroma_width.max(1)));
+    for (y, (cb_row, cr_row)) in chroma_rows.enumerate() {
+        let pairs = uv_plane.row(y).chunks_exact(4);
+        for ((cb, cr), bytes) in cb_row.iter_mut().zip(cr_row.iter_mut()).zip(pairs) {
+            *cb = u16::from_le_bytes([bytes[0], bytes[1]]) >> shift;
+            *cr = u16::from_le_bytes([bytes[2], bytes[3]]) >> shift;
+        }
+    }
+    Ok(image)
+}
+
+/// Packs `image` into bi-planar Y'CbCr with MSB-aligned 16-bit containers, leaving the low
+/// bits zero. The planes must match the image's size and chroma format.
+pub fn pack_bi_planar_16(
+    image: &PlanarYCbCr16,
+    y_plane: &mut PlaneMut<'_>,
+    uv_plane: &mut PlaneMut<'_>,
+) -> Result<(), CVPixelBufferError> {
+    image.check()?;
+    if y_plane.width() != image.width || y_plane.height() != image.height {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    check_planes(image, &y_plane.as_plane(), &uv_plane.as_plane())?;
+    let shift = 16 - image.bit_depth;
+    let max = image.max_value();
+    let (width, chroma_width) = (image.width, image.chroma_width());
+    for (y, row) in image.y.chunks_exact(width.max(1)).enumerate() {
+        for (sample, bytes) in row.iter().zip(y_plane.row_mut(y).chunks_exact_mut(2)) {
+            bytes.copy_from_slice(&((*sample).min(max) << shift).to_le_bytes());
+        }
+    }
+    let chroma_rows = image
+        .cb
+        .chunks_exact(chroma_width.max(1))
+        .zip(image.cr.chunks_exact(chroma_width.max(1)));
+    for (y, (cb_row, cr_row)) in chroma_rows.enumerate() {
+        let pairs = uv_plane.row_mut(y).chunks_exact_mut(4);
+        for ((cb, cr), bytes) in cb_row.iter().zip(cr_row).zip(pairs) {
+            bytes[0..2].copy_from_slice(&((*cb).min(max) << shift).to_le_bytes());
+            bytes[2..4].copy_from_slice(&((*cr).min(max) << shift).to_le_bytes());
+        }
+    }
+    Ok(())
+}
+
+fn check_planes(
+    image: &PlanarYCbCr16,
+    y_plane: &Plane<'_>,
+    uv_plane: &Plane<'_>,
+) -> Result<(), CVPixelBufferError> {
+    let (chroma_width, chroma_height) = (image.chroma_width(), image.chroma_height());
+    if y_plane.bytes_per_row() < 2 * image.width
+        || uv_plane.width() < chroma_width
+        || uv_plane.height() < chroma_height
+        || uv_plane.bytes_per_row() < 4 * chroma_width
+    {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    Ok(())
+}
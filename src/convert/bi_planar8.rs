@@ -0,0 +1,86 @@
+use crate::cv_pixel_buffer::{
+    error::CVPixelBufferError,
+    plane::{Plane, PlaneMut},
+};
+
+use super::{ChromaFormat, PlanarYCbCr16};
+
+/// Unpacks 8-bit bi-planar Y'CbCr such as 420v, 420f, 422v or 444v.
+pub fn unpack_bi_planar_8(
+    y_plane: &Plane<'_>,
+    uv_plane: &Plane<'_>,
+    chroma_format: ChromaFormat,
+) -> Result<PlanarYCbCr16, CVPixelBufferError> {
+    let mut image = PlanarYCbCr16::new(y_plane.width(), y_plane.height(), chroma_format, 8)?;
+    check_planes(&image, y_plane, uv_plane)?;
+    let (width, chroma_width) = (image.width, image.chroma_width());
+    for (y, row) in image.y.chunks_exact_mut(width.max(1)).enumerate() {
+        for (sample, &byte) in row.iter_mut().zip(y_plane.row(y)) {
+            *sample = byte as u16;
+        }
+    }
+    let chroma_rows = image
+        .cb
+        .chunks_exact_mut(chroma_width.max(1))
+        .zip(image.cr.chunks_exact_mut(chroma_width.max(1)));
+    for (y, (cb_row, cr_row)) in chroma_rows.enumerate() {
+        let pairs = uv_plane.row(y).chunks_exact(2);
+        for ((cb, cr), bytes) in cb_row.iter_mut().zip(cr_row.iter_mut()).zip(pairs) {
+            *cb = bytes[0] as u16;
+            *cr = bytes[1] as u16;
+        }
+    }
+    Ok(image)
+}
+
+/// Packs an 8-bit `image` into bi-planar Y'CbCr. The planes must match the image's size and
+/// chroma format.
+pub fn pack_bi_planar_8(
+    image: &PlanarYCbCr16,
+    y_plane: &mut PlaneMut<'_>,
+    uv_plane: &mut PlaneMut<'_>,
+) -> Result<(), CVPixelBufferError> {
+    image.check()?;
+    if image.bit_depth != 8 {
+        return Err(CVPixelBufferError::InvalidArgument);
+    }
+    if y_plane.width() != image.width || y_plane.height() != image.height {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    check_planes(image, &y_plane.as_plane(), &uv_plane.as_plane())?;
+    let max = image.max_value();
+    let (width, chroma_width) = (image.width, image.chroma_width());
+    for (y, row) in image.y.chunks_exact(width.max(1)).enumerate() {
+        for (sample, byte) in row.iter().zip(y_plane.row_mut(y).iter_mut()) {
+            *byte = (*sample).min(max) as u8;
+        }
+    }
+    let chroma_rows = image
+        .cb
+        .chunks_exact(chroma_width.max(1))
+        .zip(image.cr.chunks_exact(chroma_width.max(1)));
+    for (y, (cb_row, cr_row)) in chroma_rows.enumerate() {
+        let pairs = uv_plane.row_mut(y).chunks_exact_mut(2);
+        for ((cb, cr), bytes) in cb_row.iter().zip(cr_row).zip(pairs) {
+            bytes[0] = (*cb).min(max) as u8;
+            bytes[1] = (*cr).min(max) as u8;
+        }
+    }
+    Ok(())
+}
+
+fn check_planes(
+    image: &PlanarYCbCr16,
+    y_plane: &Plane<'_>,
+    uv_plane: &Plane<'_>,
+) -> Result<(), CVPixelBufferError> {
+    let (chroma_width, chroma_height) = (image.chroma_width(), image.chroma_height());
+    if y_plane.bytes_per_row() < image.width
+        || uv_plane.width() < chroma_width
+        || uv_plane.height() < chroma_height
+        || uv_plane.bytes_per_row() < 2 * chroma_width
+    {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    Ok(())
+}
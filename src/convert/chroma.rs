@@ -0,0 +1,87 @@
+use crate::constants::ChromaLocation;
+
+/// Position of subsampled chroma samples relative to luma, used when downsampling to 4:2:0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChromaSiting {
+    /// Co-sited with the left luma column, between the two rows. The H.264/HEVC default.
+    #[default]
+    Left,
+    Center,
+    TopLeft,
+    Top,
+    BottomLeft,
+    Bottom,
+}
+
+impl ChromaSiting {
+    /// `(column offset, weight)` taps around even column `x`, with weights summing to
+    /// `1 << shift`.
+    pub(crate) fn horizontal_taps(&self) -> (&'static [(isize, i64)], u32) {
+        match self {
+            Self::Left | Self::TopLeft | Self::BottomLeft => (&[(-1, 1), (0, 2), (1, 1)], 2),
+            Self::Center | Self::Top | Self::Bottom => (&[(0, 1), (1, 1)], 1),
+        }
+    }
+    /// Row offsets around even row `y`, with weights summing to `1 << shift`.
+    pub(crate) fn vertical_taps(&self) -> (&'static [isize], u32) {
+        match self {
+            Self::Left | Self::Center => (&[0, 1], 1),
+            Self::TopLeft | Self::Top => (&[0], 0),
+            Self::BottomLeft | Self::Bottom => (&[1], 0),
+        }
+    }
+    /// Horizontal and vertical position of a chroma sample relative to the top-left luma
+    /// sample it is grouped with, in luma samples.
+    pub(crate) fn offsets(&self) -> (f32, f32) {
+        let horizontal = match self {
+            Self::Left | Self::TopLeft | Self::BottomLeft => 0.0,
+            Self::Center | Self::Top | Self::Bottom => 0.5,
+        };
+        let vertical = match self {
+            Self::TopLeft | Self::Top => 0.0,
+            Self::Left | Self::Center => 0.5,
+            Self::BottomLeft | Self::Bottom => 1.0,
+        };
+        (horizontal, vertical)
+    }
+    /// Inverse of [`ChromaSiting::offsets`]; `None` for positions no variant describes.
+    pub(crate) fn from_offsets(horizontal: f32, vertical: f32) -> Option<Self> {
+        [
+            Self::Left,
+            Self::Center,
+            Self::TopLeft,
+            Self::Top,
+            Self::BottomLeft,
+            Self::Bottom,
+        ]
+        .into_iter()
+        .find(|siting| siting.offsets() == (horizontal, vertical))
+    }
+}
+
+impl From<&ChromaLocation> for ChromaSiting {
+    /// DV 4:2:0 and unknown locations fall back to [`ChromaSiting::Left`].
+    fn from(val: &ChromaLocation) -> Self {
+        match val {
+            ChromaLocation::Center => Self::Center,
+            ChromaLocation::TopLeft => Self::TopLeft,
+            ChromaLocation::Top => Self::Top,
+            ChromaLocation::BottomLeft => Self::BottomLeft,
+            ChromaLocation::Bottom => Self::Bottom,
+            _ => Self::Left,
+        }
+    }
+}
+
+impl From<ChromaSiting> for ChromaLocation {
+    fn from(val: ChromaSiting) -> Self {
+        match val {
+            ChromaSiting::Left => Self::Left,
+            ChromaSiting::Center => Self::Center,
+            ChromaSiting::TopLeft => Self::TopLeft,
+            ChromaSiting::Top => Self::Top,
+            ChromaSiting::BottomLeft => Self::BottomLeft,
+            ChromaSiting::Bottom => Self::Bottom,
+        }
+    }
+}
@@ -0,0 +1,353 @@
+//! Software pixel format conversion.
+//!
+//! Conversions run on locked plane views, so they work the same on [`CVPixelBuffer`]s and on
+//! [`SoftwarePixelBuffer`]s.
+//!
+//! [`CVPixelBuffer`]: crate::cv_pixel_buffer::CVPixelBuffer
+//! [`SoftwarePixelBuffer`]: crate::cv_pixel_buffer::SoftwarePixelBuffer
+
+mod bi_planar16;
+mod bi_planar8;
+mod chroma;
+mod packed422;
+mod planar16;
+mod planar8;
+mod rgb;
+mod rgb_nv12;
+mod v210;
+mod y416;
+mod ycbcr;
+
+pub use bi_planar16::{pack_bi_planar_16, unpack_bi_planar_16};
+pub use bi_planar8::{pack_bi_planar_8, unpack_bi_planar_8};
+pub use chroma::ChromaSiting;
+pub use packed422::{pack_packed_422, unpack_packed_422, Packed422Order};
+pub use planar16::{ChromaFormat, PlanarYCbCr16};
+pub use planar8::{pack_planar_8, unpack_planar_8};
+pub use rgb::RgbLayout;
+pub use rgb_nv12::{nv12_to_rgb, rgb_to_nv12};
+pub use v210::{pack_v210, unpack_v210, v210_bytes_per_row};
+pub use y416::{pack_y416, unpack_y416};
+pub use ycbcr::{YCbCrCoefficients, YCbCrRange};
+
+use crate::{
+    attachments::{CVAttachmentMode, ImageBufferAttachment, ImageBufferAttachmentKey},
+    constants::{ChromaLocation, YCbCrMatrix},
+    cv_pixel_buffer::{
+        error::CVPixelBufferError,
+        pixel_format::PixelFormat,
+        plane::{Plane, PlaneMut},
+        PixelBufferLike,
+    },
+};
+
+/// Overrides for values otherwise read from the source buffer's attachments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConvertOptions {
+    /// Defaults to the source `YCbCrMatrix` attachment, then to BT.709.
+    pub matrix: Option<YCbCrMatrix>,
+    /// Defaults to the source `ChromaLocationTopField` attachment, then to
+    /// [`ChromaSiting::Left`].
+    pub chroma_siting: Option<ChromaSiting>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Layout {
+    Rgb(RgbLayout),
+    YCbCr(Packing, YCbCrRange),
+}
+
+/// Y'CbCr formats that unpack to [`PlanarYCbCr16`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Packing {
+    /// Luma plane and interleaved CbCr plane; 8-bit samples or MSB-aligned 16-bit containers.
+    BiPlanar(ChromaFormat, u32),
+    /// Separate 8-bit Y', Cb and Cr planes.
+    Planar(ChromaFormat),
+    Packed422(Packed422Order),
+    V210,
+    Y416,
+}
+
+const NV12: Packing = Packing::BiPlanar(ChromaFormat::Yuv420, 8);
+
+impl Layout {
+    fn from_pixel_format(pixel_format: PixelFormat) -> Option<Self> {
+        use {ChromaFormat::*, Packing::*, YCbCrRange::*};
+        let (packing, range) = match pixel_format {
+            PixelFormat::YCbCr420_8BiPlanarVideoRange => (NV12, Video),
+            PixelFormat::YCbCr420_8BiPlanarFullRange => (NV12, Full),
+            PixelFormat::YCbCr422_8BiPlanarVideoRange => (BiPlanar(Yuv422, 8), Video),
+            PixelFormat::YCbCr422_8BiPlanarFullRange => (BiPlanar(Yuv422, 8), Full),
+            PixelFormat::YCbCr420_8Planar => (Planar(Yuv420), Video),
+            PixelFormat::YCbCr420_8PlanarFullRange => (Planar(Yuv420), Full),
+            PixelFormat::YCbCr422_8 => (Packed422(Packed422Order::CbYCrY), Video),
+            PixelFormat::YCbCr422_8Yuvs => (Packed422(Packed422Order::YCbYCr), Video),
+            PixelFormat::YCbCr422_8FullRange => (Packed422(Packed422Order::YCbYCr), Full),
+            PixelFormat::YCbCr420_10BiPlanarVideoRange => (BiPlanar(Yuv420, 10), Video),
+            PixelFormat::YCbCr420_10BiPlanarFullRange => (BiPlanar(Yuv420, 10), Full),
+            PixelFormat::YCbCr422_10BiPlanarVideoRange => (BiPlanar(Yuv422, 10), Video),
+            PixelFormat::YCbCr422_10BiPlanarFullRange => (BiPlanar(Yuv422, 10), Full),
+            PixelFormat::YCbCr444_10BiPlanarVideoRange => (BiPlanar(Yuv444, 10), Video),
+            PixelFormat::YCbCr444_10BiPlanarFullRange => (BiPlanar(Yuv444, 10), Full),
+            PixelFormat::YCbCr422_16BiPlanarVideoRange => (BiPlanar(Yuv422, 16), Video),
+            PixelFormat::YCbCr422_10 => (V210, Video),
+            PixelFormat::AYCbCr4444_16 => (Y416, Video),
+            _ => return RgbLayout::from_pixel_format(pixel_format).map(Self::Rgb),
+        };
+        Some(Self::YCbCr(packing, range))
+    }
+    fn is_ycbcr(&self) -> bool {
+        !matches!(self, Self::Rgb(_))
+    }
+}
+
+/// Whether [`unpack`] and [`pack`] support `pixel_format`.
+pub(crate) fn is_ycbcr_format(pixel_format: PixelFormat) -> bool {
+    matches!(
+        Layout::from_pixel_format(pixel_format),
+        Some(Layout::YCbCr(..))
+    )
+}
+
+/// Converts `src` into `dst`, which must have the same dimensions.
+///
+/// Supports 32BGRA, 32RGBA and 32ARGB to and from 420v and 420f, and conversion between any
+/// two Y'CbCr formats of the same range through [`PlanarYCbCr16`]: 420v, 420f, 422v, 422f,
+/// y420, f420, 2vuy, yuvs, yuvf, x420, xf20, x422, xf22, x444, xf44, sv22, v210 and y416.
+/// Y'CbCr destinations get `YCbCrMatrix` and `ChromaLocationTopField` attachments describing the conversion, and
+/// color primaries and transfer function attachments are copied from the source.
+pub fn convert<S, D>(src: &S, dst: &mut D) -> Result<(), CVPixelBufferError>
+where
+    S: PixelBufferLike + ?Sized,
+    D: PixelBufferLike + ?Sized,
+{
+    convert_with_options(src, dst, &ConvertOptions::default())
+}
+
+pub fn convert_with_options<S, D>(
+    src: &S,
+    dst: &mut D,
+    options: &ConvertOptions,
+) -> Result<(), CVPixelBufferError>
+where
+    S: PixelBufferLike + ?Sized,
+    D: PixelBufferLike + ?Sized,
+{
+    if src.get_width() != dst.get_width() || src.get_height() != dst.get_height() {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    let src_layout = Layout::from_pixel_format(src.get_pixel_format()?)
+        .ok_or(CVPixelBufferError::InvalidPixelFormat)?;
+    let dst_layout = Layout::from_pixel_format(dst.get_pixel_format()?)
+        .ok_or(CVPixelBufferError::InvalidPixelFormat)?;
+
+    let matrix = options
+        .matrix
+        .clone()
+        .or_else(
+            || match src.get_attachment(ImageBufferAttachmentKey::YCbCrMatrix) {
+                Some((ImageBufferAttachment::YCbCrMatrix(matrix), _)) => Some(matrix),
+                _ => None,
+            },
+        )
+        .unwrap_or(YCbCrMatrix::ItuR709_2);
+    let siting = options
+        .chroma_siting
+        .or_else(
+            || match src.get_attachment(ImageBufferAttachmentKey::ChromaLocationTopField) {
+                Some((ImageBufferAttachment::ChromaLocationTopField(location), _)) => {
+                    Some(ChromaSiting::from(&location))
+                }
+                _ => None,
+            },
+        )
+        .unwrap_or_default();
+
+    {
+        let src_guard = src.lock()?;
+        let src_planes = src_guard.planes()?;
+        let mut dst_guard = dst.lock_mut()?;
+        let mut dst_planes = dst_guard.planes_mut()?;
+        convert_planes(
+            src_layout,
+            &src_planes,
+            dst_layout,
+            &mut dst_planes,
+            &matrix,
+            siting,
+        )?;
+    }
+
+    for key in [
+        ImageBufferAttachmentKey::ColorPrimaries,
+        ImageBufferAttachmentKey::TransferFunction,
+    ] {
+        if let Some((attachment, mode)) = src.get_attachment(key) {
+            dst.set_attachment(&attachment, mode);
+        }
+    }
+    if dst_layout.is_ycbcr() {
+        dst.set_attachment(
+            &ImageBufferAttachment::YCbCrMatrix(matrix),
+            CVAttachmentMode::ShouldPropagate,
+        );
+        dst.set_attachment(
+            &ImageBufferAttachment::ChromaLocationTopField(ChromaLocation::from(siting)),
+            CVAttachmentMode::ShouldPropagate,
+        );
+    }
+    Ok(())
+}
+
+fn convert_planes(
+    src_layout: Layout,
+    src: &[Plane<'_>],
+    dst_layout: Layout,
+    dst: &mut [PlaneMut<'_>],
+    matrix: &YCbCrMatrix,
+    siting: ChromaSiting,
+) -> Result<(), CVPixelBufferError> {
+    match (src_layout, dst_layout, src, dst) {
+        (Layout::Rgb(src_rgb), Layout::YCbCr(NV12, range), [rgb], [y_plane, uv_plane]) => {
+            let coefficients = YCbCrCoefficients::new(matrix, range)?;
+            rgb_to_nv12(rgb, src_rgb, y_plane, uv_plane, &coefficients, siting)
+        }
+        (Layout::YCbCr(NV12, range), Layout::Rgb(dst_rgb), [y_plane, uv_plane], [rgb]) => {
+            let coefficients = YCbCrCoefficients::new(matrix, range)?;
+            nv12_to_rgb(y_plane, uv_plane, rgb, dst_rgb, &coefficients)
+        }
+        (Layout::Rgb(src_rgb), Layout::Rgb(dst_rgb), [src], [dst]) => {
+            swizzle_rgb(src, src_rgb, dst, dst_rgb)
+        }
+        (
+            Layout::YCbCr(src_packing, src_range),
+            Layout::YCbCr(dst_packing, dst_range),
+            src,
+            dst,
+        ) if src_range == dst_range => {
+            let image = unpack_planes(src_packing, src)?;
+            let (chroma_format, bit_depth) = dst_packing.chroma_format_and_bit_depth();
+            let image = image
+                .with_chroma_format(chroma_format)
+                .with_bit_depth(bit_depth, dst_range)?;
+            pack_planes(&image, dst_packing, dst)
+        }
+        (Layout::Rgb(_), Layout::Rgb(_), _, _)
+        | (Layout::Rgb(_), Layout::YCbCr(NV12, _), _, _)
+        | (Layout::YCbCr(NV12, _), Layout::Rgb(_), _, _) => Err(CVPixelBufferError::InvalidSize),
+        // RGB converts to and from 420v and 420f only.
+        (Layout::Rgb(_), Layout::YCbCr(..), _, _) | (Layout::YCbCr(..), Layout::Rgb(_), _, _) => {
+            Err(CVPixelBufferError::InvalidPixelFormat)
+        }
+        _ => Err(CVPixelBufferError::Unsupported),
+    }
+}
+
+impl Packing {
+    fn chroma_format_and_bit_depth(&self) -> (ChromaFormat, u32) {
+        match *self {
+            Self::BiPlanar(chroma_format, bit_depth) => (chroma_format, bit_depth),
+            Self::Planar(chroma_format) => (chroma_format, 8),
+            Self::Packed422(_) => (ChromaFormat::Yuv422, 8),
+            Self::V210 => (ChromaFormat::Yuv422, 10),
+            Self::Y416 => (ChromaFormat::Yuv444, 16),
+        }
+    }
+}
+
+/// Unpacks a locked Y'CbCr buffer, such as 2vuy, x420, v210 or y416.
+pub fn unpack<S>(src: &S) -> Result<PlanarYCbCr16, CVPixelBufferError>
+where
+    S: PixelBufferLike + ?Sized,
+{
+    let Some(Layout::YCbCr(packing, _)) = Layout::from_pixel_format(src.get_pixel_format()?) else {
+        return Err(CVPixelBufferError::InvalidPixelFormat);
+    };
+    let guard = src.lock()?;
+    unpack_planes(packing, &guard.planes()?)
+}
+
+/// Packs `image` into a Y'CbCr buffer of the same size. The image's chroma format and bit
+/// depth must match the destination format.
+pub fn pack<D>(image: &PlanarYCbCr16, dst: &mut D) -> Result<(), CVPixelBufferError>
+where
+    D: PixelBufferLike + ?Sized,
+{
+    let Some(Layout::YCbCr(packing, _)) = Layout::from_pixel_format(dst.get_pixel_format()?) else {
+        return Err(CVPixelBufferError::InvalidPixelFormat);
+    };
+    if packing.chroma_format_and_bit_depth() != (image.chroma_format, image.bit_depth) {
+        return Err(CVPixelBufferError::InvalidArgument);
+    }
+    let mut guard = dst.lock_mut()?;
+    pack_planes(image, packing, &mut guard.planes_mut()?)
+}
+
+fn unpack_planes(
+    packing: Packing,
+    planes: &[Plane<'_>],
+) -> Result<PlanarYCbCr16, CVPixelBufferError> {
+    match (packing, planes) {
+        (Packing::BiPlanar(chroma_format, 8), [y_plane, uv_plane]) => {
+            unpack_bi_planar_8(y_plane, uv_plane, chroma_format)
+        }
+        (Packing::BiPlanar(chroma_format, bit_depth), [y_plane, uv_plane]) => {
+            unpack_bi_planar_16(y_plane, uv_plane, chroma_format, bit_depth)
+        }
+        (Packing::Planar(chroma_format), [y_plane, cb_plane, cr_plane]) => {
+            unpack_planar_8(y_plane, cb_plane, cr_plane, chroma_format)
+        }
+        (Packing::Packed422(order), [plane]) => unpack_packed_422(plane, order),
+        (Packing::V210, [plane]) => unpack_v210(plane),
+        (Packing::Y416, [plane]) => unpack_y416(plane),
+        _ => Err(CVPixelBufferError::InvalidSize),
+    }
+}
+
+fn pack_planes(
+    image: &PlanarYCbCr16,
+    packing: Packing,
+    planes: &mut [PlaneMut<'_>],
+) -> Result<(), CVPixelBufferError> {
+    match (packing, planes) {
+        (Packing::BiPlanar(_, 8), [y_plane, uv_plane]) => {
+            pack_bi_planar_8(image, y_plane, uv_plane)
+        }
+        (Packing::BiPlanar(..), [y_plane, uv_plane]) => pack_bi_planar_16(image, y_plane, uv_plane),
+        (Packing::Planar(_), [y_plane, cb_plane, cr_plane]) => {
+            pack_planar_8(image, y_plane, cb_plane, cr_plane)
+        }
+        (Packing::Packed422(order), [plane]) => pack_packed_422(image, plane, order),
+        (Packing::V210, [plane]) => pack_v210(image, plane),
+        (Packing::Y416, [plane]) => pack_y416(image, plane),
+        _ => Err(CVPixelBufferError::InvalidSize),
+    }
+}
+
+fn swizzle_rgb(
+    src: &Plane<'_>,
+    src_layout: RgbLayout,
+    dst: &mut PlaneMut<'_>,
+    dst_layout: RgbLayout,
+) -> Result<(), CVPixelBufferError> {
+    let (width, height) = (src.width(), src.height());
+    rgb_nv12::check_rgb_plane(src, width, height)?;
+    rgb_nv12::check_rgb_plane(&dst.as_plane(), width, height)?;
+    for y in 0..height {
+        let src_row = src.row(y);
+        let dst_row = dst.row_mut(y);
+        for (src_pixel, dst_pixel) in src_row
+            .chunks_exact(4)
+            .zip(dst_row.chunks_exact_mut(4))
+            .take(width)
+        {
+            dst_layout.write(
+                dst_pixel,
+                src_layout.read(src_pixel),
+                src_pixel[src_layout.a],
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::cv_pixel_buffer::{
+    error::CVPixelBufferError,
+    plane::{Plane, PlaneMut},
+};
+
+use super::{ChromaFormat, PlanarYCbCr16};
+
+const BLOCK_SIZE: usize = 4;
+
+/// Byte order of a packed 8-bit 4:2:2 pixel pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Packed422Order {
+    /// Cb Y'0 Cr Y'1, as in '2vuy' (UYVY).
+    CbYCrY,
+    /// Y'0 Cb Y'1 Cr, as in 'yuvs' and 'yuvf' (YUY2).
+    YCbYCr,
+}
+
+impl Packed422Order {
+    /// Offsets of Y'0, Cb, Y'1 and Cr within a block.
+    fn offsets(&self) -> [usize; 4] {
+        match self {
+            Self::CbYCrY => [1, 0, 3, 2],
+            Self::YCbYCr => [0, 1, 2, 3],
+        }
+    }
+}
+
+/// Unpacks packed 8-bit 4:2:2 into a planar 4:2:2 image. For odd widths the second luma
+/// sample of the last pair is ignored.
+pub fn unpack_packed_422(
+    plane: &Plane<'_>,
+    order: Packed422Order,
+) -> Result<PlanarYCbCr16, CVPixelBufferError> {
+    let mut image = PlanarYCbCr16::new(plane.width(), plane.height(), ChromaFormat::Yuv422, 8)?;
+    check_plane(plane, image.width)?;
+    let [y0, cb, y1, cr] = order.offsets();
+    let (width, chroma_width) = (image.width, image.chroma_width());
+    for row in 0..image.height {
+        let blocks = plane.row(row).chunks_exact(BLOCK_SIZE).take(chroma_width);
+        for (x, block) in blocks.enumerate() {
+            image.y[row * width + 2 * x] = block[y0] as u16;
+            if 2 * x + 1 < width {
+                image.y[row * width + 2 * x + 1] = block[y1] as u16;
+            }
+            image.cb[row * chroma_width + x] = block[cb] as u16;
+            image.cr[row * chroma_width + x] = block[cr] as u16;
+        }
+    }
+    Ok(image)
+}
+
+/// Packs an 8-bit 4:2:2 image. For odd widths the last luma sample is repeated to fill the
+/// final pair; row padding beyond the last pair is left untouched.
+pub fn pack_packed_422(
+    image: &PlanarYCbCr16,
+    plane: &mut PlaneMut<'_>,
+    order: Packed422Order,
+) -> Result<(), CVPixelBufferError> {
+    image.check()?;
+    if image.chroma_format != ChromaFormat::Yuv422 || image.bit_depth != 8 {
+        return Err(CVPixelBufferError::InvalidArgument);
+    }
+    if plane.width() != image.width || plane.height() != image.height {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    check_plane(&plane.as_plane(), image.width)?;
+    let [y0, cb, y1, cr] = order.offsets();
+    let (width, chroma_width) = (image.width, image.chroma_width());
+    let byte = |sample: u16| sample.min(u8::MAX as u16) as u8;
+    for row in 0..image.height {
+        let luma = &image.y[row * width..(row + 1) * width];
+        let blocks = plane
+            .row_mut(row)
+            .chunks_exact_mut(BLOCK_SIZE)
+            .take(chroma_width);
+        for (x, block) in blocks.enumerate() {
+            block[y0] = byte(luma[2 * x]);
+            block[y1] = byte(luma[(2 * x + 1).min(width - 1)]);
+            block[cb] = byte(image.cb[row * chroma_width + x]);
+            block[cr] = byte(image.cr[row * chroma_width + x]);
+        }
+    }
+    Ok(())
+}
+
+fn check_plane(plane: &Plane<'_>, width: usize) -> Result<(), CVPixelBufferError> {
+    if plane.bytes_per_row() < width.div_ceil(2) * BLOCK_SIZE {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::cv_pixel_buffer::error::CVPixelBufferError;
+
+use super::YCbCrRange;
+
+/// Chroma subsampling of a Y'CbCr image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChromaFormat {
+    Yuv420,
+    Yuv422,
+    Yuv444,
+}
+
+impl ChromaFormat {
+    /// Horizontal and vertical subsampling factors.
+    pub fn factors(&self) -> (usize, usize) {
+        match self {
+            Self::Yuv420 => (2, 2),
+            Self::Yuv422 => (2, 1),
+            Self::Yuv444 => (1, 1),
+        }
+    }
+    pub fn chroma_size(&self, width: usize, height: usize) -> (usize, usize) {
+        let (h, v) = self.factors();
+        (width.div_ceil(h), height.div_ceil(v))
+    }
+}
+
+/// Planar Y'CbCr image with one `u16` per sample, LSB-aligned to `bit_depth` bits.
+///
+/// This is the intermediate the Y'CbCr packers and unpackers convert through. Planes
+/// are tightly packed: luma and alpha rows hold `width` samples, chroma rows hold the
+/// subsampled width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanarYCbCr16 {
+    pub width: usize,
+    pub height: usize,
+    pub chroma_format: ChromaFormat,
+    pub bit_depth: u32,
+    pub y: Vec<u16>,
+    pub cb: Vec<u16>,
+    pub cr: Vec<u16>,
+    /// Full-range alpha, if the source format carries it.
+    pub alpha: Option<Vec<u16>>,
+}
+
+impl PlanarYCbCr16 {
+    /// Creates a zero-filled image without alpha.
+    pub fn new(
+        width: usize,
+        height: usize,
+        chroma_format: ChromaFormat,
+        bit_depth: u32,
+    ) -> Result<Self, CVPixelBufferError> {
+        if !(1..=16).contains(&bit_depth) {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let (chroma_width, chroma_height) = chroma_format.chroma_size(width, height);
+        Ok(Self {
+            width,
+            height,
+            chroma_format,
+            bit_depth,
+            y: vec![0; width * height],
+            cb: vec![0; chroma_width * chroma_height],
+            cr: vec![0; chroma_width * chroma_height],
+            alpha: None,
+        })
+    }
+    pub fn chroma_width(&self) -> usize {
+        self.chroma_format.chroma_size(self.width, self.height).0
+    }
+    pub fn chroma_height(&self) -> usize {
+        self.chroma_format.chroma_size(self.width, self.height).1
+    }
+    pub fn max_value(&self) -> u16 {
+        max_value(self.bit_depth)
+    }
+
+    /// Rescales samples to `bit_depth`. Video range samples are shifted so that the nominal
+    /// black and white levels stay exact; full range samples and alpha are scaled to the new
+    /// maximum.
+    pub fn with_bit_depth(
+        &self,
+        bit_depth: u32,
+        range: YCbCrRange,
+    ) -> Result<Self, CVPixelBufferError> {
+        if !(1..=16).contains(&bit_depth) {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let (from, to) = (self.bit_depth, bit_depth);
+        let full = |value: &u16| {
+            let (from_max, to_max) = (max_value(from) as u32, max_value(to) as u32);
+            ((*value as u32 * to_max + from_max / 2) / from_max) as u16
+        };
+        let video = |value: &u16| {
+            if to >= from {
+                *value << (to - from)
+            } else {
+                let shift = from - to;
+                let rounded = (*value as u32 + (1 << (shift - 1))) >> shift;
+                rounded.min(max_value(to) as u32) as u16
+            }
+        };
+        let rescale = |samples: &[u16]| -> Vec<u16> {
+            match range {
+                YCbCrRange::Video => samples.iter().map(video).collect(),
+                YCbCrRange::Full => samples.iter().map(full).collect(),
+            }
+        };
+        Ok(Self {
+            bit_depth,
+            y: rescale(&self.y),
+            cb: rescale(&self.cb),
+            cr: rescale(&self.cr),
+            alpha: self
+                .alpha
+                .as_ref()
+                .map(|alpha| alpha.iter().map(full).collect()),
+            ..self.clone()
+        })
+    }
+
+    /// Resamples chroma to `chroma_format`, averaging when subsampling and replicating when
+    /// upsampling.
+    pub fn with_chroma_format(&self, chroma_format: ChromaFormat) -> Self {
+        let (src_h, src_v) = self.chroma_format.factors();
+        let (dst_h, dst_v) = chroma_format.factors();
+        let (src_width, src_height) = (self.chroma_width(), self.chroma_height());
+        let (dst_width, dst_height) = chroma_format.chroma_size(self.width, self.height);
+        let resample = |samples: &[u16]| -> Vec<u16> {
+            let mut result = Vec::with_capacity(dst_width * dst_height);
+            for y in 0..dst_height {
+                // The block of source chroma samples covering the same luma area.
+                let rows = (y * dst_v / src_v)..((y + 1) * dst_v).div_ceil(src_v).min(src_height);
+                for x in 0..dst_width {
+                    let columns =
+                        (x * dst_h / src_h)..((x + 1) * dst_h).div_ceil(src_h).min(src_width);
+                    let mut sum = 0u32;
+                    let mut count = 0u32;
+                    for row in rows.clone() {
+                        for &sample in &samples[row * src_width..][columns.clone()] {
+                            sum += sample as u32;
+                            count += 1;
+                        }
+                    }
+                    result.push(((sum + count / 2) / count.max(1)) as u16);
+                }
+            }
+            result
+        };
+        Self {
+            chroma_format,
+            cb: resample(&self.cb),
+            cr: resample(&self.cr),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn check(&self) -> Result<(), CVPixelBufferError> {
+        let luma = self.width * self.height;
+        let chroma = self.chroma_width() * self.chroma_height();
+        if self.y.len() != luma
+            || self.cb.len() != chroma
+            || self.cr.len() != chroma
+            || self.alpha.as_ref().is_some_and(|alpha| alpha.len() != luma)
+        {
+            return Err(CVPixelBufferError::InvalidSize);
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn max_value(bit_depth: u32) -> u16 {
+    (u16::MAX as u32 >> (16 - bit_depth)) as u16
+}
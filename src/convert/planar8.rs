@@ -0,0 +1,84 @@
+use crate::cv_pixel_buffer::{
+    error::CVPixelBufferError,
+    plane::{Plane, PlaneMut},
+};
+
+use super::{ChromaFormat, PlanarYCbCr16};
+
+/// Unpacks 8-bit tri-planar Y'CbCr such as y420 and f420.
+pub fn unpack_planar_8(
+    y_plane: &Plane<'_>,
+    cb_plane: &Plane<'_>,
+    cr_plane: &Plane<'_>,
+    chroma_format: ChromaFormat,
+) -> Result<PlanarYCbCr16, CVPixelBufferError> {
+    let mut image = PlanarYCbCr16::new(y_plane.width(), y_plane.height(), chroma_format, 8)?;
+    check_planes(&image, y_plane, cb_plane, cr_plane)?;
+    let (width, chroma_width) = (image.width, image.chroma_width());
+    for (samples, plane, width) in [
+        (&mut image.y, y_plane, width),
+        (&mut image.cb, cb_plane, chroma_width),
+        (&mut image.cr, cr_plane, chroma_width),
+    ] {
+        for (y, row) in samples.chunks_exact_mut(width.max(1)).enumerate() {
+            for (sample, &byte) in row.iter_mut().zip(plane.row(y)) {
+                *sample = byte as u16;
+            }
+        }
+    }
+    Ok(image)
+}
+
+/// Packs an 8-bit `image` into tri-planar Y'CbCr. The planes must match the image's size and
+/// chroma format.
+pub fn pack_planar_8(
+    image: &PlanarYCbCr16,
+    y_plane: &mut PlaneMut<'_>,
+    cb_plane: &mut PlaneMut<'_>,
+    cr_plane: &mut PlaneMut<'_>,
+) -> Result<(), CVPixelBufferError> {
+    image.check()?;
+    if image.bit_depth != 8 {
+        return Err(CVPixelBufferError::InvalidArgument);
+    }
+    if y_plane.width() != image.width || y_plane.height() != image.height {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    check_planes(
+        image,
+        &y_plane.as_plane(),
+        &cb_plane.as_plane(),
+        &cr_plane.as_plane(),
+    )?;
+    let chroma_width = image.chroma_width();
+    write_plane(&image.y, y_plane, image.width);
+    write_plane(&image.cb, cb_plane, chroma_width);
+    write_plane(&image.cr, cr_plane, chroma_width);
+    Ok(())
+}
+
+fn write_plane(samples: &[u16], plane: &mut PlaneMut<'_>, width: usize) {
+    for (y, row) in samples.chunks_exact(width.max(1)).enumerate() {
+        for (&sample, byte) in row.iter().zip(plane.row_mut(y)) {
+            *byte = sample.min(u8::MAX as u16) as u8;
+        }
+    }
+}
+
+fn check_planes(
+    image: &PlanarYCbCr16,
+    y_plane: &Plane<'_>,
+    cb_plane: &Plane<'_>,
+    cr_plane: &Plane<'_>,
+) -> Result<(), CVPixelBufferError> {
+    let (chroma_width, chroma_height) = (image.chroma_width(), image.chroma_height());
+    let chroma_fits = |plane: &Plane<'_>| {
+        plane.width() >= chroma_width
+            && plane.height() >= chroma_height
+            && plane.bytes_per_row() >= chroma_width
+    };
+    if y_plane.bytes_per_row() < image.width || !chroma_fits(cb_plane) || !chroma_fits(cr_plane) {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::cv_pixel_buffer::pixel_format::PixelFormat;
+
+/// Byte offsets of the channels within a 4-byte packed RGB pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RgbLayout {
+    pub r: usize,
+    pub g: usize,
+    pub b: usize,
+    pub a: usize,
+}
+
+impl RgbLayout {
+    pub const BGRA: Self = Self {
+        r: 2,
+        g: 1,
+        b: 0,
+        a: 3,
+    };
+    pub const RGBA: Self = Self {
+        r: 0,
+        g: 1,
+        b: 2,
+        a: 3,
+    };
+    pub const ARGB: Self = Self {
+        r: 1,
+        g: 2,
+        b: 3,
+        a: 0,
+    };
+
+    pub fn from_pixel_format(pixel_format: PixelFormat) -> Option<Self> {
+        match pixel_format {
+            PixelFormat::BGRA32 => Some(Self::BGRA),
+            PixelFormat::RGBA32 => Some(Self::RGBA),
+            PixelFormat::ARGB32 => Some(Self::ARGB),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn read(&self, pixel: &[u8]) -> [u8; 3] {
+        [pixel[self.r], pixel[self.g], pixel[self.b]]
+    }
+
+    pub(crate) fn write(&self, pixel: &mut [u8], [r, g, b]: [u8; 3], a: u8) {
+        pixel[self.r] = r;
+        pixel[self.g] = g;
+        pixel[self.b] = b;
+        pixel[self.a] = a;
+    }
+}
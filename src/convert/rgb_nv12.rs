@@ -0,0 +1,118 @@
+use crate::cv_pixel_buffer::{
+    error::CVPixelBufferError,
+    plane::{Plane, PlaneMut},
+};
+
+use super::{ChromaSiting, RgbLayout, YCbCrCoefficients};
+
+const RGB_PIXEL_SIZE: usize = 4;
+
+/// Converts packed RGB to bi-planar 4:2:0 Y'CbCr. The size of `y_plane` sets the image size.
+pub fn rgb_to_nv12(
+    src: &Plane<'_>,
+    layout: RgbLayout,
+    y_plane: &mut PlaneMut<'_>,
+    uv_plane: &mut PlaneMut<'_>,
+    coefficients: &YCbCrCoefficients,
+    siting: ChromaSiting,
+) -> Result<(), CVPixelBufferError> {
+    let (width, height) = (y_plane.width(), y_plane.height());
+    check_rgb_plane(src, width, height)?;
+    check_nv12_planes(&y_plane.as_plane(), &uv_plane.as_plane())?;
+    let pixel = |x: usize, y: usize| {
+        let offset = x * RGB_PIXEL_SIZE;
+        layout
+            .read(&src.row(y)[offset..offset + RGB_PIXEL_SIZE])
+            .map(i64::from)
+    };
+
+    for y in 0..height {
+        let row = y_plane.row_mut(y);
+        for (x, luma) in row[..width].iter_mut().enumerate() {
+            *luma = coefficients.luma(pixel(x, y));
+        }
+    }
+
+    let (h_taps, h_shift) = siting.horizontal_taps();
+    let (v_taps, v_shift) = siting.vertical_taps();
+    for cy in 0..height.div_ceil(2) {
+        let row = uv_plane.row_mut(cy);
+        for cx in 0..width.div_ceil(2) {
+            let mut sum = [0i64; 3];
+            for &dy in v_taps {
+                let y = clamp_index(2 * cy, dy, height);
+                for &(dx, weight) in h_taps {
+                    let x = clamp_index(2 * cx, dx, width);
+                    for (total, value) in sum.iter_mut().zip(pixel(x, y)) {
+                        *total += weight * value;
+                    }
+                }
+            }
+            row[2 * cx..2 * cx + 2].copy_from_slice(&coefficients.chroma(sum, h_shift + v_shift));
+        }
+    }
+    Ok(())
+}
+
+/// Converts bi-planar 4:2:0 Y'CbCr to packed RGB with opaque alpha. Chroma is replicated to
+/// each 2x2 block of luma samples.
+pub fn nv12_to_rgb(
+    y_plane: &Plane<'_>,
+    uv_plane: &Plane<'_>,
+    dst: &mut PlaneMut<'_>,
+    layout: RgbLayout,
+    coefficients: &YCbCrCoefficients,
+) -> Result<(), CVPixelBufferError> {
+    let (width, height) = (y_plane.width(), y_plane.height());
+    check_nv12_planes(y_plane, uv_plane)?;
+    check_rgb_plane(&dst.as_plane(), width, height)?;
+    for y in 0..height {
+        let luma = y_plane.row(y);
+        let chroma = uv_plane.row(y / 2);
+        let row = dst.row_mut(y);
+        for (x, (&luma, pixel)) in luma
+            .iter()
+            .zip(row.chunks_exact_mut(RGB_PIXEL_SIZE))
+            .take(width)
+            .enumerate()
+        {
+            let c = x / 2 * 2;
+            layout.write(
+                pixel,
+                coefficients.rgb(luma, chroma[c], chroma[c + 1]),
+                u8::MAX,
+            );
+        }
+    }
+    Ok(())
+}
+
+fn clamp_index(base: usize, offset: isize, len: usize) -> usize {
+    base.saturating_add_signed(offset).min(len - 1)
+}
+
+pub(crate) fn check_rgb_plane(
+    plane: &Plane<'_>,
+    width: usize,
+    height: usize,
+) -> Result<(), CVPixelBufferError> {
+    if plane.width() < width
+        || plane.height() < height
+        || plane.bytes_per_row() < width * RGB_PIXEL_SIZE
+    {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    Ok(())
+}
+
+fn check_nv12_planes(y_plane: &Plane<'_>, uv_plane: &Plane<'_>) -> Result<(), CVPixelBufferError> {
+    let (width, height) = (y_plane.width(), y_plane.height());
+    if y_plane.bytes_per_row() < width
+        || uv_plane.width() < width.div_ceil(2)
+        || uv_plane.height() < height.div_ceil(2)
+        || uv_plane.bytes_per_row() < 2 * width.div_ceil(2)
+    {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::cv_pixel_buffer::{
+    error::CVPixelBufferError,
+    plane::{Plane, PlaneMut},
+};
+
+use super::{ChromaFormat, PlanarYCbCr16};
+
+const GROUP_PIXELS: usize = 6;
+const GROUP_BYTES: usize = 16;
+
+/// Row size CoreVideo uses for v210: rows are padded to a multiple of 48 pixels (128 bytes).
+pub fn v210_bytes_per_row(width: usize) -> usize {
+    width.div_ceil(48) * 128
+}
+
+// Sample order within a group of six pixels, as (component, index) pairs packed three per
+// little-endian 32-bit word, starting at the least significant bits.
+#[derive(Clone, Copy)]
+enum Component {
+    Y,
+    Cb,
+    Cr,
+}
+
+const GROUP_LAYOUT: [(Component, usize); 12] = [
+    (Component::Cb, 0),
+    (Component::Y, 0),
+    (Component::Cr, 0),
+    (Component::Y, 1),
+    (Component::Cb, 1),
+    (Component::Y, 2),
+    (Component::Cr, 1),
+    (Component::Y, 3),
+    (Component::Cb, 2),
+    (Component::Y, 4),
+    (Component::Cr, 2),
+    (Component::Y, 5),
+];
+
+/// Unpacks 10-bit 4:2:2 v210, which packs six pixels into four 32-bit words.
+pub fn unpack_v210(plane: &Plane<'_>) -> Result<PlanarYCbCr16, CVPixelBufferError> {
+    let mut image = PlanarYCbCr16::new(plane.width(), plane.height(), ChromaFormat::Yuv422, 10)?;
+    check_plane(plane, image.width)?;
+    let (width, chroma_width) = (image.width, image.chroma_width());
+    for row in 0..image.height {
+        let data = plane.row(row);
+        let y = &mut image.y[row * width..(row + 1) * width];
+        let cb = &mut image.cb[row * chroma_width..(row + 1) * chroma_width];
+        let cr = &mut image.cr[row * chroma_width..(row + 1) * chroma_width];
+        for (group, bytes) in data
+            .chunks_exact(GROUP_BYTES)
+            .take(width.div_ceil(GROUP_PIXELS))
+            .enumerate()
+        {
+            for (i, &(component, index)) in GROUP_LAYOUT.iter().enumerate() {
+                let word = &bytes[i / 3 * 4..i / 3 * 4 + 4];
+                let word = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+                let sample = ((word >> (i % 3 * 10)) & 0x3ff) as u16;
+                let (samples, position) = match component {
+                    Component::Y => (&mut *y, group * GROUP_PIXELS + index),
+                    Component::Cb => (&mut *cb, group * GROUP_PIXELS / 2 + index),
+                    Component::Cr => (&mut *cr, group * GROUP_PIXELS / 2 + index),
+                };
+                if let Some(target) = samples.get_mut(position) {
+                    *target = sample;
+                }
+            }
+        }
+    }
+    Ok(image)
+}
+
+/// Packs a 10-bit 4:2:2 image into v210. A trailing partial group repeats the last samples
+/// of the row, and the remaining row padding is zeroed.
+pub fn pack_v210(
+    image: &PlanarYCbCr16,
+    plane: &mut PlaneMut<'_>,
+) -> Result<(), CVPixelBufferError> {
+    image.check()?;
+    if image.chroma_format != ChromaFormat::Yuv422 || image.bit_depth != 10 {
+        return Err(CVPixelBufferError::InvalidArgument);
+    }
+    if plane.width() != image.width || plane.height() != image.height {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    check_plane(&plane.as_plane(), image.width)?;
+    let (width, chroma_width) = (image.width, image.chroma_width());
+    for row in 0..image.height {
+        let y = &image.y[row * width..(row + 1) * width];
+        let cb = &image.cb[row * chroma_width..(row + 1) * chroma_width];
+        let cr = &image.cr[row * chroma_width..(row + 1) * chroma_width];
+        let data = plane.row_mut(row);
+        data.fill(0);
+        for (group, bytes) in data
+            .chunks_exact_mut(GROUP_BYTES)
+            .take(width.div_ceil(GROUP_PIXELS))
+            .enumerate()
+        {
+            let mut words = [0u32; 4];
+            for (i, &(component, index)) in GROUP_LAYOUT.iter().enumerate() {
+                let (samples, position) = match component {
+                    Component::Y => (y, group * GROUP_PIXELS + index),
+                    Component::Cb => (cb, group * GROUP_PIXELS / 2 + index),
+                    Component::Cr => (cr, group * GROUP_PIXELS / 2 + index),
+                };
+                let sample = samples[position.min(samples.len() - 1)].min(0x3ff);
+                words[i / 3] |= (sample as u32) << (i % 3 * 10);
+            }
+            for (word, bytes) in words.iter().zip(bytes.chunks_exact_mut(4)) {
+                bytes.copy_from_slice(&word.to_le_bytes());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_plane(plane: &Plane<'_>, width: usize) -> Result<(), CVPixelBufferError> {
+    if plane.bytes_per_row() < width.div_ceil(GROUP_PIXELS) * GROUP_BYTES {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::cv_pixel_buffer::{
+    error::CVPixelBufferError,
+    plane::{Plane, PlaneMut},
+};
+
+use super::{ChromaFormat, PlanarYCbCr16};
+
+const PIXEL_SIZE: usize = 8;
+
+/// Unpacks y416: 16-bit 4:4:4:4 little-endian samples ordered A, Y', Cb, Cr.
+pub fn unpack_y416(plane: &Plane<'_>) -> Result<PlanarYCbCr16, CVPixelBufferError> {
+    let mut image = PlanarYCbCr16::new(plane.width(), plane.height(), ChromaFormat::Yuv444, 16)?;
+    check_plane(plane, image.width)?;
+    let width = image.width;
+    let mut alpha = vec![0; width * image.height];
+    for row in 0..image.height {
+        let pixels = plane.row(row).chunks_exact(PIXEL_SIZE).take(width);
+        for (x, pixel) in pixels.enumerate() {
+            let sample = |i: usize| u16::from_le_bytes([pixel[2 * i], pixel[2 * i + 1]]);
+            let index = row * width + x;
+            alpha[index] = sample(0);
+            image.y[index] = sample(1);
+            image.cb[index] = sample(2);
+            image.cr[index] = sample(3);
+        }
+    }
+    image.alpha = Some(alpha);
+    Ok(image)
+}
+
+/// Packs a 16-bit 4:4:4 image into y416. Images without alpha are written opaque.
+pub fn pack_y416(
+    image: &PlanarYCbCr16,
+    plane: &mut PlaneMut<'_>,
+) -> Result<(), CVPixelBufferError> {
+    image.check()?;
+    if image.chroma_format != ChromaFormat::Yuv444 || image.bit_depth != 16 {
+        return Err(CVPixelBufferError::InvalidArgument);
+    }
+    if plane.width() != image.width || plane.height() != image.height {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    check_plane(&plane.as_plane(), image.width)?;
+    let width = image.width;
+    for row in 0..image.height {
+        let pixels = plane.row_mut(row).chunks_exact_mut(PIXEL_SIZE).take(width);
+        for (x, pixel) in pixels.enumerate() {
+            let index = row * width + x;
+            let alpha = image.alpha.as_ref().map_or(u16::MAX, |alpha| alpha[index]);
+            for (i, sample) in [alpha, image.y[index], image.cb[index], image.cr[index]]
+                .into_iter()
+                .enumerate()
+            {
+                pixel[2 * i..2 * i + 2].copy_from_slice(&sample.to_le_bytes());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_plane(plane: &Plane<'_>, width: usize) -> Result<(), CVPixelBufferError> {
+    if plane.bytes_per_row() < width * PIXEL_SIZE {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::{constants::YCbCrMatrix, cv_pixel_buffer::error::CVPixelBufferError};
+
+const SHIFT: u32 = 16;
+const ONE: f64 = (1 << SHIFT) as f64;
+const HALF: i64 = 1 << (SHIFT - 1);
+
+/// Whether luma and chroma use the full sample range or the video (studio) range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YCbCrRange {
+    /// Luma in 16..=235 and chroma in 16..=240.
+    Video,
+    Full,
+}
+
+/// Fixed-point coefficients for 8-bit RGB to Y'CbCr conversion and back.
+///
+/// Coefficients carry 16 fractional bits and results are rounded half up, so conversions are
+/// bit-exact across platforms. Each row of the forward matrix is adjusted to sum exactly to
+/// its scale so that greys map to neutral chroma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YCbCrCoefficients {
+    y: [i64; 3],
+    cb: [i64; 3],
+    cr: [i64; 3],
+    y_offset: i64,
+    y_scale: i64,
+    r_cr: i64,
+    g_cb: i64,
+    g_cr: i64,
+    b_cb: i64,
+}
+
+impl YCbCrCoefficients {
+    /// Supports the BT.601, BT.709, BT.2020 (non-constant luminance) and SMPTE 240M matrices.
+    pub fn new(matrix: &YCbCrMatrix, range: YCbCrRange) -> Result<Self, CVPixelBufferError> {
+        let (kr, kb) = match matrix {
+            YCbCrMatrix::ItuR601_4 => (0.299, 0.114),
+            YCbCrMatrix::ItuR709_2 => (0.2126, 0.0722),
+            YCbCrMatrix::ItuR2020 => (0.2627, 0.0593),
+            YCbCrMatrix::Smpte240M1995 => (0.212, 0.087),
+            _ => return Err(CVPixelBufferError::Unsupported),
+        };
+        let kg = 1.0 - kr - kb;
+        let (y_range, c_range, y_offset) = match range {
+            YCbCrRange::Video => (219.0 / 255.0, 224.0 / 255.0, 16),
+            YCbCrRange::Full => (1.0, 1.0, 0),
+        };
+        let fixed = |value: f64| (value * ONE).round() as i64;
+
+        let y_r = fixed(kr * y_range);
+        let y_b = fixed(kb * y_range);
+        let cb_r = fixed(-kr * c_range / (2.0 * (1.0 - kb)));
+        let cb_b = fixed(0.5 * c_range);
+        let cr_r = fixed(0.5 * c_range);
+        let cr_b = fixed(-kb * c_range / (2.0 * (1.0 - kr)));
+        Ok(Self {
+            y: [y_r, fixed(y_range) - y_r - y_b, y_b],
+            cb: [cb_r, -cb_r - cb_b, cb_b],
+            cr: [cr_r, -cr_r - cr_b, cr_b],
+            y_offset,
+            y_scale: fixed(1.0 / y_range),
+            r_cr: fixed(2.0 * (1.0 - kr) / c_range),
+            g_cb: fixed(-2.0 * kb * (1.0 - kb) / (kg * c_range)),
+            g_cr: fixed(-2.0 * kr * (1.0 - kr) / (kg * c_range)),
+            b_cb: fixed(2.0 * (1.0 - kb) / c_range),
+        })
+    }
+
+    pub(crate) fn luma(&self, [r, g, b]: [i64; 3]) -> u8 {
+        let y = self.y[0] * r + self.y[1] * g + self.y[2] * b;
+        clamp_u8(((y + HALF) >> SHIFT) + self.y_offset)
+    }
+
+    /// Chroma for an RGB sum of `1 << weight_shift` samples.
+    pub(crate) fn chroma(&self, [r, g, b]: [i64; 3], weight_shift: u32) -> [u8; 2] {
+        let shift = SHIFT + weight_shift;
+        let round = |value: i64| clamp_u8(((value + (1 << (shift - 1))) >> shift) + 128);
+        [
+            round(self.cb[0] * r + self.cb[1] * g + self.cb[2] * b),
+            round(self.cr[0] * r + self.cr[1] * g + self.cr[2] * b),
+        ]
+    }
+
+    pub(crate) fn rgb(&self, y: u8, cb: u8, cr: u8) -> [u8; 3] {
+        let y = self.y_scale * (y as i64 - self.y_offset) + HALF;
+        let cb = cb as i64 - 128;
+        let cr = cr as i64 - 128;
+        [
+            clamp_u8((y + self.r_cr * cr) >> SHIFT),
+            clamp_u8((y + self.g_cb * cb + self.g_cr * cr) >> SHIFT),
+            clamp_u8((y + self.b_cb * cb) >> SHIFT),
+        ]
+    }
+}
+
+fn clamp_u8(value: i64) -> u8 {
+    value.clamp(0, u8::MAX as i64) as u8
+}
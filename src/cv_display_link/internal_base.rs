@@ -0,0 +1,112 @@
+use core::fmt;
+
+use std::{ffi::c_void, fmt::Formatter, ptr};
+
+use core_foundation::{
+    base::{Boolean, CFTypeID, TCFType},
+    declare_TCFType, impl_TCFType,
+};
+use core_graphics::display::CGDirectDisplayID;
+
+use super::timing_source::{RawOutputCallback, TimingSource};
+use crate::{
+    cv_pixel_buffer::error::{CVPixelBufferError, CV_RETURN_SUCCESS},
+    types::{CVReturn, CVTime},
+};
+
+#[repr(C)]
+pub struct __CVDisplayLinkRef(c_void);
+
+pub type CVDisplayLinkRef = *mut __CVDisplayLinkRef;
+
+declare_TCFType! {CVDisplayLink, CVDisplayLinkRef}
+impl_TCFType!(CVDisplayLink, CVDisplayLinkRef, CVDisplayLinkGetTypeID);
+
+impl fmt::Debug for CVDisplayLink {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "CVDisplayLink")
+    }
+}
+
+extern "C" {
+    fn CVDisplayLinkGetTypeID() -> CFTypeID;
+    fn CVDisplayLinkCreateWithActiveCGDisplays(display_link_out: *mut CVDisplayLinkRef)
+        -> CVReturn;
+    fn CVDisplayLinkCreateWithCGDisplay(
+        display_id: CGDirectDisplayID,
+        display_link_out: *mut CVDisplayLinkRef,
+    ) -> CVReturn;
+    fn CVDisplayLinkSetOutputCallback(
+        display_link: CVDisplayLinkRef,
+        callback: RawOutputCallback,
+        user_info: *mut c_void,
+    ) -> CVReturn;
+    fn CVDisplayLinkStart(display_link: CVDisplayLinkRef) -> CVReturn;
+    fn CVDisplayLinkStop(display_link: CVDisplayLinkRef) -> CVReturn;
+    fn CVDisplayLinkIsRunning(display_link: CVDisplayLinkRef) -> Boolean;
+    fn CVDisplayLinkGetNominalOutputVideoRefreshPeriod(display_link: CVDisplayLinkRef) -> CVTime;
+    fn CVDisplayLinkGetActualOutputVideoRefreshPeriod(display_link: CVDisplayLinkRef) -> f64;
+    fn CVDisplayLinkGetOutputVideoLatency(display_link: CVDisplayLinkRef) -> CVTime;
+}
+
+fn result(result: CVReturn) -> Result<(), CVPixelBufferError> {
+    if result == CV_RETURN_SUCCESS {
+        Ok(())
+    } else {
+        Err(CVPixelBufferError::from(result))
+    }
+}
+
+impl CVDisplayLink {
+    pub fn create_with_active_displays() -> Result<Self, CVPixelBufferError> {
+        let mut display_link_out: CVDisplayLinkRef = ptr::null_mut();
+        unsafe {
+            result(CVDisplayLinkCreateWithActiveCGDisplays(
+                &mut display_link_out,
+            ))?;
+            Ok(Self::wrap_under_create_rule(display_link_out))
+        }
+    }
+    pub fn create_with_display(display_id: CGDirectDisplayID) -> Result<Self, CVPixelBufferError> {
+        let mut display_link_out: CVDisplayLinkRef = ptr::null_mut();
+        unsafe {
+            result(CVDisplayLinkCreateWithCGDisplay(
+                display_id,
+                &mut display_link_out,
+            ))?;
+            Ok(Self::wrap_under_create_rule(display_link_out))
+        }
+    }
+}
+
+impl TimingSource for CVDisplayLink {
+    unsafe fn set_output_callback(
+        &self,
+        callback: RawOutputCallback,
+        context: *mut c_void,
+    ) -> Result<(), CVPixelBufferError> {
+        result(CVDisplayLinkSetOutputCallback(
+            self.as_concrete_TypeRef(),
+            callback,
+            context,
+        ))
+    }
+    fn start(&self) -> Result<(), CVPixelBufferError> {
+        unsafe { result(CVDisplayLinkStart(self.as_concrete_TypeRef())) }
+    }
+    fn stop(&self) -> Result<(), CVPixelBufferError> {
+        unsafe { result(CVDisplayLinkStop(self.as_concrete_TypeRef())) }
+    }
+    fn is_running(&self) -> bool {
+        unsafe { CVDisplayLinkIsRunning(self.as_concrete_TypeRef()) != 0 }
+    }
+    fn get_nominal_output_video_refresh_period(&self) -> CVTime {
+        unsafe { CVDisplayLinkGetNominalOutputVideoRefreshPeriod(self.as_concrete_TypeRef()) }
+    }
+    fn get_actual_output_video_refresh_period(&self) -> f64 {
+        unsafe { CVDisplayLinkGetActualOutputVideoRefreshPeriod(self.as_concrete_TypeRef()) }
+    }
+    fn get_output_video_latency(&self) -> CVTime {
+        unsafe { CVDisplayLinkGetOutputVideoLatency(self.as_concrete_TypeRef()) }
+    }
+}
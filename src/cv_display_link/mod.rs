@@ -0,0 +1,153 @@
+#[cfg(target_vendor = "apple")]
+mod internal_base;
+pub mod timing_source;
+
+use std::{ffi::c_void, ptr};
+
+#[cfg(target_vendor = "apple")]
+pub use internal_base::{CVDisplayLink, CVDisplayLinkRef};
+pub use timing_source::{RawOutputCallback, TimingSource};
+
+use crate::{
+    cv_pixel_buffer::error::{CVPixelBufferError, CV_RETURN_SUCCESS},
+    types::{CVOptionFlags, CVReturn, CVTime, CVTimeStamp},
+};
+
+type OutputHandler = Box<dyn FnMut(&CVTimeStamp, &CVTimeStamp) + Send>;
+
+/// Calls a closure once per display refresh.
+///
+/// The closure is called on the source's thread with the current time and the time the
+/// next frame will be displayed. It is owned by the display link, which unregisters it and
+/// stops the source before the closure is dropped, so it never outlives its registration. A panic in the closure
+/// aborts the process.
+pub struct DisplayLink<S: TimingSource> {
+    source: S,
+    // Boxed twice so the context pointer handed to the source is thin and stable.
+    handler: Option<Box<OutputHandler>>,
+}
+
+impl<S: TimingSource> DisplayLink<S> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            handler: None,
+        }
+    }
+    /// Sets the closure called for every frame. Fails with `DisplayLinkAlreadyRunning` while
+    /// the link runs, since the previous closure could still be executing.
+    pub fn set_output_callback<F>(&mut self, callback: F) -> Result<(), CVPixelBufferError>
+    where
+        F: FnMut(&CVTimeStamp, &CVTimeStamp) + Send + 'static,
+    {
+        if self.source.is_running() {
+            return Err(CVPixelBufferError::DisplayLinkAlreadyRunning);
+        }
+        let mut handler: Box<OutputHandler> = Box::new(Box::new(callback));
+        let context: *mut OutputHandler = &mut *handler;
+        // SAFETY: the handler is kept alive until it is replaced while stopped, or until the
+        // link is dropped after stopping the source.
+        unsafe {
+            self.source
+                .set_output_callback(output_trampoline, context.cast())?;
+        }
+        self.handler = Some(handler);
+        Ok(())
+    }
+    pub fn has_output_callback(&self) -> bool {
+        self.handler.is_some()
+    }
+    pub fn start(&self) -> Result<(), CVPixelBufferError> {
+        if self.handler.is_none() {
+            return Err(CVPixelBufferError::DisplayLinkCallbacksNotSet);
+        }
+        if self.source.is_running() {
+            return Err(CVPixelBufferError::DisplayLinkAlreadyRunning);
+        }
+        self.source.start()
+    }
+    pub fn stop(&self) -> Result<(), CVPixelBufferError> {
+        if !self.source.is_running() {
+            return Err(CVPixelBufferError::DisplayLinkNotRunning);
+        }
+        self.source.stop()
+    }
+    pub fn is_running(&self) -> bool {
+        self.source.is_running()
+    }
+    pub fn get_nominal_output_video_refresh_period(&self) -> CVTime {
+        self.source.get_nominal_output_video_refresh_period()
+    }
+    /// Measured refresh period in seconds, or zero before the link has run.
+    pub fn get_actual_output_video_refresh_period(&self) -> f64 {
+        self.source.get_actual_output_video_refresh_period()
+    }
+    pub fn get_output_video_latency(&self) -> CVTime {
+        self.source.get_output_video_latency()
+    }
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+}
+
+impl<S: TimingSource> Drop for DisplayLink<S> {
+    fn drop(&mut self) {
+        if self.handler.is_none() {
+            return;
+        }
+        // The source may be shared, e.g. a cloned `CVDisplayLink`, and restarted after this
+        // link is gone. Point it at a callback without context before freeing the handler,
+        // then stop it so no call into the old handler is still running.
+        // SAFETY: `noop_trampoline` never reads its context.
+        unsafe {
+            let _ = self
+                .source
+                .set_output_callback(noop_trampoline, ptr::null_mut());
+        }
+        let _ = self.source.stop();
+    }
+}
+
+unsafe extern "C" fn noop_trampoline(
+    _link: *mut c_void,
+    _now: *const CVTimeStamp,
+    _output_time: *const CVTimeStamp,
+    _flags_in: CVOptionFlags,
+    _flags_out: *mut CVOptionFlags,
+    _context: *mut c_void,
+) -> CVReturn {
+    CV_RETURN_SUCCESS
+}
+
+// Written by hand rather than with the `core_utils_rs` trampolines, which wrap a `FnOnce`
+// taking a single argument; the source calls this repeatedly with six.
+unsafe extern "C" fn output_trampoline(
+    _link: *mut c_void,
+    now: *const CVTimeStamp,
+    output_time: *const CVTimeStamp,
+    _flags_in: CVOptionFlags,
+    _flags_out: *mut CVOptionFlags,
+    context: *mut c_void,
+) -> CVReturn {
+    // SAFETY: `context` is the handler registered by `set_output_callback`, and the source
+    // only calls this while running, so from one thread at a time.
+    let handler = unsafe { &mut *context.cast::<OutputHandler>() };
+    if let (Some(now), Some(output_time)) = unsafe { (now.as_ref(), output_time.as_ref()) } {
+        handler(now, output_time);
+    }
+    CV_RETURN_SUCCESS
+}
+
+#[cfg(target_vendor = "apple")]
+impl DisplayLink<CVDisplayLink> {
+    /// Creates a display link that follows whichever active display shows the content.
+    pub fn create_with_active_displays() -> Result<Self, CVPixelBufferError> {
+        Ok(Self::new(CVDisplayLink::create_with_active_displays()?))
+    }
+    /// Creates a display link for the display with `display_id`.
+    pub fn create_with_display(
+        display_id: core_graphics::display::CGDirectDisplayID,
+    ) -> Result<Self, CVPixelBufferError> {
+        Ok(Self::new(CVDisplayLink::create_with_display(display_id)?))
+    }
+}
@@ -0,0 +1,45 @@
+use std::ffi::c_void;
+
+use crate::{
+    cv_pixel_buffer::error::CVPixelBufferError,
+    types::{CVOptionFlags, CVReturn, CVTime, CVTimeStamp},
+};
+
+/// Output callback with the signature of `CVDisplayLinkOutputCallback`. `link` is the
+/// source's own handle and `context` the pointer registered with
+/// [`TimingSource::set_output_callback`].
+pub type RawOutputCallback = unsafe extern "C" fn(
+    link: *mut c_void,
+    now: *const CVTimeStamp,
+    output_time: *const CVTimeStamp,
+    flags_in: CVOptionFlags,
+    flags_out: *mut CVOptionFlags,
+    context: *mut c_void,
+) -> CVReturn;
+
+/// A clock that calls an output callback once per frame, such as a `CVDisplayLink`.
+///
+/// [`DisplayLink`](super::DisplayLink) implements the closure bookkeeping on top of this, so
+/// other implementations, e.g. a fake clock in tests, share it.
+pub trait TimingSource {
+    /// Registers `callback`, to be called with `context` on every frame, replacing any
+    /// previous registration.
+    ///
+    /// # Safety
+    ///
+    /// `context` must stay valid for `callback` until it is replaced or the source is
+    /// dropped, and must not be used by the source while it is stopped.
+    unsafe fn set_output_callback(
+        &self,
+        callback: RawOutputCallback,
+        context: *mut c_void,
+    ) -> Result<(), CVPixelBufferError>;
+    fn start(&self) -> Result<(), CVPixelBufferError>;
+    /// Stops calling the output callback. Once this returns, no callback is running.
+    fn stop(&self) -> Result<(), CVPixelBufferError>;
+    fn is_running(&self) -> bool;
+    fn get_nominal_output_video_refresh_period(&self) -> CVTime;
+    /// Measured refresh period in seconds, or zero before the source has run.
+    fn get_actual_output_video_refresh_period(&self) -> f64;
+    fn get_output_video_latency(&self) -> CVTime;
+}
@@ -17,3 +17,45 @@ mod internal {
     }
 }
 pub use internal::{CVImageBuffer, CVImageBufferRef};
+
+use core_foundation::base::TCFType;
+
+use crate::attachments::{
+    internal_attachments::{CVBufferRef, InternalAttachments},
+    CVAttachmentMode, ImageBufferAttachment, ImageBufferAttachmentKey, ImageBufferAttachments,
+};
+
+impl CVImageBuffer {
+    pub fn set_attachment(&self, attachment: &ImageBufferAttachment, mode: CVAttachmentMode) {
+        self.internal_set_attachment(attachment, mode)
+    }
+    pub fn get_attachment(
+        &self,
+        key: ImageBufferAttachmentKey,
+    ) -> Option<(ImageBufferAttachment, CVAttachmentMode)> {
+        self.internal_get_attachment(key)
+    }
+    pub fn remove_attachment(&self, key: ImageBufferAttachmentKey) {
+        self.internal_remove_attachment(key)
+    }
+    pub fn remove_all_attachments(&self) {
+        self.internal_remove_all_attachments()
+    }
+    /// Returns the attachments with the given mode that this crate can represent.
+    pub fn get_attachments(&self, mode: CVAttachmentMode) -> ImageBufferAttachments {
+        self.internal_copy_attachments(mode)
+    }
+    pub fn set_attachments(&self, attachments: ImageBufferAttachments, mode: CVAttachmentMode) {
+        self.internal_set_attachments(attachments, mode)
+    }
+    /// Copies all `ShouldPropagate` attachments to `destination`.
+    pub fn propagate_attachments(&self, destination: &CVImageBuffer) {
+        self.internal_propagate_attachments(destination)
+    }
+}
+
+impl InternalAttachments for CVImageBuffer {
+    fn buffer_ref(&self) -> CVBufferRef {
+        self.as_CFTypeRef()
+    }
+}
@@ -1,21 +1,38 @@
-use core_foundation::{
-    array::CFArray,
-    base::{CFAllocatorRef, CFType, TCFType},
-    boolean::CFBoolean,
-    number::CFNumber,
-    string::{CFString, CFStringRef},
-};
+use std::fmt;
 
+#[cfg(target_vendor = "apple")]
+use core_foundation::base::CFAllocatorRef;
+#[cfg(target_vendor = "apple")]
 use core_graphics::display::CFDictionary;
 
-#[derive(Debug, Clone)]
+use crate::{
+    dictionary::{Dictionary, DictionaryValue},
+    types::OSType,
+};
+
+use super::{
+    error::CVPixelBufferError, extended_pixels::ExtendedPixels, pixel_format::PixelFormat,
+    view::lcm,
+};
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum PixelFormatValue {
-    Single(usize),
-    Many(Vec<usize>),
+    Single(PixelFormat),
+    Many(Vec<PixelFormat>),
+}
+
+impl PixelFormatValue {
+    pub fn as_slice(&self) -> &[PixelFormat] {
+        match self {
+            Self::Single(pixel_format) => std::slice::from_ref(pixel_format),
+            Self::Many(pixel_formats) => pixel_formats,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PixelBufferAttribute {
+    #[cfg(target_vendor = "apple")]
     MemoryAllocator(CFAllocatorRef),
     PixelFormat(PixelFormatValue),
     Width(usize),
@@ -29,6 +46,7 @@ pub enum PixelBufferAttribute {
     CGImageCompatibility(bool),
     OpenGLCompatibility(bool),
     PlaneAlignment(usize),
+    #[cfg(target_vendor = "apple")]
     IOSurfaceProperties(CFDictionary),
     OpenGLESCompatibility(bool),
     MetalCompatibility(bool),
@@ -41,14 +59,505 @@ pub enum PixelBufferAttribute {
     OpenGLESTextureCacheCompatibility(bool),
 }
 
-#[derive(Debug, Clone)]
-pub struct PixelBufferAttributes(Vec<PixelBufferAttribute>);
+/// Identifies a [`PixelBufferAttribute`] independent of its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelBufferAttributeKey {
+    MemoryAllocator,
+    PixelFormat,
+    Width,
+    Height,
+    ExtendedPixelsLeft,
+    ExtendedPixelsTop,
+    ExtendedPixelsRight,
+    ExtendedPixelsBottom,
+    BytesPerRowAlignment,
+    CGBitmapContextCompatibility,
+    CGImageCompatibility,
+    OpenGLCompatibility,
+    PlaneAlignment,
+    IOSurfaceProperties,
+    OpenGLESCompatibility,
+    MetalCompatibility,
+    IOSurfaceCoreAnimationCompatibility,
+    IOSurfaceOpenGLFBOCompatibility,
+    IOSurfaceOpenGLESFBOCompatibility,
+    IOSurfaceOpenGLTextureCompatibility,
+    IOSurfaceOpenGLESTextureCompatibility,
+    OpenGLTextureCacheCompatibility,
+    OpenGLESTextureCacheCompatibility,
+}
+
+impl PixelBufferAttributeKey {
+    pub const ALL: &'static [PixelBufferAttributeKey] = &[
+        PixelBufferAttributeKey::MemoryAllocator,
+        PixelBufferAttributeKey::PixelFormat,
+        PixelBufferAttributeKey::Width,
+        PixelBufferAttributeKey::Height,
+        PixelBufferAttributeKey::ExtendedPixelsLeft,
+        PixelBufferAttributeKey::ExtendedPixelsTop,
+        PixelBufferAttributeKey::ExtendedPixelsRight,
+        PixelBufferAttributeKey::ExtendedPixelsBottom,
+        PixelBufferAttributeKey::BytesPerRowAlignment,
+        PixelBufferAttributeKey::CGBitmapContextCompatibility,
+        PixelBufferAttributeKey::CGImageCompatibility,
+        PixelBufferAttributeKey::OpenGLCompatibility,
+        PixelBufferAttributeKey::PlaneAlignment,
+        PixelBufferAttributeKey::IOSurfaceProperties,
+        PixelBufferAttributeKey::OpenGLESCompatibility,
+        PixelBufferAttributeKey::MetalCompatibility,
+        PixelBufferAttributeKey::IOSurfaceCoreAnimationCompatibility,
+        PixelBufferAttributeKey::IOSurfaceOpenGLFBOCompatibility,
+        PixelBufferAttributeKey::IOSurfaceOpenGLESFBOCompatibility,
+        PixelBufferAttributeKey::IOSurfaceOpenGLTextureCompatibility,
+        PixelBufferAttributeKey::IOSurfaceOpenGLESTextureCompatibility,
+        PixelBufferAttributeKey::OpenGLTextureCacheCompatibility,
+        PixelBufferAttributeKey::OpenGLESTextureCacheCompatibility,
+    ];
+
+    /// The value of the `kCVPixelBuffer*Key` constant for this key.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::MemoryAllocator => "MemoryAllocator",
+            Self::PixelFormat => "PixelFormatType",
+            Self::Width => "Width",
+            Self::Height => "Height",
+            Self::ExtendedPixelsLeft => "ExtendedPixelsLeft",
+            Self::ExtendedPixelsTop => "ExtendedPixelsTop",
+            Self::ExtendedPixelsRight => "ExtendedPixelsRight",
+            Self::ExtendedPixelsBottom => "ExtendedPixelsBottom",
+            Self::BytesPerRowAlignment => "BytesPerRowAlignment",
+            Self::CGBitmapContextCompatibility => "CGBitmapContextCompatibility",
+            Self::CGImageCompatibility => "CGImageCompatibility",
+            Self::OpenGLCompatibility => "OpenGLCompatibility",
+            Self::PlaneAlignment => "PlaneAlignment",
+            Self::IOSurfaceProperties => "IOSurfaceProperties",
+            Self::OpenGLESCompatibility => "OpenGLESCompatibility",
+            Self::MetalCompatibility => "MetalCompatibility",
+            Self::IOSurfaceCoreAnimationCompatibility => "IOSurfaceCoreAnimationCompatibility",
+            Self::IOSurfaceOpenGLFBOCompatibility => "IOSurfaceOpenGLFBOCompatibility",
+            Self::IOSurfaceOpenGLESFBOCompatibility => "IOSurfaceOpenGLESFBOCompatibility",
+            Self::IOSurfaceOpenGLTextureCompatibility => "IOSurfaceOpenGLTextureCompatibility",
+            Self::IOSurfaceOpenGLESTextureCompatibility => "IOSurfaceOpenGLESTextureCompatibility",
+            Self::OpenGLTextureCacheCompatibility => "OpenGLTextureCacheCompatibility",
+            Self::OpenGLESTextureCacheCompatibility => "OpenGLESTextureCacheCompatibility",
+        }
+    }
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|k| k.as_str() == key)
+    }
+}
+
+impl PixelBufferAttribute {
+    pub fn key(&self) -> PixelBufferAttributeKey {
+        match self {
+            #[cfg(target_vendor = "apple")]
+            Self::MemoryAllocator(_) => PixelBufferAttributeKey::MemoryAllocator,
+            Self::PixelFormat(_) => PixelBufferAttributeKey::PixelFormat,
+            Self::Width(_) => PixelBufferAttributeKey::Width,
+            Self::Height(_) => PixelBufferAttributeKey::Height,
+            Self::ExtendedPixelsLeft(_) => PixelBufferAttributeKey::ExtendedPixelsLeft,
+            Self::ExtendedPixelsTop(_) => PixelBufferAttributeKey::ExtendedPixelsTop,
+            Self::ExtendedPixelsRight(_) => PixelBufferAttributeKey::ExtendedPixelsRight,
+            Self::ExtendedPixelsBottom(_) => PixelBufferAttributeKey::ExtendedPixelsBottom,
+            Self::BytesPerRowAlignment(_) => PixelBufferAttributeKey::BytesPerRowAlignment,
+            Self::CGBitmapContextCompatibility(_) => {
+                PixelBufferAttributeKey::CGBitmapContextCompatibility
+            }
+            Self::CGImageCompatibility(_) => PixelBufferAttributeKey::CGImageCompatibility,
+            Self::OpenGLCompatibility(_) => PixelBufferAttributeKey::OpenGLCompatibility,
+            Self::PlaneAlignment(_) => PixelBufferAttributeKey::PlaneAlignment,
+            #[cfg(target_vendor = "apple")]
+            Self::IOSurfaceProperties(_) => PixelBufferAttributeKey::IOSurfaceProperties,
+            Self::OpenGLESCompatibility(_) => PixelBufferAttributeKey::OpenGLESCompatibility,
+            Self::MetalCompatibility(_) => PixelBufferAttributeKey::MetalCompatibility,
+            Self::IOSurfaceCoreAnimationCompatibility(_) => {
+                PixelBufferAttributeKey::IOSurfaceCoreAnimationCompatibility
+            }
+            Self::IOSurfaceOpenGLFBOCompatibility(_) => {
+                PixelBufferAttributeKey::IOSurfaceOpenGLFBOCompatibility
+            }
+            Self::IOSurfaceOpenGLESFBOCompatibility(_) => {
+                PixelBufferAttributeKey::IOSurfaceOpenGLESFBOCompatibility
+            }
+            Self::IOSurfaceOpenGLTextureCompatibility(_) => {
+                PixelBufferAttributeKey::IOSurfaceOpenGLTextureCompatibility
+            }
+            Self::IOSurfaceOpenGLESTextureCompatibility(_) => {
+                PixelBufferAttributeKey::IOSurfaceOpenGLESTextureCompatibility
+            }
+            Self::OpenGLTextureCacheCompatibility(_) => {
+                PixelBufferAttributeKey::OpenGLTextureCacheCompatibility
+            }
+            Self::OpenGLESTextureCacheCompatibility(_) => {
+                PixelBufferAttributeKey::OpenGLESTextureCacheCompatibility
+            }
+        }
+    }
+    /// The value alone, for printing attribute sets as maps.
+    fn value(&self) -> &dyn fmt::Debug {
+        match self {
+            #[cfg(target_vendor = "apple")]
+            Self::MemoryAllocator(value) => value,
+            Self::PixelFormat(value) => value,
+            Self::Width(value)
+            | Self::Height(value)
+            | Self::ExtendedPixelsLeft(value)
+            | Self::ExtendedPixelsTop(value)
+            | Self::ExtendedPixelsRight(value)
+            | Self::ExtendedPixelsBottom(value)
+            | Self::BytesPerRowAlignment(value)
+            | Self::PlaneAlignment(value) => value,
+            #[cfg(target_vendor = "apple")]
+            Self::IOSurfaceProperties(value) => value,
+            Self::CGBitmapContextCompatibility(value)
+            | Self::CGImageCompatibility(value)
+            | Self::OpenGLCompatibility(value)
+            | Self::OpenGLESCompatibility(value)
+            | Self::MetalCompatibility(value)
+            | Self::IOSurfaceCoreAnimationCompatibility(value)
+            | Self::IOSurfaceOpenGLFBOCompatibility(value)
+            | Self::IOSurfaceOpenGLESFBOCompatibility(value)
+            | Self::IOSurfaceOpenGLTextureCompatibility(value)
+            | Self::IOSurfaceOpenGLESTextureCompatibility(value)
+            | Self::OpenGLTextureCacheCompatibility(value)
+            | Self::OpenGLESTextureCacheCompatibility(value) => value,
+        }
+    }
+    /// The value of the boolean compatibility attributes.
+    fn flag(&self) -> Option<bool> {
+        match *self {
+            Self::CGBitmapContextCompatibility(value)
+            | Self::CGImageCompatibility(value)
+            | Self::OpenGLCompatibility(value)
+            | Self::OpenGLESCompatibility(value)
+            | Self::MetalCompatibility(value)
+            | Self::IOSurfaceCoreAnimationCompatibility(value)
+            | Self::IOSurfaceOpenGLFBOCompatibility(value)
+            | Self::IOSurfaceOpenGLESFBOCompatibility(value)
+            | Self::IOSurfaceOpenGLTextureCompatibility(value)
+            | Self::IOSurfaceOpenGLESTextureCompatibility(value)
+            | Self::OpenGLTextureCacheCompatibility(value)
+            | Self::OpenGLESTextureCacheCompatibility(value) => Some(value),
+            _ => None,
+        }
+    }
+    /// Reads the attribute for the `kCVPixelBuffer*Key` named `key` from a dictionary value.
+    /// Returns `None` for keys without a typed attribute, the CoreFoundation-only ones, and
+    /// pixel formats that are not a known [`PixelFormat`].
+    pub(super) fn from_value(
+        key: &str,
+        value: &DictionaryValue,
+    ) -> Result<Option<Self>, CVPixelBufferError> {
+        let Some(key) = PixelBufferAttributeKey::parse(key) else {
+            return Ok(None);
+        };
+        let count = || {
+            value
+                .as_i64()
+                .and_then(|value| usize::try_from(value).ok())
+                .ok_or(CVPixelBufferError::InvalidPixelBufferAttributes)
+        };
+        let flag = || {
+            value
+                .as_bool()
+                .ok_or(CVPixelBufferError::InvalidPixelBufferAttributes)
+        };
+        Ok(Some(match key {
+            PixelBufferAttributeKey::PixelFormat => {
+                let pixel_format = |value: &DictionaryValue| {
+                    value
+                        .as_u32()
+                        .ok_or(CVPixelBufferError::InvalidPixelBufferAttributes)
+                        .map(PixelFormat::from_os_type)
+                };
+                let value = match value.as_array() {
+                    Some(values) => values
+                        .iter()
+                        .map(pixel_format)
+                        .collect::<Result<Option<_>, _>>()?
+                        .map(PixelFormatValue::Many),
+                    None => pixel_format(value)?.map(PixelFormatValue::Single),
+                };
+                match value {
+                    Some(value) => Self::PixelFormat(value),
+                    None => return Ok(None),
+                }
+            }
+            PixelBufferAttributeKey::Width => Self::Width(count()?),
+            PixelBufferAttributeKey::Height => Self::Height(count()?),
+            PixelBufferAttributeKey::ExtendedPixelsLeft => Self::ExtendedPixelsLeft(count()?),
+            PixelBufferAttributeKey::ExtendedPixelsTop => Self::ExtendedPixelsTop(count()?),
+            PixelBufferAttributeKey::ExtendedPixelsRight => Self::ExtendedPixelsRight(count()?),
+            PixelBufferAttributeKey::ExtendedPixelsBottom => Self::ExtendedPixelsBottom(count()?),
+            PixelBufferAttributeKey::BytesPerRowAlignment => Self::BytesPerRowAlignment(count()?),
+            PixelBufferAttributeKey::PlaneAlignment => Self::PlaneAlignment(count()?),
+            PixelBufferAttributeKey::CGBitmapContextCompatibility => {
+                Self::CGBitmapContextCompatibility(flag()?)
+            }
+            PixelBufferAttributeKey::CGImageCompatibility => Self::CGImageCompatibility(flag()?),
+            PixelBufferAttributeKey::OpenGLCompatibility => Self::OpenGLCompatibility(flag()?),
+            PixelBufferAttributeKey::OpenGLESCompatibility => Self::OpenGLESCompatibility(flag()?),
+            PixelBufferAttributeKey::MetalCompatibility => Self::MetalCompatibility(flag()?),
+            PixelBufferAttributeKey::IOSurfaceCoreAnimationCompatibility => {
+                Self::IOSurfaceCoreAnimationCompatibility(flag()?)
+            }
+            PixelBufferAttributeKey::IOSurfaceOpenGLFBOCompatibility => {
+                Self::IOSurfaceOpenGLFBOCompatibility(flag()?)
+            }
+            PixelBufferAttributeKey::IOSurfaceOpenGLESFBOCompatibility => {
+                Self::IOSurfaceOpenGLESFBOCompatibility(flag()?)
+            }
+            PixelBufferAttributeKey::IOSurfaceOpenGLTextureCompatibility => {
+                Self::IOSurfaceOpenGLTextureCompatibility(flag()?)
+            }
+            PixelBufferAttributeKey::IOSurfaceOpenGLESTextureCompatibility => {
+                Self::IOSurfaceOpenGLESTextureCompatibility(flag()?)
+            }
+            PixelBufferAttributeKey::OpenGLTextureCacheCompatibility => {
+                Self::OpenGLTextureCacheCompatibility(flag()?)
+            }
+            PixelBufferAttributeKey::OpenGLESTextureCacheCompatibility => {
+                Self::OpenGLESTextureCacheCompatibility(flag()?)
+            }
+            PixelBufferAttributeKey::MemoryAllocator
+            | PixelBufferAttributeKey::IOSurfaceProperties => return Ok(None),
+        }))
+    }
+}
+
+/// Attributes for creating pixel buffers.
+///
+/// The list may hold several values for one key, in which case the last one is used.
+/// [`PixelBufferAttributesBuilder`] keeps a single value per key and validates the result.
+/// Entries read from a dictionary that have no typed attribute are kept as
+/// [`unknown`](Self::unknown) and written back along with the others.
+#[derive(Clone, PartialEq)]
+pub struct PixelBufferAttributes {
+    attributes: Vec<PixelBufferAttribute>,
+    unknown: Dictionary,
+}
 impl PixelBufferAttributes {
     pub fn new(attributes: &[PixelBufferAttribute]) -> Self {
-        Self(attributes.to_vec())
+        Self {
+            attributes: attributes.to_vec(),
+            unknown: Dictionary::new(),
+        }
+    }
+    pub fn builder() -> PixelBufferAttributesBuilder {
+        PixelBufferAttributesBuilder::new()
+    }
+    /// Reads a dictionary keyed by `kCVPixelBuffer*Key` names, such as the pure Rust form
+    /// of `CVPixelBufferGetAttributes` output.
+    ///
+    /// Entries for other keys, and pixel format lists with codes that are not a known
+    /// [`PixelFormat`], are kept as [`unknown`](Self::unknown). Known keys with values of
+    /// the wrong type fail with `InvalidPixelBufferAttributes`.
+    pub fn from_dictionary(dictionary: &Dictionary) -> Result<Self, CVPixelBufferError> {
+        let mut attributes = Self::default();
+        for (key, value) in dictionary {
+            attributes.insert_value(key, value)?;
+        }
+        Ok(attributes)
+    }
+    pub(crate) fn insert_value(
+        &mut self,
+        key: &str,
+        value: &DictionaryValue,
+    ) -> Result<(), CVPixelBufferError> {
+        match PixelBufferAttribute::from_value(key, value)? {
+            Some(attribute) => {
+                self.set(attribute);
+            }
+            None => self.set_unknown(key, value.clone()),
+        }
+        Ok(())
     }
     pub fn add(&mut self, attribute: PixelBufferAttribute) {
-        self.0.push(attribute);
+        self.attributes.push(attribute);
+    }
+    /// Replaces every value for the key of `attribute`, returning the one that was in
+    /// effect.
+    pub fn set(&mut self, attribute: PixelBufferAttribute) -> Option<PixelBufferAttribute> {
+        let previous = self.remove(attribute.key());
+        self.attributes.push(attribute);
+        previous
+    }
+    /// The value in effect for `key`, which is the last one added.
+    pub fn get(&self, key: PixelBufferAttributeKey) -> Option<&PixelBufferAttribute> {
+        self.attributes
+            .iter()
+            .rev()
+            .find(|attribute| attribute.key() == key)
+    }
+    /// Removes every value for `key`, returning the one that was in effect.
+    pub fn remove(&mut self, key: PixelBufferAttributeKey) -> Option<PixelBufferAttribute> {
+        let mut removed = None;
+        self.attributes.retain(|attribute| {
+            if attribute.key() == key {
+                removed = Some(attribute.clone());
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+    /// Entries without a typed attribute, by key.
+    pub fn unknown(&self) -> &Dictionary {
+        &self.unknown
+    }
+    pub fn set_unknown(&mut self, key: impl Into<String>, value: DictionaryValue) {
+        self.unknown.insert(key.into(), value);
+    }
+    pub fn remove_unknown(&mut self, key: &str) -> Option<DictionaryValue> {
+        self.unknown.remove(key)
+    }
+    /// Sets every attribute of `other`, so its values win over those already present.
+    pub fn merge(&mut self, other: &PixelBufferAttributes) {
+        for attribute in other.effective() {
+            self.set(attribute.clone());
+        }
+        self.unknown
+            .extend(other.unknown.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    /// Combines the requirements of several clients into one set, with the precedence
+    /// rules of `CVPixelBufferCreateResolvedAttributesDictionary`:
+    ///
+    /// - pixel formats are narrowed to those every set accepts, in the order of the first
+    ///   set that names any; when sets share none, the earlier set's formats are kept;
+    /// - widths and heights take the largest value, so the buffer fits every client;
+    /// - extended pixels take the largest value per side and alignments the least common
+    ///   multiple;
+    /// - compatibility flags are set if any set asks for them;
+    /// - the memory allocator, IOSurface properties and unknown entries come from the
+    ///   first set that has them, so a set without IOSurface properties doesn't remove
+    ///   them.
+    ///
+    /// The result is checked with [`validate`](Self::validate).
+    pub fn resolve(sets: &[PixelBufferAttributes]) -> Result<Self, CVPixelBufferError> {
+        let mut resolved = Self::default();
+        for set in sets {
+            for attribute in set.effective() {
+                let current = resolved
+                    .attributes
+                    .iter_mut()
+                    .find(|current| current.key() == attribute.key());
+                let Some(current) = current else {
+                    resolved.attributes.push(attribute.clone());
+                    continue;
+                };
+                let combined = match (&*current, attribute) {
+                    (
+                        PixelBufferAttribute::PixelFormat(current),
+                        PixelBufferAttribute::PixelFormat(other),
+                    ) => {
+                        let common: Vec<PixelFormat> = current
+                            .as_slice()
+                            .iter()
+                            .copied()
+                            .filter(|pixel_format| other.as_slice().contains(pixel_format))
+                            .collect();
+                        PixelBufferAttribute::PixelFormat(match *common.as_slice() {
+                            [] => continue,
+                            [pixel_format] => PixelFormatValue::Single(pixel_format),
+                            _ => PixelFormatValue::Many(common),
+                        })
+                    }
+                    (PixelBufferAttribute::Width(a), PixelBufferAttribute::Width(b)) => {
+                        PixelBufferAttribute::Width(*a.max(b))
+                    }
+                    (PixelBufferAttribute::Height(a), PixelBufferAttribute::Height(b)) => {
+                        PixelBufferAttribute::Height(*a.max(b))
+                    }
+                    (
+                        PixelBufferAttribute::ExtendedPixelsLeft(a),
+                        PixelBufferAttribute::ExtendedPixelsLeft(b),
+                    ) => PixelBufferAttribute::ExtendedPixelsLeft(*a.max(b)),
+                    (
+                        PixelBufferAttribute::ExtendedPixelsTop(a),
+                        PixelBufferAttribute::ExtendedPixelsTop(b),
+                    ) => PixelBufferAttribute::ExtendedPixelsTop(*a.max(b)),
+                    (
+                        PixelBufferAttribute::ExtendedPixelsRight(a),
+                        PixelBufferAttribute::ExtendedPixelsRight(b),
+                    ) => PixelBufferAttribute::ExtendedPixelsRight(*a.max(b)),
+                    (
+                        PixelBufferAttribute::ExtendedPixelsBottom(a),
+                        PixelBufferAttribute::ExtendedPixelsBottom(b),
+                    ) => PixelBufferAttribute::ExtendedPixelsBottom(*a.max(b)),
+                    (
+                        PixelBufferAttribute::BytesPerRowAlignment(a),
+                        PixelBufferAttribute::BytesPerRowAlignment(b),
+                    ) => PixelBufferAttribute::BytesPerRowAlignment(lcm(*a, *b)),
+                    (
+                        PixelBufferAttribute::PlaneAlignment(a),
+                        PixelBufferAttribute::PlaneAlignment(b),
+                    ) => PixelBufferAttribute::PlaneAlignment(lcm(*a, *b)),
+                    (current, attribute) if current.flag() == Some(false) => attribute.clone(),
+                    _ => continue,
+                };
+                *current = combined;
+            }
+            for (key, value) in &set.unknown {
+                resolved
+                    .unknown
+                    .entry(key.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+        resolved.validate()?;
+        Ok(resolved)
+    }
+    /// Checks the values in effect without CoreVideo: alignments must be powers of two,
+    /// sizes non-zero and at least one pixel format given if the key is present.
+    pub fn validate(&self) -> Result<(), CVPixelBufferError> {
+        for attribute in self.effective() {
+            match attribute {
+                PixelBufferAttribute::PixelFormat(PixelFormatValue::Many(formats))
+                    if formats.is_empty() =>
+                {
+                    return Err(CVPixelBufferError::InvalidPixelFormat)
+                }
+                PixelBufferAttribute::Width(0) | PixelBufferAttribute::Height(0) => {
+                    return Err(CVPixelBufferError::InvalidSize)
+                }
+                PixelBufferAttribute::BytesPerRowAlignment(alignment)
+                | PixelBufferAttribute::PlaneAlignment(alignment)
+                    if !alignment.is_power_of_two() =>
+                {
+                    return Err(CVPixelBufferError::InvalidPixelBufferAttributes)
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+    /// The values in effect, one per key, in the order they were added.
+    pub fn effective(&self) -> impl Iterator<Item = &PixelBufferAttribute> {
+        self.attributes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, attribute)| {
+                let key = attribute.key();
+                (!self.attributes[i + 1..]
+                    .iter()
+                    .any(|later| later.key() == key))
+                .then_some(attribute)
+            })
+    }
+    /// The number of attributes, including repeated keys and unknown entries.
+    pub fn len(&self) -> usize {
+        self.attributes.len() + self.unknown.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.unknown.is_empty()
+    }
+    pub fn as_slice(&self) -> &[PixelBufferAttribute] {
+        &self.attributes
+    }
+    /// The typed attributes; unknown entries are dropped.
+    pub fn into_vec(self) -> Vec<PixelBufferAttribute> {
+        self.attributes
     }
 }
 
@@ -57,262 +566,156 @@ impl Default for PixelBufferAttributes {
         Self::new(&[])
     }
 }
-impl From<PixelBufferAttributes> for CFDictionary<CFString, CFType> {
-    fn from(val: PixelBufferAttributes) -> Self {
-        let mut pairs = Vec::new();
-        for attribute in val.0 {
-            match attribute {
-                // fill missing
-                PixelBufferAttribute::MemoryAllocator(allocator) => {
-                    pairs.push(unsafe {
-                        (
-                            CFString::wrap_under_get_rule(kCVPixelBufferMemoryAllocatorKey),
-                            CFType::wrap_under_get_rule(allocator),
-                        )
-                    });
-                }
-                PixelBufferAttribute::PixelFormat(format) => pairs.push((
-                    unsafe { CFString::wrap_under_get_rule(kCVPixelBufferPixelFormatTypeKey) },
-                    match format {
-                        PixelFormatValue::Single(val) => CFNumber::from(val as i64).into_CFType(),
-                        PixelFormatValue::Many(vals) => CFArray::from_CFTypes(
-                            vals.into_iter()
-                                .map(|n| CFNumber::from(n as i64))
-                                .collect::<Vec<CFNumber>>()
-                                .as_slice(),
-                        )
-                        .into_CFType(),
-                    },
-                )),
-                PixelBufferAttribute::Width(val) => {
-                    pairs.push((
-                        unsafe { CFString::wrap_under_get_rule(kCVPixelBufferWidthKey) },
-                        CFNumber::from(val as i64).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::Height(val) => {
-                    pairs.push((
-                        unsafe { CFString::wrap_under_get_rule(kCVPixelBufferHeightKey) },
-                        CFNumber::from(val as i64).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::ExtendedPixelsLeft(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferExtendedPixelsLeftKey)
-                        },
-                        CFNumber::from(val as i64).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::ExtendedPixelsTop(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferExtendedPixelsTopKey)
-                        },
-                        CFNumber::from(val as i64).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::ExtendedPixelsRight(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferExtendedPixelsRightKey)
-                        },
-                        CFNumber::from(val as i64).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::ExtendedPixelsBottom(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferExtendedPixelsBottomKey)
-                        },
-                        CFNumber::from(val as i64).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::BytesPerRowAlignment(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferBytesPerRowAlignmentKey)
-                        },
-                        CFNumber::from(val as i64).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::CGImageCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferCGImageCompatibilityKey)
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::CGBitmapContextCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(
-                                kCVPixelBufferCGBitmapContextCompatibilityKey,
-                            )
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::OpenGLCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferOpenGLCompatibilityKey)
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::PlaneAlignment(val) => {
-                    pairs.push((
-                        unsafe { CFString::wrap_under_get_rule(kCVPixelBufferPlaneAlignmentKey) },
-                        CFNumber::from(val as i64).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::IOSurfaceProperties(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferIOSurfacePropertiesKey)
-                        },
-                        val.into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::OpenGLESCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferOpenGLESCompatibilityKey)
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::MetalCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(kCVPixelBufferMetalCompatibilityKey)
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::IOSurfaceCoreAnimationCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(
-                                kCVPixelBufferIOSurfaceCoreAnimationCompatibilityKey,
-                            )
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::IOSurfaceOpenGLFBOCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(
-                                kCVPixelBufferIOSurfaceOpenGLFBOCompatibilityKey,
-                            )
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::IOSurfaceOpenGLESFBOCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(
-                                kCVPixelBufferIOSurfaceOpenGLESFBOCompatibilityKey,
-                            )
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::IOSurfaceOpenGLTextureCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(
-                                kCVPixelBufferIOSurfaceOpenGLTextureCompatibilityKey,
-                            )
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::IOSurfaceOpenGLESTextureCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(
-                                kCVPixelBufferIOSurfaceOpenGLESTextureCompatibilityKey,
-                            )
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::OpenGLTextureCacheCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(
-                                kCVPixelBufferOpenGLTextureCacheCompatibilityKey,
-                            )
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-                PixelBufferAttribute::OpenGLESTextureCacheCompatibility(val) => {
-                    pairs.push((
-                        unsafe {
-                            CFString::wrap_under_get_rule(
-                                kCVPixelBufferOpenGLESTextureCacheCompatibilityKey,
-                            )
-                        },
-                        CFBoolean::from(val).into_CFType(),
-                    ));
-                }
-            }
+
+/// Prints the attributes as a map from key to value, e.g.
+/// `{Width: 1920, PixelFormat: Single(BGRA32)}`, followed by unknown entries.
+impl fmt::Debug for PixelBufferAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.attributes
+                    .iter()
+                    .map(|attribute| (attribute.key(), attribute.value())),
+            )
+            .entries(&self.unknown)
+            .finish()
+    }
+}
+
+impl FromIterator<PixelBufferAttribute> for PixelBufferAttributes {
+    fn from_iter<T: IntoIterator<Item = PixelBufferAttribute>>(iter: T) -> Self {
+        Self {
+            attributes: iter.into_iter().collect(),
+            unknown: Dictionary::new(),
         }
-        CFDictionary::from_CFType_pairs(&pairs)
     }
 }
 
-extern "C" {
-    // A key to the allocator that the system uses to create the pixel buffer.
-    static kCVPixelBufferMemoryAllocatorKey: CFStringRef;
-    // A key to one or more pixel buffer format types.
-    static kCVPixelBufferPixelFormatTypeKey: CFStringRef;
-    // A key to the width of the pixel buffer.
-    static kCVPixelBufferWidthKey: CFStringRef;
-    // A key to the height of the pixel buffer.
-    static kCVPixelBufferHeightKey: CFStringRef;
-    // A key to the number of pixels padding the left of the image.
-    static kCVPixelBufferExtendedPixelsLeftKey: CFStringRef;
-    // A key to the number of pixels padding the top of the image.
-    static kCVPixelBufferExtendedPixelsTopKey: CFStringRef;
-    // A key to the number of pixels padding the right of the image.
-    static kCVPixelBufferExtendedPixelsRightKey: CFStringRef;
-    // A key to the number of pixels padding the bottom of the image.
-    static kCVPixelBufferExtendedPixelsBottomKey: CFStringRef;
-    // A key to a number that specifies the alignment of number of bytes per row in the pixel buffer.
-    static kCVPixelBufferBytesPerRowAlignmentKey: CFStringRef;
-    // A key to a Boolean value that indicates whether the pixel buffer is compatible with Core Graphics bitmap contexts.
-    static kCVPixelBufferCGBitmapContextCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whether the pixel buffer is compatible with Core Graphics bitmap image types.
-    static kCVPixelBufferCGImageCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whether the pixel buffer is compatible with OpenGL contexts.
-    static kCVPixelBufferOpenGLCompatibilityKey: CFStringRef;
-    // A key to a number that specifies the alignment of the planes in the pixel buffer.
-    static kCVPixelBufferPlaneAlignmentKey: CFStringRef;
-    // A key to the dictionary containing optional CFStringRef for the IOSurface framework.
-    static kCVPixelBufferIOSurfacePropertiesKey: CFStringRef;
-    // A key to a Boolean value that indicates whether the pixel buffer is compatible with OpenGL ES contexts.
-    static kCVPixelBufferOpenGLESCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whetherCFStringRef pixel buffer is compatible with the Metal framework.
-    static kCVPixelBufferMetalCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whether Core Animation can display the pixel buffer.
-    static kCVPixelBufferIOSurfaceCoreAnimationCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whether OpenGL can cCFStringRef a valid texture for use as a color buffer attachment.
-    static kCVPixelBufferIOSurfaceOpenGLFBOCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whether OpenGL ES can create a valid texture for use as a color buffer attachment.
-    static kCVPixelBufferIOSurfaceOpenGLESFBOCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whether OpenGL can crCFStringRef a valid texture object from the IOSurface-backed pixel buffer.
-    static kCVPixelBufferIOSurfaceOpenGLTextureCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whether OpenGL ES can create a valid texture object from the IOSurface-backed pixel buffer.
-    static kCVPixelBufferIOSurfaceOpenGLESTextureCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whether OpenGL performs format conversions of the texture-cache data in a shader.
-    static kCVPixelBufferOpenGLTextureCacheCompatibilityKey: CFStringRef;
-    // A key to a Boolean value that indicates whether OpenGL ES performs format conversions of the texture-cache data in a shader.
-    static kCVPixelBufferOpenGLESTextureCacheCompatibilityKey: CFStringRef;
+macro_rules! bool_setters {
+    ($($name:ident => $variant:ident),* $(,)?) => {$(
+        pub fn $name(self, value: bool) -> Self {
+            self.set(PixelBufferAttribute::$variant(value))
+        }
+    )*};
+}
+
+/// Builds [`PixelBufferAttributes`] with one value per key; later calls override earlier
+/// ones.
+#[derive(Debug, Clone, Default)]
+pub struct PixelBufferAttributesBuilder {
+    attributes: PixelBufferAttributes,
+    unknown_pixel_formats: Vec<OSType>,
+}
+
+impl PixelBufferAttributesBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn set(mut self, attribute: PixelBufferAttribute) -> Self {
+        if attribute.key() == PixelBufferAttributeKey::PixelFormat {
+            self.unknown_pixel_formats.clear();
+        }
+        self.attributes.set(attribute);
+        self
+    }
+    pub fn pixel_format(self, pixel_format: PixelFormat) -> Self {
+        self.set(PixelBufferAttribute::PixelFormat(PixelFormatValue::Single(
+            pixel_format,
+        )))
+    }
+    /// Accepts any of `pixel_formats`, in order of preference.
+    pub fn pixel_formats(self, pixel_formats: impl IntoIterator<Item = PixelFormat>) -> Self {
+        self.set(PixelBufferAttribute::PixelFormat(PixelFormatValue::Many(
+            pixel_formats.into_iter().collect(),
+        )))
+    }
+    /// Like [`pixel_formats`](Self::pixel_formats) for raw codes. Codes that are not a
+    /// known [`PixelFormat`] make [`build`](Self::build) fail with `InvalidPixelFormat`.
+    pub fn pixel_format_types(self, pixel_formats: impl IntoIterator<Item = OSType>) -> Self {
+        let (known, unknown): (Vec<_>, Vec<_>) = pixel_formats
+            .into_iter()
+            .partition(|&code| PixelFormat::from_os_type(code).is_some());
+        let mut builder = match known.as_slice() {
+            &[code] => self.pixel_format(PixelFormat::from_os_type(code).unwrap()),
+            _ => self.pixel_formats(known.into_iter().filter_map(PixelFormat::from_os_type)),
+        };
+        builder.unknown_pixel_formats = unknown;
+        builder
+    }
+    pub fn width(self, width: usize) -> Self {
+        self.set(PixelBufferAttribute::Width(width))
+    }
+    pub fn height(self, height: usize) -> Self {
+        self.set(PixelBufferAttribute::Height(height))
+    }
+    pub fn size(self, width: usize, height: usize) -> Self {
+        self.width(width).height(height)
+    }
+    /// Sets all four `ExtendedPixels*` attributes.
+    pub fn extended_pixels(self, extended_pixels: ExtendedPixels) -> Self {
+        self.set(PixelBufferAttribute::ExtendedPixelsLeft(
+            extended_pixels.left,
+        ))
+        .set(PixelBufferAttribute::ExtendedPixelsTop(extended_pixels.top))
+        .set(PixelBufferAttribute::ExtendedPixelsRight(
+            extended_pixels.right,
+        ))
+        .set(PixelBufferAttribute::ExtendedPixelsBottom(
+            extended_pixels.bottom,
+        ))
+    }
+    pub fn bytes_per_row_alignment(self, alignment: usize) -> Self {
+        self.set(PixelBufferAttribute::BytesPerRowAlignment(alignment))
+    }
+    pub fn plane_alignment(self, alignment: usize) -> Self {
+        self.set(PixelBufferAttribute::PlaneAlignment(alignment))
+    }
+    bool_setters!(
+        cg_bitmap_context_compatibility => CGBitmapContextCompatibility,
+        cg_image_compatibility => CGImageCompatibility,
+        opengl_compatibility => OpenGLCompatibility,
+        opengles_compatibility => OpenGLESCompatibility,
+        metal_compatibility => MetalCompatibility,
+        io_surface_core_animation_compatibility => IOSurfaceCoreAnimationCompatibility,
+        io_surface_opengl_fbo_compatibility => IOSurfaceOpenGLFBOCompatibility,
+        io_surface_opengles_fbo_compatibility => IOSurfaceOpenGLESFBOCompatibility,
+        io_surface_opengl_texture_compatibility => IOSurfaceOpenGLTextureCompatibility,
+        io_surface_opengles_texture_compatibility => IOSurfaceOpenGLESTextureCompatibility,
+        opengl_texture_cache_compatibility => OpenGLTextureCacheCompatibility,
+        opengles_texture_cache_compatibility => OpenGLESTextureCacheCompatibility,
+    );
+    #[cfg(target_vendor = "apple")]
+    pub fn memory_allocator(self, allocator: CFAllocatorRef) -> Self {
+        self.set(PixelBufferAttribute::MemoryAllocator(allocator))
+    }
+    #[cfg(target_vendor = "apple")]
+    pub fn io_surface_properties(self, properties: CFDictionary) -> Self {
+        self.set(PixelBufferAttribute::IOSurfaceProperties(properties))
+    }
+    /// Sets every attribute in effect in `attributes`, overriding values set so far, and
+    /// takes over its unknown entries.
+    pub fn merge(mut self, attributes: &PixelBufferAttributes) -> Self {
+        for attribute in attributes.effective() {
+            self = self.set(attribute.clone());
+        }
+        for (key, value) in attributes.unknown() {
+            self.attributes.set_unknown(key.clone(), value.clone());
+        }
+        self
+    }
+    /// Returns the attributes, or the first problem [`PixelBufferAttributes::validate`]
+    /// finds.
+    pub fn build(self) -> Result<PixelBufferAttributes, CVPixelBufferError> {
+        if !self.unknown_pixel_formats.is_empty() {
+            return Err(CVPixelBufferError::InvalidPixelFormat);
+        }
+        self.attributes.validate()?;
+        Ok(self.attributes)
+    }
+}
+
+impl From<PixelBufferAttributes> for PixelBufferAttributesBuilder {
+    fn from(attributes: PixelBufferAttributes) -> Self {
+        Self::new().merge(&attributes)
+    }
 }
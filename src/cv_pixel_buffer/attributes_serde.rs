@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{
+    attributes::{
+        PixelBufferAttribute, PixelBufferAttributeKey, PixelBufferAttributes,
+        PixelBufferAttributesBuilder, PixelFormatValue,
+    },
+    pixel_format::PixelFormat,
+};
+use crate::dictionary::DictionaryValue;
+
+/// An attribute value as it is stored. Pixel formats are four character strings where
+/// they are printable and numbers otherwise.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Bool(bool),
+    Integer(u64),
+    String(String),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    fn from_pixel_format(pixel_format: PixelFormat) -> Self {
+        let bytes = pixel_format.as_os_type().to_be_bytes();
+        if bytes.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+            Self::String(String::from_utf8_lossy(&bytes).into_owned())
+        } else {
+            Self::Integer(pixel_format.as_os_type() as u64)
+        }
+    }
+    fn from_attribute(attribute: &PixelBufferAttribute) -> Option<Self> {
+        Some(match attribute {
+            PixelBufferAttribute::PixelFormat(PixelFormatValue::Single(pixel_format)) => {
+                Self::from_pixel_format(*pixel_format)
+            }
+            PixelBufferAttribute::PixelFormat(PixelFormatValue::Many(pixel_formats)) => {
+                Self::Array(
+                    pixel_formats
+                        .iter()
+                        .map(|&pixel_format| Self::from_pixel_format(pixel_format))
+                        .collect(),
+                )
+            }
+            PixelBufferAttribute::Width(value)
+            | PixelBufferAttribute::Height(value)
+            | PixelBufferAttribute::ExtendedPixelsLeft(value)
+            | PixelBufferAttribute::ExtendedPixelsTop(value)
+            | PixelBufferAttribute::ExtendedPixelsRight(value)
+            | PixelBufferAttribute::ExtendedPixelsBottom(value)
+            | PixelBufferAttribute::BytesPerRowAlignment(value)
+            | PixelBufferAttribute::PlaneAlignment(value) => Self::Integer(*value as u64),
+            PixelBufferAttribute::CGBitmapContextCompatibility(value)
+            | PixelBufferAttribute::CGImageCompatibility(value)
+            | PixelBufferAttribute::OpenGLCompatibility(value)
+            | PixelBufferAttribute::OpenGLESCompatibility(value)
+            | PixelBufferAttribute::MetalCompatibility(value)
+            | PixelBufferAttribute::IOSurfaceCoreAnimationCompatibility(value)
+            | PixelBufferAttribute::IOSurfaceOpenGLFBOCompatibility(value)
+            | PixelBufferAttribute::IOSurfaceOpenGLESFBOCompatibility(value)
+            | PixelBufferAttribute::IOSurfaceOpenGLTextureCompatibility(value)
+            | PixelBufferAttribute::IOSurfaceOpenGLESTextureCompatibility(value)
+            | PixelBufferAttribute::OpenGLTextureCacheCompatibility(value)
+            | PixelBufferAttribute::OpenGLESTextureCacheCompatibility(value) => Self::Bool(*value),
+            #[cfg(target_vendor = "apple")]
+            PixelBufferAttribute::MemoryAllocator(_)
+            | PixelBufferAttribute::IOSurfaceProperties(_) => return None,
+        })
+    }
+    /// The value as [`PixelBufferAttribute::from_value`] reads it, with four character
+    /// pixel format strings turned back into codes.
+    fn into_dictionary_value(self, pixel_format: bool) -> Result<DictionaryValue, String> {
+        Ok(match self {
+            Self::Bool(value) => DictionaryValue::Bool(value),
+            Self::Integer(value) => DictionaryValue::Integer(
+                i64::try_from(value).map_err(|_| format!("{value} is out of range"))?,
+            ),
+            Self::String(code) if pixel_format => {
+                let bytes: [u8; 4] = code
+                    .as_bytes()
+                    .try_into()
+                    .map_err(|_| format!("`{code}` is not a four character code"))?;
+                u32::from_be_bytes(bytes).into()
+            }
+            Self::String(value) => DictionaryValue::String(value),
+            Self::Array(values) => DictionaryValue::Array(
+                values
+                    .into_iter()
+                    .map(|value| value.into_dictionary_value(pixel_format))
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+    fn into_attribute(self, key: &str) -> Result<PixelBufferAttribute, String> {
+        let parsed = PixelBufferAttributeKey::parse(key)
+            .ok_or_else(|| format!("unknown pixel buffer attribute `{key}`"))?;
+        let value = self.into_dictionary_value(parsed == PixelBufferAttributeKey::PixelFormat)?;
+        match PixelBufferAttribute::from_value(key, &value) {
+            Ok(Some(attribute)) => Ok(attribute),
+            Ok(None) if parsed == PixelBufferAttributeKey::PixelFormat => {
+                Err("unknown pixel format".to_string())
+            }
+            Ok(None) => Err(format!("`{key}` cannot be deserialized")),
+            Err(_) => Err(format!("invalid value for `{key}`")),
+        }
+    }
+}
+
+/// Serializes the values in effect as a map from `kCVPixelBuffer*Key` names to values.
+/// `MemoryAllocator` and `IOSurfaceProperties` refer to live CoreFoundation objects and
+/// are skipped, as are unknown entries.
+impl Serialize for PixelBufferAttributes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let entries: Vec<(&str, Value)> = self
+            .effective()
+            .filter_map(|attribute| {
+                Some((attribute.key().as_str(), Value::from_attribute(attribute)?))
+            })
+            .collect();
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (key, value) in &entries {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Deserializes the map written by `Serialize`. Values are read like entries of
+/// [`PixelBufferAttributes::from_dictionary`]; unknown keys and sets that fail
+/// [`PixelBufferAttributes::validate`] are rejected.
+impl<'de> Deserialize<'de> for PixelBufferAttributes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut builder = PixelBufferAttributesBuilder::new();
+        for (key, value) in BTreeMap::<String, Value>::deserialize(deserializer)? {
+            builder = builder.set(value.into_attribute(&key).map_err(de::Error::custom)?);
+        }
+        builder.build().map_err(de::Error::custom)
+    }
+}
@@ -1,36 +1,37 @@
-use core_foundation::base::OSStatus;
 use thiserror::Error;
 
-pub const CV_RETURN_SUCCESS: OSStatus = 0;
+use crate::types::CVReturn;
+
+pub const CV_RETURN_SUCCESS: CVReturn = 0;
 
 // General errors
 
-const ERROR: OSStatus = -6660;
-const INVALID_ARGUMENT: OSStatus = -6661;
-const ALLOCATION_FAILED: OSStatus = -6662;
-const UNSUPPORTED: OSStatus = -6663;
+const ERROR: CVReturn = -6660;
+const INVALID_ARGUMENT: CVReturn = -6661;
+const ALLOCATION_FAILED: CVReturn = -6662;
+const UNSUPPORTED: CVReturn = -6663;
 
 // Displaylink errors
-const INVALID_DISPLAY: OSStatus = -6670;
-const DISPLAYLINK_ALREADY_RUNNING: OSStatus = -6671;
-const DISPLAYLINK_NOT_RUNNING: OSStatus = -6672;
-const DISPLAYLINK_CALLBACKS_NOT_SET: OSStatus = -6673;
+const INVALID_DISPLAY: CVReturn = -6670;
+const DISPLAYLINK_ALREADY_RUNNING: CVReturn = -6671;
+const DISPLAYLINK_NOT_RUNNING: CVReturn = -6672;
+const DISPLAYLINK_CALLBACKS_NOT_SET: CVReturn = -6673;
 
 // Buffer errors
-const INVALID_PIXEL_FORMAT: OSStatus = -6680;
-const INVALID_SIZE: OSStatus = -6681;
-const INVALID_PIXEL_BUFFER_ATTRIBUTES: OSStatus = -6682;
-const PIXEL_BUFFER_NOT_OPENGL_COMPATIBLE: OSStatus = -6683;
-const PIXEL_BUFFER_NOT_METAL_COMPATIBLE: OSStatus = -6684;
+const INVALID_PIXEL_FORMAT: CVReturn = -6680;
+const INVALID_SIZE: CVReturn = -6681;
+const INVALID_PIXEL_BUFFER_ATTRIBUTES: CVReturn = -6682;
+const PIXEL_BUFFER_NOT_OPENGL_COMPATIBLE: CVReturn = -6683;
+const PIXEL_BUFFER_NOT_METAL_COMPATIBLE: CVReturn = -6684;
 
 // Buffer pool errors
-const WOULD_EXCEED_ALLOCATION_THRESHOLD: OSStatus = -6689;
-const POOL_ALLOCATION_FAILED: OSStatus = -6690;
-const INVALID_POOL_ATTRIBUTES: OSStatus = -6691;
-const RETRY: OSStatus = -6692;
+const WOULD_EXCEED_ALLOCATION_THRESHOLD: CVReturn = -6689;
+const POOL_ALLOCATION_FAILED: CVReturn = -6690;
+const INVALID_POOL_ATTRIBUTES: CVReturn = -6691;
+const RETRY: CVReturn = -6692;
 
 #[derive(Error, Debug, Clone)]
-pub enum CVPixelBufferError<TUnknown = OSStatus> {
+pub enum CVPixelBufferError<TUnknown = CVReturn> {
     #[error("Invalid function parameter. For example, out of range or the wrong type.")]
     InvalidArgument,
     #[error("Memory allocation for a buffer or buffer pool failed.")]
@@ -73,8 +74,8 @@ pub enum CVPixelBufferError<TUnknown = OSStatus> {
     UnknownError(TUnknown),
 }
 
-impl From<OSStatus> for CVPixelBufferError {
-    fn from(value: OSStatus) -> Self {
+impl From<CVReturn> for CVPixelBufferError {
+    fn from(value: CVReturn) -> Self {
         match value {
             INVALID_ARGUMENT => CVPixelBufferError::InvalidArgument,
             ALLOCATION_FAILED => CVPixelBufferError::AllocationFailed,
@@ -99,7 +100,7 @@ impl From<OSStatus> for CVPixelBufferError {
         }
     }
 }
-impl From<CVPixelBufferError> for OSStatus {
+impl From<CVPixelBufferError> for CVReturn {
     fn from(value: CVPixelBufferError) -> Self {
         match value {
             CVPixelBufferError::InvalidArgument => INVALID_ARGUMENT,
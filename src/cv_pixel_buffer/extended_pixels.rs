@@ -0,0 +1,206 @@
+use std::ops::Range;
+
+use super::{
+    attributes::{PixelBufferAttribute, PixelBufferAttributes},
+    error::CVPixelBufferError,
+    lock::{MutBaseAddressGuard, RawPlane},
+    pixel_format::PixelFormat,
+    pixel_format_layout::{PixelFormatLayout, PlaneFormat},
+    plane::PlaneMut,
+    view::alignment,
+};
+
+/// Padding allocated around the visible image, like `CVPixelBufferGetExtendedPixels`.
+///
+/// Counts are in pixels of the full-resolution image. [`ExtendedPixels::of_plane`] gives
+/// the padding of a subsampled plane in its own samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ExtendedPixels {
+    pub left: usize,
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+}
+
+impl ExtendedPixels {
+    pub const fn new(left: usize, top: usize, right: usize, bottom: usize) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+    /// Reads the `ExtendedPixels*` attributes; later attributes override earlier ones.
+    pub fn from_attributes(attributes: &PixelBufferAttributes) -> Self {
+        attributes
+            .as_slice()
+            .iter()
+            .fold(Self::default(), |mut extended, attribute| {
+                match *attribute {
+                    PixelBufferAttribute::ExtendedPixelsLeft(left) => extended.left = left,
+                    PixelBufferAttribute::ExtendedPixelsTop(top) => extended.top = top,
+                    PixelBufferAttribute::ExtendedPixelsRight(right) => extended.right = right,
+                    PixelBufferAttribute::ExtendedPixelsBottom(bottom) => extended.bottom = bottom,
+                    _ => {}
+                }
+                extended
+            })
+    }
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+    /// Rounds every side up to whole chroma samples and packed blocks, e.g. even counts
+    /// for 420v and multiples of six columns for v210. Buffers report the rounded values.
+    pub fn aligned(&self, layout: &PixelFormatLayout) -> Self {
+        let (horizontal, vertical) = alignment(layout);
+        Self::new(
+            self.left.next_multiple_of(horizontal),
+            self.top.next_multiple_of(vertical),
+            self.right.next_multiple_of(horizontal),
+            self.bottom.next_multiple_of(vertical),
+        )
+    }
+    /// Padding of a plane with format `plane`, in samples of that plane.
+    pub fn of_plane(&self, plane: &PlaneFormat) -> Self {
+        Self::new(
+            self.left / plane.horizontal_subsampling,
+            self.top / plane.vertical_subsampling,
+            self.right / plane.horizontal_subsampling,
+            self.bottom / plane.vertical_subsampling,
+        )
+    }
+}
+
+/// Fills the padding of `plane` by repeating the outermost visible blocks and rows.
+///
+/// `plane` covers the visible samples and `extended` samples of padding around them, as
+/// handed out by [`MutBaseAddressGuard::padded_plane_mut`]. Horizontal padding is filled
+/// with whole blocks of `format`, so packed formats repeat their edge block rather than
+/// their edge pixel.
+pub fn replicate_edges(
+    plane: &mut PlaneMut<'_>,
+    extended: ExtendedPixels,
+    format: &PlaneFormat,
+) -> Result<(), CVPixelBufferError> {
+    let (width, height, bytes_per_row) = (plane.width(), plane.height(), plane.bytes_per_row());
+    let block = format.bytes_per_block;
+    let visible_width = width
+        .checked_sub(extended.left + extended.right)
+        .filter(|&width| width > 0)
+        .ok_or(CVPixelBufferError::InvalidArgument)?;
+    let visible_height = height
+        .checked_sub(extended.top + extended.bottom)
+        .filter(|&height| height > 0)
+        .ok_or(CVPixelBufferError::InvalidArgument)?;
+    if !extended.left.is_multiple_of(format.block_width)
+        || !extended.right.is_multiple_of(format.block_width)
+    {
+        return Err(CVPixelBufferError::InvalidArgument);
+    }
+    let left = extended.left / format.block_width * block;
+    let visible_end = left + visible_width.div_ceil(format.block_width) * block;
+    let row_end = visible_end + extended.right / format.block_width * block;
+    if row_end > bytes_per_row {
+        return Err(CVPixelBufferError::InvalidArgument);
+    }
+
+    for y in extended.top..extended.top + visible_height {
+        let row = plane.row_mut(y);
+        for x in (0..left).step_by(block) {
+            row.copy_within(left..left + block, x);
+        }
+        for x in (visible_end..row_end).step_by(block) {
+            row.copy_within(visible_end - block..visible_end, x);
+        }
+    }
+    let data = plane.as_mut_slice();
+    let first = extended.top * bytes_per_row;
+    for y in 0..extended.top {
+        data.copy_within(first..first + row_end, y * bytes_per_row);
+    }
+    let last = (extended.top + visible_height - 1) * bytes_per_row;
+    for y in extended.top + visible_height..height {
+        data.copy_within(last..last + row_end, y * bytes_per_row);
+    }
+    Ok(())
+}
+
+/// Grows each visible plane of a locked buffer by its share of `extended`.
+///
+/// `allocation` is the memory backing the planes, where the buffer knows it. Fails with
+/// `BaseAddress` if a padded plane would reach outside it.
+pub(crate) fn padded_planes(
+    planes: &[RawPlane],
+    allocation: Option<Range<*const u8>>,
+    pixel_format: PixelFormat,
+    extended: ExtendedPixels,
+) -> Result<Vec<RawPlane>, CVPixelBufferError> {
+    if extended.is_empty() {
+        return Ok(planes.to_vec());
+    }
+    let layout = pixel_format
+        .layout()
+        .ok_or(CVPixelBufferError::InvalidPixelFormat)?;
+    planes
+        .iter()
+        .zip(layout.planes)
+        .map(|(plane, format)| {
+            let padding = extended.of_plane(format);
+            let before = padding
+                .top
+                .checked_mul(plane.bytes_per_row)
+                .zip((padding.left / format.block_width).checked_mul(format.bytes_per_block))
+                .and_then(|(rows, columns)| rows.checked_add(columns))
+                .ok_or(CVPixelBufferError::BaseAddress)?;
+            let (width, height) = plane
+                .width
+                .checked_add(padding.left)
+                .and_then(|width| width.checked_add(padding.right))
+                .zip(
+                    plane
+                        .height
+                        .checked_add(padding.top)
+                        .and_then(|height| height.checked_add(padding.bottom)),
+                )
+                .ok_or(CVPixelBufferError::BaseAddress)?;
+            if let Some(allocation) = &allocation {
+                let start = (plane.ptr as usize).checked_sub(before);
+                let end = height
+                    .checked_mul(plane.bytes_per_row)
+                    .and_then(|size| start?.checked_add(size));
+                match (start, end) {
+                    (Some(start), Some(end))
+                        if start >= allocation.start as usize && end <= allocation.end as usize => {
+                    }
+                    _ => return Err(CVPixelBufferError::BaseAddress),
+                }
+            }
+            Ok(RawPlane::new(
+                plane.ptr.wrapping_sub(before),
+                width,
+                height,
+                plane.bytes_per_row,
+            ))
+        })
+        .collect()
+}
+
+/// Replicates edges into the padding of every plane of a buffer locked read-write.
+pub(crate) fn fill_extended_pixels(
+    guard: &mut MutBaseAddressGuard<'_>,
+    pixel_format: PixelFormat,
+    extended: ExtendedPixels,
+) -> Result<(), CVPixelBufferError> {
+    if extended.is_empty() {
+        return Ok(());
+    }
+    let layout = pixel_format
+        .layout()
+        .ok_or(CVPixelBufferError::InvalidPixelFormat)?;
+    for (plane_index, format) in layout.planes.iter().enumerate().take(guard.plane_count()) {
+        let mut plane = guard.padded_plane_mut(plane_index)?;
+        replicate_edges(&mut plane, extended.of_plane(format), format)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,357 @@
+use std::ptr;
+
+use core_foundation::{
+    array::{CFArray, CFArrayRef},
+    base::{kCFAllocatorDefault, CFAllocatorRef, CFType, TCFType},
+    boolean::CFBoolean,
+    dictionary::{CFDictionary as CFUntypedDictionary, CFDictionaryRef},
+    number::CFNumber,
+    string::{CFString, CFStringRef},
+};
+use core_graphics::display::CFDictionary;
+
+use super::{
+    attributes::{
+        PixelBufferAttribute, PixelBufferAttributeKey, PixelBufferAttributes, PixelFormatValue,
+    },
+    error::{CVPixelBufferError, CV_RETURN_SUCCESS},
+    internal_base::CVPixelBuffer,
+};
+use crate::{dictionary::DictionaryValue, types::CVReturn};
+
+impl From<PixelBufferAttributes> for CFDictionary<CFString, CFType> {
+    fn from(val: PixelBufferAttributes) -> Self {
+        let mut pairs: Vec<(CFString, CFType)> = val
+            .unknown()
+            .iter()
+            .map(|(key, value)| (CFString::new(key), value.to_cf_type()))
+            .collect();
+        for attribute in val.into_vec() {
+            match attribute {
+                // fill missing
+                PixelBufferAttribute::MemoryAllocator(allocator) => {
+                    pairs.push(unsafe {
+                        (
+                            CFString::wrap_under_get_rule(kCVPixelBufferMemoryAllocatorKey),
+                            CFType::wrap_under_get_rule(allocator),
+                        )
+                    });
+                }
+                PixelBufferAttribute::PixelFormat(format) => pairs.push((
+                    unsafe { CFString::wrap_under_get_rule(kCVPixelBufferPixelFormatTypeKey) },
+                    match format {
+                        PixelFormatValue::Single(val) => {
+                            CFNumber::from(val.as_os_type() as i64).into_CFType()
+                        }
+                        PixelFormatValue::Many(vals) => CFArray::from_CFTypes(
+                            vals.into_iter()
+                                .map(|n| CFNumber::from(n.as_os_type() as i64))
+                                .collect::<Vec<CFNumber>>()
+                                .as_slice(),
+                        )
+                        .into_CFType(),
+                    },
+                )),
+                PixelBufferAttribute::Width(val) => {
+                    pairs.push((
+                        unsafe { CFString::wrap_under_get_rule(kCVPixelBufferWidthKey) },
+                        CFNumber::from(val as i64).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::Height(val) => {
+                    pairs.push((
+                        unsafe { CFString::wrap_under_get_rule(kCVPixelBufferHeightKey) },
+                        CFNumber::from(val as i64).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::ExtendedPixelsLeft(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferExtendedPixelsLeftKey)
+                        },
+                        CFNumber::from(val as i64).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::ExtendedPixelsTop(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferExtendedPixelsTopKey)
+                        },
+                        CFNumber::from(val as i64).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::ExtendedPixelsRight(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferExtendedPixelsRightKey)
+                        },
+                        CFNumber::from(val as i64).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::ExtendedPixelsBottom(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferExtendedPixelsBottomKey)
+                        },
+                        CFNumber::from(val as i64).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::BytesPerRowAlignment(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferBytesPerRowAlignmentKey)
+                        },
+                        CFNumber::from(val as i64).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::CGImageCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferCGImageCompatibilityKey)
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::CGBitmapContextCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(
+                                kCVPixelBufferCGBitmapContextCompatibilityKey,
+                            )
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::OpenGLCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferOpenGLCompatibilityKey)
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::PlaneAlignment(val) => {
+                    pairs.push((
+                        unsafe { CFString::wrap_under_get_rule(kCVPixelBufferPlaneAlignmentKey) },
+                        CFNumber::from(val as i64).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::IOSurfaceProperties(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferIOSurfacePropertiesKey)
+                        },
+                        val.into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::OpenGLESCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferOpenGLESCompatibilityKey)
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::MetalCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(kCVPixelBufferMetalCompatibilityKey)
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::IOSurfaceCoreAnimationCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(
+                                kCVPixelBufferIOSurfaceCoreAnimationCompatibilityKey,
+                            )
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::IOSurfaceOpenGLFBOCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(
+                                kCVPixelBufferIOSurfaceOpenGLFBOCompatibilityKey,
+                            )
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::IOSurfaceOpenGLESFBOCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(
+                                kCVPixelBufferIOSurfaceOpenGLESFBOCompatibilityKey,
+                            )
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::IOSurfaceOpenGLTextureCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(
+                                kCVPixelBufferIOSurfaceOpenGLTextureCompatibilityKey,
+                            )
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::IOSurfaceOpenGLESTextureCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(
+                                kCVPixelBufferIOSurfaceOpenGLESTextureCompatibilityKey,
+                            )
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::OpenGLTextureCacheCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(
+                                kCVPixelBufferOpenGLTextureCacheCompatibilityKey,
+                            )
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+                PixelBufferAttribute::OpenGLESTextureCacheCompatibility(val) => {
+                    pairs.push((
+                        unsafe {
+                            CFString::wrap_under_get_rule(
+                                kCVPixelBufferOpenGLESTextureCacheCompatibilityKey,
+                            )
+                        },
+                        CFBoolean::from(val).into_CFType(),
+                    ));
+                }
+            }
+        }
+        CFDictionary::from_CFType_pairs(&pairs)
+    }
+}
+
+/// Reads attributes as CoreVideo hands them out, e.g. from `CVPixelBufferGetAttributes` or
+/// a pool. Unknown keys are kept when their values are property lists. The memory
+/// allocator is skipped, since `PixelBufferAttribute::MemoryAllocator` cannot keep it alive.
+impl TryFrom<CFDictionary<CFString, CFType>> for PixelBufferAttributes {
+    type Error = CVPixelBufferError;
+
+    fn try_from(dictionary: CFDictionary<CFString, CFType>) -> Result<Self, Self::Error> {
+        let mut attributes = PixelBufferAttributes::default();
+        let (keys, values) = dictionary.get_keys_and_values();
+        for (key, value) in keys.into_iter().zip(values) {
+            let key = unsafe { CFType::wrap_under_get_rule(key) };
+            let value = unsafe { CFType::wrap_under_get_rule(value) };
+            let Some(key) = key.downcast::<CFString>().map(|key| key.to_string()) else {
+                continue;
+            };
+            match PixelBufferAttributeKey::parse(&key) {
+                Some(PixelBufferAttributeKey::MemoryAllocator) => {}
+                Some(PixelBufferAttributeKey::IOSurfaceProperties) => {
+                    let properties = value
+                        .downcast::<CFUntypedDictionary>()
+                        .ok_or(CVPixelBufferError::InvalidPixelBufferAttributes)?;
+                    attributes.set(PixelBufferAttribute::IOSurfaceProperties(unsafe {
+                        CFDictionary::wrap_under_get_rule(properties.as_concrete_TypeRef())
+                    }));
+                }
+                _ => {
+                    if let Some(value) = DictionaryValue::from_cf_type(&value) {
+                        attributes.insert_value(&key, &value)?;
+                    }
+                }
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+extern "C" {
+    fn CVPixelBufferCreateResolvedAttributesDictionary(
+        allocator: CFAllocatorRef,
+        attributes: CFArrayRef,
+        resolved_dictionary_out: *mut CFDictionaryRef,
+    ) -> CVReturn;
+}
+
+impl CVPixelBuffer {
+    pub(super) fn internal_create_resolved_attributes(
+        attributes: &[PixelBufferAttributes],
+    ) -> Result<PixelBufferAttributes, CVPixelBufferError> {
+        let dictionaries: Vec<CFDictionary<CFString, CFType>> =
+            attributes.iter().cloned().map(Into::into).collect();
+        let dictionaries = CFArray::from_CFTypes(&dictionaries);
+        let mut resolved: CFDictionaryRef = ptr::null();
+        let result = unsafe {
+            CVPixelBufferCreateResolvedAttributesDictionary(
+                kCFAllocatorDefault,
+                dictionaries.as_concrete_TypeRef(),
+                &mut resolved,
+            )
+        };
+        if result != CV_RETURN_SUCCESS {
+            return Err(CVPixelBufferError::from(result));
+        }
+        if resolved.is_null() {
+            return Ok(PixelBufferAttributes::default());
+        }
+        let resolved: CFDictionary<CFString, CFType> =
+            unsafe { CFDictionary::wrap_under_create_rule(resolved) };
+        resolved.try_into()
+    }
+}
+
+extern "C" {
+    // A key to the allocator that the system uses to create the pixel buffer.
+    static kCVPixelBufferMemoryAllocatorKey: CFStringRef;
+    // A key to one or more pixel buffer format types.
+    static kCVPixelBufferPixelFormatTypeKey: CFStringRef;
+    // A key to the width of the pixel buffer.
+    static kCVPixelBufferWidthKey: CFStringRef;
+    // A key to the height of the pixel buffer.
+    static kCVPixelBufferHeightKey: CFStringRef;
+    // A key to the number of pixels padding the left of the image.
+    static kCVPixelBufferExtendedPixelsLeftKey: CFStringRef;
+    // A key to the number of pixels padding the top of the image.
+    static kCVPixelBufferExtendedPixelsTopKey: CFStringRef;
+    // A key to the number of pixels padding the right of the image.
+    static kCVPixelBufferExtendedPixelsRightKey: CFStringRef;
+    // A key to the number of pixels padding the bottom of the image.
+    static kCVPixelBufferExtendedPixelsBottomKey: CFStringRef;
+    // A key to a number that specifies the alignment of number of bytes per row in the pixel buffer.
+    static kCVPixelBufferBytesPerRowAlignmentKey: CFStringRef;
+    // A key to a Boolean value that indicates whether the pixel buffer is compatible with Core Graphics bitmap contexts.
+    static kCVPixelBufferCGBitmapContextCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whether the pixel buffer is compatible with Core Graphics bitmap image types.
+    static kCVPixelBufferCGImageCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whether the pixel buffer is compatible with OpenGL contexts.
+    static kCVPixelBufferOpenGLCompatibilityKey: CFStringRef;
+    // A key to a number that specifies the alignment of the planes in the pixel buffer.
+    static kCVPixelBufferPlaneAlignmentKey: CFStringRef;
+    // A key to the dictionary containing optional CFStringRef for the IOSurface framework.
+    static kCVPixelBufferIOSurfacePropertiesKey: CFStringRef;
+    // A key to a Boolean value that indicates whether the pixel buffer is compatible with OpenGL ES contexts.
+    static kCVPixelBufferOpenGLESCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whetherCFStringRef pixel buffer is compatible with the Metal framework.
+    static kCVPixelBufferMetalCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whether Core Animation can display the pixel buffer.
+    static kCVPixelBufferIOSurfaceCoreAnimationCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whether OpenGL can cCFStringRef a valid texture for use as a color buffer attachment.
+    static kCVPixelBufferIOSurfaceOpenGLFBOCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whether OpenGL ES can create a valid texture for use as a color buffer attachment.
+    static kCVPixelBufferIOSurfaceOpenGLESFBOCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whether OpenGL can crCFStringRef a valid texture object from the IOSurface-backed pixel buffer.
+    static kCVPixelBufferIOSurfaceOpenGLTextureCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whether OpenGL ES can create a valid texture object from the IOSurface-backed pixel buffer.
+    static kCVPixelBufferIOSurfaceOpenGLESTextureCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whether OpenGL performs format conversions of the texture-cache data in a shader.
+    static kCVPixelBufferOpenGLTextureCacheCompatibilityKey: CFStringRef;
+    // A key to a Boolean value that indicates whether OpenGL ES performs format conversions of the texture-cache data in a shader.
+    static kCVPixelBufferOpenGLESTextureCacheCompatibilityKey: CFStringRef;
+}
@@ -8,7 +8,6 @@ use core_foundation::base::{kCFAllocatorDefault, CFAllocatorRef, CFType, TCFType
 use core_foundation::dictionary::CFDictionaryRef;
 use core_foundation::string::CFString;
 use core_graphics::display::CFDictionary;
-use core_utils_rs::four_char_code::FourCharCode;
 use core_utils_rs::trampoline::{create_left_trampoline, TrampolineLeftCallback, TrampolineRefcon};
 use io_surface::{IOSurface, IOSurfaceRef};
 use std::marker::PhantomData;
@@ -17,6 +16,7 @@ use std::ptr::{self};
 
 use super::attributes::PixelBufferAttributes;
 use super::error::CVPixelBufferError;
+use super::pixel_format::PixelFormat;
 use super::planar_data::PlanarDataPointer;
 
 #[derive(Debug)]
@@ -38,7 +38,7 @@ impl CVPixelBuffer {
     pub(super) fn internal_create_with_planar_bytes<'a, TReleaseCallback>(
         width: usize,
         height: usize,
-        pixel_format_type: FourCharCode,
+        pixel_format: PixelFormat,
         data_pointer: PlanarDataPointer,
         release_callback: TReleaseCallback,
         pixel_buffer_attributes: PixelBufferAttributes,
@@ -66,6 +66,8 @@ impl CVPixelBuffer {
             ) -> CVReturn;
 
         }
+        data_pointer.validate_for(pixel_format, width, height)?;
+
         let mut pixel_buffer_out: CVPixelBufferRef = ptr::null_mut();
         let data_ptr = data_pointer.as_ptr();
         let data_size = data_pointer.data_size();
@@ -82,11 +84,11 @@ impl CVPixelBuffer {
                 kCFAllocatorDefault,
                 width,
                 height,
-                pixel_format_type.as_u32(),
+                pixel_format.as_os_type(),
                 data_ptr,
                 data_size,
                 number_of_planes,
-                base_addresses,
+                base_addresses.as_ptr(),
                 plane_width,
                 plane_height,
                 plane_bytes_per_row,
@@ -109,7 +111,7 @@ impl CVPixelBuffer {
     pub(super) fn internal_create_with_bytes<'a, TReleaseCallback>(
         width: usize,
         height: usize,
-        pixel_format_type: FourCharCode,
+        pixel_format: PixelFormat,
         base_address: Vec<u8>,
         bytes_per_row: usize,
         release_callback: TReleaseCallback,
@@ -147,7 +149,7 @@ impl CVPixelBuffer {
                 kCFAllocatorDefault,
                 width,
                 height,
-                pixel_format_type.as_u32(),
+                pixel_format.as_os_type(),
                 base_address_ptr,
                 bytes_per_row,
                 caller,
@@ -156,7 +158,10 @@ impl CVPixelBuffer {
                 &mut pixel_buffer_out,
             );
             if result == CV_RETURN_SUCCESS {
-                Ok(CVPixelBufferWithLifetime(CVPixelBuffer::wrap_under_create_rule(pixel_buffer_out), PhantomData))
+                Ok(CVPixelBufferWithLifetime(
+                    CVPixelBuffer::wrap_under_create_rule(pixel_buffer_out),
+                    PhantomData,
+                ))
             } else {
                 Err(CVPixelBufferError::from(result))
             }
@@ -196,7 +201,7 @@ impl CVPixelBuffer {
     pub(super) fn internal_create(
         width: usize,
         height: usize,
-        pixel_format_type: FourCharCode,
+        pixel_format: PixelFormat,
         pixel_buffer_attributes: PixelBufferAttributes,
     ) -> Result<Self, CVPixelBufferError> {
         extern "C" {
@@ -217,7 +222,7 @@ impl CVPixelBuffer {
                 kCFAllocatorDefault,
                 width,
                 height,
-                pixel_format_type.as_u32(),
+                pixel_format.as_os_type(),
                 pixel_buffer_attributes.as_concrete_TypeRef(),
                 &mut pixel_buffer_out,
             );
@@ -1,3 +1,5 @@
+use std::{ffi::c_void, ops::Range};
+
 use core_foundation::base::TCFType;
 
 use crate::{
@@ -5,13 +7,13 @@ use crate::{
     types::CVReturn,
 };
 
-use super::{error::CVPixelBufferError, internal_base::CVPixelBuffer};
+use super::{
+    error::CVPixelBufferError,
+    extended_pixels::padded_planes,
+    internal_base::CVPixelBuffer,
+    lock::{BaseAddressLock, CVPixelBufferLockFlags, RawPlane},
+};
 
-#[repr(u32)]
-pub enum CVPixelBufferLockFlags {
-    ReadWrite = 0x0,
-    ReadOnly = 0x00000001,
-}
 impl CVPixelBuffer {
     pub fn internal_lock_base_address(
         &self,
@@ -52,30 +54,104 @@ impl CVPixelBuffer {
             Err(CVPixelBufferError::from(result))
         }
     }
-    pub fn internal_base_address<'a>(&self) -> Result<&'a [u8], CVPixelBufferError> {
+    pub fn internal_plane(&self, plane_index: usize) -> Result<RawPlane, CVPixelBufferError> {
         extern "C" {
             fn CVPixelBufferGetBaseAddress(pixelBuffer: CVPixelBufferRef) -> *mut u8;
+            fn CVPixelBufferGetBaseAddressOfPlane(
+                pixelBuffer: CVPixelBufferRef,
+                planeIndex: usize,
+            ) -> *mut u8;
         }
 
-        let result = unsafe { CVPixelBufferGetBaseAddress(self.as_concrete_TypeRef()) };
-        if result.is_null() {
+        let plane = if self.internal_is_planar() {
+            if plane_index >= self.internal_plane_count() {
+                return Err(CVPixelBufferError::InvalidArgument);
+            }
+            RawPlane::new(
+                unsafe {
+                    CVPixelBufferGetBaseAddressOfPlane(self.as_concrete_TypeRef(), plane_index)
+                },
+                self.internal_width_of_plane(plane_index),
+                self.internal_height_of_plane(plane_index),
+                self.internal_bytes_per_row_of_plane(plane_index),
+            )
+        } else {
+            if plane_index != 0 {
+                return Err(CVPixelBufferError::InvalidArgument);
+            }
+            RawPlane::new(
+                unsafe { CVPixelBufferGetBaseAddress(self.as_concrete_TypeRef()) },
+                self.internal_width(),
+                self.internal_height(),
+                self.internal_bytes_per_row(),
+            )
+        };
+        if plane.ptr.is_null() {
             Err(CVPixelBufferError::BaseAddress)
         } else {
-            let size = self.internal_bytes_per_row() * self.internal_height();
-            Ok(unsafe { std::slice::from_raw_parts(result, size) })
+            Ok(plane)
         }
     }
-    pub fn internal_base_address_mut<'a>(&self) -> Result<&'a mut [u8], CVPixelBufferError> {
+    /// Memory of the backing IOSurface, if any. CoreVideo allocates the extended pixels it
+    /// reports for other buffers itself.
+    fn internal_allocation(&self) -> Option<Range<*const u8>> {
+        // Declared here rather than taken from `io_surface`, which deprecates its bindings.
         extern "C" {
-            fn CVPixelBufferGetBaseAddress(pixelBuffer: CVPixelBufferRef) -> *mut u8;
+            fn CVPixelBufferGetIOSurface(pixelBuffer: CVPixelBufferRef) -> *const c_void;
+            fn IOSurfaceGetBaseAddress(buffer: *const c_void) -> *mut c_void;
+            fn IOSurfaceGetAllocSize(buffer: *const c_void) -> usize;
         }
 
-        let result = unsafe { CVPixelBufferGetBaseAddress(self.as_concrete_TypeRef()) };
-        if result.is_null() {
-            Err(CVPixelBufferError::BaseAddress)
+        let surface = unsafe { CVPixelBufferGetIOSurface(self.as_concrete_TypeRef()) };
+        if surface.is_null() {
+            return None;
+        }
+        let start = unsafe { IOSurfaceGetBaseAddress(surface) } as *const u8;
+        let size = unsafe { IOSurfaceGetAllocSize(surface) };
+        Some(start..start.wrapping_add(size))
+    }
+    pub fn internal_planes(&self) -> Result<Vec<RawPlane>, CVPixelBufferError> {
+        let count = if self.internal_is_planar() {
+            self.internal_plane_count()
         } else {
-            let size = self.internal_bytes_per_row() * self.internal_height();
-            Ok(unsafe { std::slice::from_raw_parts_mut(result, size) })
+            1
+        };
+        (0..count).map(|i| self.internal_plane(i)).collect()
+    }
+}
+
+impl BaseAddressLock for CVPixelBuffer {
+    fn lock_base_address(
+        &self,
+        lock_flags: CVPixelBufferLockFlags,
+    ) -> Result<(), CVPixelBufferError> {
+        self.internal_lock_base_address(lock_flags)
+    }
+    fn unlock_base_address(
+        &self,
+        unlock_flags: CVPixelBufferLockFlags,
+    ) -> Result<(), CVPixelBufferError> {
+        self.internal_unlock_base_address(unlock_flags)
+    }
+    fn base_address_planes(&self) -> Result<Vec<RawPlane>, CVPixelBufferError> {
+        self.internal_planes()
+    }
+    fn base_address_planes_mut(&mut self) -> Result<Vec<RawPlane>, CVPixelBufferError> {
+        self.internal_planes()
+    }
+    fn base_address_padded_planes(
+        &self,
+        planes: &[RawPlane],
+    ) -> Result<Vec<RawPlane>, CVPixelBufferError> {
+        let extended = self.internal_extended_pixels();
+        if extended.is_empty() {
+            return Ok(planes.to_vec());
         }
+        padded_planes(
+            planes,
+            self.internal_allocation(),
+            self.get_pixel_format()?,
+            extended,
+        )
     }
 }
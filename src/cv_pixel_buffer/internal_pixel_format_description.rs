@@ -0,0 +1,71 @@
+use core_foundation::{
+    array::{CFArray, CFArrayRef},
+    base::{kCFAllocatorDefault, CFAllocatorRef, CFType, TCFType},
+    dictionary::CFDictionaryRef,
+    number::CFNumber,
+};
+use core_graphics::display::CFDictionary;
+
+use super::{error::CVPixelBufferError, pixel_format_description::PixelFormatDescription};
+use crate::{
+    dictionary::internal_dictionary::{dictionary_from_cf, dictionary_to_cf},
+    types::OSType,
+};
+
+extern "C" {
+    fn CVPixelFormatDescriptionCreateWithPixelFormatType(
+        allocator: CFAllocatorRef,
+        pixel_format: OSType,
+    ) -> CFDictionaryRef;
+    fn CVPixelFormatDescriptionArrayCreateWithAllPixelFormatTypes(
+        allocator: CFAllocatorRef,
+    ) -> CFArrayRef;
+    fn CVPixelFormatDescriptionRegisterDescriptionWithPixelFormatType(
+        description: CFDictionaryRef,
+        pixel_format: OSType,
+    );
+}
+
+impl PixelFormatDescription {
+    /// Loads CoreVideo's description of `pixel_format`, including registered custom formats.
+    pub fn load(pixel_format: OSType) -> Result<Self, CVPixelBufferError> {
+        let description = unsafe {
+            CVPixelFormatDescriptionCreateWithPixelFormatType(kCFAllocatorDefault, pixel_format)
+        };
+        if description.is_null() {
+            return Err(CVPixelBufferError::InvalidPixelFormat);
+        }
+        let description: CFDictionary<CFType, CFType> =
+            unsafe { CFDictionary::wrap_under_create_rule(description) };
+        Self::from_dictionary(&dictionary_from_cf(&description))
+    }
+    /// Every pixel format CoreVideo has a description for.
+    pub fn all_pixel_formats() -> Vec<OSType> {
+        let array = unsafe {
+            CVPixelFormatDescriptionArrayCreateWithAllPixelFormatTypes(kCFAllocatorDefault)
+        };
+        if array.is_null() {
+            return Vec::new();
+        }
+        let array: CFArray<CFNumber> = unsafe { CFArray::wrap_under_create_rule(array) };
+        array
+            .iter()
+            .filter_map(|number| Some(number.to_i64()? as OSType))
+            .collect()
+    }
+    /// Registers this description with CoreVideo so buffers of a custom format can be
+    /// created. Fails with `InvalidArgument` for descriptions without planes.
+    pub fn register(&self) -> Result<(), CVPixelBufferError> {
+        if self.planes.is_empty() {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let description = dictionary_to_cf(&self.to_dictionary());
+        unsafe {
+            CVPixelFormatDescriptionRegisterDescriptionWithPixelFormatType(
+                description.as_concrete_TypeRef(),
+                self.pixel_format,
+            );
+        }
+        Ok(())
+    }
+}
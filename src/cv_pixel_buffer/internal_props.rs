@@ -2,10 +2,14 @@ use core_foundation::base::TCFType;
 
 use crate::cv_pixel_buffer::internal_base::CVPixelBufferRef;
 
-use super::internal_base::CVPixelBuffer;
+use super::{
+    error::{CVPixelBufferError, CV_RETURN_SUCCESS},
+    extended_pixels::ExtendedPixels,
+    internal_base::CVPixelBuffer,
+};
+use crate::types::{CVReturn, OSType};
 
-
-impl  CVPixelBuffer {
+impl CVPixelBuffer {
     pub(super) fn internal_is_planar(&self) -> bool {
         extern "C" {
             fn CVPixelBufferIsPlanar(pixel_buffer_ref: CVPixelBufferRef) -> i32;
@@ -34,4 +38,83 @@ impl  CVPixelBuffer {
 
         unsafe { CVPixelBufferGetHeight(self.as_concrete_TypeRef()) }
     }
+    pub(super) fn internal_pixel_format_type(&self) -> OSType {
+        extern "C" {
+            fn CVPixelBufferGetPixelFormatType(pixel_buffer_ref: CVPixelBufferRef) -> OSType;
+        }
+
+        unsafe { CVPixelBufferGetPixelFormatType(self.as_concrete_TypeRef()) }
+    }
+    pub(super) fn internal_plane_count(&self) -> usize {
+        extern "C" {
+            fn CVPixelBufferGetPlaneCount(pixel_buffer_ref: CVPixelBufferRef) -> usize;
+        }
+
+        unsafe { CVPixelBufferGetPlaneCount(self.as_concrete_TypeRef()) }
+    }
+    pub(super) fn internal_bytes_per_row_of_plane(&self, plane_index: usize) -> usize {
+        extern "C" {
+            fn CVPixelBufferGetBytesPerRowOfPlane(
+                pixel_buffer_ref: CVPixelBufferRef,
+                plane_index: usize,
+            ) -> usize;
+        }
+
+        unsafe { CVPixelBufferGetBytesPerRowOfPlane(self.as_concrete_TypeRef(), plane_index) }
+    }
+    pub(super) fn internal_width_of_plane(&self, plane_index: usize) -> usize {
+        extern "C" {
+            fn CVPixelBufferGetWidthOfPlane(
+                pixel_buffer_ref: CVPixelBufferRef,
+                plane_index: usize,
+            ) -> usize;
+        }
+
+        unsafe { CVPixelBufferGetWidthOfPlane(self.as_concrete_TypeRef(), plane_index) }
+    }
+    pub(super) fn internal_height_of_plane(&self, plane_index: usize) -> usize {
+        extern "C" {
+            fn CVPixelBufferGetHeightOfPlane(
+                pixel_buffer_ref: CVPixelBufferRef,
+                plane_index: usize,
+            ) -> usize;
+        }
+
+        unsafe { CVPixelBufferGetHeightOfPlane(self.as_concrete_TypeRef(), plane_index) }
+    }
+    pub(super) fn internal_extended_pixels(&self) -> ExtendedPixels {
+        extern "C" {
+            fn CVPixelBufferGetExtendedPixels(
+                pixel_buffer_ref: CVPixelBufferRef,
+                extra_columns_on_left: *mut usize,
+                extra_columns_on_right: *mut usize,
+                extra_rows_on_top: *mut usize,
+                extra_rows_on_bottom: *mut usize,
+            );
+        }
+
+        let mut extended = ExtendedPixels::default();
+        unsafe {
+            CVPixelBufferGetExtendedPixels(
+                self.as_concrete_TypeRef(),
+                &mut extended.left,
+                &mut extended.right,
+                &mut extended.top,
+                &mut extended.bottom,
+            )
+        };
+        extended
+    }
+    pub(super) fn internal_fill_extended_pixels(&self) -> Result<(), CVPixelBufferError> {
+        extern "C" {
+            fn CVPixelBufferFillExtendedPixels(pixel_buffer_ref: CVPixelBufferRef) -> CVReturn;
+        }
+
+        let result = unsafe { CVPixelBufferFillExtendedPixels(self.as_concrete_TypeRef()) };
+        if result == CV_RETURN_SUCCESS {
+            Ok(())
+        } else {
+            Err(CVPixelBufferError::from(result))
+        }
+    }
 }
@@ -1,98 +1,360 @@
 use std::{
-    io, ops::{Deref, DerefMut}
+    fmt::Debug,
+    io,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
 };
 
-use core_utils_rs::lock::{
-    self, LockGuard, LockGuardTrait, LockTrait, MutLockGuard, MutLockGuardTrait, MutLockTrait,
+#[cfg(target_vendor = "apple")]
+use crate::cv_pixel_buffer::CVPixelBuffer;
+
+use super::{
+    error::CVPixelBufferError,
+    plane::{Plane, PlaneMut},
 };
 
-use crate::cv_pixel_buffer::{error::CVPixelBufferError, CVPixelBuffer};
+#[repr(u64)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CVPixelBufferLockFlags {
+    ReadWrite = 0x0,
+    ReadOnly = 0x00000001,
+}
 
-use super::internal_lock::CVPixelBufferLockFlags;
+/// Lock primitives shared by every pixel buffer backend the guards can sit on.
+pub(crate) trait BaseAddressLock: Debug {
+    fn lock_base_address(
+        &self,
+        lock_flags: CVPixelBufferLockFlags,
+    ) -> Result<(), CVPixelBufferError>;
+    fn unlock_base_address(
+        &self,
+        unlock_flags: CVPixelBufferLockFlags,
+    ) -> Result<(), CVPixelBufferError>;
+    /// Planes of a buffer locked read-only; non-planar buffers report a single plane.
+    fn base_address_planes(&self) -> Result<Vec<RawPlane>, CVPixelBufferError>;
+    /// Planes of a buffer locked read-write.
+    fn base_address_planes_mut(&mut self) -> Result<Vec<RawPlane>, CVPixelBufferError>;
+    /// `planes` grown to include the buffer's extended pixels.
+    fn base_address_padded_planes(
+        &self,
+        planes: &[RawPlane],
+    ) -> Result<Vec<RawPlane>, CVPixelBufferError> {
+        Ok(planes.to_vec())
+    }
+}
 
+/// Address and geometry of one plane, as reported by CoreVideo while the buffer is locked.
+#[derive(Debug, Clone, Copy)]
+pub struct RawPlane {
+    pub ptr: *mut u8,
+    pub width: usize,
+    pub height: usize,
+    pub bytes_per_row: usize,
+    /// Number of addressable bytes, which is `bytes_per_row * height` unless the plane is
+    /// cropped and its last row ends early.
+    pub size: usize,
+}
 
+impl RawPlane {
+    pub fn new(ptr: *mut u8, width: usize, height: usize, bytes_per_row: usize) -> Self {
+        Self {
+            ptr,
+            width,
+            height,
+            bytes_per_row,
+            size: bytes_per_row * height,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.size
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// # Safety
+    /// The buffer must stay locked for `'a`.
+    pub unsafe fn as_plane<'a>(&self) -> Result<Plane<'a>, CVPixelBufferError> {
+        Plane::new_cropped(
+            std::slice::from_raw_parts(self.ptr, self.len()),
+            self.width,
+            self.height,
+            self.bytes_per_row,
+        )
+    }
+    /// # Safety
+    /// The buffer must stay locked for `'a` and no other view of this plane may exist.
+    pub unsafe fn as_plane_mut<'a>(&self) -> Result<PlaneMut<'a>, CVPixelBufferError> {
+        PlaneMut::new(
+            std::slice::from_raw_parts_mut(self.ptr, self.len()),
+            self.width,
+            self.height,
+            self.bytes_per_row,
+        )
+    }
+    /// Returns the `width` x `height` region starting `x_offset` bytes into row `y`.
+    pub(crate) fn crop(
+        &self,
+        x_offset: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Self, CVPixelBufferError> {
+        let offset = y
+            .checked_mul(self.bytes_per_row)
+            .and_then(|offset| offset.checked_add(x_offset))
+            .filter(|&offset| offset <= self.size && x_offset <= self.bytes_per_row)
+            .ok_or(CVPixelBufferError::InvalidArgument)?;
+        if y.checked_add(height).is_none_or(|end| end > self.height) || width > self.width {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        Ok(Self {
+            ptr: self.ptr.wrapping_add(offset),
+            width,
+            height,
+            bytes_per_row: self.bytes_per_row,
+            size: (self.size - offset).min(self.bytes_per_row * height),
+        })
+    }
+    /// Fails if any two planes share memory, which would make handing out mutable views of
+    /// all of them at once unsound.
+    pub fn check_disjoint(planes: &[RawPlane]) -> Result<(), CVPixelBufferError> {
+        let mut ranges: Vec<(usize, usize)> = planes
+            .iter()
+            .map(|p| (p.ptr as usize, p.ptr as usize + p.len()))
+            .collect();
+        ranges.sort_unstable();
+        if ranges.windows(2).all(|w| w[0].1 <= w[1].0) {
+            Ok(())
+        } else {
+            Err(CVPixelBufferError::BaseAddress)
+        }
+    }
+}
 
+/// Read-only lock on the base address of a pixel buffer.
+///
+/// Every slice handed out borrows from the guard, so it cannot outlive the lock. The buffer
+/// is unlocked when the guard is dropped; call [`BaseAddressGuard::unlock`] to observe
+/// unlock failures instead of ignoring them.
 #[derive(Debug)]
-pub struct BaseAddressGuard<'a>(CVPixelBuffer, &'a [u8]);
+pub struct BaseAddressGuard<'a> {
+    buffer: &'a dyn BaseAddressLock,
+    planes: Vec<RawPlane>,
+    padded_planes: Vec<RawPlane>,
+    locked: bool,
+}
 
-impl BaseAddressGuard<'_> {
+impl<'a> BaseAddressGuard<'a> {
+    pub(crate) fn new<B: BaseAddressLock>(buffer: &'a B) -> Result<Self, CVPixelBufferError> {
+        buffer.lock_base_address(CVPixelBufferLockFlags::ReadOnly)?;
+        let planes = buffer.base_address_planes();
+        let mut guard = Self {
+            buffer,
+            planes: Vec::new(),
+            padded_planes: Vec::new(),
+            locked: true,
+        };
+        guard.planes = planes?;
+        guard.padded_planes = buffer.base_address_padded_planes(&guard.planes)?;
+        Ok(guard)
+    }
     pub fn as_slice(&self) -> &[u8] {
-        self.1
+        let plane = &self.planes[0];
+        unsafe { std::slice::from_raw_parts(plane.ptr, plane.len()) }
     }
     pub fn as_cursor(&self) -> io::Cursor<&[u8]> {
-        io::Cursor::new(self.1)
+        io::Cursor::new(self.as_slice())
+    }
+    /// Number of planes; non-planar buffers are reported as a single plane.
+    pub fn plane_count(&self) -> usize {
+        self.planes.len()
+    }
+    pub fn plane(&self, plane_index: usize) -> Result<Plane<'_>, CVPixelBufferError> {
+        let plane = self
+            .planes
+            .get(plane_index)
+            .ok_or(CVPixelBufferError::InvalidArgument)?;
+        unsafe { plane.as_plane() }
+    }
+    pub fn planes(&self) -> Result<Vec<Plane<'_>>, CVPixelBufferError> {
+        self.planes
+            .iter()
+            .map(|plane| unsafe { plane.as_plane() })
+            .collect()
+    }
+    /// Returns the plane together with its extended pixels, or the same as
+    /// [`BaseAddressGuard::plane`] if the buffer has none.
+    pub fn padded_plane(&self, plane_index: usize) -> Result<Plane<'_>, CVPixelBufferError> {
+        let plane = self
+            .padded_planes
+            .get(plane_index)
+            .ok_or(CVPixelBufferError::InvalidArgument)?;
+        unsafe { plane.as_plane() }
+    }
+    /// Replaces every plane with the result of `f`, keeping the lock. The new planes have
+    /// no extended pixels.
+    pub(crate) fn map_planes(
+        mut self,
+        f: impl Fn(usize, &RawPlane) -> Result<RawPlane, CVPixelBufferError>,
+    ) -> Result<Self, CVPixelBufferError> {
+        self.planes = self
+            .planes
+            .iter()
+            .enumerate()
+            .map(|(i, plane)| f(i, plane))
+            .collect::<Result<_, _>>()?;
+        self.padded_planes = self.planes.clone();
+        Ok(self)
+    }
+    pub fn unlock(mut self) -> Result<(), CVPixelBufferError> {
+        self.locked = false;
+        self.buffer
+            .unlock_base_address(CVPixelBufferLockFlags::ReadOnly)
     }
 }
+
 impl Deref for BaseAddressGuard<'_> {
     type Target = [u8];
     fn deref(&self) -> &Self::Target {
-        self.1
+        self.as_slice()
+    }
+}
+
+impl Drop for BaseAddressGuard<'_> {
+    fn drop(&mut self) {
+        if self.locked {
+            let _ = self
+                .buffer
+                .unlock_base_address(CVPixelBufferLockFlags::ReadOnly);
+        }
     }
 }
 
+/// Read-write lock on the base address of a pixel buffer.
+///
+/// Taking the lock requires a mutable borrow of the buffer, so no other guard can exist for
+/// the same handle while this one is alive. Clones of a `CVPixelBuffer` share the underlying
+/// buffer and are not covered by this guarantee.
 #[derive(Debug)]
-pub struct MutBaseAddressGuard<'a>(CVPixelBuffer, &'a mut [u8]);
+pub struct MutBaseAddressGuard<'a> {
+    buffer: &'a dyn BaseAddressLock,
+    planes: Vec<RawPlane>,
+    padded_planes: Vec<RawPlane>,
+    locked: bool,
+    _exclusive: PhantomData<&'a mut ()>,
+}
 
-impl MutBaseAddressGuard<'_> {
+impl<'a> MutBaseAddressGuard<'a> {
+    pub(crate) fn new<B: BaseAddressLock>(buffer: &'a mut B) -> Result<Self, CVPixelBufferError> {
+        buffer.lock_base_address(CVPixelBufferLockFlags::ReadWrite)?;
+        let planes = buffer.base_address_planes_mut();
+        let mut guard = Self {
+            buffer,
+            planes: Vec::new(),
+            padded_planes: Vec::new(),
+            locked: true,
+            _exclusive: PhantomData,
+        };
+        guard.planes = planes?;
+        guard.padded_planes = guard.buffer.base_address_padded_planes(&guard.planes)?;
+        Ok(guard)
+    }
     pub fn as_slice(&self) -> &[u8] {
-        self.1
+        let plane = &self.planes[0];
+        unsafe { std::slice::from_raw_parts(plane.ptr, plane.len()) }
     }
     pub fn as_mut_slice(&mut self) -> &mut [u8] {
-        self.1
+        let plane = &self.planes[0];
+        unsafe { std::slice::from_raw_parts_mut(plane.ptr, plane.len()) }
     }
     pub fn as_cursor(&self) -> io::Cursor<&[u8]> {
-        io::Cursor::new(self.1)
+        io::Cursor::new(self.as_slice())
     }
     pub fn as_mut_cursor(&mut self) -> io::Cursor<&mut [u8]> {
-        io::Cursor::new(self.1)
+        io::Cursor::new(self.as_mut_slice())
+    }
+    /// Number of planes; non-planar buffers are reported as a single plane.
+    pub fn plane_count(&self) -> usize {
+        self.planes.len()
+    }
+    pub fn plane(&self, plane_index: usize) -> Result<Plane<'_>, CVPixelBufferError> {
+        let plane = self
+            .planes
+            .get(plane_index)
+            .ok_or(CVPixelBufferError::InvalidArgument)?;
+        unsafe { plane.as_plane() }
+    }
+    pub fn plane_mut(&mut self, plane_index: usize) -> Result<PlaneMut<'_>, CVPixelBufferError> {
+        let plane = self
+            .planes
+            .get(plane_index)
+            .ok_or(CVPixelBufferError::InvalidArgument)?;
+        unsafe { plane.as_plane_mut() }
+    }
+    /// Returns the plane together with its extended pixels, or the same as
+    /// [`MutBaseAddressGuard::plane`] if the buffer has none.
+    pub fn padded_plane(&self, plane_index: usize) -> Result<Plane<'_>, CVPixelBufferError> {
+        let plane = self
+            .padded_planes
+            .get(plane_index)
+            .ok_or(CVPixelBufferError::InvalidArgument)?;
+        unsafe { plane.as_plane() }
+    }
+    pub fn padded_plane_mut(
+        &mut self,
+        plane_index: usize,
+    ) -> Result<PlaneMut<'_>, CVPixelBufferError> {
+        let plane = self
+            .padded_planes
+            .get(plane_index)
+            .ok_or(CVPixelBufferError::InvalidArgument)?;
+        unsafe { plane.as_plane_mut() }
+    }
+    /// Returns mutable views of all planes at once, e.g. to write luma and chroma in a
+    /// single pass.
+    pub fn planes_mut(&mut self) -> Result<Vec<PlaneMut<'_>>, CVPixelBufferError> {
+        RawPlane::check_disjoint(&self.planes)?;
+        self.planes
+            .iter()
+            .map(|plane| unsafe { plane.as_plane_mut() })
+            .collect()
+    }
+    pub fn unlock(mut self) -> Result<(), CVPixelBufferError> {
+        self.locked = false;
+        self.buffer
+            .unlock_base_address(CVPixelBufferLockFlags::ReadWrite)
     }
 }
 
 impl Deref for MutBaseAddressGuard<'_> {
     type Target = [u8];
     fn deref(&self) -> &Self::Target {
-        self.1
+        self.as_slice()
     }
 }
 
 impl DerefMut for MutBaseAddressGuard<'_> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        self.1
+        self.as_mut_slice()
     }
 }
 
-impl LockGuardTrait for BaseAddressGuard<'_> {
-    fn unlock(&self) {
-        self.0
-            .internal_unlock_base_address(CVPixelBufferLockFlags::ReadOnly)
-            .expect("Could not unlock base");
-    }
-}
-impl MutLockGuardTrait for MutBaseAddressGuard<'_> {
-    fn unlock_mut(&mut self) {
-        self.0
-            .internal_unlock_base_address(CVPixelBufferLockFlags::ReadWrite)
-            .expect("Could not unlock base");
+impl Drop for MutBaseAddressGuard<'_> {
+    fn drop(&mut self) {
+        if self.locked {
+            let _ = self
+                .buffer
+                .unlock_base_address(CVPixelBufferLockFlags::ReadWrite);
+        }
     }
 }
 
-impl<'a> LockTrait<BaseAddressGuard<'a>, CVPixelBufferError> for CVPixelBuffer {
-    fn lock(&self) -> Result<LockGuard<BaseAddressGuard<'a>>, CVPixelBufferError> {
-        self.internal_lock_base_address(CVPixelBufferLockFlags::ReadOnly)?;
-        Ok(LockGuard(BaseAddressGuard(
-            self.clone(),
-            self.internal_base_address()?,
-        )))
+#[cfg(target_vendor = "apple")]
+impl CVPixelBuffer {
+    pub fn lock(&self) -> Result<BaseAddressGuard<'_>, CVPixelBufferError> {
+        BaseAddressGuard::new(self)
     }
-}
-impl<'a> MutLockTrait<MutBaseAddressGuard<'a>, CVPixelBufferError> for CVPixelBuffer {
-    fn lock_mut(
-        &mut self,
-    ) -> Result<lock::MutLockGuard<MutBaseAddressGuard<'a>>, CVPixelBufferError> {
-        self.internal_lock_base_address(CVPixelBufferLockFlags::ReadWrite)?;
-        Ok(MutLockGuard(MutBaseAddressGuard(
-            self.clone(),
-            self.internal_base_address_mut()?,
-        )))
+    pub fn lock_mut(&mut self) -> Result<MutBaseAddressGuard<'_>, CVPixelBufferError> {
+        MutBaseAddressGuard::new(self)
     }
 }
@@ -1,20 +1,60 @@
 pub mod attributes;
+#[cfg(feature = "serde")]
+mod attributes_serde;
 pub mod error;
+pub mod extended_pixels;
+#[cfg(target_vendor = "apple")]
+mod internal_attributes;
+#[cfg(target_vendor = "apple")]
 mod internal_base;
+#[cfg(target_vendor = "apple")]
 mod internal_create;
+#[cfg(target_vendor = "apple")]
 mod internal_lock;
+#[cfg(target_vendor = "apple")]
+mod internal_pixel_format_description;
+#[cfg(target_vendor = "apple")]
 mod internal_props;
 pub mod lock;
+pub mod pixel_buffer_like;
+pub mod pixel_format;
+pub mod pixel_format_description;
+pub mod pixel_format_layout;
 pub mod planar_data;
+pub mod plane;
+pub mod software;
+pub mod view;
 
+pub use extended_pixels::ExtendedPixels;
+#[cfg(target_vendor = "apple")]
+pub use internal_base::CVPixelBuffer;
+#[cfg(target_vendor = "apple")]
+pub(crate) use internal_base::CVPixelBufferRef;
+pub use pixel_buffer_like::PixelBufferLike;
+pub use software::SoftwarePixelBuffer;
+pub use view::{PixelBufferView, PixelRect};
+
+#[cfg(target_vendor = "apple")]
+use crate::attachments::{
+    internal_attachments::{CVBufferRef, InternalAttachments},
+    CVAttachmentMode, ImageBufferAttachment, ImageBufferAttachmentKey, ImageBufferAttachments,
+};
+#[cfg(target_vendor = "apple")]
 use attributes::PixelBufferAttributes;
-use core_utils_rs::four_char_code::FourCharCode;
+#[cfg(target_vendor = "apple")]
+use core_foundation::base::TCFType;
+#[cfg(target_vendor = "apple")]
 use error::CVPixelBufferError;
-pub use internal_base::CVPixelBuffer;
+#[cfg(target_vendor = "apple")]
 use internal_create::CVPixelBufferWithLifetime;
+#[cfg(target_vendor = "apple")]
 use io_surface::IOSurface;
+#[cfg(target_vendor = "apple")]
+use pixel_format::PixelFormat;
+#[cfg(target_vendor = "apple")]
 use planar_data::PlanarDataPointer;
 
+#[cfg(target_vendor = "apple")]
 impl CVPixelBuffer {
     pub fn is_planar(&self) -> bool {
         self.internal_is_planar()
@@ -28,14 +68,77 @@ impl CVPixelBuffer {
     pub fn get_height(&self) -> usize {
         self.internal_height()
     }
+    pub fn get_pixel_format(&self) -> Result<PixelFormat, CVPixelBufferError> {
+        PixelFormat::try_from(self.internal_pixel_format_type())
+    }
+    /// Returns 0 for non-planar buffers.
+    pub fn get_plane_count(&self) -> usize {
+        self.internal_plane_count()
+    }
+    pub fn get_width_of_plane(&self, plane_index: usize) -> usize {
+        self.internal_width_of_plane(plane_index)
+    }
+    pub fn get_height_of_plane(&self, plane_index: usize) -> usize {
+        self.internal_height_of_plane(plane_index)
+    }
+    pub fn get_bytes_per_row_of_plane(&self, plane_index: usize) -> usize {
+        self.internal_bytes_per_row_of_plane(plane_index)
+    }
+    /// Padding CoreVideo allocated around the image. The base addresses point at the first
+    /// visible pixel; [`BaseAddressGuard::padded_plane`] includes the padding.
+    ///
+    /// [`BaseAddressGuard::padded_plane`]: lock::BaseAddressGuard::padded_plane
+    pub fn get_extended_pixels(&self) -> ExtendedPixels {
+        self.internal_extended_pixels()
+    }
+    /// Fills the extended pixels with the pixel format's fill callback, which replicates
+    /// the edges for the built-in formats. Does nothing if there are none.
+    pub fn fill_extended_pixels(&mut self) -> Result<(), CVPixelBufferError> {
+        self.internal_fill_extended_pixels()
+    }
+
+    pub fn set_attachment(&self, attachment: &ImageBufferAttachment, mode: CVAttachmentMode) {
+        self.internal_set_attachment(attachment, mode)
+    }
+    pub fn get_attachment(
+        &self,
+        key: ImageBufferAttachmentKey,
+    ) -> Option<(ImageBufferAttachment, CVAttachmentMode)> {
+        self.internal_get_attachment(key)
+    }
+    pub fn remove_attachment(&self, key: ImageBufferAttachmentKey) {
+        self.internal_remove_attachment(key)
+    }
+    pub fn remove_all_attachments(&self) {
+        self.internal_remove_all_attachments()
+    }
+    /// Returns the attachments with the given mode that this crate can represent.
+    pub fn get_attachments(&self, mode: CVAttachmentMode) -> ImageBufferAttachments {
+        self.internal_copy_attachments(mode)
+    }
+    pub fn set_attachments(&self, attachments: ImageBufferAttachments, mode: CVAttachmentMode) {
+        self.internal_set_attachments(attachments, mode)
+    }
+    /// Copies all `ShouldPropagate` attachments to `destination`.
+    pub fn propagate_attachments(&self, destination: &CVPixelBuffer) {
+        self.internal_propagate_attachments(destination)
+    }
 
     pub fn create(
         width: usize,
         height: usize,
-        pixel_format_type: FourCharCode,
+        pixel_format: PixelFormat,
         pixel_buffer_attributes: PixelBufferAttributes,
     ) -> Result<Self, CVPixelBufferError> {
-        Self::internal_create(width, height, pixel_format_type, pixel_buffer_attributes)
+        Self::internal_create(width, height, pixel_format, pixel_buffer_attributes)
+    }
+    /// Resolves the requirements of several clients into one attribute set with
+    /// `CVPixelBufferCreateResolvedAttributesDictionary`. [`PixelBufferAttributes::resolve`]
+    /// follows the same rules without CoreVideo.
+    pub fn create_resolved_attributes(
+        attributes: &[PixelBufferAttributes],
+    ) -> Result<PixelBufferAttributes, CVPixelBufferError> {
+        Self::internal_create_resolved_attributes(attributes)
     }
 
     pub fn create_with_io_surface(
@@ -47,14 +150,14 @@ impl CVPixelBuffer {
     pub fn create_with_planar_bytes<'a>(
         width: usize,
         height: usize,
-        pixel_format_type: FourCharCode,
+        pixel_format: PixelFormat,
         data_pointer: PlanarDataPointer,
         pixel_buffer_attributes: PixelBufferAttributes,
     ) -> Result<CVPixelBufferWithLifetime<'a>, CVPixelBufferError> {
         Self::internal_create_with_planar_bytes(
             width,
             height,
-            pixel_format_type,
+            pixel_format,
             data_pointer,
             |_| {},
             pixel_buffer_attributes,
@@ -64,7 +167,7 @@ impl CVPixelBuffer {
     pub fn create_with_bytes<'a>(
         width: usize,
         height: usize,
-        pixel_format_type: FourCharCode,
+        pixel_format: PixelFormat,
         base_address: Vec<u8>,
         bytes_per_row: usize,
         pixel_buffer_attributes: PixelBufferAttributes,
@@ -72,7 +175,7 @@ impl CVPixelBuffer {
         Self::internal_create_with_bytes(
             width,
             height,
-            pixel_format_type,
+            pixel_format,
             base_address,
             bytes_per_row,
             |_| {},
@@ -83,7 +186,7 @@ impl CVPixelBuffer {
     pub fn create_with_bytes_release_cb<'a, TReleaseCallback>(
         width: usize,
         height: usize,
-        pixel_format_type: FourCharCode,
+        pixel_format: PixelFormat,
         base_address: Vec<u8>,
         bytes_per_row: usize,
         release_callback: TReleaseCallback,
@@ -95,7 +198,7 @@ impl CVPixelBuffer {
         Self::internal_create_with_bytes(
             width,
             height,
-            pixel_format_type,
+            pixel_format,
             base_address,
             bytes_per_row,
             release_callback,
@@ -106,7 +209,7 @@ impl CVPixelBuffer {
     pub fn create_with_planar_bytes_release_cb<'a, TReleaseCallback>(
         width: usize,
         height: usize,
-        pixel_format_type: FourCharCode,
+        pixel_format: PixelFormat,
         data_pointer: PlanarDataPointer,
         release_callback: TReleaseCallback,
         pixel_buffer_attributes: PixelBufferAttributes,
@@ -117,10 +220,17 @@ impl CVPixelBuffer {
         Self::internal_create_with_planar_bytes(
             width,
             height,
-            pixel_format_type,
+            pixel_format,
             data_pointer,
             release_callback,
             pixel_buffer_attributes,
         )
     }
 }
+
+#[cfg(target_vendor = "apple")]
+impl InternalAttachments for CVPixelBuffer {
+    fn buffer_ref(&self) -> CVBufferRef {
+        self.as_CFTypeRef()
+    }
+}
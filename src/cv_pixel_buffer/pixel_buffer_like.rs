@@ -0,0 +1,145 @@
+#[cfg(target_vendor = "apple")]
+use super::CVPixelBuffer;
+use crate::attachments::{
+    CVAttachmentMode, ImageBufferAttachment, ImageBufferAttachmentKey, ImageBufferAttachments,
+    Rect, Size,
+};
+
+use super::{
+    error::CVPixelBufferError,
+    extended_pixels::ExtendedPixels,
+    lock::{BaseAddressGuard, MutBaseAddressGuard},
+    pixel_format::PixelFormat,
+    software::SoftwarePixelBuffer,
+};
+
+/// Read and lock operations shared by [`CVPixelBuffer`] and [`SoftwarePixelBuffer`].
+///
+/// Frame processing written against this trait runs unchanged on CoreVideo buffers and on
+/// the heap-backed software buffer.
+pub trait PixelBufferLike {
+    fn get_width(&self) -> usize;
+    fn get_height(&self) -> usize;
+    fn get_bytes_per_row(&self) -> usize;
+    fn get_pixel_format(&self) -> Result<PixelFormat, CVPixelBufferError>;
+    fn is_planar(&self) -> bool;
+    fn get_plane_count(&self) -> usize;
+    fn get_width_of_plane(&self, plane_index: usize) -> usize;
+    fn get_height_of_plane(&self, plane_index: usize) -> usize;
+    fn get_bytes_per_row_of_plane(&self, plane_index: usize) -> usize;
+    fn lock(&self) -> Result<BaseAddressGuard<'_>, CVPixelBufferError>;
+    fn lock_mut(&mut self) -> Result<MutBaseAddressGuard<'_>, CVPixelBufferError>;
+    fn get_extended_pixels(&self) -> ExtendedPixels;
+    fn fill_extended_pixels(&mut self) -> Result<(), CVPixelBufferError>;
+    fn set_attachment(&self, attachment: &ImageBufferAttachment, mode: CVAttachmentMode);
+    fn get_attachment(
+        &self,
+        key: ImageBufferAttachmentKey,
+    ) -> Option<(ImageBufferAttachment, CVAttachmentMode)>;
+    fn remove_attachment(&self, key: ImageBufferAttachmentKey);
+    fn remove_all_attachments(&self);
+    fn get_attachments(&self, mode: CVAttachmentMode) -> ImageBufferAttachments;
+    fn set_attachments(&self, attachments: ImageBufferAttachments, mode: CVAttachmentMode);
+
+    /// Size of the whole buffer, like `CVImageBufferGetEncodedSize`.
+    fn get_encoded_size(&self) -> Size {
+        Size::new(self.get_width() as f64, self.get_height() as f64)
+    }
+    /// The `CleanAperture` attachment in buffer coordinates, or the whole buffer if there is
+    /// none, like `CVImageBufferGetCleanRect`.
+    fn get_clean_rect(&self) -> Rect {
+        let encoded_size = self.get_encoded_size();
+        match self.get_attachment(ImageBufferAttachmentKey::CleanAperture) {
+            Some((ImageBufferAttachment::CleanAperture(aperture), _)) => {
+                aperture.clean_rect(encoded_size)
+            }
+            _ => Rect::new(0.0, 0.0, encoded_size.width, encoded_size.height),
+        }
+    }
+    /// Size to display the image at in square pixels, like `CVImageBufferGetDisplaySize`:
+    /// the `DisplayDimensions` attachment, or else the clean rect stretched by the
+    /// `PixelAspectRatio` attachment.
+    fn get_display_size(&self) -> Size {
+        if let Some((ImageBufferAttachment::DisplayDimensions(dimensions), _)) =
+            self.get_attachment(ImageBufferAttachmentKey::DisplayDimensions)
+        {
+            return dimensions.size();
+        }
+        let size = self.get_clean_rect().size();
+        match self.get_attachment(ImageBufferAttachmentKey::PixelAspectRatio) {
+            Some((ImageBufferAttachment::PixelAspectRatio(ratio), _)) => ratio.display_size(size),
+            _ => size,
+        }
+    }
+}
+
+macro_rules! impl_pixel_buffer_like {
+    ($($ty:ty),*) => {$(
+        impl PixelBufferLike for $ty {
+            fn get_width(&self) -> usize {
+                <$ty>::get_width(self)
+            }
+            fn get_height(&self) -> usize {
+                <$ty>::get_height(self)
+            }
+            fn get_bytes_per_row(&self) -> usize {
+                <$ty>::get_bytes_per_row(self)
+            }
+            fn get_pixel_format(&self) -> Result<PixelFormat, CVPixelBufferError> {
+                <$ty>::get_pixel_format(self)
+            }
+            fn is_planar(&self) -> bool {
+                <$ty>::is_planar(self)
+            }
+            fn get_plane_count(&self) -> usize {
+                <$ty>::get_plane_count(self)
+            }
+            fn get_width_of_plane(&self, plane_index: usize) -> usize {
+                <$ty>::get_width_of_plane(self, plane_index)
+            }
+            fn get_height_of_plane(&self, plane_index: usize) -> usize {
+                <$ty>::get_height_of_plane(self, plane_index)
+            }
+            fn get_bytes_per_row_of_plane(&self, plane_index: usize) -> usize {
+                <$ty>::get_bytes_per_row_of_plane(self, plane_index)
+            }
+            fn lock(&self) -> Result<BaseAddressGuard<'_>, CVPixelBufferError> {
+                <$ty>::lock(self)
+            }
+            fn lock_mut(&mut self) -> Result<MutBaseAddressGuard<'_>, CVPixelBufferError> {
+                <$ty>::lock_mut(self)
+            }
+            fn get_extended_pixels(&self) -> ExtendedPixels {
+                <$ty>::get_extended_pixels(self)
+            }
+            fn fill_extended_pixels(&mut self) -> Result<(), CVPixelBufferError> {
+                <$ty>::fill_extended_pixels(self)
+            }
+            fn set_attachment(&self, attachment: &ImageBufferAttachment, mode: CVAttachmentMode) {
+                <$ty>::set_attachment(self, attachment, mode)
+            }
+            fn get_attachment(
+                &self,
+                key: ImageBufferAttachmentKey,
+            ) -> Option<(ImageBufferAttachment, CVAttachmentMode)> {
+                <$ty>::get_attachment(self, key)
+            }
+            fn remove_attachment(&self, key: ImageBufferAttachmentKey) {
+                <$ty>::remove_attachment(self, key)
+            }
+            fn remove_all_attachments(&self) {
+                <$ty>::remove_all_attachments(self)
+            }
+            fn get_attachments(&self, mode: CVAttachmentMode) -> ImageBufferAttachments {
+                <$ty>::get_attachments(self, mode)
+            }
+            fn set_attachments(&self, attachments: ImageBufferAttachments, mode: CVAttachmentMode) {
+                <$ty>::set_attachments(self, attachments, mode)
+            }
+        }
+    )*};
+}
+
+impl_pixel_buffer_like!(SoftwarePixelBuffer<'_>);
+#[cfg(target_vendor = "apple")]
+impl_pixel_buffer_like!(CVPixelBuffer);
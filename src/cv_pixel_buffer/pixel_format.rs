@@ -0,0 +1,371 @@
+use std::fmt;
+
+use core_utils_rs::four_char_code::FourCharCode;
+
+use crate::types::OSType;
+
+use super::error::CVPixelBufferError;
+
+const fn fourcc(code: &[u8; 4]) -> OSType {
+    u32::from_be_bytes(*code)
+}
+
+/// Every `kCVPixelFormatType_*` code known to CoreVideo.
+///
+/// The discriminant of each variant is the `OSType` CoreVideo uses for it. A handful of
+/// legacy formats use plain integers instead of printable four character codes, which is
+/// why converting to a [`FourCharCode`] is fallible while converting to [`OSType`] is not.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    /// `kCVPixelFormatType_1Monochrome`
+    Monochrome1 = 0x0000_0001,
+    /// `kCVPixelFormatType_2Indexed`
+    Indexed2 = 0x0000_0002,
+    /// `kCVPixelFormatType_4Indexed`
+    Indexed4 = 0x0000_0004,
+    /// `kCVPixelFormatType_8Indexed`
+    Indexed8 = 0x0000_0008,
+    /// `kCVPixelFormatType_1IndexedGray_WhiteIsZero`
+    IndexedGrayWhiteIsZero1 = 0x0000_0021,
+    /// `kCVPixelFormatType_2IndexedGray_WhiteIsZero`
+    IndexedGrayWhiteIsZero2 = 0x0000_0022,
+    /// `kCVPixelFormatType_4IndexedGray_WhiteIsZero`
+    IndexedGrayWhiteIsZero4 = 0x0000_0024,
+    /// `kCVPixelFormatType_8IndexedGray_WhiteIsZero`
+    IndexedGrayWhiteIsZero8 = 0x0000_0028,
+    /// `kCVPixelFormatType_16BE555`
+    RGB16BE555 = 0x0000_0010,
+    /// `kCVPixelFormatType_16LE555` ('L555')
+    RGB16LE555 = fourcc(b"L555"),
+    /// `kCVPixelFormatType_16LE5551` ('5551')
+    RGB16LE5551 = fourcc(b"5551"),
+    /// `kCVPixelFormatType_16BE565` ('B565')
+    RGB16BE565 = fourcc(b"B565"),
+    /// `kCVPixelFormatType_16LE565` ('L565')
+    RGB16LE565 = fourcc(b"L565"),
+    /// `kCVPixelFormatType_24RGB`
+    RGB24 = 0x0000_0018,
+    /// `kCVPixelFormatType_24BGR` ('24BG')
+    BGR24 = fourcc(b"24BG"),
+    /// `kCVPixelFormatType_32ARGB`
+    ARGB32 = 0x0000_0020,
+    /// `kCVPixelFormatType_32BGRA` ('BGRA')
+    BGRA32 = fourcc(b"BGRA"),
+    /// `kCVPixelFormatType_32ABGR` ('ABGR')
+    ABGR32 = fourcc(b"ABGR"),
+    /// `kCVPixelFormatType_32RGBA` ('RGBA')
+    RGBA32 = fourcc(b"RGBA"),
+    /// `kCVPixelFormatType_64ARGB` ('b64a')
+    ARGB64 = fourcc(b"b64a"),
+    /// `kCVPixelFormatType_64RGBALE` ('l64r')
+    RGBA64LE = fourcc(b"l64r"),
+    /// `kCVPixelFormatType_48RGB` ('b48r')
+    RGB48 = fourcc(b"b48r"),
+    /// `kCVPixelFormatType_32AlphaGray` ('b32a')
+    AlphaGray32 = fourcc(b"b32a"),
+    /// `kCVPixelFormatType_16Gray` ('b16g')
+    Gray16 = fourcc(b"b16g"),
+    /// `kCVPixelFormatType_30RGB` ('R10k')
+    RGB30 = fourcc(b"R10k"),
+    /// `kCVPixelFormatType_30RGB_r210` ('r210')
+    RGB30r210 = fourcc(b"r210"),
+    /// `kCVPixelFormatType_422YpCbCr8` ('2vuy')
+    YCbCr422_8 = fourcc(b"2vuy"),
+    /// `kCVPixelFormatType_4444YpCbCrA8` ('v408')
+    YCbCrA4444_8 = fourcc(b"v408"),
+    /// `kCVPixelFormatType_4444YpCbCrA8R` ('r408')
+    YCbCrA4444_8R = fourcc(b"r408"),
+    /// `kCVPixelFormatType_4444AYpCbCr8` ('y408')
+    AYCbCr4444_8 = fourcc(b"y408"),
+    /// `kCVPixelFormatType_4444AYpCbCr16` ('y416')
+    AYCbCr4444_16 = fourcc(b"y416"),
+    /// `kCVPixelFormatType_4444AYpCbCrFloat` ('r4fl')
+    AYCbCr4444Float = fourcc(b"r4fl"),
+    /// `kCVPixelFormatType_444YpCbCr8` ('v308')
+    YCbCr444_8 = fourcc(b"v308"),
+    /// `kCVPixelFormatType_422YpCbCr16` ('v216')
+    YCbCr422_16 = fourcc(b"v216"),
+    /// `kCVPixelFormatType_422YpCbCr10` ('v210')
+    YCbCr422_10 = fourcc(b"v210"),
+    /// `kCVPixelFormatType_444YpCbCr10` ('v410')
+    YCbCr444_10 = fourcc(b"v410"),
+    /// `kCVPixelFormatType_420YpCbCr8Planar` ('y420')
+    YCbCr420_8Planar = fourcc(b"y420"),
+    /// `kCVPixelFormatType_420YpCbCr8PlanarFullRange` ('f420')
+    YCbCr420_8PlanarFullRange = fourcc(b"f420"),
+    /// `kCVPixelFormatType_422YpCbCr_4A_8BiPlanar` ('a2vy')
+    YCbCr422_8BiPlanar4A = fourcc(b"a2vy"),
+    /// `kCVPixelFormatType_420YpCbCr8BiPlanarVideoRange` ('420v')
+    YCbCr420_8BiPlanarVideoRange = fourcc(b"420v"),
+    /// `kCVPixelFormatType_420YpCbCr8BiPlanarFullRange` ('420f')
+    YCbCr420_8BiPlanarFullRange = fourcc(b"420f"),
+    /// `kCVPixelFormatType_422YpCbCr8BiPlanarVideoRange` ('422v')
+    YCbCr422_8BiPlanarVideoRange = fourcc(b"422v"),
+    /// `kCVPixelFormatType_422YpCbCr8BiPlanarFullRange` ('422f')
+    YCbCr422_8BiPlanarFullRange = fourcc(b"422f"),
+    /// `kCVPixelFormatType_444YpCbCr8BiPlanarVideoRange` ('444v')
+    YCbCr444_8BiPlanarVideoRange = fourcc(b"444v"),
+    /// `kCVPixelFormatType_444YpCbCr8BiPlanarFullRange` ('444f')
+    YCbCr444_8BiPlanarFullRange = fourcc(b"444f"),
+    /// `kCVPixelFormatType_422YpCbCr8_yuvs` ('yuvs')
+    YCbCr422_8Yuvs = fourcc(b"yuvs"),
+    /// `kCVPixelFormatType_422YpCbCr8FullRange` ('yuvf')
+    YCbCr422_8FullRange = fourcc(b"yuvf"),
+    /// `kCVPixelFormatType_OneComponent8` ('L008')
+    OneComponent8 = fourcc(b"L008"),
+    /// `kCVPixelFormatType_TwoComponent8` ('2C08')
+    TwoComponent8 = fourcc(b"2C08"),
+    /// `kCVPixelFormatType_30RGBLEPackedWideGamut` ('w30r')
+    RGB30LEPackedWideGamut = fourcc(b"w30r"),
+    /// `kCVPixelFormatType_ARGB2101010LEPacked` ('l10r')
+    ARGB2101010LEPacked = fourcc(b"l10r"),
+    /// `kCVPixelFormatType_40ARGBLEWideGamut` ('w40a')
+    ARGB40LEWideGamut = fourcc(b"w40a"),
+    /// `kCVPixelFormatType_40ARGBLEWideGamutPremultiplied` ('w40m')
+    ARGB40LEWideGamutPremultiplied = fourcc(b"w40m"),
+    /// `kCVPixelFormatType_OneComponent10` ('L010')
+    OneComponent10 = fourcc(b"L010"),
+    /// `kCVPixelFormatType_OneComponent12` ('L012')
+    OneComponent12 = fourcc(b"L012"),
+    /// `kCVPixelFormatType_OneComponent16` ('L016')
+    OneComponent16 = fourcc(b"L016"),
+    /// `kCVPixelFormatType_TwoComponent16` ('2C16')
+    TwoComponent16 = fourcc(b"2C16"),
+    /// `kCVPixelFormatType_OneComponent16Half` ('L00h')
+    OneComponent16Half = fourcc(b"L00h"),
+    /// `kCVPixelFormatType_OneComponent32Float` ('L00f')
+    OneComponent32Float = fourcc(b"L00f"),
+    /// `kCVPixelFormatType_TwoComponent16Half` ('2C0h')
+    TwoComponent16Half = fourcc(b"2C0h"),
+    /// `kCVPixelFormatType_TwoComponent32Float` ('2C0f')
+    TwoComponent32Float = fourcc(b"2C0f"),
+    /// `kCVPixelFormatType_64RGBAHalf` ('RGhA')
+    RGBAHalf64 = fourcc(b"RGhA"),
+    /// `kCVPixelFormatType_128RGBAFloat` ('RGfA')
+    RGBAFloat128 = fourcc(b"RGfA"),
+    /// `kCVPixelFormatType_14Bayer_GRBG` ('grb4')
+    Bayer14GRBG = fourcc(b"grb4"),
+    /// `kCVPixelFormatType_14Bayer_RGGB` ('rgg4')
+    Bayer14RGGB = fourcc(b"rgg4"),
+    /// `kCVPixelFormatType_14Bayer_BGGR` ('bgg4')
+    Bayer14BGGR = fourcc(b"bgg4"),
+    /// `kCVPixelFormatType_14Bayer_GBRG` ('gbr4')
+    Bayer14GBRG = fourcc(b"gbr4"),
+    /// `kCVPixelFormatType_DisparityFloat16` ('hdis')
+    DisparityFloat16 = fourcc(b"hdis"),
+    /// `kCVPixelFormatType_DisparityFloat32` ('fdis')
+    DisparityFloat32 = fourcc(b"fdis"),
+    /// `kCVPixelFormatType_DepthFloat16` ('hdep')
+    DepthFloat16 = fourcc(b"hdep"),
+    /// `kCVPixelFormatType_DepthFloat32` ('fdep')
+    DepthFloat32 = fourcc(b"fdep"),
+    /// `kCVPixelFormatType_420YpCbCr10BiPlanarVideoRange` ('x420'), the CoreVideo
+    /// counterpart of P010.
+    YCbCr420_10BiPlanarVideoRange = fourcc(b"x420"),
+    /// `kCVPixelFormatType_422YpCbCr10BiPlanarVideoRange` ('x422')
+    YCbCr422_10BiPlanarVideoRange = fourcc(b"x422"),
+    /// `kCVPixelFormatType_444YpCbCr10BiPlanarVideoRange` ('x444')
+    YCbCr444_10BiPlanarVideoRange = fourcc(b"x444"),
+    /// `kCVPixelFormatType_420YpCbCr10BiPlanarFullRange` ('xf20')
+    YCbCr420_10BiPlanarFullRange = fourcc(b"xf20"),
+    /// `kCVPixelFormatType_422YpCbCr10BiPlanarFullRange` ('xf22')
+    YCbCr422_10BiPlanarFullRange = fourcc(b"xf22"),
+    /// `kCVPixelFormatType_444YpCbCr10BiPlanarFullRange` ('xf44')
+    YCbCr444_10BiPlanarFullRange = fourcc(b"xf44"),
+    /// `kCVPixelFormatType_420YpCbCr8VideoRange_8A_TriPlanar` ('v0a8')
+    YCbCr420_8VideoRange8ATriPlanar = fourcc(b"v0a8"),
+    /// `kCVPixelFormatType_16VersatileBayer` ('bp16')
+    VersatileBayer16 = fourcc(b"bp16"),
+    /// `kCVPixelFormatType_64RGBA_DownscaledProResRAW` ('bp64')
+    RGBA64DownscaledProResRAW = fourcc(b"bp64"),
+    /// `kCVPixelFormatType_422YpCbCr16BiPlanarVideoRange` ('sv22')
+    YCbCr422_16BiPlanarVideoRange = fourcc(b"sv22"),
+    /// `kCVPixelFormatType_444YpCbCr16BiPlanarVideoRange` ('sv44')
+    YCbCr444_16BiPlanarVideoRange = fourcc(b"sv44"),
+    /// `kCVPixelFormatType_444YpCbCr16VideoRange_16A_TriPlanar` ('s4as')
+    YCbCr444_16VideoRange16ATriPlanar = fourcc(b"s4as"),
+    /// `kCVPixelFormatType_30RGBLE_8A_BiPlanar` ('b3a8')
+    RGB30LE8ABiPlanar = fourcc(b"b3a8"),
+    /// `kCVPixelFormatType_Lossless_32BGRA` ('&BGA')
+    LosslessBGRA32 = fourcc(b"&BGA"),
+    /// `kCVPixelFormatType_Lossless_64RGBAHalf` ('&RhA')
+    LosslessRGBAHalf64 = fourcc(b"&RhA"),
+    /// `kCVPixelFormatType_Lossless_420YpCbCr8BiPlanarVideoRange` ('&8v0')
+    LosslessYCbCr420_8BiPlanarVideoRange = fourcc(b"&8v0"),
+    /// `kCVPixelFormatType_Lossless_420YpCbCr8BiPlanarFullRange` ('&8f0')
+    LosslessYCbCr420_8BiPlanarFullRange = fourcc(b"&8f0"),
+    /// `kCVPixelFormatType_Lossless_420YpCbCr10PackedBiPlanarVideoRange` ('&xv0')
+    LosslessYCbCr420_10PackedBiPlanarVideoRange = fourcc(b"&xv0"),
+    /// `kCVPixelFormatType_Lossless_422YpCbCr10PackedBiPlanarVideoRange` ('&xv2')
+    LosslessYCbCr422_10PackedBiPlanarVideoRange = fourcc(b"&xv2"),
+    /// `kCVPixelFormatType_Lossless_420YpCbCr10PackedBiPlanarFullRange` ('&xf0')
+    LosslessYCbCr420_10PackedBiPlanarFullRange = fourcc(b"&xf0"),
+    /// `kCVPixelFormatType_Lossy_32BGRA` ('-BGA')
+    LossyBGRA32 = fourcc(b"-BGA"),
+    /// `kCVPixelFormatType_Lossy_420YpCbCr8BiPlanarVideoRange` ('-8v0')
+    LossyYCbCr420_8BiPlanarVideoRange = fourcc(b"-8v0"),
+    /// `kCVPixelFormatType_Lossy_420YpCbCr8BiPlanarFullRange` ('-8f0')
+    LossyYCbCr420_8BiPlanarFullRange = fourcc(b"-8f0"),
+    /// `kCVPixelFormatType_Lossy_420YpCbCr10PackedBiPlanarVideoRange` ('-xv0')
+    LossyYCbCr420_10PackedBiPlanarVideoRange = fourcc(b"-xv0"),
+    /// `kCVPixelFormatType_Lossy_422YpCbCr10PackedBiPlanarVideoRange` ('-xv2')
+    LossyYCbCr422_10PackedBiPlanarVideoRange = fourcc(b"-xv2"),
+}
+
+impl PixelFormat {
+    pub const ALL: &'static [PixelFormat] = &[
+        PixelFormat::Monochrome1,
+        PixelFormat::Indexed2,
+        PixelFormat::Indexed4,
+        PixelFormat::Indexed8,
+        PixelFormat::IndexedGrayWhiteIsZero1,
+        PixelFormat::IndexedGrayWhiteIsZero2,
+        PixelFormat::IndexedGrayWhiteIsZero4,
+        PixelFormat::IndexedGrayWhiteIsZero8,
+        PixelFormat::RGB16BE555,
+        PixelFormat::RGB16LE555,
+        PixelFormat::RGB16LE5551,
+        PixelFormat::RGB16BE565,
+        PixelFormat::RGB16LE565,
+        PixelFormat::RGB24,
+        PixelFormat::BGR24,
+        PixelFormat::ARGB32,
+        PixelFormat::BGRA32,
+        PixelFormat::ABGR32,
+        PixelFormat::RGBA32,
+        PixelFormat::ARGB64,
+        PixelFormat::RGBA64LE,
+        PixelFormat::RGB48,
+        PixelFormat::AlphaGray32,
+        PixelFormat::Gray16,
+        PixelFormat::RGB30,
+        PixelFormat::RGB30r210,
+        PixelFormat::YCbCr422_8,
+        PixelFormat::YCbCrA4444_8,
+        PixelFormat::YCbCrA4444_8R,
+        PixelFormat::AYCbCr4444_8,
+        PixelFormat::AYCbCr4444_16,
+        PixelFormat::AYCbCr4444Float,
+        PixelFormat::YCbCr444_8,
+        PixelFormat::YCbCr422_16,
+        PixelFormat::YCbCr422_10,
+        PixelFormat::YCbCr444_10,
+        PixelFormat::YCbCr420_8Planar,
+        PixelFormat::YCbCr420_8PlanarFullRange,
+        PixelFormat::YCbCr422_8BiPlanar4A,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PixelFormat::YCbCr420_8BiPlanarFullRange,
+        PixelFormat::YCbCr422_8BiPlanarVideoRange,
+        PixelFormat::YCbCr422_8BiPlanarFullRange,
+        PixelFormat::YCbCr444_8BiPlanarVideoRange,
+        PixelFormat::YCbCr444_8BiPlanarFullRange,
+        PixelFormat::YCbCr422_8Yuvs,
+        PixelFormat::YCbCr422_8FullRange,
+        PixelFormat::OneComponent8,
+        PixelFormat::TwoComponent8,
+        PixelFormat::RGB30LEPackedWideGamut,
+        PixelFormat::ARGB2101010LEPacked,
+        PixelFormat::ARGB40LEWideGamut,
+        PixelFormat::ARGB40LEWideGamutPremultiplied,
+        PixelFormat::OneComponent10,
+        PixelFormat::OneComponent12,
+        PixelFormat::OneComponent16,
+        PixelFormat::TwoComponent16,
+        PixelFormat::OneComponent16Half,
+        PixelFormat::OneComponent32Float,
+        PixelFormat::TwoComponent16Half,
+        PixelFormat::TwoComponent32Float,
+        PixelFormat::RGBAHalf64,
+        PixelFormat::RGBAFloat128,
+        PixelFormat::Bayer14GRBG,
+        PixelFormat::Bayer14RGGB,
+        PixelFormat::Bayer14BGGR,
+        PixelFormat::Bayer14GBRG,
+        PixelFormat::DisparityFloat16,
+        PixelFormat::DisparityFloat32,
+        PixelFormat::DepthFloat16,
+        PixelFormat::DepthFloat32,
+        PixelFormat::YCbCr420_10BiPlanarVideoRange,
+        PixelFormat::YCbCr422_10BiPlanarVideoRange,
+        PixelFormat::YCbCr444_10BiPlanarVideoRange,
+        PixelFormat::YCbCr420_10BiPlanarFullRange,
+        PixelFormat::YCbCr422_10BiPlanarFullRange,
+        PixelFormat::YCbCr444_10BiPlanarFullRange,
+        PixelFormat::YCbCr420_8VideoRange8ATriPlanar,
+        PixelFormat::VersatileBayer16,
+        PixelFormat::RGBA64DownscaledProResRAW,
+        PixelFormat::YCbCr422_16BiPlanarVideoRange,
+        PixelFormat::YCbCr444_16BiPlanarVideoRange,
+        PixelFormat::YCbCr444_16VideoRange16ATriPlanar,
+        PixelFormat::RGB30LE8ABiPlanar,
+        PixelFormat::LosslessBGRA32,
+        PixelFormat::LosslessRGBAHalf64,
+        PixelFormat::LosslessYCbCr420_8BiPlanarVideoRange,
+        PixelFormat::LosslessYCbCr420_8BiPlanarFullRange,
+        PixelFormat::LosslessYCbCr420_10PackedBiPlanarVideoRange,
+        PixelFormat::LosslessYCbCr422_10PackedBiPlanarVideoRange,
+        PixelFormat::LosslessYCbCr420_10PackedBiPlanarFullRange,
+        PixelFormat::LossyBGRA32,
+        PixelFormat::LossyYCbCr420_8BiPlanarVideoRange,
+        PixelFormat::LossyYCbCr420_8BiPlanarFullRange,
+        PixelFormat::LossyYCbCr420_10PackedBiPlanarVideoRange,
+        PixelFormat::LossyYCbCr422_10PackedBiPlanarVideoRange,
+    ];
+
+    pub const fn as_os_type(self) -> OSType {
+        self as OSType
+    }
+
+    pub fn from_os_type(value: OSType) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|format| format.as_os_type() == value)
+    }
+}
+
+impl From<PixelFormat> for OSType {
+    fn from(value: PixelFormat) -> Self {
+        value.as_os_type()
+    }
+}
+
+impl TryFrom<OSType> for PixelFormat {
+    type Error = CVPixelBufferError;
+
+    fn try_from(value: OSType) -> Result<Self, Self::Error> {
+        Self::from_os_type(value).ok_or(CVPixelBufferError::InvalidPixelFormat)
+    }
+}
+
+impl TryFrom<FourCharCode> for PixelFormat {
+    type Error = CVPixelBufferError;
+
+    fn try_from(value: FourCharCode) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_u32())
+    }
+}
+
+impl TryFrom<PixelFormat> for FourCharCode {
+    type Error = CVPixelBufferError;
+
+    fn try_from(value: PixelFormat) -> Result<Self, Self::Error> {
+        match FourCharCode::new(value.as_os_type()) {
+            Ok(code) if code.as_u32() == value.as_os_type() => Ok(code),
+            _ => Err(CVPixelBufferError::InvalidPixelFormat),
+        }
+    }
+}
+
+impl fmt::Display for PixelFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = self.as_os_type();
+        let bytes = code.to_be_bytes();
+        if bytes.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+            write!(f, "'{}'", String::from_utf8_lossy(&bytes))
+        } else {
+            write!(f, "{code:#010x}")
+        }
+    }
+}
@@ -0,0 +1,483 @@
+use crate::{
+    constants::ComponentRange,
+    dictionary::{Dictionary, DictionaryValue},
+    types::OSType,
+};
+
+use super::{error::CVPixelBufferError, pixel_format::PixelFormat};
+
+// Values of the `kCVPixelFormat*` description keys.
+const PIXEL_FORMAT_KEY: &str = "PixelFormat";
+const NAME_KEY: &str = "Name";
+const CODEC_TYPE_KEY: &str = "CodecType";
+const PLANES_KEY: &str = "Planes";
+const BITS_PER_BLOCK_KEY: &str = "BitsPerBlock";
+const BLOCK_WIDTH_KEY: &str = "BlockWidth";
+const BLOCK_HEIGHT_KEY: &str = "BlockHeight";
+const BLOCK_HORIZONTAL_ALIGNMENT_KEY: &str = "BlockHorizontalAlignment";
+const BLOCK_VERTICAL_ALIGNMENT_KEY: &str = "BlockVerticalAlignment";
+const HORIZONTAL_SUBSAMPLING_KEY: &str = "HorizontalSubsampling";
+const VERTICAL_SUBSAMPLING_KEY: &str = "VerticalSubsampling";
+const BLACK_BLOCK_KEY: &str = "BlackBlock";
+const COMPONENT_RANGE_KEY: &str = "ComponentRange";
+const CONTAINS_ALPHA_KEY: &str = "ContainsAlpha";
+const CONTAINS_YCBCR_KEY: &str = "ContainsYCbCr";
+const CONTAINS_RGB_KEY: &str = "ContainsRGB";
+const CONTAINS_GRAYSCALE_KEY: &str = "ContainsGrayscale";
+const CONTAINS_SENSEL_ARRAY_KEY: &str = "ContainsSenselArray";
+const FILL_EXTENDED_PIXELS_CALLBACK_KEY: &str = "FillExtendedPixelsCallback";
+const OPENGL_COMPATIBILITY_KEY: &str = "OpenGLCompatibility";
+const OPENGLES_COMPATIBILITY_KEY: &str = "OpenGLESCompatibility";
+const METAL_COMPATIBILITY_KEY: &str = "MetalCompatibility";
+const CG_BITMAP_CONTEXT_COMPATIBILITY_KEY: &str = "CGBitmapContextCompatibility";
+const CG_IMAGE_COMPATIBILITY_KEY: &str = "CGImageCompatibility";
+
+/// Layout of one plane of a [`PixelFormatDescription`].
+///
+/// A block is the smallest unit of `block_width` x `block_height` pixels that can be
+/// addressed, e.g. 6 x 1 pixels in 128 bits for v210.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaneDescription {
+    pub bits_per_block: u32,
+    pub block_width: u32,
+    pub block_height: u32,
+    /// Rows must start on a multiple of this many bytes, if set.
+    pub block_horizontal_alignment: Option<u32>,
+    /// Plane heights must be a multiple of this many rows, if set.
+    pub block_vertical_alignment: Option<u32>,
+    pub horizontal_subsampling: u32,
+    pub vertical_subsampling: u32,
+    /// Bytes of a block that is black, used to fill padding.
+    pub black_block: Option<Vec<u8>>,
+}
+
+impl PlaneDescription {
+    pub fn new(bits_per_block: u32) -> Self {
+        Self {
+            bits_per_block,
+            block_width: 1,
+            block_height: 1,
+            block_horizontal_alignment: None,
+            block_vertical_alignment: None,
+            horizontal_subsampling: 1,
+            vertical_subsampling: 1,
+            black_block: None,
+        }
+    }
+
+    fn from_dictionary(dictionary: &Dictionary) -> Result<Self, CVPixelBufferError> {
+        let count = |key: &str| -> Result<Option<u32>, CVPixelBufferError> {
+            dictionary
+                .get(key)
+                .map(|value| {
+                    value
+                        .as_u32()
+                        .filter(|&value| value > 0)
+                        .ok_or(CVPixelBufferError::InvalidArgument)
+                })
+                .transpose()
+        };
+        Ok(Self {
+            bits_per_block: count(BITS_PER_BLOCK_KEY)?
+                .ok_or(CVPixelBufferError::InvalidArgument)?,
+            block_width: count(BLOCK_WIDTH_KEY)?.unwrap_or(1),
+            block_height: count(BLOCK_HEIGHT_KEY)?.unwrap_or(1),
+            block_horizontal_alignment: count(BLOCK_HORIZONTAL_ALIGNMENT_KEY)?,
+            block_vertical_alignment: count(BLOCK_VERTICAL_ALIGNMENT_KEY)?,
+            horizontal_subsampling: count(HORIZONTAL_SUBSAMPLING_KEY)?.unwrap_or(1),
+            vertical_subsampling: count(VERTICAL_SUBSAMPLING_KEY)?.unwrap_or(1),
+            black_block: dictionary
+                .get(BLACK_BLOCK_KEY)
+                .and_then(DictionaryValue::as_data)
+                .map(<[u8]>::to_vec),
+        })
+    }
+
+    fn write_to(&self, dictionary: &mut Dictionary) {
+        dictionary.insert(BITS_PER_BLOCK_KEY.into(), self.bits_per_block.into());
+        let optional = [
+            (BLOCK_WIDTH_KEY, Some(self.block_width).filter(|&n| n != 1)),
+            (
+                BLOCK_HEIGHT_KEY,
+                Some(self.block_height).filter(|&n| n != 1),
+            ),
+            (
+                BLOCK_HORIZONTAL_ALIGNMENT_KEY,
+                self.block_horizontal_alignment,
+            ),
+            (BLOCK_VERTICAL_ALIGNMENT_KEY, self.block_vertical_alignment),
+            (
+                HORIZONTAL_SUBSAMPLING_KEY,
+                Some(self.horizontal_subsampling).filter(|&n| n != 1),
+            ),
+            (
+                VERTICAL_SUBSAMPLING_KEY,
+                Some(self.vertical_subsampling).filter(|&n| n != 1),
+            ),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                dictionary.insert(key.into(), value.into());
+            }
+        }
+        if let Some(black_block) = &self.black_block {
+            dictionary.insert(BLACK_BLOCK_KEY.into(), black_block.clone().into());
+        }
+    }
+}
+
+/// What CoreVideo knows about a pixel format: its planes, components and which APIs can
+/// use it.
+///
+/// Compatibility flags are `None` where the source does not say, which is always the case
+/// for descriptions built from the crate's own table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PixelFormatDescription {
+    /// The `OSType` of the format, which may be a custom code without a [`PixelFormat`].
+    pub pixel_format: OSType,
+    pub name: Option<String>,
+    pub codec_type: Option<OSType>,
+    pub planes: Vec<PlaneDescription>,
+    pub component_range: Option<ComponentRange>,
+    pub contains_alpha: bool,
+    pub contains_ycbcr: bool,
+    pub contains_rgb: bool,
+    pub contains_grayscale: bool,
+    pub contains_sensel_array: bool,
+    /// Whether the format has a callback to fill extended pixels. Descriptions registered
+    /// through this crate never have one.
+    pub has_fill_extended_pixels_callback: bool,
+    pub opengl_compatibility: Option<bool>,
+    pub opengles_compatibility: Option<bool>,
+    pub metal_compatibility: Option<bool>,
+    pub cg_bitmap_context_compatibility: Option<bool>,
+    pub cg_image_compatibility: Option<bool>,
+}
+
+impl PixelFormatDescription {
+    /// A description of `pixel_format` with `planes` and no other properties.
+    pub fn new(pixel_format: OSType, planes: Vec<PlaneDescription>) -> Self {
+        Self {
+            pixel_format,
+            name: None,
+            codec_type: None,
+            planes,
+            component_range: None,
+            contains_alpha: false,
+            contains_ycbcr: false,
+            contains_rgb: false,
+            contains_grayscale: false,
+            contains_sensel_array: false,
+            has_fill_extended_pixels_callback: false,
+            opengl_compatibility: None,
+            opengles_compatibility: None,
+            metal_compatibility: None,
+            cg_bitmap_context_compatibility: None,
+            cg_image_compatibility: None,
+        }
+    }
+
+    /// Builds a description from the crate's built-in layout table, or `None` for
+    /// compressed formats.
+    pub fn for_format(pixel_format: PixelFormat) -> Option<Self> {
+        let layout = pixel_format.layout()?;
+        let planes = layout
+            .planes
+            .iter()
+            .map(|plane| PlaneDescription {
+                bits_per_block: (plane.bytes_per_block * 8) as u32,
+                block_width: plane.block_width as u32,
+                block_horizontal_alignment: Some(layout.min_bytes_per_row_alignment as u32)
+                    .filter(|&n| n > 1),
+                // Every block in the table is one row high, so rows need no alignment.
+                block_vertical_alignment: None,
+                horizontal_subsampling: plane.horizontal_subsampling as u32,
+                vertical_subsampling: plane.vertical_subsampling as u32,
+                ..PlaneDescription::new(0)
+            })
+            .collect();
+        let components = Components::of(pixel_format);
+        Some(Self {
+            name: Some(format!("{pixel_format:?}")),
+            component_range: components.ycbcr.then(|| {
+                if is_full_range(pixel_format) {
+                    ComponentRange::FullRange
+                } else {
+                    ComponentRange::VideoRange
+                }
+            }),
+            contains_alpha: components.alpha,
+            contains_ycbcr: components.ycbcr,
+            contains_rgb: components.rgb,
+            contains_grayscale: components.grayscale,
+            contains_sensel_array: components.sensel_array,
+            ..Self::new(pixel_format.as_os_type(), planes)
+        })
+    }
+
+    /// Parses a `CVPixelFormatDescription` dictionary.
+    ///
+    /// Single-plane formats may describe their plane at the top level instead of in
+    /// `Planes`. Fails with `InvalidArgument` if the pixel format or a plane's bits per
+    /// block is missing, or a value has the wrong type.
+    pub fn from_dictionary(dictionary: &Dictionary) -> Result<Self, CVPixelBufferError> {
+        let pixel_format = dictionary
+            .get(PIXEL_FORMAT_KEY)
+            .and_then(DictionaryValue::as_u32)
+            .ok_or(CVPixelBufferError::InvalidArgument)?;
+        let planes = match dictionary.get(PLANES_KEY) {
+            Some(planes) => planes
+                .as_array()
+                .ok_or(CVPixelBufferError::InvalidArgument)?
+                .iter()
+                .map(|plane| {
+                    plane
+                        .as_dictionary()
+                        .ok_or(CVPixelBufferError::InvalidArgument)
+                        .and_then(PlaneDescription::from_dictionary)
+                })
+                .collect::<Result<_, _>>()?,
+            None if dictionary.contains_key(BITS_PER_BLOCK_KEY) => {
+                vec![PlaneDescription::from_dictionary(dictionary)?]
+            }
+            None => Vec::new(),
+        };
+        let flag = |key: &str| -> Result<Option<bool>, CVPixelBufferError> {
+            dictionary
+                .get(key)
+                .map(|value| value.as_bool().ok_or(CVPixelBufferError::InvalidArgument))
+                .transpose()
+        };
+        Ok(Self {
+            name: dictionary
+                .get(NAME_KEY)
+                .and_then(DictionaryValue::as_str)
+                .map(str::to_string),
+            codec_type: dictionary
+                .get(CODEC_TYPE_KEY)
+                .and_then(DictionaryValue::as_u32),
+            component_range: dictionary
+                .get(COMPONENT_RANGE_KEY)
+                .and_then(DictionaryValue::as_str)
+                .map(ComponentRange::from),
+            contains_alpha: flag(CONTAINS_ALPHA_KEY)?.unwrap_or(false),
+            contains_ycbcr: flag(CONTAINS_YCBCR_KEY)?.unwrap_or(false),
+            contains_rgb: flag(CONTAINS_RGB_KEY)?.unwrap_or(false),
+            contains_grayscale: flag(CONTAINS_GRAYSCALE_KEY)?.unwrap_or(false),
+            contains_sensel_array: flag(CONTAINS_SENSEL_ARRAY_KEY)?.unwrap_or(false),
+            has_fill_extended_pixels_callback: dictionary
+                .contains_key(FILL_EXTENDED_PIXELS_CALLBACK_KEY),
+            opengl_compatibility: flag(OPENGL_COMPATIBILITY_KEY)?,
+            opengles_compatibility: flag(OPENGLES_COMPATIBILITY_KEY)?,
+            metal_compatibility: flag(METAL_COMPATIBILITY_KEY)?,
+            cg_bitmap_context_compatibility: flag(CG_BITMAP_CONTEXT_COMPATIBILITY_KEY)?,
+            cg_image_compatibility: flag(CG_IMAGE_COMPATIBILITY_KEY)?,
+            ..Self::new(pixel_format, planes)
+        })
+    }
+
+    /// The description as a `CVPixelFormatDescription` dictionary. A single plane is
+    /// written at the top level, as CoreVideo does for packed formats.
+    pub fn to_dictionary(&self) -> Dictionary {
+        let mut dictionary = Dictionary::new();
+        dictionary.insert(PIXEL_FORMAT_KEY.into(), self.pixel_format.into());
+        if let Some(name) = &self.name {
+            dictionary.insert(NAME_KEY.into(), name.as_str().into());
+        }
+        if let Some(codec_type) = self.codec_type {
+            dictionary.insert(CODEC_TYPE_KEY.into(), codec_type.into());
+        }
+        match &self.planes[..] {
+            [] => {}
+            [plane] => plane.write_to(&mut dictionary),
+            planes => {
+                let planes = planes
+                    .iter()
+                    .map(|plane| {
+                        let mut plane_dictionary = Dictionary::new();
+                        plane.write_to(&mut plane_dictionary);
+                        plane_dictionary.into()
+                    })
+                    .collect::<Vec<DictionaryValue>>();
+                dictionary.insert(PLANES_KEY.into(), planes.into());
+            }
+        }
+        if let Some(component_range) = &self.component_range {
+            dictionary.insert(COMPONENT_RANGE_KEY.into(), component_range.as_str().into());
+        }
+        let flags = [
+            (CONTAINS_ALPHA_KEY, Some(self.contains_alpha)),
+            (CONTAINS_YCBCR_KEY, Some(self.contains_ycbcr)),
+            (CONTAINS_RGB_KEY, Some(self.contains_rgb)),
+            (CONTAINS_GRAYSCALE_KEY, Some(self.contains_grayscale)),
+            (CONTAINS_SENSEL_ARRAY_KEY, Some(self.contains_sensel_array)),
+            (OPENGL_COMPATIBILITY_KEY, self.opengl_compatibility),
+            (OPENGLES_COMPATIBILITY_KEY, self.opengles_compatibility),
+            (METAL_COMPATIBILITY_KEY, self.metal_compatibility),
+            (
+                CG_BITMAP_CONTEXT_COMPATIBILITY_KEY,
+                self.cg_bitmap_context_compatibility,
+            ),
+            (CG_IMAGE_COMPATIBILITY_KEY, self.cg_image_compatibility),
+        ];
+        for (key, value) in flags {
+            if let Some(value) = value {
+                dictionary.insert(key.into(), value.into());
+            }
+        }
+        dictionary
+    }
+
+    /// The described format, if the crate knows it.
+    pub fn get_pixel_format(&self) -> Option<PixelFormat> {
+        PixelFormat::from_os_type(self.pixel_format)
+    }
+    pub fn is_planar(&self) -> bool {
+        self.planes.len() > 1
+    }
+}
+
+#[derive(Default)]
+struct Components {
+    alpha: bool,
+    ycbcr: bool,
+    rgb: bool,
+    grayscale: bool,
+    sensel_array: bool,
+}
+
+impl Components {
+    fn of(pixel_format: PixelFormat) -> Self {
+        use PixelFormat::*;
+        let (ycbcr, rgb, grayscale, sensel_array) = match pixel_format {
+            YCbCr422_8
+            | YCbCrA4444_8
+            | YCbCrA4444_8R
+            | AYCbCr4444_8
+            | AYCbCr4444_16
+            | AYCbCr4444Float
+            | YCbCr444_8
+            | YCbCr422_16
+            | YCbCr422_10
+            | YCbCr444_10
+            | YCbCr420_8Planar
+            | YCbCr420_8PlanarFullRange
+            | YCbCr422_8BiPlanar4A
+            | YCbCr420_8BiPlanarVideoRange
+            | YCbCr420_8BiPlanarFullRange
+            | YCbCr422_8BiPlanarVideoRange
+            | YCbCr422_8BiPlanarFullRange
+            | YCbCr444_8BiPlanarVideoRange
+            | YCbCr444_8BiPlanarFullRange
+            | YCbCr422_8Yuvs
+            | YCbCr422_8FullRange
+            | YCbCr420_10BiPlanarVideoRange
+            | YCbCr422_10BiPlanarVideoRange
+            | YCbCr444_10BiPlanarVideoRange
+            | YCbCr420_10BiPlanarFullRange
+            | YCbCr422_10BiPlanarFullRange
+            | YCbCr444_10BiPlanarFullRange
+            | YCbCr420_8VideoRange8ATriPlanar
+            | YCbCr422_16BiPlanarVideoRange
+            | YCbCr444_16BiPlanarVideoRange
+            | YCbCr444_16VideoRange16ATriPlanar => (true, false, false, false),
+            RGB16BE555
+            | RGB16LE555
+            | RGB16LE5551
+            | RGB16BE565
+            | RGB16LE565
+            | RGB24
+            | BGR24
+            | ARGB32
+            | BGRA32
+            | ABGR32
+            | RGBA32
+            | ARGB64
+            | RGBA64LE
+            | RGB48
+            | RGB30
+            | RGB30r210
+            | RGB30LEPackedWideGamut
+            | ARGB2101010LEPacked
+            | ARGB40LEWideGamut
+            | ARGB40LEWideGamutPremultiplied
+            | RGBAHalf64
+            | RGBAFloat128
+            | RGBA64DownscaledProResRAW
+            | RGB30LE8ABiPlanar
+            | Indexed2
+            | Indexed4
+            | Indexed8 => (false, true, false, false),
+            Monochrome1
+            | IndexedGrayWhiteIsZero1
+            | IndexedGrayWhiteIsZero2
+            | IndexedGrayWhiteIsZero4
+            | IndexedGrayWhiteIsZero8
+            | Gray16
+            | AlphaGray32
+            | OneComponent8
+            | OneComponent10
+            | OneComponent12
+            | OneComponent16
+            | OneComponent16Half
+            | OneComponent32Float
+            | DisparityFloat16
+            | DisparityFloat32
+            | DepthFloat16
+            | DepthFloat32 => (false, false, true, false),
+            Bayer14GRBG | Bayer14RGGB | Bayer14BGGR | Bayer14GBRG | VersatileBayer16 => {
+                (false, false, false, true)
+            }
+            _ => (false, false, false, false),
+        };
+        let alpha = matches!(
+            pixel_format,
+            RGB16LE5551
+                | ARGB32
+                | BGRA32
+                | ABGR32
+                | RGBA32
+                | ARGB64
+                | RGBA64LE
+                | AlphaGray32
+                | YCbCrA4444_8
+                | YCbCrA4444_8R
+                | AYCbCr4444_8
+                | AYCbCr4444_16
+                | AYCbCr4444Float
+                | ARGB2101010LEPacked
+                | ARGB40LEWideGamut
+                | ARGB40LEWideGamutPremultiplied
+                | RGBAHalf64
+                | RGBAFloat128
+                | RGBA64DownscaledProResRAW
+                | YCbCr422_8BiPlanar4A
+                | YCbCr420_8VideoRange8ATriPlanar
+                | YCbCr444_16VideoRange16ATriPlanar
+                | RGB30LE8ABiPlanar
+        );
+        Self {
+            alpha,
+            ycbcr,
+            rgb,
+            grayscale,
+            sensel_array,
+        }
+    }
+}
+
+fn is_full_range(pixel_format: PixelFormat) -> bool {
+    matches!(
+        pixel_format,
+        PixelFormat::YCbCr420_8PlanarFullRange
+            | PixelFormat::YCbCr420_8BiPlanarFullRange
+            | PixelFormat::YCbCr422_8BiPlanarFullRange
+            | PixelFormat::YCbCr444_8BiPlanarFullRange
+            | PixelFormat::YCbCr422_8FullRange
+            | PixelFormat::YCbCr420_10BiPlanarFullRange
+            | PixelFormat::YCbCr422_10BiPlanarFullRange
+            | PixelFormat::YCbCr444_10BiPlanarFullRange
+            | PixelFormat::YCbCrA4444_8R
+            | PixelFormat::AYCbCr4444Float
+    )
+}
@@ -0,0 +1,367 @@
+use super::{
+    error::CVPixelBufferError, extended_pixels::ExtendedPixels, pixel_format::PixelFormat,
+};
+
+/// Row alignment CoreVideo applies to buffers it allocates itself.
+pub const DEFAULT_BYTES_PER_ROW_ALIGNMENT: usize = 64;
+
+/// Static description of a single plane of a pixel format.
+///
+/// A plane is made of blocks of `bytes_per_block` bytes, each covering `block_width`
+/// horizontally adjacent samples of the plane. Plane dimensions are the buffer dimensions
+/// divided by the subsampling factors, rounded up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaneFormat {
+    pub bytes_per_block: usize,
+    pub block_width: usize,
+    pub horizontal_subsampling: usize,
+    pub vertical_subsampling: usize,
+}
+
+impl PlaneFormat {
+    const fn new(
+        bytes_per_block: usize,
+        block_width: usize,
+        horizontal_subsampling: usize,
+        vertical_subsampling: usize,
+    ) -> Self {
+        Self {
+            bytes_per_block,
+            block_width,
+            horizontal_subsampling,
+            vertical_subsampling,
+        }
+    }
+    pub fn width(&self, width: usize) -> usize {
+        width.div_ceil(self.horizontal_subsampling)
+    }
+    pub fn height(&self, height: usize) -> usize {
+        height.div_ceil(self.vertical_subsampling)
+    }
+    pub fn min_bytes_per_row(&self, width: usize) -> usize {
+        self.width(width).div_ceil(self.block_width) * self.bytes_per_block
+    }
+}
+
+/// Memory layout rules of a [`PixelFormat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelFormatLayout {
+    pub planes: &'static [PlaneFormat],
+    pub bits_per_component: usize,
+    /// Row alignment the format itself requires, e.g. v210 rows are padded to 48 pixels.
+    pub min_bytes_per_row_alignment: usize,
+    /// Row alignment used when none is requested.
+    pub default_bytes_per_row_alignment: usize,
+}
+
+/// Computed geometry of one plane for a concrete buffer size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaneLayout {
+    pub width: usize,
+    pub height: usize,
+    pub bytes_per_row: usize,
+    pub offset: usize,
+    pub size: usize,
+}
+
+/// Computed geometry of all planes for a concrete buffer size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PixelBufferLayout {
+    pub width: usize,
+    pub height: usize,
+    pub planes: Vec<PlaneLayout>,
+    pub data_size: usize,
+}
+
+impl PixelBufferLayout {
+    pub fn is_planar(&self) -> bool {
+        self.planes.len() > 1
+    }
+}
+
+const PACKED_1_8: &[PlaneFormat] = &[PlaneFormat::new(1, 8, 1, 1)];
+const PACKED_1_4: &[PlaneFormat] = &[PlaneFormat::new(1, 4, 1, 1)];
+const PACKED_1_2: &[PlaneFormat] = &[PlaneFormat::new(1, 2, 1, 1)];
+const PACKED_1: &[PlaneFormat] = &[PlaneFormat::new(1, 1, 1, 1)];
+const PACKED_2: &[PlaneFormat] = &[PlaneFormat::new(2, 1, 1, 1)];
+const PACKED_3: &[PlaneFormat] = &[PlaneFormat::new(3, 1, 1, 1)];
+const PACKED_4: &[PlaneFormat] = &[PlaneFormat::new(4, 1, 1, 1)];
+const PACKED_4_2: &[PlaneFormat] = &[PlaneFormat::new(4, 2, 1, 1)];
+const PACKED_6: &[PlaneFormat] = &[PlaneFormat::new(6, 1, 1, 1)];
+const PACKED_8: &[PlaneFormat] = &[PlaneFormat::new(8, 1, 1, 1)];
+const PACKED_8_2: &[PlaneFormat] = &[PlaneFormat::new(8, 2, 1, 1)];
+const PACKED_16: &[PlaneFormat] = &[PlaneFormat::new(16, 1, 1, 1)];
+const PACKED_16_6: &[PlaneFormat] = &[PlaneFormat::new(16, 6, 1, 1)];
+const PLANAR_420_8: &[PlaneFormat] = &[
+    PlaneFormat::new(1, 1, 1, 1),
+    PlaneFormat::new(1, 1, 2, 2),
+    PlaneFormat::new(1, 1, 2, 2),
+];
+const BI_PLANAR_420_8: &[PlaneFormat] =
+    &[PlaneFormat::new(1, 1, 1, 1), PlaneFormat::new(2, 1, 2, 2)];
+const BI_PLANAR_422_8: &[PlaneFormat] =
+    &[PlaneFormat::new(1, 1, 1, 1), PlaneFormat::new(2, 1, 2, 1)];
+const BI_PLANAR_444_8: &[PlaneFormat] =
+    &[PlaneFormat::new(1, 1, 1, 1), PlaneFormat::new(2, 1, 1, 1)];
+const BI_PLANAR_420_16: &[PlaneFormat] =
+    &[PlaneFormat::new(2, 1, 1, 1), PlaneFormat::new(4, 1, 2, 2)];
+const BI_PLANAR_422_16: &[PlaneFormat] =
+    &[PlaneFormat::new(2, 1, 1, 1), PlaneFormat::new(4, 1, 2, 1)];
+const BI_PLANAR_444_16: &[PlaneFormat] =
+    &[PlaneFormat::new(2, 1, 1, 1), PlaneFormat::new(4, 1, 1, 1)];
+const BI_PLANAR_422_8_ALPHA: &[PlaneFormat] =
+    &[PlaneFormat::new(4, 2, 1, 1), PlaneFormat::new(1, 1, 1, 1)];
+const TRI_PLANAR_420_8_ALPHA: &[PlaneFormat] = &[
+    PlaneFormat::new(1, 1, 1, 1),
+    PlaneFormat::new(2, 1, 2, 2),
+    PlaneFormat::new(1, 1, 1, 1),
+];
+const TRI_PLANAR_444_16_ALPHA: &[PlaneFormat] = &[
+    PlaneFormat::new(2, 1, 1, 1),
+    PlaneFormat::new(4, 1, 1, 1),
+    PlaneFormat::new(2, 1, 1, 1),
+];
+const BI_PLANAR_RGB30_ALPHA: &[PlaneFormat] =
+    &[PlaneFormat::new(4, 1, 1, 1), PlaneFormat::new(1, 1, 1, 1)];
+
+impl PixelFormatLayout {
+    const fn new(planes: &'static [PlaneFormat], bits_per_component: usize) -> Self {
+        Self {
+            planes,
+            bits_per_component,
+            min_bytes_per_row_alignment: 1,
+            default_bytes_per_row_alignment: DEFAULT_BYTES_PER_ROW_ALIGNMENT,
+        }
+    }
+
+    /// Returns the layout of `format`, or `None` for compressed formats whose memory
+    /// layout is private to CoreVideo.
+    pub const fn for_format(format: PixelFormat) -> Option<Self> {
+        let layout = match format {
+            PixelFormat::Monochrome1 => Self::new(PACKED_1_8, 1),
+            PixelFormat::Indexed2 | PixelFormat::IndexedGrayWhiteIsZero2 => {
+                Self::new(PACKED_1_4, 2)
+            }
+            PixelFormat::Indexed4 | PixelFormat::IndexedGrayWhiteIsZero4 => {
+                Self::new(PACKED_1_2, 4)
+            }
+            PixelFormat::IndexedGrayWhiteIsZero1 => Self::new(PACKED_1_8, 1),
+            PixelFormat::Indexed8 | PixelFormat::IndexedGrayWhiteIsZero8 => Self::new(PACKED_1, 8),
+            PixelFormat::RGB16BE555
+            | PixelFormat::RGB16LE555
+            | PixelFormat::RGB16LE5551
+            | PixelFormat::RGB16BE565
+            | PixelFormat::RGB16LE565 => Self::new(PACKED_2, 5),
+            PixelFormat::RGB24 | PixelFormat::BGR24 | PixelFormat::YCbCr444_8 => {
+                Self::new(PACKED_3, 8)
+            }
+            PixelFormat::ARGB32
+            | PixelFormat::BGRA32
+            | PixelFormat::ABGR32
+            | PixelFormat::RGBA32
+            | PixelFormat::YCbCrA4444_8
+            | PixelFormat::YCbCrA4444_8R
+            | PixelFormat::AYCbCr4444_8 => Self::new(PACKED_4, 8),
+            PixelFormat::ARGB64
+            | PixelFormat::RGBA64LE
+            | PixelFormat::RGBA64DownscaledProResRAW => Self::new(PACKED_8, 16),
+            PixelFormat::RGB48 => Self::new(PACKED_6, 16),
+            PixelFormat::AlphaGray32 => Self::new(PACKED_4, 16),
+            PixelFormat::Gray16 | PixelFormat::OneComponent16 | PixelFormat::VersatileBayer16 => {
+                Self::new(PACKED_2, 16)
+            }
+            PixelFormat::RGB30
+            | PixelFormat::RGB30r210
+            | PixelFormat::RGB30LEPackedWideGamut
+            | PixelFormat::ARGB2101010LEPacked
+            | PixelFormat::YCbCr444_10 => Self::new(PACKED_4, 10),
+            PixelFormat::YCbCr422_8
+            | PixelFormat::YCbCr422_8Yuvs
+            | PixelFormat::YCbCr422_8FullRange => Self::new(PACKED_4_2, 8),
+            PixelFormat::AYCbCr4444_16 => Self::new(PACKED_8, 16),
+            PixelFormat::AYCbCr4444Float | PixelFormat::RGBAFloat128 => Self::new(PACKED_16, 32),
+            PixelFormat::YCbCr422_16 => Self::new(PACKED_8_2, 16),
+            PixelFormat::YCbCr422_10 => Self {
+                min_bytes_per_row_alignment: 128,
+                default_bytes_per_row_alignment: 128,
+                ..Self::new(PACKED_16_6, 10)
+            },
+            PixelFormat::YCbCr420_8Planar | PixelFormat::YCbCr420_8PlanarFullRange => {
+                Self::new(PLANAR_420_8, 8)
+            }
+            PixelFormat::YCbCr422_8BiPlanar4A => Self::new(BI_PLANAR_422_8_ALPHA, 8),
+            PixelFormat::YCbCr420_8BiPlanarVideoRange
+            | PixelFormat::YCbCr420_8BiPlanarFullRange => Self::new(BI_PLANAR_420_8, 8),
+            PixelFormat::YCbCr422_8BiPlanarVideoRange
+            | PixelFormat::YCbCr422_8BiPlanarFullRange => Self::new(BI_PLANAR_422_8, 8),
+            PixelFormat::YCbCr444_8BiPlanarVideoRange
+            | PixelFormat::YCbCr444_8BiPlanarFullRange => Self::new(BI_PLANAR_444_8, 8),
+            PixelFormat::OneComponent8 => Self::new(PACKED_1, 8),
+            PixelFormat::TwoComponent8 => Self::new(PACKED_2, 8),
+            PixelFormat::ARGB40LEWideGamut | PixelFormat::ARGB40LEWideGamutPremultiplied => {
+                Self::new(PACKED_8, 10)
+            }
+            PixelFormat::OneComponent10 => Self::new(PACKED_2, 10),
+            PixelFormat::OneComponent12 => Self::new(PACKED_2, 12),
+            PixelFormat::TwoComponent16 | PixelFormat::TwoComponent16Half => {
+                Self::new(PACKED_4, 16)
+            }
+            PixelFormat::OneComponent16Half
+            | PixelFormat::DisparityFloat16
+            | PixelFormat::DepthFloat16 => Self::new(PACKED_2, 16),
+            PixelFormat::OneComponent32Float
+            | PixelFormat::DisparityFloat32
+            | PixelFormat::DepthFloat32 => Self::new(PACKED_4, 32),
+            PixelFormat::TwoComponent32Float => Self::new(PACKED_8, 32),
+            PixelFormat::RGBAHalf64 => Self::new(PACKED_8, 16),
+            PixelFormat::Bayer14GRBG
+            | PixelFormat::Bayer14RGGB
+            | PixelFormat::Bayer14BGGR
+            | PixelFormat::Bayer14GBRG => Self::new(PACKED_2, 14),
+            PixelFormat::YCbCr420_10BiPlanarVideoRange
+            | PixelFormat::YCbCr420_10BiPlanarFullRange => Self::new(BI_PLANAR_420_16, 10),
+            PixelFormat::YCbCr422_10BiPlanarVideoRange
+            | PixelFormat::YCbCr422_10BiPlanarFullRange => Self::new(BI_PLANAR_422_16, 10),
+            PixelFormat::YCbCr444_10BiPlanarVideoRange
+            | PixelFormat::YCbCr444_10BiPlanarFullRange => Self::new(BI_PLANAR_444_16, 10),
+            PixelFormat::YCbCr420_8VideoRange8ATriPlanar => Self::new(TRI_PLANAR_420_8_ALPHA, 8),
+            PixelFormat::YCbCr422_16BiPlanarVideoRange => Self::new(BI_PLANAR_422_16, 16),
+            PixelFormat::YCbCr444_16BiPlanarVideoRange => Self::new(BI_PLANAR_444_16, 16),
+            PixelFormat::YCbCr444_16VideoRange16ATriPlanar => {
+                Self::new(TRI_PLANAR_444_16_ALPHA, 16)
+            }
+            PixelFormat::RGB30LE8ABiPlanar => Self::new(BI_PLANAR_RGB30_ALPHA, 10),
+            PixelFormat::LosslessBGRA32
+            | PixelFormat::LosslessRGBAHalf64
+            | PixelFormat::LosslessYCbCr420_8BiPlanarVideoRange
+            | PixelFormat::LosslessYCbCr420_8BiPlanarFullRange
+            | PixelFormat::LosslessYCbCr420_10PackedBiPlanarVideoRange
+            | PixelFormat::LosslessYCbCr422_10PackedBiPlanarVideoRange
+            | PixelFormat::LosslessYCbCr420_10PackedBiPlanarFullRange
+            | PixelFormat::LossyBGRA32
+            | PixelFormat::LossyYCbCr420_8BiPlanarVideoRange
+            | PixelFormat::LossyYCbCr420_8BiPlanarFullRange
+            | PixelFormat::LossyYCbCr420_10PackedBiPlanarVideoRange
+            | PixelFormat::LossyYCbCr422_10PackedBiPlanarVideoRange => return None,
+        };
+        Some(layout)
+    }
+
+    pub fn plane_count(&self) -> usize {
+        self.planes.len()
+    }
+
+    /// Computes plane sizes, strides and offsets for a `width` x `height` buffer.
+    ///
+    /// Rows and planes are aligned to `bytes_per_row_alignment`, raised to the format's
+    /// own minimum alignment. The alignment must be a power of two.
+    pub fn compute(
+        &self,
+        width: usize,
+        height: usize,
+        bytes_per_row_alignment: usize,
+    ) -> Result<PixelBufferLayout, CVPixelBufferError> {
+        self.compute_extended(
+            width,
+            height,
+            bytes_per_row_alignment,
+            ExtendedPixels::default(),
+        )
+    }
+
+    /// Computes the layout of a buffer with `extended_pixels` of padding around the image,
+    /// after rounding them with [`ExtendedPixels::aligned`].
+    ///
+    /// Rows cover the padding, and plane offsets point at the first visible sample.
+    pub fn compute_extended(
+        &self,
+        width: usize,
+        height: usize,
+        bytes_per_row_alignment: usize,
+        extended_pixels: ExtendedPixels,
+    ) -> Result<PixelBufferLayout, CVPixelBufferError> {
+        if width == 0 || height == 0 {
+            return Err(CVPixelBufferError::InvalidSize);
+        }
+        if !bytes_per_row_alignment.is_power_of_two() {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let alignment = bytes_per_row_alignment.max(self.min_bytes_per_row_alignment);
+        let extended = extended_pixels.aligned(self);
+        let padded_width = width
+            .checked_add(extended.left)
+            .and_then(|width| width.checked_add(extended.right))
+            .ok_or(CVPixelBufferError::InvalidSize)?;
+        let padded_height = height
+            .checked_add(extended.top)
+            .and_then(|height| height.checked_add(extended.bottom))
+            .ok_or(CVPixelBufferError::InvalidSize)?;
+        let mut planes = Vec::with_capacity(self.planes.len());
+        let mut offset: usize = 0;
+        for plane in self.planes {
+            let bytes_per_row = plane
+                .min_bytes_per_row(padded_width)
+                .checked_next_multiple_of(alignment)
+                .ok_or(CVPixelBufferError::InvalidSize)?;
+            let plane_height = plane.height(height);
+            let size = bytes_per_row
+                .checked_mul(plane_height)
+                .ok_or(CVPixelBufferError::InvalidSize)?;
+            let padded_size = bytes_per_row
+                .checked_mul(plane.height(padded_height))
+                .ok_or(CVPixelBufferError::InvalidSize)?;
+            let padding = extended.of_plane(plane);
+            let padding_before = padding
+                .top
+                .checked_mul(bytes_per_row)
+                .and_then(|top| {
+                    (padding.left / plane.block_width)
+                        .checked_mul(plane.bytes_per_block)
+                        .and_then(|left| top.checked_add(left))
+                })
+                .ok_or(CVPixelBufferError::InvalidSize)?;
+            // With little bottom padding the last visible row can run past the padded
+            // plane, so the plane is sized for whichever ends later.
+            let plane_size = padding_before
+                .checked_add(size)
+                .ok_or(CVPixelBufferError::InvalidSize)?
+                .max(padded_size);
+            offset = offset
+                .checked_next_multiple_of(alignment)
+                .ok_or(CVPixelBufferError::InvalidSize)?;
+            planes.push(PlaneLayout {
+                width: plane.width(width),
+                height: plane_height,
+                bytes_per_row,
+                offset: offset
+                    .checked_add(padding_before)
+                    .ok_or(CVPixelBufferError::InvalidSize)?,
+                size,
+            });
+            offset = offset
+                .checked_add(plane_size)
+                .ok_or(CVPixelBufferError::InvalidSize)?;
+        }
+        Ok(PixelBufferLayout {
+            width,
+            height,
+            planes,
+            data_size: offset,
+        })
+    }
+
+    /// Computes the layout using the format's default row alignment.
+    pub fn compute_default(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> Result<PixelBufferLayout, CVPixelBufferError> {
+        self.compute(width, height, self.default_bytes_per_row_alignment)
+    }
+}
+
+impl PixelFormat {
+    pub const fn layout(self) -> Option<PixelFormatLayout> {
+        PixelFormatLayout::for_format(self)
+    }
+}
@@ -1,5 +1,7 @@
 use std::ptr;
 
+use super::{error::CVPixelBufferError, pixel_format::PixelFormat};
+
 #[derive(Debug)]
 pub struct PlanarDataPointer {
     pub data: Option<Vec<u8>>,
@@ -27,6 +29,76 @@ impl PlanarDataPointer {
             base_addresses,
         }
     }
+    /// Allocates zeroed planes sized for a `width` x `height` buffer of `pixel_format`,
+    /// using the format's default row alignment.
+    pub fn for_format(
+        pixel_format: PixelFormat,
+        width: usize,
+        height: usize,
+    ) -> Result<PlanarDataPointer, CVPixelBufferError> {
+        let layout = pixel_format
+            .layout()
+            .ok_or(CVPixelBufferError::InvalidPixelFormat)?
+            .compute_default(width, height)?;
+        Ok(PlanarDataPointer::new(
+            None,
+            layout.planes.iter().map(|p| p.bytes_per_row).collect(),
+            layout.planes.iter().map(|p| p.width).collect(),
+            layout.planes.iter().map(|p| p.height).collect(),
+            layout.planes.iter().map(|p| vec![0; p.size]).collect(),
+        ))
+    }
+    /// Checks that every plane has a width, height and stride, and that each plane
+    /// buffer is large enough to hold `bytes_per_row * height` bytes.
+    pub fn validate(&self) -> Result<(), CVPixelBufferError> {
+        if self.number_of_planes == 0
+            || self.plane_bytes_per_row.len() != self.number_of_planes
+            || self.plane_width.len() != self.number_of_planes
+            || self.plane_height.len() != self.number_of_planes
+        {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        if self.base_addresses.len() != self.number_of_planes {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        for (i, plane) in self.base_addresses.iter().enumerate() {
+            let required = self.plane_bytes_per_row[i]
+                .checked_mul(self.plane_height[i])
+                .ok_or(CVPixelBufferError::InvalidSize)?;
+            if plane.len() < required {
+                return Err(CVPixelBufferError::InvalidSize);
+            }
+        }
+        Ok(())
+    }
+    /// Like [`validate`](Self::validate), and also checks the planes against a `width` x
+    /// `height` buffer of `pixel_format`: each plane must have the subsampled size of its
+    /// plane format and a stride that holds a full row of it.
+    pub fn validate_for(
+        &self,
+        pixel_format: PixelFormat,
+        width: usize,
+        height: usize,
+    ) -> Result<(), CVPixelBufferError> {
+        self.validate()?;
+        let layout = pixel_format
+            .layout()
+            .ok_or(CVPixelBufferError::InvalidPixelFormat)?;
+        if layout.planes.len() != self.number_of_planes {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        for (i, format) in layout.planes.iter().enumerate() {
+            if self.plane_width[i] != format.width(width)
+                || self.plane_height[i] != format.height(height)
+            {
+                return Err(CVPixelBufferError::InvalidSize);
+            }
+            if self.plane_bytes_per_row[i] < format.min_bytes_per_row(width) {
+                return Err(CVPixelBufferError::InvalidArgument);
+            }
+        }
+        Ok(())
+    }
     pub fn number_of_planes(&self) -> usize {
         self.number_of_planes
     }
@@ -42,8 +114,13 @@ impl PlanarDataPointer {
         self.data.as_ref().map(|v| v.len()).unwrap_or(0)
     }
 
-    pub fn raw_base_addresses(&self) -> *const *const u8 {
-        self.base_addresses.as_ptr().cast()
+    /// The address of each plane. Keep the returned vector alive while CoreVideo reads
+    /// the pointer to its first element.
+    pub fn raw_base_addresses(&self) -> Vec<*const u8> {
+        self.base_addresses
+            .iter()
+            .map(|plane| plane.as_ptr())
+            .collect()
     }
     pub fn plane_bytes_per_row(&self) -> *const usize {
         self.plane_bytes_per_row.as_ptr()
@@ -0,0 +1,144 @@
+use super::error::CVPixelBufferError;
+
+/// Read-only view of one plane of a locked pixel buffer.
+///
+/// `data` covers `bytes_per_row * height` bytes; each row holds `width` samples followed
+/// by padding up to `bytes_per_row`. Planes of a [`PixelBufferView`] may stop short of the
+/// padding on their last row.
+///
+/// [`PixelBufferView`]: super::view::PixelBufferView
+#[derive(Debug, Clone, Copy)]
+pub struct Plane<'a> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+    bytes_per_row: usize,
+}
+
+impl<'a> Plane<'a> {
+    pub fn new(
+        data: &'a [u8],
+        width: usize,
+        height: usize,
+        bytes_per_row: usize,
+    ) -> Result<Self, CVPixelBufferError> {
+        check_plane_size(data.len(), height, bytes_per_row)?;
+        Ok(Self {
+            data: &data[..bytes_per_row * height],
+            width,
+            height,
+            bytes_per_row,
+        })
+    }
+    /// Like [`Plane::new`], but `data` may end anywhere within the last row.
+    pub(crate) fn new_cropped(
+        data: &'a [u8],
+        width: usize,
+        height: usize,
+        bytes_per_row: usize,
+    ) -> Result<Self, CVPixelBufferError> {
+        check_plane_size(
+            data.len().saturating_add(bytes_per_row),
+            height,
+            bytes_per_row,
+        )?;
+        Ok(Self {
+            data: &data[..data.len().min(bytes_per_row * height)],
+            width,
+            height,
+            bytes_per_row,
+        })
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn bytes_per_row(&self) -> usize {
+        self.bytes_per_row
+    }
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.data
+    }
+    /// Returns row `y` including its trailing padding.
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        let end = ((y + 1) * self.bytes_per_row).min(self.data.len());
+        &self.data[y * self.bytes_per_row..end]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.data.chunks(self.bytes_per_row.max(1))
+    }
+}
+
+/// Mutable view of one plane of a locked pixel buffer.
+#[derive(Debug)]
+pub struct PlaneMut<'a> {
+    data: &'a mut [u8],
+    width: usize,
+    height: usize,
+    bytes_per_row: usize,
+}
+
+impl<'a> PlaneMut<'a> {
+    pub fn new(
+        data: &'a mut [u8],
+        width: usize,
+        height: usize,
+        bytes_per_row: usize,
+    ) -> Result<Self, CVPixelBufferError> {
+        check_plane_size(data.len(), height, bytes_per_row)?;
+        Ok(Self {
+            data: &mut data[..bytes_per_row * height],
+            width,
+            height,
+            bytes_per_row,
+        })
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn bytes_per_row(&self) -> usize {
+        self.bytes_per_row
+    }
+    pub fn as_plane(&self) -> Plane<'_> {
+        Plane {
+            data: self.data,
+            width: self.width,
+            height: self.height,
+            bytes_per_row: self.bytes_per_row,
+        }
+    }
+    pub fn as_slice(&self) -> &[u8] {
+        self.data
+    }
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.data
+    }
+    pub fn into_mut_slice(self) -> &'a mut [u8] {
+        self.data
+    }
+    pub fn row(&self, y: usize) -> &[u8] {
+        &self.data[y * self.bytes_per_row..(y + 1) * self.bytes_per_row]
+    }
+    pub fn row_mut(&mut self, y: usize) -> &mut [u8] {
+        &mut self.data[y * self.bytes_per_row..(y + 1) * self.bytes_per_row]
+    }
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [u8]> {
+        self.data.chunks_exact_mut(self.bytes_per_row.max(1))
+    }
+}
+
+fn check_plane_size(
+    len: usize,
+    height: usize,
+    bytes_per_row: usize,
+) -> Result<(), CVPixelBufferError> {
+    match bytes_per_row.checked_mul(height) {
+        Some(size) if size <= len => Ok(()),
+        _ => Err(CVPixelBufferError::InvalidSize),
+    }
+}
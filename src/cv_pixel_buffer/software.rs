@@ -0,0 +1,538 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt, mem, ptr,
+};
+
+use crate::attachments::{
+    CVAttachmentMode, ImageBufferAttachment, ImageBufferAttachmentKey, ImageBufferAttachments,
+};
+
+use super::{
+    attributes::{PixelBufferAttribute, PixelBufferAttributes},
+    error::CVPixelBufferError,
+    extended_pixels::{self, padded_planes, ExtendedPixels},
+    lock::{
+        BaseAddressGuard, BaseAddressLock, CVPixelBufferLockFlags, MutBaseAddressGuard, RawPlane,
+    },
+    pixel_format::PixelFormat,
+    pixel_format_layout::{PixelBufferLayout, PixelFormatLayout, PlaneLayout},
+    planar_data::PlanarDataPointer,
+};
+
+pub(crate) type BytesReleaseCallback<'a> = Box<dyn FnOnce(Vec<u8>) + Send + 'a>;
+type PlanarReleaseCallback<'a> = Box<dyn FnOnce(PlanarDataPointer) + Send + 'a>;
+
+enum Storage<'a> {
+    Bytes(Vec<u8>, Option<BytesReleaseCallback<'a>>),
+    Planar(PlanarDataPointer, Option<PlanarReleaseCallback<'a>>),
+}
+
+/// Heap-backed pixel buffer that follows CoreVideo's layout and locking rules.
+///
+/// It is available on every platform, so code written against [`PixelBufferLike`] can be
+/// exercised without CoreVideo. Strides and plane offsets match what `CVPixelBufferCreate`
+/// produces for the same format, size and `BytesPerRowAlignment` attribute.
+///
+/// [`PixelBufferLike`]: super::PixelBufferLike
+pub struct SoftwarePixelBuffer<'a> {
+    width: usize,
+    height: usize,
+    pixel_format: PixelFormat,
+    planes: Vec<PlaneLayout>,
+    planar: bool,
+    extended_pixels: ExtendedPixels,
+    storage: Storage<'a>,
+    read_only_locks: Cell<usize>,
+    read_write_locks: Cell<usize>,
+    attachments: RefCell<Vec<(ImageBufferAttachment, CVAttachmentMode)>>,
+}
+
+impl<'a> SoftwarePixelBuffer<'a> {
+    pub fn create(
+        width: usize,
+        height: usize,
+        pixel_format: PixelFormat,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError> {
+        Self::internal_create(
+            width,
+            height,
+            pixel_format,
+            &pixel_buffer_attributes,
+            None,
+            None,
+        )
+    }
+    /// Resolves several attribute sets into one with [`PixelBufferAttributes::resolve`].
+    pub fn create_resolved_attributes(
+        attributes: &[PixelBufferAttributes],
+    ) -> Result<PixelBufferAttributes, CVPixelBufferError> {
+        PixelBufferAttributes::resolve(attributes)
+    }
+
+    pub fn create_with_bytes(
+        width: usize,
+        height: usize,
+        pixel_format: PixelFormat,
+        base_address: Vec<u8>,
+        bytes_per_row: usize,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError> {
+        Self::internal_create_with_bytes(
+            width,
+            height,
+            pixel_format,
+            base_address,
+            bytes_per_row,
+            None,
+            pixel_buffer_attributes,
+        )
+    }
+
+    pub fn create_with_bytes_release_cb<TReleaseCallback>(
+        width: usize,
+        height: usize,
+        pixel_format: PixelFormat,
+        base_address: Vec<u8>,
+        bytes_per_row: usize,
+        release_callback: TReleaseCallback,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError>
+    where
+        TReleaseCallback: 'a + Send + FnOnce(Vec<u8>),
+    {
+        Self::internal_create_with_bytes(
+            width,
+            height,
+            pixel_format,
+            base_address,
+            bytes_per_row,
+            Some(Box::new(release_callback)),
+            pixel_buffer_attributes,
+        )
+    }
+
+    pub fn create_with_planar_bytes(
+        width: usize,
+        height: usize,
+        pixel_format: PixelFormat,
+        data_pointer: PlanarDataPointer,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError> {
+        Self::internal_create_with_planar_bytes(
+            width,
+            height,
+            pixel_format,
+            data_pointer,
+            None,
+            pixel_buffer_attributes,
+        )
+    }
+
+    pub fn create_with_planar_bytes_release_cb<TReleaseCallback>(
+        width: usize,
+        height: usize,
+        pixel_format: PixelFormat,
+        data_pointer: PlanarDataPointer,
+        release_callback: TReleaseCallback,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError>
+    where
+        TReleaseCallback: 'a + Send + FnOnce(PlanarDataPointer),
+    {
+        Self::internal_create_with_planar_bytes(
+            width,
+            height,
+            pixel_format,
+            data_pointer,
+            Some(Box::new(release_callback)),
+            pixel_buffer_attributes,
+        )
+    }
+
+    pub fn is_planar(&self) -> bool {
+        self.planar
+    }
+    /// For planar buffers this is the stride of plane 0.
+    pub fn get_bytes_per_row(&self) -> usize {
+        self.planes[0].bytes_per_row
+    }
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+    pub fn get_pixel_format(&self) -> Result<PixelFormat, CVPixelBufferError> {
+        Ok(self.pixel_format)
+    }
+    /// Returns 0 for non-planar buffers, as CoreVideo does.
+    pub fn get_plane_count(&self) -> usize {
+        if self.planar {
+            self.planes.len()
+        } else {
+            0
+        }
+    }
+    pub fn get_width_of_plane(&self, plane_index: usize) -> usize {
+        self.planar_plane(plane_index).map_or(0, |p| p.width)
+    }
+    pub fn get_height_of_plane(&self, plane_index: usize) -> usize {
+        self.planar_plane(plane_index).map_or(0, |p| p.height)
+    }
+    pub fn get_bytes_per_row_of_plane(&self, plane_index: usize) -> usize {
+        self.planar_plane(plane_index)
+            .map_or(0, |p| p.bytes_per_row)
+    }
+    pub fn lock(&self) -> Result<BaseAddressGuard<'_>, CVPixelBufferError> {
+        BaseAddressGuard::new(self)
+    }
+    pub fn set_attachment(&self, attachment: &ImageBufferAttachment, mode: CVAttachmentMode) {
+        let mut attachments = self.attachments.borrow_mut();
+        let key = attachment.key();
+        attachments.retain(|(a, _)| a.key() != key);
+        attachments.push((attachment.clone(), mode));
+    }
+    pub fn get_attachment(
+        &self,
+        key: ImageBufferAttachmentKey,
+    ) -> Option<(ImageBufferAttachment, CVAttachmentMode)> {
+        self.attachments
+            .borrow()
+            .iter()
+            .find(|(a, _)| a.key() == key)
+            .cloned()
+    }
+    pub fn remove_attachment(&self, key: ImageBufferAttachmentKey) {
+        self.attachments
+            .borrow_mut()
+            .retain(|(a, _)| a.key() != key);
+    }
+    pub fn remove_all_attachments(&self) {
+        self.attachments.borrow_mut().clear();
+    }
+    pub fn get_attachments(&self, mode: CVAttachmentMode) -> ImageBufferAttachments {
+        self.attachments
+            .borrow()
+            .iter()
+            .filter(|(_, m)| *m == mode)
+            .map(|(a, _)| a.clone())
+            .collect()
+    }
+    pub fn set_attachments(&self, attachments: ImageBufferAttachments, mode: CVAttachmentMode) {
+        for attachment in attachments.as_slice() {
+            self.set_attachment(attachment, mode);
+        }
+    }
+    /// Copies all `ShouldPropagate` attachments to `destination`.
+    pub fn propagate_attachments(&self, destination: &SoftwarePixelBuffer) {
+        let attachments = self.get_attachments(CVAttachmentMode::ShouldPropagate);
+        destination.set_attachments(attachments, CVAttachmentMode::ShouldPropagate);
+    }
+    pub fn lock_mut(&mut self) -> Result<MutBaseAddressGuard<'_>, CVPixelBufferError> {
+        MutBaseAddressGuard::new(self)
+    }
+    /// Padding around the image, as requested with the `ExtendedPixels*` attributes and
+    /// rounded up to whole chroma samples and blocks. Always empty for buffers created from
+    /// caller-provided bytes.
+    pub fn get_extended_pixels(&self) -> ExtendedPixels {
+        self.extended_pixels
+    }
+    /// Fills the extended pixels by replicating the edges of the image.
+    pub fn fill_extended_pixels(&mut self) -> Result<(), CVPixelBufferError> {
+        let (pixel_format, extended) = (self.pixel_format, self.extended_pixels);
+        extended_pixels::fill_extended_pixels(&mut self.lock_mut()?, pixel_format, extended)
+    }
+
+    fn new(
+        width: usize,
+        height: usize,
+        pixel_format: PixelFormat,
+        planes: Vec<PlaneLayout>,
+        planar: bool,
+        extended_pixels: ExtendedPixels,
+        storage: Storage<'a>,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            pixel_format,
+            planes,
+            planar,
+            extended_pixels,
+            storage,
+            read_only_locks: Cell::new(0),
+            read_write_locks: Cell::new(0),
+            attachments: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Creates a buffer backed by `data` when it has exactly the size the layout needs, so
+    /// pools can hand out recycled allocations.
+    pub(crate) fn internal_create(
+        width: usize,
+        height: usize,
+        pixel_format: PixelFormat,
+        pixel_buffer_attributes: &PixelBufferAttributes,
+        data: Option<Vec<u8>>,
+        release_callback: Option<BytesReleaseCallback<'a>>,
+    ) -> Result<Self, CVPixelBufferError> {
+        let buffer_layout = buffer_layout(width, height, pixel_format, pixel_buffer_attributes)?;
+        let planar = buffer_layout.is_planar();
+        let extended_pixels = ExtendedPixels::from_attributes(pixel_buffer_attributes)
+            .aligned(&format_layout(pixel_format)?);
+        let data = match data {
+            Some(data) if data.len() == buffer_layout.data_size => data,
+            _ => vec![0; buffer_layout.data_size],
+        };
+        Ok(Self::new(
+            width,
+            height,
+            pixel_format,
+            buffer_layout.planes,
+            planar,
+            extended_pixels,
+            Storage::Bytes(data, release_callback),
+        ))
+    }
+
+    fn internal_create_with_bytes(
+        width: usize,
+        height: usize,
+        pixel_format: PixelFormat,
+        base_address: Vec<u8>,
+        bytes_per_row: usize,
+        release_callback: Option<BytesReleaseCallback<'a>>,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError> {
+        let layout = format_layout(pixel_format)?;
+        if layout.planes.len() != 1 {
+            return Err(CVPixelBufferError::InvalidPixelFormat);
+        }
+        bytes_per_row_alignment(&layout, &pixel_buffer_attributes)?;
+        if width == 0 || height == 0 {
+            return Err(CVPixelBufferError::InvalidSize);
+        }
+        if bytes_per_row < layout.planes[0].min_bytes_per_row(width) {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let size = bytes_per_row
+            .checked_mul(height)
+            .ok_or(CVPixelBufferError::InvalidSize)?;
+        if base_address.len() < size {
+            return Err(CVPixelBufferError::InvalidSize);
+        }
+        let plane = PlaneLayout {
+            width,
+            height,
+            bytes_per_row,
+            offset: 0,
+            size,
+        };
+        Ok(Self::new(
+            width,
+            height,
+            pixel_format,
+            vec![plane],
+            false,
+            ExtendedPixels::default(),
+            Storage::Bytes(base_address, release_callback),
+        ))
+    }
+
+    fn internal_create_with_planar_bytes(
+        width: usize,
+        height: usize,
+        pixel_format: PixelFormat,
+        data_pointer: PlanarDataPointer,
+        release_callback: Option<PlanarReleaseCallback<'a>>,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError> {
+        let layout = format_layout(pixel_format)?;
+        bytes_per_row_alignment(&layout, &pixel_buffer_attributes)?;
+        if width == 0 || height == 0 {
+            return Err(CVPixelBufferError::InvalidSize);
+        }
+        data_pointer.validate_for(pixel_format, width, height)?;
+        let planes = (0..data_pointer.number_of_planes())
+            .map(|i| {
+                let height = data_pointer.plane_height[i];
+                let bytes_per_row = data_pointer.plane_bytes_per_row[i];
+                PlaneLayout {
+                    width: data_pointer.plane_width[i],
+                    height,
+                    bytes_per_row,
+                    offset: 0,
+                    size: bytes_per_row * height,
+                }
+            })
+            .collect();
+        // CoreVideo reports buffers created from planar bytes as planar even when they only
+        // carry a single plane.
+        Ok(Self::new(
+            width,
+            height,
+            pixel_format,
+            planes,
+            true,
+            ExtendedPixels::default(),
+            Storage::Planar(data_pointer, release_callback),
+        ))
+    }
+
+    fn planar_plane(&self, plane_index: usize) -> Option<&PlaneLayout> {
+        if self.planar {
+            self.planes.get(plane_index)
+        } else {
+            None
+        }
+    }
+
+    fn raw_planes(&self, base: impl Fn(usize) -> *mut u8) -> Vec<RawPlane> {
+        self.planes
+            .iter()
+            .enumerate()
+            .map(|(i, plane)| {
+                RawPlane::new(base(i), plane.width, plane.height, plane.bytes_per_row)
+            })
+            .collect()
+    }
+
+    fn lock_count(&self, flags: CVPixelBufferLockFlags) -> &Cell<usize> {
+        match flags {
+            CVPixelBufferLockFlags::ReadOnly => &self.read_only_locks,
+            CVPixelBufferLockFlags::ReadWrite => &self.read_write_locks,
+        }
+    }
+}
+
+impl BaseAddressLock for SoftwarePixelBuffer<'_> {
+    fn lock_base_address(
+        &self,
+        lock_flags: CVPixelBufferLockFlags,
+    ) -> Result<(), CVPixelBufferError> {
+        let count = self.lock_count(lock_flags);
+        count.set(count.get() + 1);
+        Ok(())
+    }
+    fn unlock_base_address(
+        &self,
+        unlock_flags: CVPixelBufferLockFlags,
+    ) -> Result<(), CVPixelBufferError> {
+        let count = self.lock_count(unlock_flags);
+        match count.get() {
+            0 => Err(CVPixelBufferError::Unlock),
+            n => {
+                count.set(n - 1);
+                Ok(())
+            }
+        }
+    }
+    fn base_address_planes(&self) -> Result<Vec<RawPlane>, CVPixelBufferError> {
+        Ok(match &self.storage {
+            Storage::Bytes(data, _) => {
+                self.raw_planes(|i| data[self.planes[i].offset..].as_ptr().cast_mut())
+            }
+            Storage::Planar(data, _) => {
+                self.raw_planes(|i| data.base_addresses[i].as_ptr().cast_mut())
+            }
+        })
+    }
+    fn base_address_planes_mut(&mut self) -> Result<Vec<RawPlane>, CVPixelBufferError> {
+        let bases: Vec<*mut u8> = match &mut self.storage {
+            Storage::Bytes(data, _) => self
+                .planes
+                .iter()
+                .map(|plane| data[plane.offset..].as_mut_ptr())
+                .collect(),
+            Storage::Planar(data, _) => data
+                .base_addresses
+                .iter_mut()
+                .map(|plane| plane.as_mut_ptr())
+                .collect(),
+        };
+        Ok(self.raw_planes(|i| bases[i]))
+    }
+    fn base_address_padded_planes(
+        &self,
+        planes: &[RawPlane],
+    ) -> Result<Vec<RawPlane>, CVPixelBufferError> {
+        let allocation = match &self.storage {
+            Storage::Bytes(data, _) => data.as_ptr_range(),
+            // Buffers over caller planes have no room for extended pixels.
+            Storage::Planar(..) => ptr::null()..ptr::null(),
+        };
+        padded_planes(
+            planes,
+            Some(allocation),
+            self.pixel_format,
+            self.extended_pixels,
+        )
+    }
+}
+
+impl fmt::Debug for SoftwarePixelBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoftwarePixelBuffer")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("pixel_format", &self.pixel_format)
+            .field("planes", &self.planes)
+            .field("planar", &self.planar)
+            .field("extended_pixels", &self.extended_pixels)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for SoftwarePixelBuffer<'_> {
+    fn drop(&mut self) {
+        match mem::replace(&mut self.storage, Storage::Bytes(Vec::new(), None)) {
+            Storage::Bytes(data, Some(release_callback)) => release_callback(data),
+            Storage::Planar(data, Some(release_callback)) => release_callback(data),
+            _ => {}
+        }
+    }
+}
+
+/// Layout `SoftwarePixelBuffer::create` would use for these arguments.
+pub(crate) fn buffer_layout(
+    width: usize,
+    height: usize,
+    pixel_format: PixelFormat,
+    pixel_buffer_attributes: &PixelBufferAttributes,
+) -> Result<PixelBufferLayout, CVPixelBufferError> {
+    let layout = format_layout(pixel_format)?;
+    let alignment = bytes_per_row_alignment(&layout, pixel_buffer_attributes)?;
+    layout.compute_extended(
+        width,
+        height,
+        alignment,
+        ExtendedPixels::from_attributes(pixel_buffer_attributes),
+    )
+}
+
+fn format_layout(pixel_format: PixelFormat) -> Result<PixelFormatLayout, CVPixelBufferError> {
+    pixel_format
+        .layout()
+        .ok_or(CVPixelBufferError::InvalidPixelFormat)
+}
+
+fn bytes_per_row_alignment(
+    layout: &PixelFormatLayout,
+    attributes: &PixelBufferAttributes,
+) -> Result<usize, CVPixelBufferError> {
+    let alignment = attributes
+        .as_slice()
+        .iter()
+        .rev()
+        .find_map(|attribute| match attribute {
+            PixelBufferAttribute::BytesPerRowAlignment(alignment) => Some(*alignment),
+            _ => None,
+        })
+        .unwrap_or(layout.default_bytes_per_row_alignment);
+    if alignment.is_power_of_two() {
+        Ok(alignment)
+    } else {
+        Err(CVPixelBufferError::InvalidPixelBufferAttributes)
+    }
+}
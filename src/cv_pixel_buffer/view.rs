@@ -0,0 +1,312 @@
+use crate::attachments::{
+    CVAttachmentMode, CleanAperture, DisplayDimensions, ImageBufferAttachment,
+    ImageBufferAttachmentKey, ImageBufferAttachments, Rect,
+};
+
+use super::{
+    error::CVPixelBufferError,
+    extended_pixels::ExtendedPixels,
+    lock::{BaseAddressGuard, MutBaseAddressGuard},
+    pixel_format::PixelFormat,
+    pixel_format_layout::{PixelFormatLayout, PlaneFormat},
+    PixelBufferLike,
+};
+
+/// Rectangle in whole pixels, with the origin at the top-left corner of the image.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PixelRect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl PixelRect {
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+    /// Smallest rect containing `self` whose edges fall on multiples of `horizontal` and
+    /// `vertical`, except where they would extend past `width` x `height`.
+    fn align_outward(
+        &self,
+        horizontal: usize,
+        vertical: usize,
+        width: usize,
+        height: usize,
+    ) -> Self {
+        let x = self.x / horizontal * horizontal;
+        let y = self.y / vertical * vertical;
+        let right = (self.x + self.width)
+            .next_multiple_of(horizontal)
+            .min(width);
+        let bottom = (self.y + self.height)
+            .next_multiple_of(vertical)
+            .min(height);
+        Self::new(x, y, right - x, bottom - y)
+    }
+}
+
+/// Read-only, zero-copy view of a rectangle of a pixel buffer.
+///
+/// The rectangle is widened so that it starts and ends on whole chroma samples and whole
+/// packed blocks, e.g. even coordinates for 420v and multiples of six columns for v210.
+/// Locking the view locks the underlying buffer and hands out planes that point into it.
+///
+/// Views implement [`PixelBufferLike`], so they can be passed as the source of
+/// [`convert`](crate::convert::convert) and [`scale`](crate::scale::scale). Attachments are
+/// those of the underlying buffer, with clean apertures clipped to the view and given
+/// relative to it, and display dimensions scaled with the clean rect; an aperture outside
+/// the view is left out. Views have no extended pixels, and
+/// [`PixelBufferLike::lock_mut`] and [`PixelBufferLike::fill_extended_pixels`] fail with
+/// [`CVPixelBufferError::Unsupported`].
+#[derive(Debug)]
+pub struct PixelBufferView<'a, B: PixelBufferLike + ?Sized> {
+    buffer: &'a B,
+    layout: PixelFormatLayout,
+    rect: PixelRect,
+}
+
+impl<'a, B: PixelBufferLike + ?Sized> PixelBufferView<'a, B> {
+    /// Creates a view of `rect`, widened to the format's chroma and block alignment.
+    ///
+    /// Fails with `InvalidArgument` if `rect` is empty or extends past the buffer, and with
+    /// `InvalidPixelFormat` for compressed formats.
+    pub fn new(buffer: &'a B, rect: PixelRect) -> Result<Self, CVPixelBufferError> {
+        let layout = buffer
+            .get_pixel_format()?
+            .layout()
+            .ok_or(CVPixelBufferError::InvalidPixelFormat)?;
+        let (width, height) = (buffer.get_width(), buffer.get_height());
+        if rect.width == 0
+            || rect.height == 0
+            || rect
+                .x
+                .checked_add(rect.width)
+                .is_none_or(|right| right > width)
+            || rect
+                .y
+                .checked_add(rect.height)
+                .is_none_or(|bottom| bottom > height)
+        {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let (horizontal, vertical) = alignment(&layout);
+        Ok(Self {
+            buffer,
+            layout,
+            rect: rect.align_outward(horizontal, vertical, width, height),
+        })
+    }
+
+    /// Creates a view of the buffer's `CleanAperture` attachment, or of the whole buffer if
+    /// it has none.
+    pub fn clean_aperture(buffer: &'a B) -> Result<Self, CVPixelBufferError> {
+        let (width, height) = (buffer.get_width(), buffer.get_height());
+        let rect = match buffer.get_attachment(ImageBufferAttachmentKey::CleanAperture) {
+            Some((ImageBufferAttachment::CleanAperture(aperture), _)) => {
+                aperture.crop_rect(width, height)
+            }
+            _ => PixelRect::new(0, 0, width, height),
+        };
+        Self::new(buffer, rect)
+    }
+
+    /// Creates a view of `rect`, given relative to this view, over the same buffer.
+    pub fn view(&self, rect: PixelRect) -> Result<Self, CVPixelBufferError> {
+        if rect
+            .x
+            .checked_add(rect.width)
+            .is_none_or(|right| right > self.rect.width)
+            || rect
+                .y
+                .checked_add(rect.height)
+                .is_none_or(|bottom| bottom > self.rect.height)
+        {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let rect = PixelRect {
+            x: self.rect.x + rect.x,
+            y: self.rect.y + rect.y,
+            ..rect
+        };
+        Self::new(self.buffer, rect)
+    }
+
+    /// The viewed rectangle in buffer coordinates, after alignment.
+    pub fn rect(&self) -> PixelRect {
+        self.rect
+    }
+    pub fn buffer(&self) -> &'a B {
+        self.buffer
+    }
+
+    /// `attachment` of the underlying buffer as it applies to the view, or `None` if it
+    /// describes a region outside it.
+    fn view_attachment(&self, attachment: ImageBufferAttachment) -> Option<ImageBufferAttachment> {
+        match attachment {
+            ImageBufferAttachment::CleanAperture(aperture) => self
+                .view_aperture(aperture)
+                .map(ImageBufferAttachment::CleanAperture),
+            ImageBufferAttachment::PreferredCleanAperture(aperture) => self
+                .view_aperture(aperture)
+                .map(ImageBufferAttachment::PreferredCleanAperture),
+            ImageBufferAttachment::DisplayDimensions(dimensions) => {
+                let (buffer, view) = (self.buffer.get_clean_rect(), self.get_clean_rect());
+                if buffer.width <= 0.0 || buffer.height <= 0.0 {
+                    return None;
+                }
+                Some(ImageBufferAttachment::DisplayDimensions(
+                    DisplayDimensions {
+                        width: (dimensions.width as f64 * view.width / buffer.width).round() as u32,
+                        height: (dimensions.height as f64 * view.height / buffer.height).round()
+                            as u32,
+                    },
+                ))
+            }
+            attachment => Some(attachment),
+        }
+    }
+    fn view_aperture(&self, aperture: CleanAperture) -> Option<CleanAperture> {
+        let rect = Rect::new(
+            self.rect.x as f64,
+            self.rect.y as f64,
+            self.rect.width as f64,
+            self.rect.height as f64,
+        );
+        let clean = aperture
+            .clean_rect(self.buffer.get_encoded_size())
+            .intersection(&rect)?;
+        Some(CleanAperture::from_clean_rect(
+            Rect {
+                x: clean.x - rect.x,
+                y: clean.y - rect.y,
+                ..clean
+            },
+            rect.size(),
+        ))
+    }
+    fn plane_format(&self, plane_index: usize) -> Option<&PlaneFormat> {
+        if self.buffer.is_planar() {
+            self.layout.planes.get(plane_index)
+        } else {
+            None
+        }
+    }
+    /// Locks the underlying buffer read-only and returns the planes of the view.
+    pub fn lock(&self) -> Result<BaseAddressGuard<'a>, CVPixelBufferError> {
+        let rect = self.rect;
+        self.buffer.lock()?.map_planes(|plane_index, plane| {
+            let format = self
+                .layout
+                .planes
+                .get(plane_index)
+                .ok_or(CVPixelBufferError::InvalidArgument)?;
+            let x = rect.x / format.horizontal_subsampling;
+            plane.crop(
+                x / format.block_width * format.bytes_per_block,
+                rect.y / format.vertical_subsampling,
+                format.width(rect.width),
+                format.height(rect.height),
+            )
+        })
+    }
+}
+
+/// Horizontal and vertical pixel alignment that keeps every plane on whole blocks.
+pub(super) fn alignment(layout: &PixelFormatLayout) -> (usize, usize) {
+    layout
+        .planes
+        .iter()
+        .fold((1, 1), |(horizontal, vertical), plane| {
+            (
+                lcm(horizontal, plane.horizontal_subsampling * plane.block_width),
+                lcm(vertical, plane.vertical_subsampling),
+            )
+        })
+}
+
+pub(super) fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
+
+impl<B: PixelBufferLike + ?Sized> PixelBufferLike for PixelBufferView<'_, B> {
+    fn get_width(&self) -> usize {
+        self.rect.width
+    }
+    fn get_height(&self) -> usize {
+        self.rect.height
+    }
+    fn get_bytes_per_row(&self) -> usize {
+        self.buffer.get_bytes_per_row()
+    }
+    fn get_pixel_format(&self) -> Result<PixelFormat, CVPixelBufferError> {
+        self.buffer.get_pixel_format()
+    }
+    fn is_planar(&self) -> bool {
+        self.buffer.is_planar()
+    }
+    fn get_plane_count(&self) -> usize {
+        self.buffer.get_plane_count()
+    }
+    fn get_width_of_plane(&self, plane_index: usize) -> usize {
+        self.plane_format(plane_index)
+            .map_or(0, |format| format.width(self.rect.width))
+    }
+    fn get_height_of_plane(&self, plane_index: usize) -> usize {
+        self.plane_format(plane_index)
+            .map_or(0, |format| format.height(self.rect.height))
+    }
+    fn get_bytes_per_row_of_plane(&self, plane_index: usize) -> usize {
+        self.buffer.get_bytes_per_row_of_plane(plane_index)
+    }
+    fn lock(&self) -> Result<BaseAddressGuard<'_>, CVPixelBufferError> {
+        PixelBufferView::lock(self)
+    }
+    fn lock_mut(&mut self) -> Result<MutBaseAddressGuard<'_>, CVPixelBufferError> {
+        Err(CVPixelBufferError::Unsupported)
+    }
+    fn get_extended_pixels(&self) -> ExtendedPixels {
+        ExtendedPixels::default()
+    }
+    fn fill_extended_pixels(&mut self) -> Result<(), CVPixelBufferError> {
+        Err(CVPixelBufferError::Unsupported)
+    }
+    fn set_attachment(&self, attachment: &ImageBufferAttachment, mode: CVAttachmentMode) {
+        self.buffer.set_attachment(attachment, mode)
+    }
+    fn get_attachment(
+        &self,
+        key: ImageBufferAttachmentKey,
+    ) -> Option<(ImageBufferAttachment, CVAttachmentMode)> {
+        let (attachment, mode) = self.buffer.get_attachment(key)?;
+        Some((self.view_attachment(attachment)?, mode))
+    }
+    fn remove_attachment(&self, key: ImageBufferAttachmentKey) {
+        self.buffer.remove_attachment(key)
+    }
+    fn remove_all_attachments(&self) {
+        self.buffer.remove_all_attachments()
+    }
+    fn get_attachments(&self, mode: CVAttachmentMode) -> ImageBufferAttachments {
+        let attachments: Vec<_> = self
+            .buffer
+            .get_attachments(mode)
+            .into_vec()
+            .into_iter()
+            .filter_map(|attachment| self.view_attachment(attachment))
+            .collect();
+        ImageBufferAttachments::new(&attachments)
+    }
+    fn set_attachments(&self, attachments: ImageBufferAttachments, mode: CVAttachmentMode) {
+        self.buffer.set_attachments(attachments, mode)
+    }
+}
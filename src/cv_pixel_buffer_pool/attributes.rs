@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use crate::{
+    cv_pixel_buffer::error::CVPixelBufferError,
+    dictionary::{Dictionary, DictionaryValue},
+};
+
+// Values of the `kCVPixelBufferPool*Key` constants.
+const MINIMUM_BUFFER_COUNT_KEY: &str = "MinimumBufferCount";
+const MAXIMUM_BUFFER_AGE_KEY: &str = "MaximumBufferAge";
+
+#[derive(Debug, Clone)]
+pub enum PixelBufferPoolAttribute {
+    /// Number of buffers the pool keeps around even when they age out.
+    MinimumBufferCount(usize),
+    /// How long an unused buffer stays in the pool before it is freed.
+    MaximumBufferAge(Duration),
+}
+
+impl PixelBufferPoolAttribute {
+    /// Reads the attribute for the `kCVPixelBufferPool*Key` named `key` from a dictionary
+    /// value. Returns `None` for other keys.
+    fn from_value(key: &str, value: &DictionaryValue) -> Result<Option<Self>, CVPixelBufferError> {
+        Ok(Some(match key {
+            MINIMUM_BUFFER_COUNT_KEY => Self::MinimumBufferCount(
+                value
+                    .as_i64()
+                    .and_then(|value| usize::try_from(value).ok())
+                    .ok_or(CVPixelBufferError::InvalidPoolAttributes)?,
+            ),
+            MAXIMUM_BUFFER_AGE_KEY => Self::MaximumBufferAge(
+                value
+                    .as_f64()
+                    .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                    .ok_or(CVPixelBufferError::InvalidPoolAttributes)?,
+            ),
+            _ => return Ok(None),
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PixelBufferPoolAttributes(Vec<PixelBufferPoolAttribute>);
+impl PixelBufferPoolAttributes {
+    pub fn new(attributes: &[PixelBufferPoolAttribute]) -> Self {
+        Self(attributes.to_vec())
+    }
+    pub fn add(&mut self, attribute: PixelBufferPoolAttribute) {
+        self.0.push(attribute);
+    }
+    pub fn as_slice(&self) -> &[PixelBufferPoolAttribute] {
+        &self.0
+    }
+    pub fn into_vec(self) -> Vec<PixelBufferPoolAttribute> {
+        self.0
+    }
+    /// Reads the attributes from a dictionary keyed by `kCVPixelBufferPool*Key` names, such
+    /// as `CVPixelBufferPoolGetAttributes` output. Other keys are ignored; known keys with
+    /// values of the wrong type fail with `InvalidPoolAttributes`.
+    pub fn from_dictionary(dictionary: &Dictionary) -> Result<Self, CVPixelBufferError> {
+        let mut attributes = Self::default();
+        for (key, value) in dictionary {
+            if let Some(attribute) = PixelBufferPoolAttribute::from_value(key, value)? {
+                attributes.add(attribute);
+            }
+        }
+        Ok(attributes)
+    }
+    pub fn minimum_buffer_count(&self) -> Option<usize> {
+        self.0.iter().rev().find_map(|attribute| match attribute {
+            PixelBufferPoolAttribute::MinimumBufferCount(count) => Some(*count),
+            _ => None,
+        })
+    }
+    pub fn maximum_buffer_age(&self) -> Option<Duration> {
+        self.0.iter().rev().find_map(|attribute| match attribute {
+            PixelBufferPoolAttribute::MaximumBufferAge(age) => Some(*age),
+            _ => None,
+        })
+    }
+}
+
+impl Default for PixelBufferPoolAttributes {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PixelBufferPoolAuxAttribute {
+    /// Fail with `WouldExceedAllocationThreshold` instead of growing the pool past this many
+    /// buffers.
+    AllocationThreshold(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct PixelBufferPoolAuxAttributes(Vec<PixelBufferPoolAuxAttribute>);
+impl PixelBufferPoolAuxAttributes {
+    pub fn new(attributes: &[PixelBufferPoolAuxAttribute]) -> Self {
+        Self(attributes.to_vec())
+    }
+    pub fn add(&mut self, attribute: PixelBufferPoolAuxAttribute) {
+        self.0.push(attribute);
+    }
+    pub fn as_slice(&self) -> &[PixelBufferPoolAuxAttribute] {
+        &self.0
+    }
+    pub fn into_vec(self) -> Vec<PixelBufferPoolAuxAttribute> {
+        self.0
+    }
+    pub fn allocation_threshold(&self) -> Option<usize> {
+        self.0
+            .iter()
+            .rev()
+            .map(|attribute| match attribute {
+                PixelBufferPoolAuxAttribute::AllocationThreshold(threshold) => *threshold,
+            })
+            .next()
+    }
+}
+
+impl Default for PixelBufferPoolAuxAttributes {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
@@ -0,0 +1,60 @@
+use core_foundation::{
+    base::{CFType, TCFType},
+    number::CFNumber,
+    string::{CFString, CFStringRef},
+};
+use core_graphics::display::CFDictionary;
+
+use super::attributes::{
+    PixelBufferPoolAttribute, PixelBufferPoolAttributes, PixelBufferPoolAuxAttribute,
+    PixelBufferPoolAuxAttributes,
+};
+
+impl From<PixelBufferPoolAttributes> for CFDictionary<CFString, CFType> {
+    fn from(val: PixelBufferPoolAttributes) -> Self {
+        let pairs: Vec<(CFString, CFType)> = val
+            .into_vec()
+            .into_iter()
+            .map(|attribute| match attribute {
+                PixelBufferPoolAttribute::MinimumBufferCount(count) => (
+                    unsafe {
+                        CFString::wrap_under_get_rule(kCVPixelBufferPoolMinimumBufferCountKey)
+                    },
+                    CFNumber::from(count as i64).into_CFType(),
+                ),
+                PixelBufferPoolAttribute::MaximumBufferAge(age) => (
+                    unsafe { CFString::wrap_under_get_rule(kCVPixelBufferPoolMaximumBufferAgeKey) },
+                    CFNumber::from(age.as_secs_f64()).into_CFType(),
+                ),
+            })
+            .collect();
+        CFDictionary::from_CFType_pairs(&pairs)
+    }
+}
+
+impl From<PixelBufferPoolAuxAttributes> for CFDictionary<CFString, CFType> {
+    fn from(val: PixelBufferPoolAuxAttributes) -> Self {
+        let pairs: Vec<(CFString, CFType)> = val
+            .into_vec()
+            .into_iter()
+            .map(|attribute| match attribute {
+                PixelBufferPoolAuxAttribute::AllocationThreshold(threshold) => (
+                    unsafe {
+                        CFString::wrap_under_get_rule(kCVPixelBufferPoolAllocationThresholdKey)
+                    },
+                    CFNumber::from(threshold as i64).into_CFType(),
+                ),
+            })
+            .collect();
+        CFDictionary::from_CFType_pairs(&pairs)
+    }
+}
+
+extern "C" {
+    // A key to the minimum number of buffers allowed in the pixel buffer pool.
+    static kCVPixelBufferPoolMinimumBufferCountKey: CFStringRef;
+    // A key to the maximum allowable age in seconds for a buffer in the pixel buffer pool.
+    static kCVPixelBufferPoolMaximumBufferAgeKey: CFStringRef;
+    // A key to the number of pixel buffers after which the pool stops allocating new buffers.
+    static kCVPixelBufferPoolAllocationThresholdKey: CFStringRef;
+}
@@ -0,0 +1,27 @@
+use core::fmt;
+
+use std::{ffi::c_void, fmt::Formatter};
+
+use core_foundation::{base::CFTypeID, declare_TCFType, impl_TCFType};
+
+#[repr(C)]
+pub struct __CVPixelBufferPoolRef(c_void);
+
+pub type CVPixelBufferPoolRef = *mut __CVPixelBufferPoolRef;
+
+declare_TCFType! {CVPixelBufferPool, CVPixelBufferPoolRef}
+impl_TCFType!(
+    CVPixelBufferPool,
+    CVPixelBufferPoolRef,
+    CVPixelBufferPoolGetTypeID
+);
+
+impl fmt::Debug for CVPixelBufferPool {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "CVPixelBufferPool")
+    }
+}
+
+extern "C" {
+    fn CVPixelBufferPoolGetTypeID() -> CFTypeID;
+}
@@ -0,0 +1,106 @@
+use core_foundation::{
+    base::{kCFAllocatorDefault, CFAllocatorRef, CFType, TCFType},
+    dictionary::CFDictionaryRef,
+    string::CFString,
+};
+use core_graphics::display::CFDictionary;
+use std::ptr;
+
+use crate::{
+    cv_pixel_buffer::{
+        attributes::PixelBufferAttributes,
+        error::{CVPixelBufferError, CV_RETURN_SUCCESS},
+        CVPixelBuffer, CVPixelBufferRef,
+    },
+    types::CVReturn,
+};
+
+use super::{
+    attributes::{PixelBufferPoolAttributes, PixelBufferPoolAuxAttributes},
+    internal_base::{CVPixelBufferPool, CVPixelBufferPoolRef},
+};
+
+impl CVPixelBufferPool {
+    pub(super) fn internal_create(
+        pool_attributes: PixelBufferPoolAttributes,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError> {
+        extern "C" {
+            fn CVPixelBufferPoolCreate(
+                allocator: CFAllocatorRef,
+                poolAttributes: CFDictionaryRef,
+                pixelBufferAttributes: CFDictionaryRef,
+                poolOut: *mut CVPixelBufferPoolRef,
+            ) -> CVReturn;
+        }
+        let mut pool_out: CVPixelBufferPoolRef = ptr::null_mut();
+        let pool_attributes: CFDictionary<CFString, CFType> = pool_attributes.into();
+        let pixel_buffer_attributes: CFDictionary<CFString, CFType> =
+            pixel_buffer_attributes.into();
+        unsafe {
+            let result = CVPixelBufferPoolCreate(
+                kCFAllocatorDefault,
+                pool_attributes.as_concrete_TypeRef(),
+                pixel_buffer_attributes.as_concrete_TypeRef(),
+                &mut pool_out,
+            );
+            if result == CV_RETURN_SUCCESS {
+                Ok(CVPixelBufferPool::wrap_under_create_rule(pool_out))
+            } else {
+                Err(CVPixelBufferError::from(result))
+            }
+        }
+    }
+
+    pub(super) fn internal_create_pixel_buffer(&self) -> Result<CVPixelBuffer, CVPixelBufferError> {
+        extern "C" {
+            fn CVPixelBufferPoolCreatePixelBuffer(
+                allocator: CFAllocatorRef,
+                pixelBufferPool: CVPixelBufferPoolRef,
+                pixelBufferOut: *mut CVPixelBufferRef,
+            ) -> CVReturn;
+        }
+        let mut pixel_buffer_out: CVPixelBufferRef = ptr::null_mut();
+        unsafe {
+            let result = CVPixelBufferPoolCreatePixelBuffer(
+                kCFAllocatorDefault,
+                self.as_concrete_TypeRef(),
+                &mut pixel_buffer_out,
+            );
+            if result == CV_RETURN_SUCCESS {
+                Ok(CVPixelBuffer::wrap_under_create_rule(pixel_buffer_out))
+            } else {
+                Err(CVPixelBufferError::from(result))
+            }
+        }
+    }
+
+    pub(super) fn internal_create_pixel_buffer_with_aux_attributes(
+        &self,
+        aux_attributes: PixelBufferPoolAuxAttributes,
+    ) -> Result<CVPixelBuffer, CVPixelBufferError> {
+        extern "C" {
+            fn CVPixelBufferPoolCreatePixelBufferWithAuxAttributes(
+                allocator: CFAllocatorRef,
+                pixelBufferPool: CVPixelBufferPoolRef,
+                auxAttributes: CFDictionaryRef,
+                pixelBufferOut: *mut CVPixelBufferRef,
+            ) -> CVReturn;
+        }
+        let mut pixel_buffer_out: CVPixelBufferRef = ptr::null_mut();
+        let aux_attributes: CFDictionary<CFString, CFType> = aux_attributes.into();
+        unsafe {
+            let result = CVPixelBufferPoolCreatePixelBufferWithAuxAttributes(
+                kCFAllocatorDefault,
+                self.as_concrete_TypeRef(),
+                aux_attributes.as_concrete_TypeRef(),
+                &mut pixel_buffer_out,
+            );
+            if result == CV_RETURN_SUCCESS {
+                Ok(CVPixelBuffer::wrap_under_create_rule(pixel_buffer_out))
+            } else {
+                Err(CVPixelBufferError::from(result))
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use core_foundation::{
+    base::{CFType, TCFType},
+    dictionary::CFDictionaryRef,
+    string::CFString,
+};
+use core_graphics::display::CFDictionary;
+
+use crate::{
+    cv_pixel_buffer::{attributes::PixelBufferAttributes, error::CVPixelBufferError},
+    dictionary::internal_dictionary::dictionary_from_cf,
+    types::CVOptionFlags,
+};
+
+use super::{
+    attributes::PixelBufferPoolAttributes,
+    internal_base::{CVPixelBufferPool, CVPixelBufferPoolRef},
+};
+
+impl CVPixelBufferPool {
+    pub(super) fn internal_flush(&self, options: CVOptionFlags) {
+        extern "C" {
+            fn CVPixelBufferPoolFlush(pool: CVPixelBufferPoolRef, options: CVOptionFlags);
+        }
+        unsafe { CVPixelBufferPoolFlush(self.as_concrete_TypeRef(), options) }
+    }
+    pub(super) fn internal_attributes(
+        &self,
+    ) -> Result<PixelBufferPoolAttributes, CVPixelBufferError> {
+        extern "C" {
+            fn CVPixelBufferPoolGetAttributes(pool: CVPixelBufferPoolRef) -> CFDictionaryRef;
+        }
+        match unsafe { wrap_dictionary(CVPixelBufferPoolGetAttributes(self.as_concrete_TypeRef())) }
+        {
+            Some(dictionary) => {
+                PixelBufferPoolAttributes::from_dictionary(&dictionary_from_cf(&dictionary))
+            }
+            None => Ok(PixelBufferPoolAttributes::default()),
+        }
+    }
+    pub(super) fn internal_pixel_buffer_attributes(
+        &self,
+    ) -> Result<PixelBufferAttributes, CVPixelBufferError> {
+        extern "C" {
+            fn CVPixelBufferPoolGetPixelBufferAttributes(
+                pool: CVPixelBufferPoolRef,
+            ) -> CFDictionaryRef;
+        }
+        match unsafe {
+            wrap_dictionary::<CFString>(CVPixelBufferPoolGetPixelBufferAttributes(
+                self.as_concrete_TypeRef(),
+            ))
+        } {
+            Some(dictionary) => PixelBufferAttributes::try_from(dictionary),
+            None => Ok(PixelBufferAttributes::default()),
+        }
+    }
+}
+
+unsafe fn wrap_dictionary<K>(dictionary: CFDictionaryRef) -> Option<CFDictionary<K, CFType>> {
+    if dictionary.is_null() {
+        None
+    } else {
+        Some(CFDictionary::wrap_under_get_rule(dictionary))
+    }
+}
@@ -0,0 +1,63 @@
+pub mod attributes;
+#[cfg(target_vendor = "apple")]
+mod internal_attributes;
+#[cfg(target_vendor = "apple")]
+mod internal_base;
+#[cfg(target_vendor = "apple")]
+mod internal_create;
+#[cfg(target_vendor = "apple")]
+mod internal_props;
+pub mod pixel_buffer_pool_like;
+pub mod software;
+
+#[cfg(target_vendor = "apple")]
+pub use internal_base::CVPixelBufferPool;
+pub use pixel_buffer_pool_like::PixelBufferPoolLike;
+pub use software::SoftwarePixelBufferPool;
+
+#[cfg(target_vendor = "apple")]
+use {
+    crate::cv_pixel_buffer::{
+        attributes::PixelBufferAttributes, error::CVPixelBufferError, CVPixelBuffer,
+    },
+    attributes::{PixelBufferPoolAttributes, PixelBufferPoolAuxAttributes},
+};
+
+#[repr(u64)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CVPixelBufferPoolFlushFlags {
+    /// Free unused buffers that are older than the pool's maximum buffer age.
+    AgedBuffers = 0x0,
+    /// Free all unused buffers regardless of age.
+    ExcessBuffers = 0x00000001,
+}
+
+#[cfg(target_vendor = "apple")]
+impl CVPixelBufferPool {
+    pub fn create(
+        pool_attributes: PixelBufferPoolAttributes,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError> {
+        Self::internal_create(pool_attributes, pixel_buffer_attributes)
+    }
+    pub fn create_pixel_buffer(&self) -> Result<CVPixelBuffer, CVPixelBufferError> {
+        self.internal_create_pixel_buffer()
+    }
+    pub fn create_pixel_buffer_with_aux_attributes(
+        &self,
+        aux_attributes: PixelBufferPoolAuxAttributes,
+    ) -> Result<CVPixelBuffer, CVPixelBufferError> {
+        self.internal_create_pixel_buffer_with_aux_attributes(aux_attributes)
+    }
+    pub fn flush(&self, flags: CVPixelBufferPoolFlushFlags) {
+        self.internal_flush(flags as u64)
+    }
+    /// The pool's attributes as CoreVideo reports them.
+    pub fn get_attributes(&self) -> Result<PixelBufferPoolAttributes, CVPixelBufferError> {
+        self.internal_attributes()
+    }
+    /// Attributes of the buffers the pool creates, as CoreVideo reports them.
+    pub fn get_pixel_buffer_attributes(&self) -> Result<PixelBufferAttributes, CVPixelBufferError> {
+        self.internal_pixel_buffer_attributes()
+    }
+}
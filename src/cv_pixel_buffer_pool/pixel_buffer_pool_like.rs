@@ -0,0 +1,78 @@
+#[cfg(target_vendor = "apple")]
+use crate::cv_pixel_buffer::CVPixelBuffer;
+use crate::cv_pixel_buffer::{
+    attributes::PixelBufferAttributes, error::CVPixelBufferError, PixelBufferLike,
+    SoftwarePixelBuffer,
+};
+
+#[cfg(target_vendor = "apple")]
+use super::CVPixelBufferPool;
+use super::{
+    attributes::{PixelBufferPoolAttributes, PixelBufferPoolAuxAttributes},
+    software::SoftwarePixelBufferPool,
+    CVPixelBufferPoolFlushFlags,
+};
+
+/// Pool operations shared by [`CVPixelBufferPool`] and [`SoftwarePixelBufferPool`].
+///
+/// Code that recycles frames through this trait runs unchanged on CoreVideo pools and on
+/// the software pool.
+pub trait PixelBufferPoolLike {
+    type PixelBuffer: PixelBufferLike;
+
+    fn create_pixel_buffer(&self) -> Result<Self::PixelBuffer, CVPixelBufferError>;
+    fn create_pixel_buffer_with_aux_attributes(
+        &self,
+        aux_attributes: PixelBufferPoolAuxAttributes,
+    ) -> Result<Self::PixelBuffer, CVPixelBufferError>;
+    fn flush(&self, flags: CVPixelBufferPoolFlushFlags);
+    fn get_attributes(&self) -> Result<PixelBufferPoolAttributes, CVPixelBufferError>;
+    fn get_pixel_buffer_attributes(&self) -> Result<PixelBufferAttributes, CVPixelBufferError>;
+}
+
+impl PixelBufferPoolLike for SoftwarePixelBufferPool {
+    type PixelBuffer = SoftwarePixelBuffer<'static>;
+
+    fn create_pixel_buffer(&self) -> Result<Self::PixelBuffer, CVPixelBufferError> {
+        SoftwarePixelBufferPool::create_pixel_buffer(self)
+    }
+    fn create_pixel_buffer_with_aux_attributes(
+        &self,
+        aux_attributes: PixelBufferPoolAuxAttributes,
+    ) -> Result<Self::PixelBuffer, CVPixelBufferError> {
+        SoftwarePixelBufferPool::create_pixel_buffer_with_aux_attributes(self, aux_attributes)
+    }
+    fn flush(&self, flags: CVPixelBufferPoolFlushFlags) {
+        SoftwarePixelBufferPool::flush(self, flags)
+    }
+    fn get_attributes(&self) -> Result<PixelBufferPoolAttributes, CVPixelBufferError> {
+        Ok(SoftwarePixelBufferPool::get_attributes(self))
+    }
+    fn get_pixel_buffer_attributes(&self) -> Result<PixelBufferAttributes, CVPixelBufferError> {
+        Ok(SoftwarePixelBufferPool::get_pixel_buffer_attributes(self))
+    }
+}
+
+#[cfg(target_vendor = "apple")]
+impl PixelBufferPoolLike for CVPixelBufferPool {
+    type PixelBuffer = CVPixelBuffer;
+
+    fn create_pixel_buffer(&self) -> Result<Self::PixelBuffer, CVPixelBufferError> {
+        CVPixelBufferPool::create_pixel_buffer(self)
+    }
+    fn create_pixel_buffer_with_aux_attributes(
+        &self,
+        aux_attributes: PixelBufferPoolAuxAttributes,
+    ) -> Result<Self::PixelBuffer, CVPixelBufferError> {
+        CVPixelBufferPool::create_pixel_buffer_with_aux_attributes(self, aux_attributes)
+    }
+    fn flush(&self, flags: CVPixelBufferPoolFlushFlags) {
+        CVPixelBufferPool::flush(self, flags)
+    }
+    fn get_attributes(&self) -> Result<PixelBufferPoolAttributes, CVPixelBufferError> {
+        CVPixelBufferPool::get_attributes(self)
+    }
+    fn get_pixel_buffer_attributes(&self) -> Result<PixelBufferAttributes, CVPixelBufferError> {
+        CVPixelBufferPool::get_pixel_buffer_attributes(self)
+    }
+}
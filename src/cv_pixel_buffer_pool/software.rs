@@ -0,0 +1,180 @@
+use std::{
+    sync::{Arc, Mutex, MutexGuard, PoisonError, Weak},
+    time::{Duration, Instant},
+};
+
+use crate::cv_pixel_buffer::{
+    attributes::{PixelBufferAttribute, PixelBufferAttributes, PixelFormatValue},
+    error::CVPixelBufferError,
+    pixel_format::PixelFormat,
+    software::buffer_layout,
+    SoftwarePixelBuffer,
+};
+
+use super::{
+    attributes::{PixelBufferPoolAttributes, PixelBufferPoolAuxAttributes},
+    CVPixelBufferPoolFlushFlags,
+};
+
+/// Age after which CoreVideo frees unused pool buffers when no maximum age is configured.
+pub const DEFAULT_MAXIMUM_BUFFER_AGE: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+struct PoolState {
+    /// Released buffers, oldest first.
+    free: Vec<(Vec<u8>, Instant)>,
+    in_use: usize,
+}
+
+/// Recycling pool of [`SoftwarePixelBuffer`]s with the same API and limits as
+/// `CVPixelBufferPool`.
+///
+/// Buffers return to the pool when dropped and are handed out again without clearing their
+/// contents. Clones share the same pool.
+#[derive(Debug, Clone)]
+pub struct SoftwarePixelBufferPool {
+    width: usize,
+    height: usize,
+    pixel_format: PixelFormat,
+    attributes: PixelBufferPoolAttributes,
+    pixel_buffer_attributes: PixelBufferAttributes,
+    /// Shared with the release callbacks of outstanding buffers, which must be `Send`.
+    state: Arc<Mutex<PoolState>>,
+}
+
+fn lock_state(state: &Mutex<PoolState>) -> MutexGuard<'_, PoolState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl SoftwarePixelBufferPool {
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        lock_state(&self.state)
+    }
+    /// Frees unused buffers, oldest first, while keeping the pool at its minimum buffer
+    /// count. Only buffers older than the maximum age are freed unless `all` is set.
+    fn free_unused(&self, state: &mut PoolState, all: bool) {
+        let keep = self
+            .attributes
+            .minimum_buffer_count()
+            .unwrap_or(0)
+            .saturating_sub(state.in_use);
+        let removable = state.free.len().saturating_sub(keep);
+        let max_age = self
+            .attributes
+            .maximum_buffer_age()
+            .unwrap_or(DEFAULT_MAXIMUM_BUFFER_AGE);
+        let now = Instant::now();
+        let expired = state
+            .free
+            .iter()
+            .take(removable)
+            .take_while(|(_, released)| all || now.duration_since(*released) >= max_age)
+            .count();
+        state.free.drain(..expired);
+    }
+
+    /// Creates a pool. `pixel_buffer_attributes` must contain the width, height and pixel
+    /// format of the buffers, as CoreVideo requires.
+    pub fn create(
+        pool_attributes: PixelBufferPoolAttributes,
+        pixel_buffer_attributes: PixelBufferAttributes,
+    ) -> Result<Self, CVPixelBufferError> {
+        let mut width = None;
+        let mut height = None;
+        let mut pixel_format = None;
+        for attribute in pixel_buffer_attributes.as_slice() {
+            match attribute {
+                PixelBufferAttribute::Width(val) => width = Some(*val),
+                PixelBufferAttribute::Height(val) => height = Some(*val),
+                PixelBufferAttribute::PixelFormat(PixelFormatValue::Single(val)) => {
+                    pixel_format = Some(*val)
+                }
+                PixelBufferAttribute::PixelFormat(PixelFormatValue::Many(vals)) => {
+                    pixel_format = vals.first().copied()
+                }
+                _ => {}
+            }
+        }
+        let (Some(width), Some(height), Some(pixel_format)) = (width, height, pixel_format) else {
+            return Err(CVPixelBufferError::InvalidPixelBufferAttributes);
+        };
+        buffer_layout(width, height, pixel_format, &pixel_buffer_attributes)?;
+        Ok(Self {
+            width,
+            height,
+            pixel_format,
+            attributes: pool_attributes,
+            pixel_buffer_attributes,
+            state: Arc::new(Mutex::new(PoolState {
+                free: Vec::new(),
+                in_use: 0,
+            })),
+        })
+    }
+
+    pub fn create_pixel_buffer(&self) -> Result<SoftwarePixelBuffer<'static>, CVPixelBufferError> {
+        self.create_pixel_buffer_with_aux_attributes(PixelBufferPoolAuxAttributes::default())
+    }
+
+    pub fn create_pixel_buffer_with_aux_attributes(
+        &self,
+        aux_attributes: PixelBufferPoolAuxAttributes,
+    ) -> Result<SoftwarePixelBuffer<'static>, CVPixelBufferError> {
+        let data = {
+            let mut state = self.state();
+            self.free_unused(&mut state, false);
+            let data = state.free.pop().map(|(data, _)| data);
+            if data.is_none()
+                && aux_attributes
+                    .allocation_threshold()
+                    .is_some_and(|threshold| state.in_use >= threshold)
+            {
+                return Err(CVPixelBufferError::WouldExceedAllocationThreshold);
+            }
+            state.in_use += 1;
+            data
+        };
+        let pool = Arc::downgrade(&self.state);
+        SoftwarePixelBuffer::internal_create(
+            self.width,
+            self.height,
+            self.pixel_format,
+            &self.pixel_buffer_attributes,
+            data,
+            Some(Box::new(move |data| {
+                if let Some(pool) = Weak::upgrade(&pool) {
+                    let mut state = lock_state(&pool);
+                    state.in_use -= 1;
+                    state.free.push((data, Instant::now()));
+                }
+            })),
+        )
+        .inspect_err(|_| self.state().in_use -= 1)
+    }
+
+    pub fn flush(&self, flags: CVPixelBufferPoolFlushFlags) {
+        let mut state = self.state();
+        self.free_unused(
+            &mut state,
+            flags == CVPixelBufferPoolFlushFlags::ExcessBuffers,
+        );
+    }
+
+    pub fn get_attributes(&self) -> PixelBufferPoolAttributes {
+        self.attributes.clone()
+    }
+
+    pub fn get_pixel_buffer_attributes(&self) -> PixelBufferAttributes {
+        self.pixel_buffer_attributes.clone()
+    }
+
+    /// Number of buffers currently handed out.
+    pub fn in_use_count(&self) -> usize {
+        self.state().in_use
+    }
+
+    /// Number of released buffers waiting to be reused.
+    pub fn free_count(&self) -> usize {
+        self.state().free.len()
+    }
+}
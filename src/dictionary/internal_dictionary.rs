@@ -0,0 +1,89 @@
+use core_foundation::{
+    array::CFArray,
+    base::{CFType, TCFType},
+    boolean::CFBoolean,
+    data::CFData,
+    dictionary::CFDictionary as CFUntypedDictionary,
+    number::{CFNumber, CFNumberIsFloatType},
+    string::CFString,
+};
+use core_graphics::display::CFDictionary;
+
+use super::{Dictionary, DictionaryValue};
+
+impl DictionaryValue {
+    /// Converts a property list object, or `None` for other CoreFoundation types.
+    pub(crate) fn from_cf_type(value: &CFType) -> Option<Self> {
+        if let Some(boolean) = value.downcast::<CFBoolean>() {
+            return Some(Self::Bool(boolean.into()));
+        }
+        if let Some(number) = value.downcast::<CFNumber>() {
+            let is_float = unsafe { CFNumberIsFloatType(number.as_concrete_TypeRef()) != 0 };
+            return if is_float {
+                number.to_f64().map(Self::Float)
+            } else {
+                number.to_i64().map(Self::Integer)
+            };
+        }
+        if let Some(string) = value.downcast::<CFString>() {
+            return Some(Self::String(string.to_string()));
+        }
+        if let Some(data) = value.downcast::<CFData>() {
+            return Some(Self::Data(data.bytes().to_vec()));
+        }
+        if let Some(array) = value.downcast::<CFArray>() {
+            let items = array
+                .iter()
+                .filter_map(|item| {
+                    Self::from_cf_type(&unsafe { CFType::wrap_under_get_rule(*item) })
+                })
+                .collect();
+            return Some(Self::Array(items));
+        }
+        if let Some(dictionary) = value.downcast::<CFUntypedDictionary>() {
+            let dictionary: CFDictionary<CFType, CFType> =
+                unsafe { CFDictionary::wrap_under_get_rule(dictionary.as_concrete_TypeRef()) };
+            return Some(Self::Dictionary(dictionary_from_cf(&dictionary)));
+        }
+        None
+    }
+
+    pub(crate) fn to_cf_type(&self) -> CFType {
+        match self {
+            Self::Bool(value) => CFBoolean::from(*value).into_CFType(),
+            Self::Integer(value) => CFNumber::from(*value).into_CFType(),
+            Self::Float(value) => CFNumber::from(*value).into_CFType(),
+            Self::String(value) => CFString::new(value).into_CFType(),
+            Self::Data(value) => CFData::from_buffer(value).into_CFType(),
+            Self::Array(values) => {
+                let values: Vec<CFType> = values.iter().map(Self::to_cf_type).collect();
+                CFArray::from_CFTypes(&values).into_CFType()
+            }
+            Self::Dictionary(dictionary) => dictionary_to_cf(dictionary).into_CFType(),
+        }
+    }
+}
+
+/// Converts the entries with string keys and property list values; others are skipped.
+pub(crate) fn dictionary_from_cf(dictionary: &CFDictionary<CFType, CFType>) -> Dictionary {
+    let (keys, values) = dictionary.get_keys_and_values();
+    keys.into_iter()
+        .zip(values)
+        .filter_map(|(key, value)| {
+            let key = unsafe { CFType::wrap_under_get_rule(key) };
+            let value = unsafe { CFType::wrap_under_get_rule(value) };
+            Some((
+                key.downcast::<CFString>()?.to_string(),
+                DictionaryValue::from_cf_type(&value)?,
+            ))
+        })
+        .collect()
+}
+
+pub(crate) fn dictionary_to_cf(dictionary: &Dictionary) -> CFDictionary<CFString, CFType> {
+    let pairs: Vec<(CFString, CFType)> = dictionary
+        .iter()
+        .map(|(key, value)| (CFString::new(key), value.to_cf_type()))
+        .collect();
+    CFDictionary::from_CFType_pairs(&pairs)
+}
@@ -0,0 +1,133 @@
+//! Property list values, the pure Rust counterpart of the `CFDictionary` trees CoreVideo
+//! uses for descriptions and attributes.
+
+#[cfg(target_vendor = "apple")]
+pub(crate) mod internal_dictionary;
+
+use std::collections::BTreeMap;
+
+/// A dictionary with string keys, as CoreVideo description and attribute dictionaries are.
+pub type Dictionary = BTreeMap<String, DictionaryValue>;
+
+/// A property list value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DictionaryValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Data(Vec<u8>),
+    Array(Vec<DictionaryValue>),
+    Dictionary(Dictionary),
+}
+
+impl DictionaryValue {
+    /// Booleans, and numbers as CoreVideo sometimes stores flags, with zero as `false`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Self::Bool(value) => Some(value),
+            Self::Integer(value) => Some(value != 0),
+            _ => None,
+        }
+    }
+    /// Integers, and floats with no fractional part.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Self::Integer(value) => Some(value),
+            Self::Float(value)
+                if value.fract() == 0.0 && value >= i64::MIN as f64 && value < i64::MAX as f64 =>
+            {
+                Some(value as i64)
+            }
+            _ => None,
+        }
+    }
+    pub fn as_u32(&self) -> Option<u32> {
+        self.as_i64()?.try_into().ok()
+    }
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Self::Integer(value) => Some(value as f64),
+            Self::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn as_data(&self) -> Option<&[u8]> {
+        match self {
+            Self::Data(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn as_array(&self) -> Option<&[DictionaryValue]> {
+        match self {
+            Self::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn as_dictionary(&self) -> Option<&Dictionary> {
+        match self {
+            Self::Dictionary(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for DictionaryValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for DictionaryValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<u32> for DictionaryValue {
+    fn from(value: u32) -> Self {
+        Self::Integer(value as i64)
+    }
+}
+
+impl From<f64> for DictionaryValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for DictionaryValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for DictionaryValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<Vec<u8>> for DictionaryValue {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Data(value)
+    }
+}
+
+impl From<Vec<DictionaryValue>> for DictionaryValue {
+    fn from(value: Vec<DictionaryValue>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl From<Dictionary> for DictionaryValue {
+    fn from(value: Dictionary) -> Self {
+        Self::Dictionary(value)
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::OnceLock;
+
+use super::{HostClock, Timebase};
+
+extern "C" {
+    fn CVGetCurrentHostTime() -> u64;
+    fn CVGetHostClockFrequency() -> f64;
+    fn CVGetHostClockMinimumTimeDelta() -> u32;
+}
+
+pub fn get_current_host_time() -> u64 {
+    unsafe { CVGetCurrentHostTime() }
+}
+/// Host clock ticks per second.
+pub fn get_host_clock_frequency() -> f64 {
+    unsafe { CVGetHostClockFrequency() }
+}
+/// Smallest number of host clock ticks that can be measured reliably.
+pub fn get_host_clock_minimum_time_delta() -> u32 {
+    unsafe { CVGetHostClockMinimumTimeDelta() }
+}
+
+/// The system host clock, as read by `CVGetCurrentHostTime`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemHostClock;
+
+impl HostClock for SystemHostClock {
+    fn now(&self) -> u64 {
+        get_current_host_time()
+    }
+    fn timebase(&self) -> Timebase {
+        static TIMEBASE: OnceLock<Timebase> = OnceLock::new();
+        *TIMEBASE.get_or_init(|| {
+            Timebase::from_frequency(get_host_clock_frequency()).unwrap_or_default()
+        })
+    }
+    fn minimum_time_delta(&self) -> u64 {
+        get_host_clock_minimum_time_delta() as u64
+    }
+}
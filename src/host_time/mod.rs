@@ -0,0 +1,212 @@
+//! Host time, the clock capture timestamps and `CVTimeStamp::host_time` are given in.
+
+#[cfg(target_vendor = "apple")]
+mod internal_host_time;
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+#[cfg(target_vendor = "apple")]
+pub use internal_host_time::{
+    get_current_host_time, get_host_clock_frequency, get_host_clock_minimum_time_delta,
+    SystemHostClock,
+};
+
+use crate::types::{CVTime, Rational};
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// Length of a host clock tick as `numerator / denominator` nanoseconds, like
+/// `mach_timebase_info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timebase {
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+impl Timebase {
+    /// One tick per nanosecond.
+    pub const NANOSECONDS: Self = Self::new(1, 1);
+
+    pub const fn new(numerator: u32, denominator: u32) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+    /// Timebase of a clock ticking `frequency` times per second, e.g. 125/3 for 24 MHz.
+    /// `None` for frequencies that are not positive and finite.
+    pub fn from_frequency(frequency: f64) -> Option<Self> {
+        if !frequency.is_finite() || frequency <= 0.0 {
+            return None;
+        }
+        let ratio = Rational::from_f64(NANOS_PER_SECOND as f64 / frequency).reduced();
+        Some(Self::new(
+            u32::try_from(ratio.numerator).ok().filter(|&n| n > 0)?,
+            u32::try_from(ratio.denominator).ok()?,
+        ))
+    }
+    /// Ticks per second.
+    pub fn frequency(&self) -> f64 {
+        NANOS_PER_SECOND as f64 * self.denominator as f64 / self.numerator as f64
+    }
+    /// Nanoseconds in `ticks`, rounded down and saturating at `u64::MAX`.
+    pub fn ticks_to_nanos(&self, ticks: u64) -> u64 {
+        scale(ticks, self.numerator as u128, self.denominator as u128)
+    }
+    /// Ticks in `nanos`, rounded down and saturating at `u64::MAX`.
+    pub fn nanos_to_ticks(&self, nanos: u64) -> u64 {
+        scale(nanos, self.denominator as u128, self.numerator as u128)
+    }
+    pub fn ticks_to_duration(&self, ticks: u64) -> Duration {
+        Duration::from_nanos(self.ticks_to_nanos(ticks))
+    }
+    pub fn duration_to_ticks(&self, duration: Duration) -> u64 {
+        scale_u128(
+            duration.as_nanos(),
+            self.denominator as u128,
+            self.numerator as u128,
+        )
+    }
+    /// `ticks` in units of `1 / time_scale` seconds, rounded down. `None` if `time_scale` is
+    /// not positive or the value does not fit.
+    pub fn ticks_to_cv_time(&self, ticks: u64, time_scale: i32) -> Option<CVTime> {
+        if time_scale <= 0 {
+            return None;
+        }
+        let time_value = (ticks as u128 * self.numerator as u128)
+            .checked_mul(time_scale as u128)?
+            / (self.denominator as u128 * NANOS_PER_SECOND);
+        Some(CVTime::new(time_value.try_into().ok()?, time_scale))
+    }
+    /// Ticks in a valid, non-negative `time`, rounded down and saturating.
+    pub fn cv_time_to_ticks(&self, time: CVTime) -> Option<u64> {
+        if !time.is_valid() || time.time_value < 0 {
+            return None;
+        }
+        Some(scale(
+            time.time_value as u64,
+            self.denominator as u128 * NANOS_PER_SECOND,
+            self.numerator as u128 * time.time_scale as u128,
+        ))
+    }
+}
+
+impl Default for Timebase {
+    fn default() -> Self {
+        Self::NANOSECONDS
+    }
+}
+
+fn scale(value: u64, numerator: u128, denominator: u128) -> u64 {
+    scale_u128(value as u128, numerator, denominator)
+}
+
+/// `value * numerator / denominator` without intermediate overflow, saturating at
+/// `u64::MAX`.
+fn scale_u128(value: u128, numerator: u128, denominator: u128) -> u64 {
+    if denominator == 0 {
+        return u64::MAX;
+    }
+    let (quotient, remainder) = (value / denominator, value % denominator);
+    quotient
+        .checked_mul(numerator)
+        .and_then(|whole| whole.checked_add(remainder * numerator / denominator))
+        .map_or(u64::MAX, |result| result.try_into().unwrap_or(u64::MAX))
+}
+
+/// A monotonic clock counting ticks of a fixed [`Timebase`].
+pub trait HostClock {
+    /// Current time in ticks.
+    fn now(&self) -> u64;
+    fn timebase(&self) -> Timebase;
+    /// Smallest number of ticks the clock can reliably measure.
+    fn minimum_time_delta(&self) -> u64 {
+        1
+    }
+
+    fn now_nanos(&self) -> u64 {
+        self.timebase().ticks_to_nanos(self.now())
+    }
+    /// Time from `ticks` to now, or zero if `ticks` is in the future.
+    fn elapsed_since(&self, ticks: u64) -> Duration {
+        self.timebase()
+            .ticks_to_duration(self.now().saturating_sub(ticks))
+    }
+    /// Pairs the current host time with the current `Instant`, to translate between them.
+    fn instant_bridge(&self) -> InstantBridge {
+        InstantBridge {
+            instant: Instant::now(),
+            ticks: self.now(),
+            timebase: self.timebase(),
+        }
+    }
+}
+
+/// A host time and an `Instant` taken together, for converting host timestamps to
+/// `Instant`s and back. Precision is bounded by the time between the two readings.
+#[derive(Debug, Clone, Copy)]
+pub struct InstantBridge {
+    pub instant: Instant,
+    pub ticks: u64,
+    pub timebase: Timebase,
+}
+
+impl InstantBridge {
+    /// The `Instant` at host time `ticks`, or `None` if it cannot be represented.
+    pub fn to_instant(&self, ticks: u64) -> Option<Instant> {
+        if ticks >= self.ticks {
+            self.instant
+                .checked_add(self.timebase.ticks_to_duration(ticks - self.ticks))
+        } else {
+            self.instant
+                .checked_sub(self.timebase.ticks_to_duration(self.ticks - ticks))
+        }
+    }
+    /// The host time at `instant`, saturating at the ends of the tick range.
+    pub fn to_ticks(&self, instant: Instant) -> u64 {
+        if instant >= self.instant {
+            self.ticks
+                .saturating_add(self.timebase.duration_to_ticks(instant - self.instant))
+        } else {
+            self.ticks
+                .saturating_sub(self.timebase.duration_to_ticks(self.instant - instant))
+        }
+    }
+}
+
+/// A host clock that only moves when told to, for tests and offline processing.
+#[derive(Debug, Default)]
+pub struct FakeHostClock {
+    ticks: AtomicU64,
+    timebase: Timebase,
+}
+
+impl FakeHostClock {
+    pub fn new(ticks: u64, timebase: Timebase) -> Self {
+        Self {
+            ticks: AtomicU64::new(ticks),
+            timebase,
+        }
+    }
+    pub fn set(&self, ticks: u64) {
+        self.ticks.store(ticks, Ordering::SeqCst);
+    }
+    pub fn advance(&self, ticks: u64) {
+        self.ticks.fetch_add(ticks, Ordering::SeqCst);
+    }
+    pub fn advance_by(&self, duration: Duration) {
+        self.advance(self.timebase.duration_to_ticks(duration));
+    }
+}
+
+impl HostClock for FakeHostClock {
+    fn now(&self) -> u64 {
+        self.ticks.load(Ordering::SeqCst)
+    }
+    fn timebase(&self) -> Timebase {
+        self.timebase
+    }
+}
@@ -1,6 +1,13 @@
 pub mod attachments;
 pub mod constants;
+pub mod convert;
+pub mod cv_display_link;
+#[cfg(target_vendor = "apple")]
+pub mod cv_image_buffer;
 pub mod cv_pixel_buffer;
+pub mod cv_pixel_buffer_pool;
+pub mod dictionary;
+pub mod host_time;
+pub mod scale;
+pub mod transform;
 pub mod types;
-pub mod cv_image_buffer;
-
@@ -0,0 +1,159 @@
+use std::f32::consts::PI;
+
+/// Resampling filter used by [`scale`](super::scale).
+///
+/// Except for `Nearest`, filters widen with the downscale factor so that every source sample
+/// contributes to the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScaleFilter {
+    Nearest,
+    #[default]
+    Bilinear,
+    /// Catmull-Rom cubic.
+    Bicubic,
+    /// Lanczos windowed sinc with three lobes.
+    Lanczos3,
+    /// Exact area coverage. Keeps thin features such as text legible at large downscale
+    /// factors.
+    Area,
+}
+
+/// Contributions of consecutive source samples, starting at `start`, to one output sample.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Taps {
+    pub start: usize,
+    pub weights: Vec<f32>,
+}
+
+/// Maps output positions to source positions along one axis.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AxisMapping {
+    /// Source length divided by output length; the same for full-resolution and subsampled
+    /// planes.
+    pub ratio: f32,
+    /// Subsampling factor of the plane along this axis.
+    pub factor: usize,
+    /// Position of subsampled samples relative to the first full-resolution sample of their
+    /// group, in full-resolution samples.
+    pub offset: f32,
+}
+
+impl AxisMapping {
+    pub fn new(src_len: usize, dst_len: usize) -> Self {
+        Self {
+            ratio: src_len as f32 / dst_len.max(1) as f32,
+            factor: 1,
+            offset: 0.0,
+        }
+    }
+    /// Source position of output sample `i`, in source plane samples.
+    fn center(&self, i: usize) -> f32 {
+        let factor = self.factor as f32;
+        let position = factor * i as f32 + self.offset;
+        ((position + 0.5) * self.ratio - 0.5 - self.offset) / factor
+    }
+}
+
+impl ScaleFilter {
+    fn radius(&self) -> f32 {
+        match self {
+            Self::Nearest | Self::Area => 0.0,
+            Self::Bilinear => 1.0,
+            Self::Bicubic => 2.0,
+            Self::Lanczos3 => 3.0,
+        }
+    }
+    fn kernel(&self, x: f32) -> f32 {
+        let x = x.abs();
+        match self {
+            Self::Bilinear => (1.0 - x).max(0.0),
+            Self::Bicubic => {
+                const A: f32 = -0.5;
+                if x < 1.0 {
+                    ((A + 2.0) * x - (A + 3.0)) * x * x + 1.0
+                } else if x < 2.0 {
+                    ((A * x - 5.0 * A) * x + 8.0 * A) * x - 4.0 * A
+                } else {
+                    0.0
+                }
+            }
+            Self::Lanczos3 => {
+                if x < 3.0 {
+                    sinc(x) * sinc(x / 3.0)
+                } else {
+                    0.0
+                }
+            }
+            Self::Nearest | Self::Area => 0.0,
+        }
+    }
+
+    /// Computes the taps for each of `dst_len` output samples. Taps falling outside the
+    /// source are folded onto the nearest edge sample.
+    pub(crate) fn taps(&self, src_len: usize, dst_len: usize, mapping: AxisMapping) -> Vec<Taps> {
+        let last = src_len as isize - 1;
+        let clamp = |j: isize| j.clamp(0, last) as usize;
+        (0..dst_len)
+            .map(|i| {
+                let center = mapping.center(i);
+                let contributions: Vec<(usize, f32)> = match self {
+                    Self::Nearest => vec![(clamp((center + 0.5).floor() as isize), 1.0)],
+                    Self::Area => {
+                        // Output sample covers [lo, hi) in pixel edge coordinates.
+                        let half = mapping.ratio / 2.0;
+                        let (lo, hi) = (center + 0.5 - half, center + 0.5 + half);
+                        (lo.floor() as isize..hi.ceil() as isize)
+                            .map(|j| {
+                                let overlap = hi.min(j as f32 + 1.0) - lo.max(j as f32);
+                                (clamp(j), overlap.max(0.0))
+                            })
+                            .collect()
+                    }
+                    _ => {
+                        let stretch = mapping.ratio.max(1.0);
+                        let support = self.radius() * stretch;
+                        let lo = (center - support).ceil() as isize;
+                        let hi = (center + support).floor() as isize;
+                        (lo..=hi)
+                            .map(|j| (clamp(j), self.kernel((j as f32 - center) / stretch)))
+                            .collect()
+                    }
+                };
+                normalize(contributions, clamp(center.round() as isize))
+            })
+            .collect()
+    }
+}
+
+fn normalize(contributions: Vec<(usize, f32)>, fallback: usize) -> Taps {
+    let sum: f32 = contributions.iter().map(|(_, weight)| weight).sum();
+    if contributions.is_empty() || sum.abs() < f32::EPSILON {
+        return Taps {
+            start: fallback,
+            weights: vec![1.0],
+        };
+    }
+    let start = contributions
+        .iter()
+        .map(|(j, _)| *j)
+        .min()
+        .unwrap_or(fallback);
+    let end = contributions
+        .iter()
+        .map(|(j, _)| *j)
+        .max()
+        .unwrap_or(fallback);
+    let mut weights = vec![0.0; end - start + 1];
+    for (j, weight) in contributions {
+        weights[j - start] += weight / sum;
+    }
+    Taps { start, weights }
+}
+
+fn sinc(x: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
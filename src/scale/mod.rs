@@ -0,0 +1,292 @@
+//! Software scaling of pixel buffers.
+//!
+//! Each plane is resampled separately. Y'CbCr formats go through
+//! [`PlanarYCbCr16`](crate::convert::PlanarYCbCr16), so chroma planes are resampled at their
+//! own resolution with their sample positions taken from the chroma siting.
+
+mod filter;
+mod resample;
+
+pub use filter::ScaleFilter;
+
+use filter::AxisMapping;
+use resample::Samples;
+
+use crate::{
+    attachments::{ImageBufferAttachment, ImageBufferAttachmentKey},
+    convert::{self, ChromaSiting, PlanarYCbCr16},
+    cv_pixel_buffer::{
+        error::CVPixelBufferError,
+        pixel_format::PixelFormat,
+        plane::{Plane, PlaneMut},
+        PixelBufferLike,
+    },
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScaleOptions {
+    pub filter: ScaleFilter,
+    /// Defaults to the source `ChromaLocationTopField` attachment, then to
+    /// [`ChromaSiting::Left`].
+    pub chroma_siting: Option<ChromaSiting>,
+}
+
+/// Sample storage of single-plane formats that are scaled in place.
+#[derive(Debug, Clone, Copy)]
+struct Interleaved {
+    channels: usize,
+    bytes_per_sample: usize,
+    big_endian: bool,
+}
+
+impl Interleaved {
+    const fn new(channels: usize, bytes_per_sample: usize, big_endian: bool) -> Self {
+        Self {
+            channels,
+            bytes_per_sample,
+            big_endian,
+        }
+    }
+    fn from_pixel_format(pixel_format: PixelFormat) -> Option<Self> {
+        Some(match pixel_format {
+            PixelFormat::OneComponent8 => Self::new(1, 1, false),
+            PixelFormat::TwoComponent8 => Self::new(2, 1, false),
+            PixelFormat::RGB24 | PixelFormat::BGR24 | PixelFormat::YCbCr444_8 => {
+                Self::new(3, 1, false)
+            }
+            PixelFormat::ARGB32
+            | PixelFormat::BGRA32
+            | PixelFormat::ABGR32
+            | PixelFormat::RGBA32
+            | PixelFormat::AYCbCr4444_8
+            | PixelFormat::YCbCrA4444_8 => Self::new(4, 1, false),
+            PixelFormat::OneComponent16 => Self::new(1, 2, false),
+            PixelFormat::TwoComponent16 => Self::new(2, 2, false),
+            PixelFormat::RGBA64LE => Self::new(4, 2, false),
+            PixelFormat::Gray16 => Self::new(1, 2, true),
+            PixelFormat::AlphaGray32 => Self::new(2, 2, true),
+            PixelFormat::RGB48 => Self::new(3, 2, true),
+            PixelFormat::ARGB64 => Self::new(4, 2, true),
+            _ => return None,
+        })
+    }
+    fn max(&self) -> u16 {
+        if self.bytes_per_sample == 1 {
+            u8::MAX as u16
+        } else {
+            u16::MAX
+        }
+    }
+    fn read(&self, plane: &Plane<'_>) -> Result<Vec<u16>, CVPixelBufferError> {
+        let row_len = plane.width() * self.channels * self.bytes_per_sample;
+        if plane.bytes_per_row() < row_len {
+            return Err(CVPixelBufferError::InvalidSize);
+        }
+        let mut samples = Vec::with_capacity(plane.width() * plane.height() * self.channels);
+        for row in plane.rows() {
+            let bytes = row[..row_len].chunks_exact(self.bytes_per_sample);
+            samples.extend(bytes.map(|bytes| match bytes {
+                [byte] => *byte as u16,
+                [a, b] if self.big_endian => u16::from_be_bytes([*a, *b]),
+                [a, b] => u16::from_le_bytes([*a, *b]),
+                _ => unreachable!(),
+            }));
+        }
+        Ok(samples)
+    }
+    fn write(&self, samples: &[u16], plane: &mut PlaneMut<'_>) -> Result<(), CVPixelBufferError> {
+        let row_len = plane.width() * self.channels * self.bytes_per_sample;
+        if plane.bytes_per_row() < row_len {
+            return Err(CVPixelBufferError::InvalidSize);
+        }
+        let row_samples = plane.width() * self.channels;
+        for (row, samples) in plane
+            .rows_mut()
+            .zip(samples.chunks_exact(row_samples.max(1)))
+        {
+            let bytes = row[..row_len].chunks_exact_mut(self.bytes_per_sample);
+            for (bytes, &sample) in bytes.zip(samples) {
+                match self.bytes_per_sample {
+                    1 => bytes[0] = sample as u8,
+                    _ if self.big_endian => bytes.copy_from_slice(&sample.to_be_bytes()),
+                    _ => bytes.copy_from_slice(&sample.to_le_bytes()),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Scales `src` into `dst`, which must have the same pixel format.
+///
+/// Supports the Y'CbCr formats handled by [`convert::unpack`] and single-plane RGB, gray and
+/// 4:4:4 formats with 8- or 16-bit components. Color attachments are copied from the source.
+pub fn scale<S, D>(src: &S, dst: &mut D) -> Result<(), CVPixelBufferError>
+where
+    S: PixelBufferLike + ?Sized,
+    D: PixelBufferLike + ?Sized,
+{
+    scale_with_options(src, dst, &ScaleOptions::default())
+}
+
+pub fn scale_with_options<S, D>(
+    src: &S,
+    dst: &mut D,
+    options: &ScaleOptions,
+) -> Result<(), CVPixelBufferError>
+where
+    S: PixelBufferLike + ?Sized,
+    D: PixelBufferLike + ?Sized,
+{
+    let pixel_format = src.get_pixel_format()?;
+    if dst.get_pixel_format()? != pixel_format {
+        return Err(CVPixelBufferError::InvalidPixelFormat);
+    }
+    let (width, height) = (dst.get_width(), dst.get_height());
+    if convert::is_ycbcr_format(pixel_format) {
+        let siting = options.chroma_siting.or_else(|| {
+            match src.get_attachment(ImageBufferAttachmentKey::ChromaLocationTopField) {
+                Some((ImageBufferAttachment::ChromaLocationTopField(location), _)) => {
+                    Some(ChromaSiting::from(&location))
+                }
+                _ => None,
+            }
+        });
+        let options = ScaleOptions {
+            chroma_siting: siting,
+            ..options.clone()
+        };
+        let image = scale_image(&convert::unpack(src)?, width, height, &options)?;
+        convert::pack(&image, dst)?;
+    } else {
+        let interleaved = Interleaved::from_pixel_format(pixel_format)
+            .ok_or(CVPixelBufferError::InvalidPixelFormat)?;
+        let src_guard = src.lock()?;
+        let mut dst_guard = dst.lock_mut()?;
+        scale_interleaved(
+            &src_guard.plane(0)?,
+            &mut dst_guard.plane_mut(0)?,
+            interleaved,
+            options.filter,
+        )?;
+    }
+
+    for key in [
+        ImageBufferAttachmentKey::ColorPrimaries,
+        ImageBufferAttachmentKey::TransferFunction,
+        ImageBufferAttachmentKey::YCbCrMatrix,
+        ImageBufferAttachmentKey::ChromaLocationTopField,
+        ImageBufferAttachmentKey::ChromaLocationBottomField,
+    ] {
+        if let Some((attachment, mode)) = src.get_attachment(key) {
+            dst.set_attachment(&attachment, mode);
+        }
+    }
+    Ok(())
+}
+
+/// Scales an 8-bit plane with `channels` interleaved components per pixel to the size of
+/// `dst`.
+pub fn scale_plane(
+    src: &Plane<'_>,
+    dst: &mut PlaneMut<'_>,
+    channels: usize,
+    filter: ScaleFilter,
+) -> Result<(), CVPixelBufferError> {
+    if channels == 0 {
+        return Err(CVPixelBufferError::InvalidArgument);
+    }
+    scale_interleaved(src, dst, Interleaved::new(channels, 1, false), filter)
+}
+
+/// Scales every plane of `image` to `width` x `height`, keeping its chroma format and bit
+/// depth.
+pub fn scale_image(
+    image: &PlanarYCbCr16,
+    width: usize,
+    height: usize,
+    options: &ScaleOptions,
+) -> Result<PlanarYCbCr16, CVPixelBufferError> {
+    image.check()?;
+    if image.width == 0 || image.height == 0 || width == 0 || height == 0 {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    let mut result = PlanarYCbCr16::new(width, height, image.chroma_format, image.bit_depth)?;
+    let max = image.max_value();
+    let (x, y) = (
+        AxisMapping::new(image.width, width),
+        AxisMapping::new(image.height, height),
+    );
+    let (src_size, dst_size) = ((image.width, image.height), (width, height));
+    result.y = resample_plane(&image.y, src_size, dst_size, x, y, options.filter, max);
+    result.alpha = image
+        .alpha
+        .as_deref()
+        .map(|alpha| resample_plane(alpha, src_size, dst_size, x, y, options.filter, max));
+
+    let (h_factor, v_factor) = image.chroma_format.factors();
+    let (h_offset, v_offset) = options.chroma_siting.unwrap_or_default().offsets();
+    let x = AxisMapping {
+        factor: h_factor,
+        offset: if h_factor > 1 { h_offset } else { 0.0 },
+        ..x
+    };
+    let y = AxisMapping {
+        factor: v_factor,
+        offset: if v_factor > 1 { v_offset } else { 0.0 },
+        ..y
+    };
+    let src_chroma = (image.chroma_width(), image.chroma_height());
+    let dst_chroma = (result.chroma_width(), result.chroma_height());
+    result.cb = resample_plane(&image.cb, src_chroma, dst_chroma, x, y, options.filter, max);
+    result.cr = resample_plane(&image.cr, src_chroma, dst_chroma, x, y, options.filter, max);
+    Ok(result)
+}
+
+fn scale_interleaved(
+    src: &Plane<'_>,
+    dst: &mut PlaneMut<'_>,
+    interleaved: Interleaved,
+    filter: ScaleFilter,
+) -> Result<(), CVPixelBufferError> {
+    let (src_width, src_height) = (src.width(), src.height());
+    let (dst_width, dst_height) = (dst.width(), dst.height());
+    if src_width == 0 || src_height == 0 || dst_width == 0 || dst_height == 0 {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    let samples = interleaved.read(src)?;
+    let src = Samples {
+        data: &samples,
+        width: src_width,
+        height: src_height,
+        channels: interleaved.channels,
+    };
+    let x_taps = filter.taps(src_width, dst_width, AxisMapping::new(src_width, dst_width));
+    let y_taps = filter.taps(
+        src_height,
+        dst_height,
+        AxisMapping::new(src_height, dst_height),
+    );
+    let result = resample::resample(&src, &x_taps, &y_taps, interleaved.max());
+    interleaved.write(&result, dst)
+}
+
+fn resample_plane(
+    samples: &[u16],
+    (src_width, src_height): (usize, usize),
+    (dst_width, dst_height): (usize, usize),
+    x: AxisMapping,
+    y: AxisMapping,
+    filter: ScaleFilter,
+    max: u16,
+) -> Vec<u16> {
+    let src = Samples {
+        data: samples,
+        width: src_width,
+        height: src_height,
+        channels: 1,
+    };
+    let x_taps = filter.taps(src_width, dst_width, x);
+    let y_taps = filter.taps(src_height, dst_height, y);
+    resample::resample(&src, &x_taps, &y_taps, max)
+}
@@ -0,0 +1,49 @@
+use super::filter::Taps;
+
+/// Interleaved samples of one plane, `channels` per pixel, rows tightly packed.
+pub(crate) struct Samples<'a> {
+    pub data: &'a [u16],
+    pub width: usize,
+    pub height: usize,
+    pub channels: usize,
+}
+
+/// Separable resampling: horizontal pass into a floating point buffer, then vertical pass
+/// with rounding and clamping to `0..=max`.
+pub(crate) fn resample(src: &Samples<'_>, x_taps: &[Taps], y_taps: &[Taps], max: u16) -> Vec<u16> {
+    let channels = src.channels;
+    let (dst_width, dst_height) = (x_taps.len(), y_taps.len());
+    let mut horizontal = vec![0f32; src.height * dst_width * channels];
+    for y in 0..src.height {
+        let row = &src.data[y * src.width * channels..(y + 1) * src.width * channels];
+        let out = &mut horizontal[y * dst_width * channels..(y + 1) * dst_width * channels];
+        for (x, taps) in x_taps.iter().enumerate() {
+            for (i, weight) in taps.weights.iter().enumerate() {
+                let pixel = &row[(taps.start + i) * channels..(taps.start + i + 1) * channels];
+                for (c, &sample) in pixel.iter().enumerate() {
+                    out[x * channels + c] += weight * sample as f32;
+                }
+            }
+        }
+    }
+
+    let row_len = dst_width * channels;
+    let mut result = vec![0u16; dst_height * row_len];
+    let mut accumulator = vec![0f32; row_len];
+    for (y, taps) in y_taps.iter().enumerate() {
+        accumulator.fill(0.0);
+        for (i, weight) in taps.weights.iter().enumerate() {
+            let row = &horizontal[(taps.start + i) * row_len..(taps.start + i + 1) * row_len];
+            for (total, &value) in accumulator.iter_mut().zip(row) {
+                *total += weight * value;
+            }
+        }
+        for (out, &value) in result[y * row_len..(y + 1) * row_len]
+            .iter_mut()
+            .zip(&accumulator)
+        {
+            *out = value.round().clamp(0.0, max as f32) as u16;
+        }
+    }
+    result
+}
@@ -0,0 +1,329 @@
+//! Rotation and mirroring of pixel buffers.
+//!
+//! Planes whose samples are whole bytes are moved sample by sample. Packed 4:2:2 formats and
+//! 4:2:2 chroma planes that would be transposed go through
+//! [`PlanarYCbCr16`](crate::convert::PlanarYCbCr16), since their chroma cannot be rotated
+//! without resampling.
+
+mod orientation;
+mod tile;
+
+pub use orientation::Orientation;
+
+use orientation::Mapping;
+
+use crate::{
+    attachments::{
+        CleanAperture, DisplayDimensions, ImageBufferAttachment, ImageBufferAttachmentKey,
+        PixelAspectRatio,
+    },
+    constants::{ChromaLocation, FieldDetail},
+    convert::{self, ChromaFormat, ChromaSiting, PlanarYCbCr16},
+    cv_pixel_buffer::{
+        error::CVPixelBufferError,
+        pixel_format::PixelFormat,
+        plane::{Plane, PlaneMut},
+        PixelBufferLike,
+    },
+};
+
+/// Writes `src`, stored with `orientation`, upright into `dst`.
+///
+/// `dst` must have the same pixel format and the size given by
+/// [`Orientation::transformed_size`]. Attachments are copied from the source, with clean
+/// aperture, pixel aspect ratio, display dimensions, chroma location and field detail
+/// adjusted to the new orientation; values that cannot be expressed are dropped. A
+/// [`PixelBufferView`](crate::cv_pixel_buffer::PixelBufferView) source supplies its
+/// geometry relative to the view, and geometry `src` lacks is removed from `dst`.
+pub fn transform<S, D>(
+    src: &S,
+    dst: &mut D,
+    orientation: Orientation,
+) -> Result<(), CVPixelBufferError>
+where
+    S: PixelBufferLike + ?Sized,
+    D: PixelBufferLike + ?Sized,
+{
+    let pixel_format = src.get_pixel_format()?;
+    if dst.get_pixel_format()? != pixel_format {
+        return Err(CVPixelBufferError::InvalidPixelFormat);
+    }
+    if (dst.get_width(), dst.get_height())
+        != orientation.transformed_size(src.get_width(), src.get_height())
+    {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    let layout = pixel_format
+        .layout()
+        .filter(|_| !is_bayer(pixel_format))
+        .ok_or(CVPixelBufferError::InvalidPixelFormat)?;
+    let mapping = orientation.mapping();
+    let by_sample = layout.planes.iter().all(|plane| {
+        plane.block_width == 1
+            && (!mapping.transpose || plane.horizontal_subsampling == plane.vertical_subsampling)
+    });
+    if by_sample {
+        let src_guard = src.lock()?;
+        let src_planes = src_guard.planes()?;
+        let mut dst_guard = dst.lock_mut()?;
+        let mut dst_planes = dst_guard.planes_mut()?;
+        if src_planes.len() != layout.planes.len() || dst_planes.len() != layout.planes.len() {
+            return Err(CVPixelBufferError::InvalidSize);
+        }
+        for ((src_plane, dst_plane), format) in src_planes
+            .iter()
+            .zip(dst_planes.iter_mut())
+            .zip(layout.planes)
+        {
+            transform_plane(src_plane, dst_plane, format.bytes_per_block, orientation)?;
+        }
+    } else if convert::is_ycbcr_format(pixel_format) {
+        let image = transform_image(&convert::unpack(src)?, orientation)?;
+        convert::pack(&image, dst)?;
+    } else {
+        return Err(CVPixelBufferError::InvalidPixelFormat);
+    }
+
+    for &key in ImageBufferAttachmentKey::ALL {
+        match src
+            .get_attachment(key)
+            .and_then(|(attachment, mode)| Some((transform_attachment(attachment, mapping)?, mode)))
+        {
+            Some((attachment, mode)) => dst.set_attachment(&attachment, mode),
+            // A geometry attachment already on `dst` would describe another image.
+            None if is_geometry_key(key) => dst.remove_attachment(key),
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Transforms a plane of `bytes_per_sample`-byte samples into `dst`, whose size must be
+/// the transformed size of `src`.
+pub fn transform_plane(
+    src: &Plane<'_>,
+    dst: &mut PlaneMut<'_>,
+    bytes_per_sample: usize,
+    orientation: Orientation,
+) -> Result<(), CVPixelBufferError> {
+    let (src_width, src_height) = (src.width(), src.height());
+    let dst_size = (dst.width(), dst.height());
+    if dst_size != orientation.transformed_size(src_width, src_height)
+        || src.bytes_per_row() < src_width * bytes_per_sample
+        || dst.bytes_per_row() < dst_size.0 * bytes_per_sample
+    {
+        return Err(CVPixelBufferError::InvalidSize);
+    }
+    let mapping = orientation.mapping();
+    let src_size = (src_width, src_height);
+    match bytes_per_sample {
+        0 => return Err(CVPixelBufferError::InvalidArgument),
+        1 => copy_samples::<1>(src, dst, mapping, src_size, dst_size),
+        2 => copy_samples::<2>(src, dst, mapping, src_size, dst_size),
+        4 => copy_samples::<4>(src, dst, mapping, src_size, dst_size),
+        8 => copy_samples::<8>(src, dst, mapping, src_size, dst_size),
+        _ => {
+            let (src_stride, dst_stride) = (src.bytes_per_row(), dst.bytes_per_row());
+            let (src_data, dst_data) = (src.as_slice(), dst.as_mut_slice());
+            tile::for_each_sample(mapping, dst_size, src_size, |x, y, src_x, src_y| {
+                let from = src_y * src_stride + src_x * bytes_per_sample;
+                let to = y * dst_stride + x * bytes_per_sample;
+                dst_data[to..to + bytes_per_sample]
+                    .copy_from_slice(&src_data[from..from + bytes_per_sample]);
+            });
+        }
+    }
+    Ok(())
+}
+
+fn copy_samples<const N: usize>(
+    src: &Plane<'_>,
+    dst: &mut PlaneMut<'_>,
+    mapping: Mapping,
+    src_size: (usize, usize),
+    dst_size: (usize, usize),
+) {
+    let (src_stride, dst_stride) = (src.bytes_per_row(), dst.bytes_per_row());
+    let (src_data, dst_data) = (src.as_slice(), dst.as_mut_slice());
+    tile::for_each_sample(mapping, dst_size, src_size, |x, y, src_x, src_y| {
+        let from = src_y * src_stride + src_x * N;
+        let to = y * dst_stride + x * N;
+        dst_data[to..to + N].copy_from_slice(&src_data[from..from + N]);
+    });
+}
+
+/// Transforms every plane of `image`. 4:2:2 images that swap dimensions are upsampled to
+/// 4:4:4 and back, as their chroma would otherwise end up subsampled vertically.
+pub fn transform_image(
+    image: &PlanarYCbCr16,
+    orientation: Orientation,
+) -> Result<PlanarYCbCr16, CVPixelBufferError> {
+    image.check()?;
+    if orientation.swaps_dimensions() && image.chroma_format == ChromaFormat::Yuv422 {
+        let full = image.with_chroma_format(ChromaFormat::Yuv444);
+        let transformed = transform_image(&full, orientation)?;
+        return Ok(transformed.with_chroma_format(ChromaFormat::Yuv422));
+    }
+    let (width, height) = orientation.transformed_size(image.width, image.height);
+    let mut result = PlanarYCbCr16::new(width, height, image.chroma_format, image.bit_depth)?;
+    let mapping = orientation.mapping();
+    let luma = ((image.width, image.height), (width, height));
+    let chroma = (
+        (image.chroma_width(), image.chroma_height()),
+        (result.chroma_width(), result.chroma_height()),
+    );
+    transform_samples(&image.y, &mut result.y, mapping, luma);
+    transform_samples(&image.cb, &mut result.cb, mapping, chroma);
+    transform_samples(&image.cr, &mut result.cr, mapping, chroma);
+    result.alpha = image.alpha.as_ref().map(|alpha| {
+        let mut transformed = vec![0; alpha.len()];
+        transform_samples(alpha, &mut transformed, mapping, luma);
+        transformed
+    });
+    Ok(result)
+}
+
+fn transform_samples(
+    src: &[u16],
+    dst: &mut [u16],
+    mapping: Mapping,
+    (src_size, dst_size): ((usize, usize), (usize, usize)),
+) {
+    tile::for_each_sample(mapping, dst_size, src_size, |x, y, src_x, src_y| {
+        dst[y * dst_size.0 + x] = src[src_y * src_size.0 + src_x];
+    });
+}
+
+fn is_bayer(pixel_format: PixelFormat) -> bool {
+    matches!(
+        pixel_format,
+        PixelFormat::Bayer14GRBG
+            | PixelFormat::Bayer14RGGB
+            | PixelFormat::Bayer14BGGR
+            | PixelFormat::Bayer14GBRG
+            | PixelFormat::VersatileBayer16
+    )
+}
+
+fn is_geometry_key(key: ImageBufferAttachmentKey) -> bool {
+    matches!(
+        key,
+        ImageBufferAttachmentKey::CleanAperture
+            | ImageBufferAttachmentKey::PreferredCleanAperture
+            | ImageBufferAttachmentKey::DisplayDimensions
+    )
+}
+
+/// Adjusts a source attachment to the transformed image, or drops it.
+fn transform_attachment(
+    attachment: ImageBufferAttachment,
+    mapping: Mapping,
+) -> Option<ImageBufferAttachment> {
+    Some(match attachment {
+        ImageBufferAttachment::CleanAperture(aperture) => {
+            ImageBufferAttachment::CleanAperture(transform_clean_aperture(aperture, mapping))
+        }
+        ImageBufferAttachment::PreferredCleanAperture(aperture) => {
+            ImageBufferAttachment::PreferredCleanAperture(transform_clean_aperture(
+                aperture, mapping,
+            ))
+        }
+        ImageBufferAttachment::PixelAspectRatio(ratio) if mapping.transpose => {
+            ImageBufferAttachment::PixelAspectRatio(PixelAspectRatio {
+                horizontal_spacing: ratio.vertical_spacing,
+                vertical_spacing: ratio.horizontal_spacing,
+            })
+        }
+        ImageBufferAttachment::DisplayDimensions(dimensions) if mapping.transpose => {
+            ImageBufferAttachment::DisplayDimensions(DisplayDimensions {
+                width: dimensions.height,
+                height: dimensions.width,
+            })
+        }
+        ImageBufferAttachment::ChromaLocationTopField(location) => {
+            ImageBufferAttachment::ChromaLocationTopField(transform_chroma_location(
+                location, mapping,
+            )?)
+        }
+        ImageBufferAttachment::ChromaLocationBottomField(location) => {
+            ImageBufferAttachment::ChromaLocationBottomField(transform_chroma_location(
+                location, mapping,
+            )?)
+        }
+        ImageBufferAttachment::FieldCount(_) | ImageBufferAttachment::FieldDetail(_)
+            if mapping.transpose =>
+        {
+            return None
+        }
+        ImageBufferAttachment::FieldDetail(detail) if mapping.flip_y => {
+            ImageBufferAttachment::FieldDetail(match detail {
+                FieldDetail::TemporalTopFirst => FieldDetail::TemporalBottomFirst,
+                FieldDetail::TemporalBottomFirst => FieldDetail::TemporalTopFirst,
+                FieldDetail::SpatialFirstLineEarly => FieldDetail::SpatialFirstLineLate,
+                FieldDetail::SpatialFirstLineLate => FieldDetail::SpatialFirstLineEarly,
+                _ => return None,
+            })
+        }
+        other => other,
+    })
+}
+
+fn transform_clean_aperture(aperture: CleanAperture, mapping: Mapping) -> CleanAperture {
+    let horizontal = if mapping.flip_x {
+        -aperture.horizontal_offset
+    } else {
+        aperture.horizontal_offset
+    };
+    let vertical = if mapping.flip_y {
+        -aperture.vertical_offset
+    } else {
+        aperture.vertical_offset
+    };
+    if mapping.transpose {
+        CleanAperture {
+            width: aperture.height,
+            height: aperture.width,
+            horizontal_offset: vertical,
+            vertical_offset: horizontal,
+        }
+    } else {
+        CleanAperture {
+            horizontal_offset: horizontal,
+            vertical_offset: vertical,
+            ..aperture
+        }
+    }
+}
+
+/// Moves the chroma sample position with the image; `None` if the new position, e.g. to the
+/// right of its luma samples, has no `ChromaLocation`.
+fn transform_chroma_location(location: ChromaLocation, mapping: Mapping) -> Option<ChromaLocation> {
+    if !matches!(
+        location,
+        ChromaLocation::Left
+            | ChromaLocation::Center
+            | ChromaLocation::TopLeft
+            | ChromaLocation::Top
+            | ChromaLocation::BottomLeft
+            | ChromaLocation::Bottom
+    ) {
+        return None;
+    }
+    let (horizontal, vertical) = ChromaSiting::from(&location).offsets();
+    let horizontal = if mapping.flip_x {
+        1.0 - horizontal
+    } else {
+        horizontal
+    };
+    let vertical = if mapping.flip_y {
+        1.0 - vertical
+    } else {
+        vertical
+    };
+    let (horizontal, vertical) = if mapping.transpose {
+        (vertical, horizontal)
+    } else {
+        (horizontal, vertical)
+    };
+    ChromaSiting::from_offsets(horizontal, vertical).map(ChromaLocation::from)
+}
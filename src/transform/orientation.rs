@@ -0,0 +1,101 @@
+use crate::cv_pixel_buffer::error::CVPixelBufferError;
+
+/// How stored image data is oriented relative to its intended display orientation, with the
+/// values of `CGImagePropertyOrientation` and the EXIF `Orientation` tag.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// Stored upright.
+    #[default]
+    Up = 1,
+    /// Mirrored horizontally.
+    UpMirrored = 2,
+    /// Rotated 180°.
+    Down = 3,
+    /// Mirrored vertically.
+    DownMirrored = 4,
+    /// Mirrored along the main diagonal (transposed).
+    LeftMirrored = 5,
+    /// Rotated 90° counterclockwise; displaying it rotates 90° clockwise.
+    Right = 6,
+    /// Mirrored along the anti-diagonal.
+    RightMirrored = 7,
+    /// Rotated 90° clockwise; displaying it rotates 90° counterclockwise.
+    Left = 8,
+}
+
+/// Source position of a destination sample: coordinates are swapped first, then mirrored.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Mapping {
+    pub transpose: bool,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 8] = [
+        Self::Up,
+        Self::UpMirrored,
+        Self::Down,
+        Self::DownMirrored,
+        Self::LeftMirrored,
+        Self::Right,
+        Self::RightMirrored,
+        Self::Left,
+    ];
+
+    /// Whether displaying the image swaps its width and height.
+    pub fn swaps_dimensions(self) -> bool {
+        self.mapping().transpose
+    }
+    /// Size of the upright image for a stored `width` x `height` image.
+    pub fn transformed_size(self, width: usize, height: usize) -> (usize, usize) {
+        if self.swaps_dimensions() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+    /// The orientation that undoes this one.
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::Right => Self::Left,
+            Self::Left => Self::Right,
+            other => other,
+        }
+    }
+    pub(crate) fn mapping(self) -> Mapping {
+        let (transpose, flip_x, flip_y) = match self {
+            Self::Up => (false, false, false),
+            Self::UpMirrored => (false, true, false),
+            Self::Down => (false, true, true),
+            Self::DownMirrored => (false, false, true),
+            Self::LeftMirrored => (true, false, false),
+            Self::Right => (true, false, true),
+            Self::RightMirrored => (true, true, true),
+            Self::Left => (true, true, false),
+        };
+        Mapping {
+            transpose,
+            flip_x,
+            flip_y,
+        }
+    }
+}
+
+impl TryFrom<u32> for Orientation {
+    type Error = CVPixelBufferError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|&orientation| orientation as u32 == value)
+            .ok_or(CVPixelBufferError::InvalidArgument)
+    }
+}
+
+impl From<Orientation> for u32 {
+    fn from(value: Orientation) -> Self {
+        value as u32
+    }
+}
@@ -0,0 +1,44 @@
+use super::orientation::Mapping;
+
+/// Side of the square tiles destinations are written in. A 64x64 tile of 4-byte pixels reads
+/// 64 source rows of 256 bytes each, which stays in L1 while the tile is transposed.
+const TILE: usize = 64;
+
+/// Calls `f(dst_x, dst_y, src_x, src_y)` for every sample of a `dst_width` x `dst_height`
+/// destination, tile by tile so that transposing reads stay cache-friendly.
+pub(crate) fn for_each_sample(
+    mapping: Mapping,
+    (dst_width, dst_height): (usize, usize),
+    (src_width, src_height): (usize, usize),
+    mut f: impl FnMut(usize, usize, usize, usize),
+) {
+    let source = |u: usize, v: usize| {
+        let x = if mapping.flip_x { src_width - 1 - u } else { u };
+        let y = if mapping.flip_y {
+            src_height - 1 - v
+        } else {
+            v
+        };
+        (x, y)
+    };
+    if !mapping.transpose {
+        // Rows map to rows, so plain row order is already sequential on both sides.
+        for y in 0..dst_height {
+            for x in 0..dst_width {
+                let (src_x, src_y) = source(x, y);
+                f(x, y, src_x, src_y);
+            }
+        }
+        return;
+    }
+    for tile_y in (0..dst_height).step_by(TILE) {
+        for tile_x in (0..dst_width).step_by(TILE) {
+            for y in tile_y..(tile_y + TILE).min(dst_height) {
+                for x in tile_x..(tile_x + TILE).min(dst_width) {
+                    let (src_x, src_y) = source(y, x);
+                    f(x, y, src_x, src_y);
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,77 @@
 pub type CVReturn = i32;
 pub type CVOptionFlags = u64;
 pub type OSType = u32;
+
+/// Declares a transparent flag set over `$bits` with the given flags. Unknown bits are
+/// kept, so values read from CoreVideo round-trip unchanged.
+macro_rules! flag_set {
+    (
+        $(#[$meta:meta])*
+        $name:ident($bits:ty) {
+            $($(#[$flag_meta:meta])* $flag:ident = $value:expr;)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name(pub $bits);
+
+        impl $name {
+            $($(#[$flag_meta])* pub const $flag: Self = Self($value);)*
+
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+            pub const fn bits(self) -> $bits {
+                self.0
+            }
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+            pub const fn intersects(self, other: Self) -> bool {
+                self.0 & other.0 != 0
+            }
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+        }
+
+        impl std::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+        }
+
+        impl std::ops::BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, other: Self) -> Self {
+                Self(self.0 & other.0)
+            }
+        }
+
+        impl std::ops::Not for $name {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                Self(!self.0)
+            }
+        }
+    };
+}
+
+mod rational;
+mod smpte;
+mod time;
+
+pub use rational::Rational;
+pub use smpte::{CVSMPTETime, CVSMPTETimeFlags, CVSMPTETimeType};
+pub use time::{CVTime, CVTimeFlags, CVTimeStamp, CVTimeStampFlags};
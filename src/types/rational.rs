@@ -0,0 +1,153 @@
+use std::{cmp::Ordering, ops::Neg};
+
+/// A signed fraction with 32-bit terms, as CoreVideo stores clean aperture values that are
+/// not whole numbers.
+///
+/// Comparison is by value, so `1/2 == 2/4`. A zero denominator orders as an infinity with
+/// the numerator's sign, and `0/0` after every other value.
+#[derive(Debug, Clone, Copy)]
+pub struct Rational {
+    pub numerator: i32,
+    pub denominator: i32,
+}
+
+impl Rational {
+    pub const fn new(numerator: i32, denominator: i32) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+    pub const fn integer(value: i32) -> Self {
+        Self::new(value, 1)
+    }
+    /// Closest fraction with 32-bit terms, by continued fraction expansion. Values that are
+    /// not finite map to zero.
+    pub fn from_f64(value: f64) -> Self {
+        if !value.is_finite() {
+            return Self::integer(0);
+        }
+        let limit = i32::MAX as i64;
+        let (mut numerator, mut previous_numerator) = (1i64, 0i64);
+        let (mut denominator, mut previous_denominator) = (0i64, 1i64);
+        let mut x = value;
+        for _ in 0..64 {
+            let term = x.floor();
+            if term.abs() > limit as f64 {
+                break;
+            }
+            let term = term as i64;
+            let next_numerator = term * numerator + previous_numerator;
+            let next_denominator = term * denominator + previous_denominator;
+            if next_numerator.abs() > limit || next_denominator > limit {
+                break;
+            }
+            (previous_numerator, numerator) = (numerator, next_numerator);
+            (previous_denominator, denominator) = (denominator, next_denominator);
+            let fraction = x - term as f64;
+            if fraction.abs() < 1e-12 {
+                break;
+            }
+            x = 1.0 / fraction;
+        }
+        if denominator == 0 {
+            return Self::integer(value.clamp(i32::MIN as f64, i32::MAX as f64) as i32);
+        }
+        Self::new(numerator as i32, denominator as i32)
+    }
+    pub fn to_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+    /// Lowest terms with a positive denominator. Terms that can't be written that way in 32
+    /// bits, such as `1/i32::MIN`, are returned unchanged.
+    pub fn reduced(self) -> Self {
+        let (mut a, mut b) = (
+            (self.numerator as i64).abs(),
+            (self.denominator as i64).abs(),
+        );
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        let sign = if self.denominator < 0 { -1 } else { 1 };
+        if a == 0 {
+            return self;
+        }
+        match (
+            i32::try_from(sign * self.numerator as i64 / a),
+            i32::try_from(sign * self.denominator as i64 / a),
+        ) {
+            (Ok(numerator), Ok(denominator)) => Self::new(numerator, denominator),
+            _ => self,
+        }
+    }
+    pub fn is_integer(self) -> bool {
+        self.denominator != 0 && self.numerator as i64 % self.denominator as i64 == 0
+    }
+    /// 0 below every finite value, 1 for finite values, 2 above them and 3 for `0/0`.
+    fn rank(self) -> u8 {
+        match (self.denominator, self.numerator.signum()) {
+            (0, -1) => 0,
+            (0, 1) => 2,
+            (0, _) => 3,
+            _ => 1,
+        }
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Self::integer(0)
+    }
+}
+
+impl From<i32> for Rational {
+    fn from(value: i32) -> Self {
+        Self::integer(value)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    /// Exact for every value: a numerator of `i32::MIN` moves the sign to the denominator.
+    fn neg(self) -> Self {
+        match (self.numerator.checked_neg(), self.denominator.checked_neg()) {
+            (Some(numerator), _) => Self::new(numerator, self.denominator),
+            (None, Some(denominator)) => Self::new(self.numerator, denominator),
+            // i32::MIN / i32::MIN
+            (None, None) => Self::integer(-1),
+        }
+    }
+}
+
+impl PartialEq for Rational {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Rational {}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (*self, *other);
+        match (a.rank(), b.rank()) {
+            (1, 1) => {
+                let ordering = (a.numerator as i64 * b.denominator as i64)
+                    .cmp(&(b.numerator as i64 * a.denominator as i64));
+                if (a.denominator < 0) != (b.denominator < 0) {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            }
+            (a, b) => a.cmp(&b),
+        }
+    }
+}
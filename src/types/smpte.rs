@@ -0,0 +1,220 @@
+use std::fmt;
+
+use crate::cv_pixel_buffer::error::CVPixelBufferError;
+
+/// Frame rate and counting mode of a [`CVSMPTETime`].
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CVSMPTETimeType {
+    Type24 = 0,
+    Type25 = 1,
+    Type30Drop = 2,
+    Type30 = 3,
+    Type2997 = 4,
+    Type2997Drop = 5,
+    Type60 = 6,
+    Type5994 = 7,
+    Type60Drop = 8,
+    Type5994Drop = 9,
+    Type50 = 10,
+    Type2398 = 11,
+}
+
+impl CVSMPTETimeType {
+    pub const ALL: [CVSMPTETimeType; 12] = [
+        Self::Type24,
+        Self::Type25,
+        Self::Type30Drop,
+        Self::Type30,
+        Self::Type2997,
+        Self::Type2997Drop,
+        Self::Type60,
+        Self::Type5994,
+        Self::Type60Drop,
+        Self::Type5994Drop,
+        Self::Type50,
+        Self::Type2398,
+    ];
+
+    /// Frames counted per timecode second, e.g. 30 for 29.97.
+    pub fn frames_per_second(self) -> u32 {
+        match self {
+            Self::Type24 | Self::Type2398 => 24,
+            Self::Type25 => 25,
+            Self::Type30Drop | Self::Type30 | Self::Type2997 | Self::Type2997Drop => 30,
+            Self::Type50 => 50,
+            Self::Type60 | Self::Type5994 | Self::Type60Drop | Self::Type5994Drop => 60,
+        }
+    }
+    /// Actual frame rate as frames per `(numerator / denominator)` seconds, e.g. 30000/1001.
+    pub fn frame_rate(self) -> (u32, u32) {
+        match self {
+            Self::Type2398
+            | Self::Type2997
+            | Self::Type2997Drop
+            | Self::Type5994
+            | Self::Type5994Drop => (self.frames_per_second() * 1000, 1001),
+            _ => (self.frames_per_second(), 1),
+        }
+    }
+    /// Whether frame numbers are skipped to keep timecode in step with wall clock time.
+    pub fn is_drop_frame(self) -> bool {
+        matches!(
+            self,
+            Self::Type30Drop | Self::Type2997Drop | Self::Type60Drop | Self::Type5994Drop
+        )
+    }
+    /// Frame numbers skipped at the start of each minute not divisible by ten.
+    fn dropped_frames(self) -> u64 {
+        if self.is_drop_frame() {
+            self.frames_per_second() as u64 / 15
+        } else {
+            0
+        }
+    }
+}
+
+impl TryFrom<u32> for CVSMPTETimeType {
+    type Error = CVPixelBufferError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|&time_type| time_type as u32 == value)
+            .ok_or(CVPixelBufferError::InvalidArgument)
+    }
+}
+
+flag_set! {
+    /// State of a [`CVSMPTETime`].
+    CVSMPTETimeFlags(u32) {
+        VALID = 1 << 0;
+        RUNNING = 1 << 1;
+    }
+}
+
+/// SMPTE timecode as CoreVideo reports it.
+///
+/// Formats as `HH:MM:SS:FF`, or `HH:MM:SS;FF` for drop-frame types.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CVSMPTETime {
+    pub subframes: i16,
+    pub subframe_divisor: i16,
+    pub counter: u32,
+    /// A [`CVSMPTETimeType`] value; see [`CVSMPTETime::get_time_type`].
+    pub time_type: u32,
+    pub flags: CVSMPTETimeFlags,
+    pub hours: i16,
+    pub minutes: i16,
+    pub seconds: i16,
+    pub frames: i16,
+}
+
+impl CVSMPTETime {
+    /// Timecode of the frame `frame_count` frames after midnight, wrapping at 24 hours.
+    pub fn from_frame_count(frame_count: u64, time_type: CVSMPTETimeType) -> Self {
+        let fps = time_type.frames_per_second() as u64;
+        let drop = time_type.dropped_frames();
+        let mut frame_count = frame_count % frames_per_day(time_type);
+        if drop > 0 {
+            let per_ten_minutes = fps * 600 - drop * 9;
+            let per_minute = fps * 60 - drop;
+            let (tens, remainder) = (frame_count / per_ten_minutes, frame_count % per_ten_minutes);
+            frame_count += drop * 9 * tens;
+            if remainder > drop {
+                frame_count += drop * ((remainder - drop) / per_minute);
+            }
+        }
+        Self {
+            time_type: time_type as u32,
+            flags: CVSMPTETimeFlags::VALID,
+            hours: (frame_count / (fps * 3600)) as i16,
+            minutes: (frame_count / (fps * 60) % 60) as i16,
+            seconds: (frame_count / fps % 60) as i16,
+            frames: (frame_count % fps) as i16,
+            ..Default::default()
+        }
+    }
+    /// Parses `HH:MM:SS:FF`; the last separator may also be `;` or `.`.
+    ///
+    /// Fails with `InvalidArgument` for malformed strings, fields out of range and frame
+    /// numbers a drop-frame type skips.
+    pub fn parse(timecode: &str, time_type: CVSMPTETimeType) -> Result<Self, CVPixelBufferError> {
+        let fields: Vec<&str> = timecode.trim().split([':', ';', '.']).collect();
+        let [hours, minutes, seconds, frames] = fields[..] else {
+            return Err(CVPixelBufferError::InvalidArgument);
+        };
+        let field = |value: &str, limit: u32| {
+            Some(value)
+                .filter(|value| !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|value| value.parse::<u32>().ok())
+                .filter(|&value| value < limit)
+                .ok_or(CVPixelBufferError::InvalidArgument)
+        };
+        let time = Self {
+            time_type: time_type as u32,
+            flags: CVSMPTETimeFlags::VALID,
+            hours: field(hours, 24)? as i16,
+            minutes: field(minutes, 60)? as i16,
+            seconds: field(seconds, 60)? as i16,
+            frames: field(frames, time_type.frames_per_second())? as i16,
+            ..Default::default()
+        };
+        if time.is_dropped(time_type) {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        Ok(time)
+    }
+    /// Whether this is a frame number `time_type` skips.
+    fn is_dropped(&self, time_type: CVSMPTETimeType) -> bool {
+        self.seconds == 0
+            && self.minutes % 10 != 0
+            && (self.frames as u64) < time_type.dropped_frames()
+    }
+    pub fn get_time_type(&self) -> Result<CVSMPTETimeType, CVPixelBufferError> {
+        CVSMPTETimeType::try_from(self.time_type)
+    }
+    /// Frames since midnight, the inverse of [`CVSMPTETime::from_frame_count`]. Fails with
+    /// `InvalidArgument` for unknown types, fields out of range and skipped frame numbers.
+    pub fn to_frame_count(&self) -> Result<u64, CVPixelBufferError> {
+        let time_type = self.get_time_type()?;
+        if self.is_dropped(time_type) {
+            return Err(CVPixelBufferError::InvalidArgument);
+        }
+        let fps = time_type.frames_per_second() as u64;
+        let field = |value: i16, limit: u64| {
+            u64::try_from(value)
+                .ok()
+                .filter(|&value| value < limit)
+                .ok_or(CVPixelBufferError::InvalidArgument)
+        };
+        let (hours, minutes) = (field(self.hours, 24)?, field(self.minutes, 60)?);
+        let total_minutes = hours * 60 + minutes;
+        let frames =
+            (total_minutes * 60 + field(self.seconds, 60)?) * fps + field(self.frames, fps)?;
+        Ok(frames - time_type.dropped_frames() * (total_minutes - total_minutes / 10))
+    }
+}
+
+fn frames_per_day(time_type: CVSMPTETimeType) -> u64 {
+    let minutes = 24 * 60;
+    time_type.frames_per_second() as u64 * 60 * minutes
+        - time_type.dropped_frames() * (minutes - minutes / 10)
+}
+
+impl fmt::Display for CVSMPTETime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = match self.get_time_type() {
+            Ok(time_type) if time_type.is_drop_frame() => ';',
+            _ => ':',
+        };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{separator}{:02}",
+            self.hours, self.minutes, self.seconds, self.frames
+        )
+    }
+}
+
+const _: () = assert!(std::mem::size_of::<CVSMPTETime>() == 24);
@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use super::CVSMPTETime;
+use crate::cv_pixel_buffer::error::CVPixelBufferError;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+flag_set! {
+    /// Flags of a [`CVTime`].
+    CVTimeFlags(i32) {
+        /// The time is unknown or unbounded; its value and scale are meaningless.
+        IS_INDEFINITE = 1 << 0;
+    }
+}
+
+/// A length of time as `time_value / time_scale` seconds.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CVTime {
+    pub time_value: i64,
+    pub time_scale: i32,
+    pub flags: CVTimeFlags,
+}
+
+impl CVTime {
+    /// `kCVZeroTime`.
+    pub const ZERO: Self = Self::new(0, 1);
+    /// `kCVIndefiniteTime`.
+    pub const INDEFINITE: Self = Self {
+        time_value: 0,
+        time_scale: 0,
+        flags: CVTimeFlags::IS_INDEFINITE,
+    };
+
+    pub const fn new(time_value: i64, time_scale: i32) -> Self {
+        Self {
+            time_value,
+            time_scale,
+            flags: CVTimeFlags::empty(),
+        }
+    }
+    /// The time in `time_scale` units, rounded to the nearest unit. `None` if `time_scale`
+    /// is not positive or the value does not fit.
+    pub fn from_duration(duration: Duration, time_scale: i32) -> Option<Self> {
+        if time_scale <= 0 {
+            return None;
+        }
+        let scaled = duration.as_nanos() as i128 * time_scale as i128;
+        let time_value = (scaled + NANOS_PER_SECOND / 2) / NANOS_PER_SECOND;
+        Some(Self::new(time_value.try_into().ok()?, time_scale))
+    }
+    pub fn is_indefinite(&self) -> bool {
+        self.flags.contains(CVTimeFlags::IS_INDEFINITE)
+    }
+    /// Whether the time is definite and has a positive scale.
+    pub fn is_valid(&self) -> bool {
+        !self.is_indefinite() && self.time_scale > 0
+    }
+    /// The time as a duration, truncated to whole nanoseconds. `None` for invalid and
+    /// negative times.
+    pub fn to_duration(&self) -> Option<Duration> {
+        if !self.is_valid() || self.time_value < 0 {
+            return None;
+        }
+        let scale = self.time_scale as i64;
+        let seconds = self.time_value / scale;
+        let nanos = (self.time_value % scale) as i128 * NANOS_PER_SECOND / scale as i128;
+        Some(Duration::new(seconds as u64, nanos as u32))
+    }
+    pub fn as_secs_f64(&self) -> Option<f64> {
+        self.is_valid()
+            .then(|| self.time_value as f64 / self.time_scale as f64)
+    }
+    /// The same time as a fraction in lowest terms, with flags kept. A zero time scale is
+    /// returned unchanged.
+    pub fn reduced(&self) -> Self {
+        if self.time_scale == 0 {
+            return *self;
+        }
+        let (mut a, mut b) = (
+            self.time_value.unsigned_abs(),
+            self.time_scale.unsigned_abs() as u64,
+        );
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        // The gcd divides `time_scale`, so only `i32::MIN` itself can fail to convert.
+        match i32::try_from(a) {
+            Ok(gcd) if gcd > 1 => Self {
+                time_value: self.time_value / gcd as i64,
+                time_scale: self.time_scale / gcd,
+                flags: self.flags,
+            },
+            _ => *self,
+        }
+    }
+}
+
+impl TryFrom<CVTime> for Duration {
+    type Error = CVPixelBufferError;
+
+    fn try_from(value: CVTime) -> Result<Self, Self::Error> {
+        value
+            .to_duration()
+            .ok_or(CVPixelBufferError::InvalidArgument)
+    }
+}
+
+flag_set! {
+    /// Which fields of a [`CVTimeStamp`] are valid, and which field of an interlaced frame it
+    /// refers to.
+    CVTimeStampFlags(u64) {
+        VIDEO_TIME_VALID = 1 << 0;
+        HOST_TIME_VALID = 1 << 1;
+        SMPTE_TIME_VALID = 1 << 2;
+        VIDEO_REFRESH_PERIOD_VALID = 1 << 3;
+        RATE_SCALAR_VALID = 1 << 4;
+        TOP_FIELD = 1 << 16;
+        BOTTOM_FIELD = 1 << 17;
+        VIDEO_HOST_TIME_VALID = (1 << 0) | (1 << 1);
+        IS_INTERLACED = (1 << 16) | (1 << 17);
+    }
+}
+
+/// A point in time in both video time and host time, as passed to display link callbacks.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CVTimeStamp {
+    pub version: u32,
+    pub video_time_scale: i32,
+    pub video_time: i64,
+    pub host_time: u64,
+    pub rate_scalar: f64,
+    pub video_refresh_period: i64,
+    pub smpte_time: CVSMPTETime,
+    pub flags: CVTimeStampFlags,
+    pub reserved: u64,
+}
+
+impl CVTimeStamp {
+    /// `video_time` in `video_time_scale` units, if valid.
+    pub fn get_video_time(&self) -> Option<CVTime> {
+        self.flags
+            .contains(CVTimeStampFlags::VIDEO_TIME_VALID)
+            .then(|| CVTime::new(self.video_time, self.video_time_scale))
+    }
+    pub fn get_host_time(&self) -> Option<u64> {
+        self.flags
+            .contains(CVTimeStampFlags::HOST_TIME_VALID)
+            .then_some(self.host_time)
+    }
+    pub fn get_rate_scalar(&self) -> Option<f64> {
+        self.flags
+            .contains(CVTimeStampFlags::RATE_SCALAR_VALID)
+            .then_some(self.rate_scalar)
+    }
+    /// Duration of one frame in `video_time_scale` units, if valid.
+    pub fn get_video_refresh_period(&self) -> Option<CVTime> {
+        self.flags
+            .contains(CVTimeStampFlags::VIDEO_REFRESH_PERIOD_VALID)
+            .then(|| CVTime::new(self.video_refresh_period, self.video_time_scale))
+    }
+    pub fn get_smpte_time(&self) -> Option<CVSMPTETime> {
+        self.flags
+            .contains(CVTimeStampFlags::SMPTE_TIME_VALID)
+            .then_some(self.smpte_time)
+    }
+}
+
+const _: () = {
+    assert!(std::mem::size_of::<CVTime>() == 16);
+    assert!(std::mem::size_of::<CVTimeStamp>() == 80);
+};
@@ -0,0 +1,36 @@
+#![cfg(target_vendor = "apple")]
+
+use core_video_rs::constants::{ColorPrimaries, TransferFunction, YCbCrMatrix};
+
+#[test]
+fn test_table_matches_core_video() {
+    for value in ColorPrimaries::KNOWN {
+        if let Some(code) = value.core_video_cicp() {
+            assert_eq!(value.cicp(), Some(code), "{value}");
+        }
+    }
+    for value in TransferFunction::KNOWN {
+        if let Some(code) = value.core_video_cicp() {
+            assert_eq!(value.cicp(), Some(code), "{value}");
+        }
+    }
+    for value in YCbCrMatrix::KNOWN {
+        if let Some(code) = value.core_video_cicp() {
+            assert_eq!(value.cicp(), Some(code), "{value}");
+        }
+    }
+    for code in 0..=u8::MAX {
+        if let Some(value) = ColorPrimaries::from_core_video_cicp(code) {
+            assert_eq!(
+                value.cicp(),
+                ColorPrimaries::from_cicp(code).and_then(|v| v.cicp())
+            );
+        }
+        if let Some(value) = YCbCrMatrix::from_core_video_cicp(code) {
+            assert_eq!(
+                value.cicp(),
+                YCbCrMatrix::from_cicp(code).and_then(|v| v.cicp())
+            );
+        }
+    }
+}
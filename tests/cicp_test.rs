@@ -0,0 +1,78 @@
+use core_video_rs::{
+    attachments::{ImageBufferAttachment, ImageBufferAttachmentKey, ImageBufferAttachments},
+    constants::{cicp::CICP_UNSPECIFIED, Cicp, ColorPrimaries, TransferFunction, YCbCrMatrix},
+};
+
+#[test]
+fn test_known_values_round_trip() {
+    for value in ColorPrimaries::KNOWN {
+        if let Some(code) = value.cicp() {
+            assert_eq!(ColorPrimaries::from_cicp(code).as_ref(), Some(value));
+        }
+    }
+    for value in TransferFunction::KNOWN {
+        if let Some(code) = value.cicp() {
+            // SMPTE 170M decodes to the identical BT.709 curve.
+            let expected = if code == 6 { 1 } else { code };
+            assert_eq!(
+                TransferFunction::from_cicp(code).unwrap().cicp(),
+                Some(expected)
+            );
+        }
+    }
+    for value in YCbCrMatrix::KNOWN {
+        if let Some(code) = value.cicp() {
+            assert_eq!(YCbCrMatrix::from_cicp(code).as_ref(), Some(value));
+        }
+    }
+}
+
+#[test]
+fn test_code_points() {
+    assert_eq!(ColorPrimaries::ItuR2020.cicp(), Some(9));
+    assert_eq!(TransferFunction::SmpteSt2084Pq.cicp(), Some(16));
+    assert_eq!(TransferFunction::ItuR2100Hlg.cicp(), Some(18));
+    assert_eq!(YCbCrMatrix::ItuR601_4.cicp(), Some(6));
+    assert_eq!(YCbCrMatrix::from_cicp(5), Some(YCbCrMatrix::ItuR601_4));
+    assert_eq!(
+        TransferFunction::from_cicp(15),
+        Some(TransferFunction::ItuR2020)
+    );
+    assert_eq!(ColorPrimaries::from_cicp(CICP_UNSPECIFIED), None);
+    assert_eq!(TransferFunction::UseGamma.cicp(), None);
+    assert_eq!(ColorPrimaries::Unknown("Custom".into()).cicp(), None);
+}
+
+#[test]
+fn test_attachments() {
+    let mut attachments = ImageBufferAttachments::new(&[
+        ImageBufferAttachment::ColorPrimaries(ColorPrimaries::ItuR2020),
+        ImageBufferAttachment::TransferFunction(TransferFunction::UseGamma),
+        ImageBufferAttachment::FieldCount(1),
+    ]);
+    let cicp = Cicp::from_attachments(&attachments);
+    assert_eq!(
+        cicp,
+        Cicp {
+            colour_primaries: 9,
+            transfer_characteristics: CICP_UNSPECIFIED,
+            matrix_coefficients: CICP_UNSPECIFIED,
+        }
+    );
+
+    let hdr = Cicp {
+        colour_primaries: 9,
+        transfer_characteristics: 16,
+        matrix_coefficients: 9,
+    };
+    hdr.apply_to(&mut attachments);
+    assert_eq!(attachments.len(), 4);
+    assert_eq!(
+        attachments.get(ImageBufferAttachmentKey::TransferFunction),
+        Some(&ImageBufferAttachment::TransferFunction(
+            TransferFunction::SmpteSt2084Pq
+        ))
+    );
+    assert_eq!(Cicp::from_attachments(&attachments), hdr);
+    assert!(Cicp::default().to_attachments().is_empty());
+}
@@ -0,0 +1,33 @@
+// Each test crate includes this module and uses a different subset of it.
+#![allow(dead_code)]
+
+use std::error::Error;
+
+use core_video_rs::cv_pixel_buffer::{
+    attributes::PixelBufferAttributes, pixel_format::PixelFormat, SoftwarePixelBuffer,
+};
+
+pub fn buffer(
+    width: usize,
+    height: usize,
+    pixel_format: PixelFormat,
+) -> Result<SoftwarePixelBuffer<'static>, Box<dyn Error>> {
+    Ok(SoftwarePixelBuffer::create(
+        width,
+        height,
+        pixel_format,
+        PixelBufferAttributes::default(),
+    )?)
+}
+
+pub fn gray(width: usize, rows: &[&[u8]]) -> Result<SoftwarePixelBuffer<'static>, Box<dyn Error>> {
+    let mut buffer = buffer(width, rows.len(), PixelFormat::OneComponent8)?;
+    {
+        let mut guard = buffer.lock_mut()?;
+        let mut plane = guard.plane_mut(0)?;
+        for (y, row) in rows.iter().enumerate() {
+            plane.row_mut(y)[..width].copy_from_slice(row);
+        }
+    }
+    Ok(buffer)
+}
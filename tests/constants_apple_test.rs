@@ -0,0 +1,174 @@
+#![cfg(target_vendor = "apple")]
+
+use core_foundation::{
+    base::TCFType,
+    string::{CFString, CFStringRef},
+};
+use core_video_rs::constants::{
+    chroma_location::*, chroma_subsampling::*, color_primaries::*, component_range::*,
+    field_detail::*, transfer_function::*, ycbcr_matrix::*,
+};
+
+fn value(constant: CFStringRef) -> String {
+    unsafe { CFString::wrap_under_get_rule(constant) }.to_string()
+}
+
+#[test]
+fn test_tables_match_core_video() {
+    unsafe {
+        assert_eq!(
+            ColorPrimaries::ItuR709_2.as_str(),
+            value(kCVImageBufferColorPrimaries_ITU_R_709_2)
+        );
+        assert_eq!(
+            ColorPrimaries::Ebu3213.as_str(),
+            value(kCVImageBufferColorPrimaries_EBU_3213)
+        );
+        assert_eq!(
+            ColorPrimaries::SmpteC.as_str(),
+            value(kCVImageBufferColorPrimaries_SMPTE_C)
+        );
+        assert_eq!(
+            ColorPrimaries::DciP3.as_str(),
+            value(kCVImageBufferColorPrimaries_DCI_P3)
+        );
+        assert_eq!(
+            ColorPrimaries::P3D65.as_str(),
+            value(kCVImageBufferColorPrimaries_P3_D65)
+        );
+        assert_eq!(
+            ColorPrimaries::ItuR2020.as_str(),
+            value(kCVImageBufferColorPrimaries_ITU_R_2020)
+        );
+        assert_eq!(
+            ColorPrimaries::P22.as_str(),
+            value(kCVImageBufferColorPrimaries_P22)
+        );
+
+        assert_eq!(
+            TransferFunction::ItuR709_2.as_str(),
+            value(kCVImageBufferTransferFunction_ITU_R_709_2)
+        );
+        assert_eq!(
+            TransferFunction::Smpte240M1995.as_str(),
+            value(kCVImageBufferTransferFunction_SMPTE_240M_1995)
+        );
+        assert_eq!(
+            TransferFunction::UseGamma.as_str(),
+            value(kCVImageBufferTransferFunction_UseGamma)
+        );
+        assert_eq!(
+            TransferFunction::ItuR2020.as_str(),
+            value(kCVImageBufferTransferFunction_ITU_R_2020)
+        );
+        assert_eq!(
+            TransferFunction::SmpteSt428_1.as_str(),
+            value(kCVImageBufferTransferFunction_SMPTE_ST_428_1)
+        );
+        assert_eq!(
+            TransferFunction::ItuR2100Hlg.as_str(),
+            value(kCVImageBufferTransferFunction_ITU_R_2100_HLG)
+        );
+        assert_eq!(
+            TransferFunction::SmpteSt2084Pq.as_str(),
+            value(kCVImageBufferTransferFunction_SMPTE_ST_2084_PQ)
+        );
+        assert_eq!(
+            TransferFunction::Linear.as_str(),
+            value(kCVImageBufferTransferFunction_Linear)
+        );
+        assert_eq!(
+            TransferFunction::SRgb.as_str(),
+            value(kCVImageBufferTransferFunction_sRGB)
+        );
+
+        assert_eq!(
+            YCbCrMatrix::ItuR709_2.as_str(),
+            value(kCVImageBufferYCbCrMatrix_ITU_R_709_2)
+        );
+        assert_eq!(
+            YCbCrMatrix::ItuR601_4.as_str(),
+            value(kCVImageBufferYCbCrMatrix_ITU_R_601_4)
+        );
+        assert_eq!(
+            YCbCrMatrix::Smpte240M1995.as_str(),
+            value(kCVImageBufferYCbCrMatrix_SMPTE_240M_1995)
+        );
+        assert_eq!(
+            YCbCrMatrix::ItuR2020.as_str(),
+            value(kCVImageBufferYCbCrMatrix_ITU_R_2020)
+        );
+
+        assert_eq!(
+            ChromaLocation::Left.as_str(),
+            value(kCVImageBufferChromaLocation_Left)
+        );
+        assert_eq!(
+            ChromaLocation::Center.as_str(),
+            value(kCVImageBufferChromaLocation_Center)
+        );
+        assert_eq!(
+            ChromaLocation::TopLeft.as_str(),
+            value(kCVImageBufferChromaLocation_TopLeft)
+        );
+        assert_eq!(
+            ChromaLocation::Top.as_str(),
+            value(kCVImageBufferChromaLocation_Top)
+        );
+        assert_eq!(
+            ChromaLocation::BottomLeft.as_str(),
+            value(kCVImageBufferChromaLocation_BottomLeft)
+        );
+        assert_eq!(
+            ChromaLocation::Bottom.as_str(),
+            value(kCVImageBufferChromaLocation_Bottom)
+        );
+        assert_eq!(
+            ChromaLocation::Dv420.as_str(),
+            value(kCVImageBufferChromaLocation_DV420)
+        );
+
+        assert_eq!(
+            ChromaSubsampling::Subsampling420.as_str(),
+            value(kCVImageBufferChromaSubsampling_420)
+        );
+        assert_eq!(
+            ChromaSubsampling::Subsampling422.as_str(),
+            value(kCVImageBufferChromaSubsampling_422)
+        );
+        assert_eq!(
+            ChromaSubsampling::Subsampling411.as_str(),
+            value(kCVImageBufferChromaSubsampling_411)
+        );
+
+        assert_eq!(
+            FieldDetail::TemporalTopFirst.as_str(),
+            value(kCVImageBufferFieldDetailTemporalTopFirst)
+        );
+        assert_eq!(
+            FieldDetail::TemporalBottomFirst.as_str(),
+            value(kCVImageBufferFieldDetailTemporalBottomFirst)
+        );
+        assert_eq!(
+            FieldDetail::SpatialFirstLineEarly.as_str(),
+            value(kCVImageBufferFieldDetailSpatialFirstLineEarly)
+        );
+        assert_eq!(
+            FieldDetail::SpatialFirstLineLate.as_str(),
+            value(kCVImageBufferFieldDetailSpatialFirstLineLate)
+        );
+
+        assert_eq!(
+            ComponentRange::VideoRange.as_str(),
+            value(kCVPixelFormatComponentRange_VideoRange)
+        );
+        assert_eq!(
+            ComponentRange::FullRange.as_str(),
+            value(kCVPixelFormatComponentRange_FullRange)
+        );
+        assert_eq!(
+            ComponentRange::WideRange.as_str(),
+            value(kCVPixelFormatComponentRange_WideRange)
+        );
+    }
+}
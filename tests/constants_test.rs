@@ -0,0 +1,50 @@
+use std::str::FromStr;
+
+use core_video_rs::constants::{
+    ChromaLocation, ChromaSubsampling, ColorPrimaries, FieldDetail, TransferFunction, YCbCrMatrix,
+};
+
+#[test]
+fn test_round_trip_known_values() {
+    for value in ColorPrimaries::KNOWN {
+        assert_eq!(&ColorPrimaries::from(value.as_str()), value);
+    }
+    for value in TransferFunction::KNOWN {
+        assert_eq!(&TransferFunction::from(value.as_str()), value);
+    }
+    for value in YCbCrMatrix::KNOWN {
+        assert_eq!(&YCbCrMatrix::from(value.as_str()), value);
+    }
+    for value in ChromaLocation::KNOWN {
+        assert_eq!(&ChromaLocation::from(value.as_str()), value);
+    }
+    for value in ChromaSubsampling::KNOWN {
+        assert_eq!(&ChromaSubsampling::from(value.as_str()), value);
+    }
+    for value in FieldDetail::KNOWN {
+        assert_eq!(&FieldDetail::from(value.as_str()), value);
+    }
+}
+
+#[test]
+fn test_string_values() {
+    assert_eq!(ColorPrimaries::ItuR709_2.as_str(), "ITU_R_709_2");
+    assert_eq!(
+        TransferFunction::SmpteSt2084Pq.to_string(),
+        "SMPTE_ST_2084_PQ"
+    );
+    assert_eq!(TransferFunction::SRgb.as_str(), "IEC_sRGB");
+    assert_eq!(ChromaLocation::Dv420.as_str(), "DV 4:2:0");
+    assert_eq!(
+        ChromaSubsampling::from_str("4:2:0"),
+        Ok(ChromaSubsampling::Subsampling420)
+    );
+}
+
+#[test]
+fn test_unknown_values() {
+    let value = YCbCrMatrix::from("Something_New");
+    assert_eq!(value, YCbCrMatrix::Unknown("Something_New".to_string()));
+    assert_eq!(value.as_str(), "Something_New");
+    assert!(!YCbCrMatrix::KNOWN.contains(&value));
+}
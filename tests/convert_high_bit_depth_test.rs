@@ -0,0 +1,160 @@
+use std::error::Error;
+
+use core_video_rs::{
+    convert::{self, v210_bytes_per_row, ChromaFormat, PlanarYCbCr16, YCbCrRange},
+    cv_pixel_buffer::{error::CVPixelBufferError, pixel_format::PixelFormat},
+};
+
+use common::buffer;
+
+mod common;
+
+// Y = [0x040, 0x080, 0x100, 0x200, 0x3ac, 0x3ff], Cb = [0x200, 0x100, 0x3c0],
+// Cr = [0x1ff, 0x300, 0x040], packed as four little-endian words.
+const V210_GROUP: [u8; 16] = [
+    0x00, 0x02, 0xf1, 0x1f, 0x80, 0x00, 0x04, 0x10, 0x00, 0x03, 0x08, 0x3c, 0xac, 0x03, 0xf1, 0x3f,
+];
+
+#[test]
+fn test_v210_golden() -> Result<(), Box<dyn Error>> {
+    let mut v210 = buffer(6, 1, PixelFormat::YCbCr422_10)?;
+    assert_eq!(v210.get_bytes_per_row(), v210_bytes_per_row(6));
+    {
+        let mut guard = v210.lock_mut()?;
+        let mut plane = guard.plane_mut(0)?;
+        plane.row_mut(0)[..16].copy_from_slice(&V210_GROUP);
+        plane.row_mut(0)[16..].fill(0xaa);
+    }
+    let image = convert::unpack(&v210)?;
+    assert_eq!(image.chroma_format, ChromaFormat::Yuv422);
+    assert_eq!(image.bit_depth, 10);
+    assert_eq!(image.y, [0x040, 0x080, 0x100, 0x200, 0x3ac, 0x3ff]);
+    assert_eq!(image.cb, [0x200, 0x100, 0x3c0]);
+    assert_eq!(image.cr, [0x1ff, 0x300, 0x040]);
+
+    convert::pack(&image, &mut v210)?;
+    let guard = v210.lock()?;
+    let row = guard.plane(0)?.row(0);
+    assert_eq!(row[..16], V210_GROUP);
+    assert!(row[16..].iter().all(|&b| b == 0));
+    Ok(())
+}
+
+#[test]
+fn test_v210_partial_group() -> Result<(), Box<dyn Error>> {
+    assert_eq!(v210_bytes_per_row(48), 128);
+    assert_eq!(v210_bytes_per_row(50), 256);
+    let mut image = PlanarYCbCr16::new(8, 2, ChromaFormat::Yuv422, 10)?;
+    for (i, sample) in image.y.iter_mut().enumerate() {
+        *sample = 64 + i as u16 * 50;
+    }
+    for (i, (cb, cr)) in image.cb.iter_mut().zip(image.cr.iter_mut()).enumerate() {
+        *cb = 100 + i as u16;
+        *cr = 900 - i as u16;
+    }
+    let mut v210 = buffer(8, 2, PixelFormat::YCbCr422_10)?;
+    convert::pack(&image, &mut v210)?;
+    {
+        // The second group repeats the last pixel pair in its unused slots.
+        let guard = v210.lock()?;
+        let row = guard.plane(0)?.row(0);
+        let word = u32::from_le_bytes([row[28], row[29], row[30], row[31]]);
+        assert_eq!(word >> 20, image.y[7] as u32);
+    }
+    assert_eq!(convert::unpack(&v210)?, image);
+    Ok(())
+}
+
+#[test]
+fn test_bi_planar_msb_aligned() -> Result<(), Box<dyn Error>> {
+    let mut x420 = buffer(2, 2, PixelFormat::YCbCr420_10BiPlanarVideoRange)?;
+    {
+        let mut guard = x420.lock_mut()?;
+        let mut planes = guard.planes_mut()?;
+        for y in 0..2 {
+            // 940 and 64 shifted into the top ten bits.
+            planes[0].row_mut(y)[..4].copy_from_slice(&[0x00, 0xeb, 0x00, 0x10]);
+        }
+        planes[1].row_mut(0)[..4].copy_from_slice(&[0x00, 0x80, 0xc0, 0x3f]);
+    }
+    let image = convert::unpack(&x420)?;
+    assert_eq!(image.y, [940, 64, 940, 64]);
+    assert_eq!(image.cb, [512]);
+    assert_eq!(image.cr, [255]);
+
+    let mut xf22 = buffer(2, 2, PixelFormat::YCbCr422_10BiPlanarVideoRange)?;
+    convert::convert(&x420, &mut xf22)?;
+    let guard = xf22.lock()?;
+    assert_eq!(guard.plane(1)?.row(1)[..4], [0x00, 0x80, 0xc0, 0x3f]);
+    Ok(())
+}
+
+#[test]
+fn test_y416_golden() -> Result<(), Box<dyn Error>> {
+    let mut y416 = buffer(1, 1, PixelFormat::AYCbCr4444_16)?;
+    {
+        let mut guard = y416.lock_mut()?;
+        guard.plane_mut(0)?.row_mut(0)[..8]
+            .copy_from_slice(&[0xff, 0x7f, 0x00, 0x10, 0x00, 0x80, 0x00, 0xf0]);
+    }
+    let image = convert::unpack(&y416)?;
+    assert_eq!(image.alpha, Some(vec![0x7fff]));
+    assert_eq!(
+        (image.y[0], image.cb[0], image.cr[0]),
+        (0x1000, 0x8000, 0xf000)
+    );
+
+    let ten_bit = image.with_bit_depth(10, YCbCrRange::Video)?;
+    assert_eq!((ten_bit.y[0], ten_bit.cb[0], ten_bit.cr[0]), (64, 512, 960));
+    assert_eq!(ten_bit.alpha, Some(vec![511]));
+
+    let mut x444 = buffer(1, 1, PixelFormat::YCbCr444_10BiPlanarVideoRange)?;
+    convert::convert(&y416, &mut x444)?;
+    assert_eq!(convert::unpack(&x444)?.y, [64]);
+
+    let mut opaque = PlanarYCbCr16::new(1, 1, ChromaFormat::Yuv444, 16)?;
+    opaque.y[0] = 0x1234;
+    convert::pack(&opaque, &mut y416)?;
+    let guard = y416.lock()?;
+    assert_eq!(guard.plane(0)?.row(0)[..4], [0xff, 0xff, 0x34, 0x12]);
+    Ok(())
+}
+
+#[test]
+fn test_chroma_format_and_bit_depth() -> Result<(), Box<dyn Error>> {
+    let mut image = PlanarYCbCr16::new(3, 3, ChromaFormat::Yuv444, 10)?;
+    image.cb = vec![0, 2, 4, 6, 8, 10, 12, 14, 16];
+    let subsampled = image.with_chroma_format(ChromaFormat::Yuv420);
+    assert_eq!(subsampled.cb, [4, 7, 13, 16]);
+    let upsampled = subsampled.with_chroma_format(ChromaFormat::Yuv444);
+    assert_eq!(upsampled.cb, [4, 4, 7, 4, 4, 7, 13, 13, 16]);
+
+    let mut full = PlanarYCbCr16::new(1, 1, ChromaFormat::Yuv444, 8)?;
+    full.y[0] = 255;
+    assert_eq!(full.with_bit_depth(10, YCbCrRange::Full)?.y, [1023]);
+    assert_eq!(full.with_bit_depth(10, YCbCrRange::Video)?.y, [1020]);
+    assert!(full.with_bit_depth(17, YCbCrRange::Full).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_errors() -> Result<(), Box<dyn Error>> {
+    let x420 = buffer(4, 4, PixelFormat::YCbCr420_10BiPlanarVideoRange)?;
+    let mut xf20 = buffer(4, 4, PixelFormat::YCbCr420_10BiPlanarFullRange)?;
+    assert!(matches!(
+        convert::convert(&x420, &mut xf20),
+        Err(CVPixelBufferError::Unsupported)
+    ));
+    let image = PlanarYCbCr16::new(4, 4, ChromaFormat::Yuv444, 10)?;
+    let mut v210 = buffer(4, 4, PixelFormat::YCbCr422_10)?;
+    assert!(matches!(
+        convert::pack(&image, &mut v210),
+        Err(CVPixelBufferError::InvalidArgument)
+    ));
+    let bgra = buffer(4, 4, PixelFormat::BGRA32)?;
+    assert!(matches!(
+        convert::unpack(&bgra),
+        Err(CVPixelBufferError::InvalidPixelFormat)
+    ));
+    Ok(())
+}
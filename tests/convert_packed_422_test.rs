@@ -0,0 +1,108 @@
+use std::error::Error;
+
+use core_video_rs::{
+    convert::{self, ChromaFormat, Packed422Order, PlanarYCbCr16},
+    cv_pixel_buffer::{error::CVPixelBufferError, pixel_format::PixelFormat, SoftwarePixelBuffer},
+};
+
+use common::buffer;
+
+mod common;
+
+/// A 3x2 2vuy buffer; the unused luma slot of each row's last pair and the row padding hold
+/// 0xaa.
+fn two_vuy() -> Result<SoftwarePixelBuffer<'static>, Box<dyn Error>> {
+    let mut buffer = buffer(3, 2, PixelFormat::YCbCr422_8)?;
+    {
+        let mut guard = buffer.lock_mut()?;
+        let mut plane = guard.plane_mut(0)?;
+        assert!(plane.bytes_per_row() > 8);
+        plane.row_mut(0).fill(0xaa);
+        plane.row_mut(0)[..7].copy_from_slice(&[100, 16, 150, 32, 110, 48, 160]);
+        plane.row_mut(1).fill(0xaa);
+        plane.row_mut(1)[..7].copy_from_slice(&[120, 64, 170, 80, 130, 96, 180]);
+    }
+    Ok(buffer)
+}
+
+#[test]
+fn test_unpack_2vuy() -> Result<(), Box<dyn Error>> {
+    let image = convert::unpack(&two_vuy()?)?;
+    assert_eq!(image.chroma_format, ChromaFormat::Yuv422);
+    assert_eq!(image.bit_depth, 8);
+    assert_eq!(image.y, [16, 32, 48, 64, 80, 96]);
+    assert_eq!(image.cb, [100, 110, 120, 130]);
+    assert_eq!(image.cr, [150, 160, 170, 180]);
+    Ok(())
+}
+
+#[test]
+fn test_pack_2vuy_and_yuvs() -> Result<(), Box<dyn Error>> {
+    let image = convert::unpack(&two_vuy()?)?;
+    let mut packed = two_vuy()?;
+    convert::pack(&image, &mut packed)?;
+    {
+        let guard = packed.lock()?;
+        let plane = guard.plane(0)?;
+        // The odd pixel repeats in the last pair and the padding is untouched.
+        assert_eq!(
+            plane.row(0)[..9],
+            [100, 16, 150, 32, 110, 48, 160, 48, 0xaa]
+        );
+    }
+
+    let mut yuvs = buffer(3, 2, PixelFormat::YCbCr422_8Yuvs)?;
+    convert::convert(&packed, &mut yuvs)?;
+    let guard = yuvs.lock()?;
+    assert_eq!(
+        guard.plane(0)?.row(1)[..8],
+        [64, 120, 80, 170, 96, 130, 96, 180]
+    );
+    assert_eq!(
+        convert::unpack_packed_422(&guard.plane(0)?, Packed422Order::YCbYCr)?,
+        image
+    );
+    Ok(())
+}
+
+#[test]
+fn test_to_planar_420() -> Result<(), Box<dyn Error>> {
+    let src = two_vuy()?;
+    let mut y420 = buffer(3, 2, PixelFormat::YCbCr420_8Planar)?;
+    convert::convert(&src, &mut y420)?;
+    {
+        let guard = y420.lock()?;
+        let planes = guard.planes()?;
+        assert_eq!(planes.len(), 3);
+        assert_eq!(planes[0].row(1)[..3], [64, 80, 96]);
+        assert_eq!(planes[1].row(0)[..2], [110, 120]);
+        assert_eq!(planes[2].row(0)[..2], [160, 170]);
+    }
+
+    let mut nv12 = buffer(3, 2, PixelFormat::YCbCr420_8BiPlanarVideoRange)?;
+    convert::convert(&y420, &mut nv12)?;
+    let mut back = buffer(3, 2, PixelFormat::YCbCr422_8)?;
+    convert::convert(&nv12, &mut back)?;
+    let image = convert::unpack(&back)?;
+    assert_eq!(image.y, [16, 32, 48, 64, 80, 96]);
+    assert_eq!(image.cb, [110, 120, 110, 120]);
+    assert_eq!(image.cr, [160, 170, 160, 170]);
+    Ok(())
+}
+
+#[test]
+fn test_errors() -> Result<(), Box<dyn Error>> {
+    let yuvf = buffer(4, 2, PixelFormat::YCbCr422_8FullRange)?;
+    let mut nv12 = buffer(4, 2, PixelFormat::YCbCr420_8BiPlanarVideoRange)?;
+    assert!(matches!(
+        convert::convert(&yuvf, &mut nv12),
+        Err(CVPixelBufferError::Unsupported)
+    ));
+    let image = PlanarYCbCr16::new(4, 2, ChromaFormat::Yuv422, 10)?;
+    let mut two_vuy = buffer(4, 2, PixelFormat::YCbCr422_8)?;
+    assert!(matches!(
+        convert::pack(&image, &mut two_vuy),
+        Err(CVPixelBufferError::InvalidArgument)
+    ));
+    Ok(())
+}
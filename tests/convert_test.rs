@@ -0,0 +1,276 @@
+use std::error::Error;
+
+use core_video_rs::{
+    attachments::{CVAttachmentMode, ImageBufferAttachment, ImageBufferAttachmentKey},
+    constants::{ChromaLocation, ColorPrimaries, YCbCrMatrix},
+    convert::{self, ChromaSiting, ConvertOptions, YCbCrCoefficients, YCbCrRange},
+    cv_pixel_buffer::{
+        attributes::PixelBufferAttributes, error::CVPixelBufferError, pixel_format::PixelFormat,
+        SoftwarePixelBuffer,
+    },
+};
+
+fn bgra_buffer(
+    width: usize,
+    height: usize,
+    pixel: impl Fn(usize, usize) -> [u8; 3],
+) -> Result<SoftwarePixelBuffer<'static>, Box<dyn Error>> {
+    let mut buffer = SoftwarePixelBuffer::create(
+        width,
+        height,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )?;
+    {
+        let mut guard = buffer.lock_mut()?;
+        let mut plane = guard.plane_mut(0)?;
+        for y in 0..height {
+            let row = plane.row_mut(y);
+            for x in 0..width {
+                let [r, g, b] = pixel(x, y);
+                row[x * 4..x * 4 + 4].copy_from_slice(&[b, g, r, 0xff]);
+            }
+        }
+    }
+    Ok(buffer)
+}
+
+fn nv12_buffer(
+    width: usize,
+    height: usize,
+    pixel_format: PixelFormat,
+) -> Result<SoftwarePixelBuffer<'static>, Box<dyn Error>> {
+    Ok(SoftwarePixelBuffer::create(
+        width,
+        height,
+        pixel_format,
+        PixelBufferAttributes::default(),
+    )?)
+}
+
+/// Converts a single color and returns `[Y, Cb, Cr]`.
+fn ycbcr(
+    rgb: [u8; 3],
+    matrix: YCbCrMatrix,
+    pixel_format: PixelFormat,
+) -> Result<[u8; 3], Box<dyn Error>> {
+    let src = bgra_buffer(2, 2, |_, _| rgb)?;
+    let mut dst = nv12_buffer(2, 2, pixel_format)?;
+    let options = ConvertOptions {
+        matrix: Some(matrix),
+        ..Default::default()
+    };
+    convert::convert_with_options(&src, &mut dst, &options)?;
+    let guard = dst.lock()?;
+    let (y_plane, uv_plane) = (guard.plane(0)?, guard.plane(1)?);
+    Ok([y_plane.row(0)[0], uv_plane.row(0)[0], uv_plane.row(0)[1]])
+}
+
+#[test]
+fn test_reference_values() -> Result<(), Box<dyn Error>> {
+    let video = PixelFormat::YCbCr420_8BiPlanarVideoRange;
+    let full = PixelFormat::YCbCr420_8BiPlanarFullRange;
+    for matrix in [
+        YCbCrMatrix::ItuR601_4,
+        YCbCrMatrix::ItuR709_2,
+        YCbCrMatrix::ItuR2020,
+    ] {
+        assert_eq!(ycbcr([0, 0, 0], matrix.clone(), video)?, [16, 128, 128]);
+        assert_eq!(
+            ycbcr([255, 255, 255], matrix.clone(), video)?,
+            [235, 128, 128]
+        );
+        assert_eq!(
+            ycbcr([128, 128, 128], matrix.clone(), video)?,
+            [126, 128, 128]
+        );
+        assert_eq!(ycbcr([0, 0, 0], matrix.clone(), full)?, [0, 128, 128]);
+        assert_eq!(ycbcr([255, 255, 255], matrix, full)?, [255, 128, 128]);
+    }
+    assert_eq!(
+        ycbcr([255, 0, 0], YCbCrMatrix::ItuR601_4, video)?,
+        [81, 90, 240]
+    );
+    assert_eq!(
+        ycbcr([0, 255, 0], YCbCrMatrix::ItuR601_4, video)?,
+        [145, 54, 34]
+    );
+    assert_eq!(
+        ycbcr([0, 0, 255], YCbCrMatrix::ItuR601_4, video)?,
+        [41, 240, 110]
+    );
+    assert_eq!(
+        ycbcr([255, 0, 0], YCbCrMatrix::ItuR709_2, video)?,
+        [63, 102, 240]
+    );
+    assert_eq!(
+        ycbcr([0, 255, 0], YCbCrMatrix::ItuR709_2, video)?,
+        [173, 42, 26]
+    );
+    assert_eq!(
+        ycbcr([0, 0, 255], YCbCrMatrix::ItuR709_2, video)?,
+        [32, 240, 118]
+    );
+    assert_eq!(
+        ycbcr([255, 0, 0], YCbCrMatrix::ItuR2020, video)?,
+        [74, 97, 240]
+    );
+    assert_eq!(
+        ycbcr([255, 0, 0], YCbCrMatrix::ItuR601_4, full)?,
+        [76, 85, 255]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> Result<(), Box<dyn Error>> {
+    let pixel = |x: usize, y: usize| [(x * 40) as u8, (y * 60) as u8, 200 - (x * 20) as u8];
+    for pixel_format in [
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PixelFormat::YCbCr420_8BiPlanarFullRange,
+    ] {
+        // Constant color per 2x2 block so that centered subsampling is lossless.
+        let src = bgra_buffer(6, 4, |x, y| pixel(x / 2, y / 2))?;
+        let mut nv12 = nv12_buffer(6, 4, pixel_format)?;
+        let options = ConvertOptions {
+            chroma_siting: Some(ChromaSiting::Center),
+            ..Default::default()
+        };
+        convert::convert_with_options(&src, &mut nv12, &options)?;
+        let mut rgba = SoftwarePixelBuffer::create(
+            6,
+            4,
+            PixelFormat::RGBA32,
+            PixelBufferAttributes::default(),
+        )?;
+        convert::convert(&nv12, &mut rgba)?;
+        let guard = rgba.lock()?;
+        let plane = guard.plane(0)?;
+        for y in 0..4 {
+            for x in 0..6 {
+                let actual = &plane.row(y)[x * 4..x * 4 + 4];
+                let expected = pixel(x / 2, y / 2);
+                for channel in 0..3 {
+                    assert!(actual[channel].abs_diff(expected[channel]) <= 2);
+                }
+                assert_eq!(actual[3], 0xff);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_chroma_siting() -> Result<(), Box<dyn Error>> {
+    // Blue ramps across columns 0, 64, 128, 192 and rows alternate red.
+    let src = bgra_buffer(4, 2, |x, y| [(y * 200) as u8, 0, (x * 64) as u8])?;
+    let coefficients = YCbCrCoefficients::new(&YCbCrMatrix::ItuR709_2, YCbCrRange::Full)?;
+    let cb = |siting: ChromaSiting| -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut dst = nv12_buffer(4, 2, PixelFormat::YCbCr420_8BiPlanarFullRange)?;
+        {
+            let src_guard = src.lock()?;
+            let mut dst_guard = dst.lock_mut()?;
+            let mut planes = dst_guard.planes_mut()?;
+            let (y_plane, uv_plane) = planes.split_at_mut(1);
+            convert::rgb_to_nv12(
+                &src_guard.plane(0)?,
+                convert::RgbLayout::BGRA,
+                &mut y_plane[0],
+                &mut uv_plane[0],
+                &coefficients,
+                siting,
+            )?;
+        }
+        let guard = dst.lock()?;
+        let row = guard.plane(1)?.row(0).to_vec();
+        Ok(vec![row[0], row[2]])
+    };
+    // Blue averages 32 and 160 for centered chroma; 16 and 128 when co-sited.
+    // Red contributes 100 when averaged over both rows and nothing from the top row alone.
+    let expected =
+        |blue: f64, red: f64| -> u8 { (128.0 + 0.5 * blue - 0.2126 / 1.8556 * red).round() as u8 };
+    assert_eq!(
+        cb(ChromaSiting::Center)?,
+        vec![expected(32.0, 100.0), expected(160.0, 100.0)]
+    );
+    assert_eq!(
+        cb(ChromaSiting::Left)?,
+        vec![expected(16.0, 100.0), expected(128.0, 100.0)]
+    );
+    assert_eq!(
+        cb(ChromaSiting::TopLeft)?,
+        vec![expected(16.0, 0.0), expected(128.0, 0.0)]
+    );
+    assert_eq!(
+        cb(ChromaSiting::Bottom)?,
+        vec![expected(32.0, 200.0), expected(160.0, 200.0)]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_attachments() -> Result<(), Box<dyn Error>> {
+    let src = bgra_buffer(3, 3, |_, _| [10, 20, 30])?;
+    src.set_attachment(
+        &ImageBufferAttachment::YCbCrMatrix(YCbCrMatrix::ItuR601_4),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    src.set_attachment(
+        &ImageBufferAttachment::ChromaLocationTopField(ChromaLocation::Center),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    src.set_attachment(
+        &ImageBufferAttachment::ColorPrimaries(ColorPrimaries::ItuR709_2),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    let mut dst = nv12_buffer(3, 3, PixelFormat::YCbCr420_8BiPlanarVideoRange)?;
+    convert::convert(&src, &mut dst)?;
+    let attachments = dst.get_attachments(CVAttachmentMode::ShouldPropagate);
+    assert_eq!(
+        attachments.get(ImageBufferAttachmentKey::YCbCrMatrix),
+        Some(&ImageBufferAttachment::YCbCrMatrix(YCbCrMatrix::ItuR601_4))
+    );
+    assert_eq!(
+        attachments.get(ImageBufferAttachmentKey::ChromaLocationTopField),
+        Some(&ImageBufferAttachment::ChromaLocationTopField(
+            ChromaLocation::Center
+        ))
+    );
+    assert_eq!(
+        attachments.get(ImageBufferAttachmentKey::ColorPrimaries),
+        Some(&ImageBufferAttachment::ColorPrimaries(
+            ColorPrimaries::ItuR709_2
+        ))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_errors() -> Result<(), Box<dyn Error>> {
+    let src = bgra_buffer(4, 4, |_, _| [0, 0, 0])?;
+    let mut small = nv12_buffer(2, 2, PixelFormat::YCbCr420_8BiPlanarVideoRange)?;
+    assert!(matches!(
+        convert::convert(&src, &mut small),
+        Err(CVPixelBufferError::InvalidSize)
+    ));
+    let mut unsupported = SoftwarePixelBuffer::create(
+        4,
+        4,
+        PixelFormat::YCbCr420_8Planar,
+        PixelBufferAttributes::default(),
+    )?;
+    assert!(matches!(
+        convert::convert(&src, &mut unsupported),
+        Err(CVPixelBufferError::InvalidPixelFormat)
+    ));
+    let mut dst = nv12_buffer(4, 4, PixelFormat::YCbCr420_8BiPlanarVideoRange)?;
+    let options = ConvertOptions {
+        matrix: Some(YCbCrMatrix::P3D65),
+        ..Default::default()
+    };
+    assert!(matches!(
+        convert::convert_with_options(&src, &mut dst, &options),
+        Err(CVPixelBufferError::Unsupported)
+    ));
+    Ok(())
+}
@@ -0,0 +1,128 @@
+#![cfg(target_vendor = "apple")]
+
+use std::error::Error;
+
+use core_foundation::base::{CFTypeRef, TCFType};
+use core_graphics::geometry::CGRect;
+use core_video_rs::{
+    attachments::{
+        CVAttachmentMode, CleanAperture, ContentLightLevelInfo, DisplayDimensions,
+        ImageBufferAttachment, ImageBufferAttachmentKey, ImageBufferAttachments, PixelAspectRatio,
+        Rational, Rect,
+    },
+    constants::{ColorPrimaries, TransferFunction},
+    cv_pixel_buffer::{
+        attributes::PixelBufferAttributes, pixel_format::PixelFormat, CVPixelBuffer,
+        PixelBufferLike,
+    },
+};
+
+extern "C" {
+    fn CVImageBufferGetCleanRect(imageBuffer: CFTypeRef) -> CGRect;
+}
+
+fn create_pixel_buffer() -> Result<CVPixelBuffer, Box<dyn Error>> {
+    Ok(CVPixelBuffer::create(
+        16,
+        16,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PixelBufferAttributes::default(),
+    )?)
+}
+
+#[test]
+fn test_set_get_attachments() -> Result<(), Box<dyn Error>> {
+    let pixel_buffer = create_pixel_buffer()?;
+    let attachments = ImageBufferAttachments::new(&[
+        ImageBufferAttachment::ColorPrimaries(ColorPrimaries::ItuR709_2),
+        ImageBufferAttachment::FieldCount(1),
+        ImageBufferAttachment::CleanAperture(CleanAperture {
+            width: Rational::integer(14),
+            height: Rational::new(27, 2),
+            horizontal_offset: Rational::integer(-1),
+            vertical_offset: Rational::new(1, 4),
+        }),
+        ImageBufferAttachment::PixelAspectRatio(PixelAspectRatio {
+            horizontal_spacing: 4,
+            vertical_spacing: 3,
+        }),
+        ImageBufferAttachment::DisplayDimensions(DisplayDimensions {
+            width: 21,
+            height: 16,
+        }),
+        ImageBufferAttachment::GammaLevel(2.2),
+        ImageBufferAttachment::ContentLightLevelInfo(ContentLightLevelInfo {
+            max_cll: 1000,
+            max_fall: 400,
+        }),
+    ]);
+    pixel_buffer.set_attachments(attachments.clone(), CVAttachmentMode::ShouldPropagate);
+    let copied = pixel_buffer.get_attachments(CVAttachmentMode::ShouldPropagate);
+    for attachment in attachments.as_slice() {
+        assert_eq!(copied.get(attachment.key()), Some(attachment));
+    }
+    assert_eq!(
+        pixel_buffer.get_attachment(ImageBufferAttachmentKey::FieldCount),
+        Some((
+            ImageBufferAttachment::FieldCount(1),
+            CVAttachmentMode::ShouldPropagate
+        ))
+    );
+    pixel_buffer.remove_attachment(ImageBufferAttachmentKey::FieldCount);
+    assert!(pixel_buffer
+        .get_attachment(ImageBufferAttachmentKey::FieldCount)
+        .is_none());
+    pixel_buffer.remove_all_attachments();
+    assert!(pixel_buffer
+        .get_attachments(CVAttachmentMode::ShouldPropagate)
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_propagate_attachments() -> Result<(), Box<dyn Error>> {
+    let source = create_pixel_buffer()?;
+    let destination = create_pixel_buffer()?;
+    source.set_attachment(
+        &ImageBufferAttachment::TransferFunction(TransferFunction::ItuR709_2),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    source.set_attachment(
+        &ImageBufferAttachment::FieldCount(2),
+        CVAttachmentMode::ShouldNotPropagate,
+    );
+    source.propagate_attachments(&destination);
+    assert!(destination
+        .get_attachment(ImageBufferAttachmentKey::TransferFunction)
+        .is_some());
+    assert!(destination
+        .get_attachment(ImageBufferAttachmentKey::FieldCount)
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn test_fractional_clean_rect_matches_core_video() -> Result<(), Box<dyn Error>> {
+    let pixel_buffer = create_pixel_buffer()?;
+    pixel_buffer.set_attachment(
+        &ImageBufferAttachment::CleanAperture(CleanAperture {
+            width: Rational::new(27, 2),
+            height: Rational::new(25, 2),
+            horizontal_offset: Rational::new(1, 4),
+            vertical_offset: Rational::new(-3, 4),
+        }),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    let rect = unsafe { CVImageBufferGetCleanRect(pixel_buffer.as_CFTypeRef()) };
+    assert_eq!(
+        pixel_buffer.get_clean_rect(),
+        Rect::new(
+            rect.origin.x,
+            rect.origin.y,
+            rect.size.width,
+            rect.size.height
+        )
+    );
+    assert_eq!(rect.size.width, 13.5);
+    Ok(())
+}
@@ -1,10 +1,10 @@
-use std::error::Error;
+#![cfg(target_vendor = "apple")]
 
-use core_utils_rs::four_char_code::FourCharCode;
-use core_utils_rs::lock::{LockTrait, MutLockTrait};
+use std::error::Error;
 
-use core_video_rs::cv_pixel_buffer::CVPixelBuffer;
 use core_video_rs::cv_pixel_buffer::attributes::PixelBufferAttributes;
+use core_video_rs::cv_pixel_buffer::pixel_format::PixelFormat;
+use core_video_rs::cv_pixel_buffer::CVPixelBuffer;
 const WIDTH: usize = 10;
 const HEIGHT: usize = 10;
 
@@ -13,7 +13,7 @@ fn test_create_lock_write() -> Result<(), Box<dyn Error>> {
     let mut pixel_buffer = CVPixelBuffer::create(
         WIDTH,
         HEIGHT,
-        FourCharCode::from_str("BGRA").unwrap(),
+        PixelFormat::BGRA32,
         PixelBufferAttributes::default(),
     )?;
 
@@ -29,3 +29,72 @@ fn test_create_lock_write() -> Result<(), Box<dyn Error>> {
     };
     Ok(())
 }
+
+#[test]
+fn test_planes_lock_write() -> Result<(), Box<dyn Error>> {
+    let mut pixel_buffer = CVPixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PixelBufferAttributes::default(),
+    )?;
+
+    {
+        let mut b = pixel_buffer.lock_mut()?;
+        assert_eq!(b.plane_count(), 2);
+        let mut planes = b.planes_mut()?;
+        let (luma, chroma) = planes.split_at_mut(1);
+        luma[0].rows_mut().for_each(|row| row.fill(16));
+        chroma[0].rows_mut().for_each(|row| row.fill(128));
+    };
+    {
+        let b = pixel_buffer.lock()?;
+        let luma = b.plane(0)?;
+        let chroma = b.plane(1)?;
+        assert_eq!((luma.width(), luma.height()), (WIDTH, HEIGHT));
+        assert_eq!((chroma.width(), chroma.height()), (WIDTH / 2, HEIGHT / 2));
+        assert!(luma.bytes_per_row() >= WIDTH);
+        assert!(chroma.bytes_per_row() >= WIDTH);
+        for y in 0..HEIGHT {
+            assert!(luma.row(y)[..WIDTH].iter().all(|v| *v == 16));
+        }
+        for y in 0..HEIGHT / 2 {
+            assert!(chroma.row(y)[..WIDTH].iter().all(|v| *v == 128));
+        }
+        assert!(b.plane(2).is_err());
+    };
+    Ok(())
+}
+
+#[test]
+fn test_non_planar_single_plane() -> Result<(), Box<dyn Error>> {
+    let pixel_buffer = CVPixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )?;
+    let b = pixel_buffer.lock()?;
+    assert_eq!(b.plane_count(), 1);
+    let plane = b.plane(0)?;
+    assert_eq!(plane.width(), WIDTH);
+    assert_eq!(plane.as_slice().len(), b.len());
+    Ok(())
+}
+
+#[test]
+fn test_explicit_unlock() -> Result<(), Box<dyn Error>> {
+    let mut pixel_buffer = CVPixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )?;
+    let mut b = pixel_buffer.lock_mut()?;
+    b.fill(7);
+    b.unlock()?;
+    let b = pixel_buffer.lock()?;
+    assert!(b.iter().all(|v| *v == 7));
+    b.unlock()?;
+    Ok(())
+}
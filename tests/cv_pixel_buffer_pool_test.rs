@@ -0,0 +1,71 @@
+#![cfg(target_vendor = "apple")]
+
+use std::error::Error;
+
+use core_video_rs::{
+    cv_pixel_buffer::{
+        attributes::{
+            PixelBufferAttribute, PixelBufferAttributeKey, PixelBufferAttributes, PixelFormatValue,
+        },
+        error::CVPixelBufferError,
+        pixel_format::PixelFormat,
+    },
+    cv_pixel_buffer_pool::{
+        attributes::{
+            PixelBufferPoolAttribute, PixelBufferPoolAttributes, PixelBufferPoolAuxAttribute,
+            PixelBufferPoolAuxAttributes,
+        },
+        CVPixelBufferPool, CVPixelBufferPoolFlushFlags,
+    },
+};
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 10;
+
+fn create_pool() -> Result<CVPixelBufferPool, CVPixelBufferError> {
+    CVPixelBufferPool::create(
+        PixelBufferPoolAttributes::new(&[PixelBufferPoolAttribute::MinimumBufferCount(1)]),
+        PixelBufferAttributes::new(&[
+            PixelBufferAttribute::Width(WIDTH),
+            PixelBufferAttribute::Height(HEIGHT),
+            PixelBufferAttribute::PixelFormat(PixelFormatValue::Single(PixelFormat::BGRA32)),
+        ]),
+    )
+}
+
+#[test]
+fn test_create_pixel_buffer() -> Result<(), Box<dyn Error>> {
+    let pool = create_pool()?;
+    let pixel_buffer = pool.create_pixel_buffer()?;
+    assert_eq!(pixel_buffer.get_width(), WIDTH);
+    assert_eq!(pixel_buffer.get_height(), HEIGHT);
+    assert_eq!(pixel_buffer.get_pixel_format()?, PixelFormat::BGRA32);
+    assert_eq!(pool.get_attributes()?.minimum_buffer_count(), Some(1));
+    let attributes = pool.get_pixel_buffer_attributes()?;
+    assert_eq!(
+        (
+            attributes.get(PixelBufferAttributeKey::Width),
+            attributes.get(PixelBufferAttributeKey::Height)
+        ),
+        (
+            Some(&PixelBufferAttribute::Width(WIDTH)),
+            Some(&PixelBufferAttribute::Height(HEIGHT))
+        )
+    );
+    pool.flush(CVPixelBufferPoolFlushFlags::ExcessBuffers);
+    Ok(())
+}
+
+#[test]
+fn test_allocation_threshold() -> Result<(), Box<dyn Error>> {
+    let pool = create_pool()?;
+    let aux_attributes =
+        PixelBufferPoolAuxAttributes::new(&[PixelBufferPoolAuxAttribute::AllocationThreshold(1)]);
+    let _first = pool.create_pixel_buffer_with_aux_attributes(aux_attributes.clone())?;
+    let result = pool.create_pixel_buffer_with_aux_attributes(aux_attributes);
+    assert!(matches!(
+        result,
+        Err(CVPixelBufferError::WouldExceedAllocationThreshold)
+    ));
+    Ok(())
+}
@@ -1,8 +1,10 @@
+#![cfg(target_vendor = "apple")]
+
 use core_foundation::{base::TCFType, number::CFNumber, string::CFString};
 use core_graphics::display::CFDictionary;
-use core_utils_rs::four_char_code::FourCharCode;
 use core_video_rs::cv_pixel_buffer::{
-    attributes::PixelBufferAttributes, planar_data::PlanarDataPointer, CVPixelBuffer,
+    attributes::PixelBufferAttributes, error::CVPixelBufferError, pixel_format::PixelFormat,
+    planar_data::PlanarDataPointer, CVPixelBuffer,
 };
 use io_surface::{
     kIOSurfaceBytesPerElement, kIOSurfaceHeight, kIOSurfacePixelFormat, kIOSurfaceWidth,
@@ -34,8 +36,7 @@ fn test_create_with_io_surface() -> Result<(), Box<dyn Error>> {
                 ),
                 (
                     CFString::wrap_under_get_rule(kIOSurfacePixelFormat),
-                    CFNumber::from(FourCharCode::from_str("BGRA").unwrap().as_u32() as i64)
-                        .as_CFType(),
+                    CFNumber::from(PixelFormat::BGRA32.as_os_type() as i64).as_CFType(),
                 ),
             ])
         }),
@@ -51,11 +52,12 @@ fn test_create() -> Result<(), Box<dyn Error>> {
     let pixel_buffer = CVPixelBuffer::create(
         WIDTH,
         HEIGHT,
-        FourCharCode::from_str("BGRA").unwrap(),
+        PixelFormat::BGRA32,
         PixelBufferAttributes::default(),
     )?;
     assert_eq!(pixel_buffer.get_width(), WIDTH);
     assert_eq!(pixel_buffer.get_height(), HEIGHT);
+    assert_eq!(pixel_buffer.get_pixel_format()?, PixelFormat::BGRA32);
     Ok(())
 }
 #[derive(Debug)]
@@ -66,7 +68,10 @@ struct TestMoveStruct {
 #[test]
 fn test_create_with_planar_bytes_and_released() -> Result<(), Box<dyn Error>> {
     let data = vec![PIXEL_VALUE; SIZE];
-    let base_addresses = vec![data.clone(), data.clone()];
+    let base_addresses = vec![
+        vec![PIXEL_VALUE; WIDTH * HEIGHT],
+        vec![PIXEL_VALUE; WIDTH * HEIGHT / 2],
+    ];
     let expected_data = data.clone();
 
     let b = TestMoveStruct { var1: 33 };
@@ -74,12 +79,12 @@ fn test_create_with_planar_bytes_and_released() -> Result<(), Box<dyn Error>> {
         CVPixelBuffer::create_with_planar_bytes_release_cb(
             WIDTH,
             HEIGHT,
-            FourCharCode::from_str("BGRA").unwrap(),
+            PixelFormat::YCbCr420_8BiPlanarVideoRange,
             PlanarDataPointer::new(
                 Some(data),
-                vec![BYTE_PER_ROW, BYTE_PER_ROW],
                 vec![WIDTH, WIDTH],
-                vec![HEIGHT, HEIGHT],
+                vec![WIDTH, WIDTH / 2],
+                vec![HEIGHT, HEIGHT / 2],
                 base_addresses,
             ),
             |data| {
@@ -100,16 +105,34 @@ fn test_create_with_planar_bytes_and_released() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_create_with_planar_bytes_plane_count_mismatch() {
+    let result = CVPixelBuffer::create_with_planar_bytes(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        PlanarDataPointer::new(
+            None,
+            vec![BYTE_PER_ROW, BYTE_PER_ROW],
+            vec![WIDTH, WIDTH],
+            vec![HEIGHT, HEIGHT],
+            vec![vec![PIXEL_VALUE; SIZE], vec![PIXEL_VALUE; SIZE]],
+        ),
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(result, Err(CVPixelBufferError::InvalidArgument)));
+}
+
 #[test]
 fn test_create_with_bytes_and_release() -> Result<(), Box<dyn Error>> {
     let move_into_closure = vec![1, 2, 3];
     let pixel_buffer = CVPixelBuffer::create_with_bytes_release_cb(
         WIDTH,
         HEIGHT,
-        FourCharCode::from_str("BGRA").unwrap(),
+        PixelFormat::BGRA32,
         vec![PIXEL_VALUE; SIZE],
         BYTE_PER_ROW,
-        move | address| {
+        move |address| {
             assert_eq!(move_into_closure, vec![1, 2, 3]);
             assert!(!address.is_empty());
         },
@@ -119,3 +142,18 @@ fn test_create_with_bytes_and_release() -> Result<(), Box<dyn Error>> {
     assert_eq!(pixel_buffer.get_height(), HEIGHT);
     Ok(())
 }
+
+#[test]
+fn test_create_with_planar_bytes_for_format() -> Result<(), Box<dyn Error>> {
+    let pixel_buffer = CVPixelBuffer::create_with_planar_bytes(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PlanarDataPointer::for_format(PixelFormat::YCbCr420_8BiPlanarVideoRange, WIDTH, HEIGHT)?,
+        PixelBufferAttributes::default(),
+    )?;
+    assert!(pixel_buffer.is_planar());
+    assert_eq!(pixel_buffer.get_width(), WIDTH);
+    assert_eq!(pixel_buffer.get_height(), HEIGHT);
+    Ok(())
+}
@@ -0,0 +1,14 @@
+#![cfg(target_vendor = "apple")]
+use core_video_rs::cv_display_link::DisplayLink;
+
+#[test]
+fn test_create_with_active_displays() {
+    // Headless machines have no display to link to.
+    if let Ok(mut link) = DisplayLink::create_with_active_displays() {
+        assert!(!link.is_running());
+        link.set_output_callback(|_, _| {}).unwrap();
+        link.start().unwrap();
+        assert!(link.is_running());
+        link.stop().unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    ffi::c_void,
+    ptr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use core_video_rs::{
+    cv_display_link::{DisplayLink, RawOutputCallback, TimingSource},
+    cv_pixel_buffer::error::CVPixelBufferError,
+    types::{CVTime, CVTimeStamp},
+};
+
+#[derive(Default)]
+struct FakeClockState {
+    callback: Option<(RawOutputCallback, usize)>,
+    running: bool,
+    frame: u64,
+}
+
+/// Timing source driven by hand through `tick`.
+#[derive(Clone, Default)]
+struct FakeClock(Arc<Mutex<FakeClockState>>);
+
+impl FakeClock {
+    fn tick(&self) {
+        let (callback, context, frame) = {
+            let mut state = self.0.lock().unwrap();
+            if !state.running {
+                return;
+            }
+            state.frame += 1;
+            let (callback, context) = state.callback.expect("running without a callback");
+            (callback, context, state.frame)
+        };
+        let now = CVTimeStamp {
+            video_time_scale: 60,
+            video_time: frame as i64,
+            host_time: frame * 1000,
+            ..Default::default()
+        };
+        let output_time = CVTimeStamp {
+            video_time: frame as i64 + 1,
+            host_time: (frame + 1) * 1000,
+            ..now
+        };
+        let mut flags_out = 0;
+        unsafe {
+            callback(
+                ptr::null_mut(),
+                &now,
+                &output_time,
+                0,
+                &mut flags_out,
+                context as *mut c_void,
+            );
+        }
+    }
+    fn is_registered(&self) -> bool {
+        self.0.lock().unwrap().callback.is_some()
+    }
+}
+
+impl TimingSource for FakeClock {
+    unsafe fn set_output_callback(
+        &self,
+        callback: RawOutputCallback,
+        context: *mut c_void,
+    ) -> Result<(), CVPixelBufferError> {
+        self.0.lock().unwrap().callback = Some((callback, context as usize));
+        Ok(())
+    }
+    fn start(&self) -> Result<(), CVPixelBufferError> {
+        self.0.lock().unwrap().running = true;
+        Ok(())
+    }
+    fn stop(&self) -> Result<(), CVPixelBufferError> {
+        self.0.lock().unwrap().running = false;
+        Ok(())
+    }
+    fn is_running(&self) -> bool {
+        self.0.lock().unwrap().running
+    }
+    fn get_nominal_output_video_refresh_period(&self) -> CVTime {
+        CVTime::new(1, 60)
+    }
+    fn get_actual_output_video_refresh_period(&self) -> f64 {
+        1.0 / 60.0
+    }
+    fn get_output_video_latency(&self) -> CVTime {
+        CVTime::default()
+    }
+}
+
+#[test]
+fn test_callback_receives_time_stamps() -> Result<(), CVPixelBufferError> {
+    let clock = FakeClock::default();
+    let mut link = DisplayLink::new(clock.clone());
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let received = frames.clone();
+    link.set_output_callback(move |now, output_time| {
+        received
+            .lock()
+            .unwrap()
+            .push((now.video_time, output_time.host_time));
+    })?;
+    clock.tick();
+    link.start()?;
+    assert!(link.is_running());
+    clock.tick();
+    clock.tick();
+    link.stop()?;
+    clock.tick();
+    assert_eq!(*frames.lock().unwrap(), [(1, 2000), (2, 3000)]);
+    Ok(())
+}
+
+#[test]
+fn test_start_and_stop_state_errors() -> Result<(), CVPixelBufferError> {
+    let mut link = DisplayLink::new(FakeClock::default());
+    assert!(matches!(
+        link.start(),
+        Err(CVPixelBufferError::DisplayLinkCallbacksNotSet)
+    ));
+    assert!(matches!(
+        link.stop(),
+        Err(CVPixelBufferError::DisplayLinkNotRunning)
+    ));
+    link.set_output_callback(|_, _| {})?;
+    link.start()?;
+    assert!(matches!(
+        link.start(),
+        Err(CVPixelBufferError::DisplayLinkAlreadyRunning)
+    ));
+    assert!(matches!(
+        link.set_output_callback(|_, _| {}),
+        Err(CVPixelBufferError::DisplayLinkAlreadyRunning)
+    ));
+    link.stop()?;
+    assert!(!link.is_running());
+    Ok(())
+}
+
+#[test]
+fn test_replacing_the_callback_drops_the_previous_one() -> Result<(), CVPixelBufferError> {
+    let clock = FakeClock::default();
+    let mut link = DisplayLink::new(clock.clone());
+    let first = Arc::new(AtomicUsize::new(0));
+    let second = Arc::new(AtomicUsize::new(0));
+    let counter = first.clone();
+    link.set_output_callback(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    })?;
+    let counter = second.clone();
+    link.set_output_callback(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    })?;
+    assert_eq!(Arc::strong_count(&first), 1);
+    link.start()?;
+    clock.tick();
+    assert_eq!(first.load(Ordering::SeqCst), 0);
+    assert_eq!(second.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[test]
+fn test_drop_stops_before_releasing_the_callback() -> Result<(), CVPixelBufferError> {
+    let clock = FakeClock::default();
+    let mut link = DisplayLink::new(clock.clone());
+    let captured = Arc::new(());
+    let held = captured.clone();
+    link.set_output_callback(move |_, _| {
+        let _ = &held;
+    })?;
+    link.start()?;
+    assert!(clock.is_registered());
+    assert_eq!(Arc::strong_count(&captured), 2);
+    drop(link);
+    assert!(!clock.is_running());
+    assert_eq!(Arc::strong_count(&captured), 1);
+    // A stopped clock never calls into the released closure.
+    clock.tick();
+    Ok(())
+}
+
+#[test]
+fn test_restarting_a_shared_source_after_drop() -> Result<(), CVPixelBufferError> {
+    let clock = FakeClock::default();
+    let mut link = DisplayLink::new(clock.clone());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    link.set_output_callback(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    })?;
+    link.start()?;
+    clock.tick();
+    drop(link);
+    assert_eq!(
+        clock.0.lock().unwrap().callback.map(|(_, context)| context),
+        Some(0)
+    );
+    clock.start()?;
+    clock.tick();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(Arc::strong_count(&calls), 1);
+    Ok(())
+}
+
+#[test]
+fn test_refresh_periods() {
+    let link = DisplayLink::new(FakeClock::default());
+    assert_eq!(
+        link.get_nominal_output_video_refresh_period(),
+        CVTime::new(1, 60)
+    );
+    assert_eq!(link.get_actual_output_video_refresh_period(), 1.0 / 60.0);
+    assert_eq!(link.get_output_video_latency(), CVTime::default());
+}
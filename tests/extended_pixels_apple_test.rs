@@ -0,0 +1,37 @@
+#![cfg(target_vendor = "apple")]
+use std::error::Error;
+
+use core_video_rs::cv_pixel_buffer::{
+    attributes::{PixelBufferAttribute, PixelBufferAttributes},
+    pixel_format::PixelFormat,
+    CVPixelBuffer,
+};
+
+#[test]
+fn test_core_video_extended_pixels() -> Result<(), Box<dyn Error>> {
+    let mut buffer = CVPixelBuffer::create(
+        64,
+        32,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PixelBufferAttributes::new(&[
+            PixelBufferAttribute::ExtendedPixelsLeft(16),
+            PixelBufferAttribute::ExtendedPixelsTop(8),
+            PixelBufferAttribute::ExtendedPixelsRight(16),
+            PixelBufferAttribute::ExtendedPixelsBottom(8),
+        ]),
+    )?;
+    let extended = buffer.get_extended_pixels();
+    assert!(extended.left >= 16 && extended.top >= 8);
+    assert!(extended.right >= 16 && extended.bottom >= 8);
+    {
+        let mut guard = buffer.lock_mut()?;
+        guard.plane_mut(0)?.as_mut_slice().fill(0x80);
+    }
+    buffer.fill_extended_pixels()?;
+    let guard = buffer.lock()?;
+    let padded = guard.padded_plane(0)?;
+    assert_eq!(padded.width(), 64 + extended.left + extended.right);
+    assert_eq!(padded.height(), 32 + extended.top + extended.bottom);
+    assert_eq!(padded.row(0)[0], 0x80);
+    Ok(())
+}
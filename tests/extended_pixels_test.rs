@@ -0,0 +1,203 @@
+use std::error::Error;
+
+use core_video_rs::cv_pixel_buffer::{
+    attributes::{PixelBufferAttribute, PixelBufferAttributes},
+    error::CVPixelBufferError,
+    extended_pixels::{replicate_edges, ExtendedPixels},
+    pixel_format::PixelFormat,
+    plane::PlaneMut,
+    PixelBufferLike, PixelBufferView, PixelRect, SoftwarePixelBuffer,
+};
+
+fn extended_attributes(
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+) -> PixelBufferAttributes {
+    PixelBufferAttributes::new(&[
+        PixelBufferAttribute::ExtendedPixelsLeft(left),
+        PixelBufferAttribute::ExtendedPixelsTop(top),
+        PixelBufferAttribute::ExtendedPixelsRight(right),
+        PixelBufferAttribute::ExtendedPixelsBottom(bottom),
+    ])
+}
+
+/// Writes a distinct value to every visible sample, derived from its position.
+fn write_pattern<B: PixelBufferLike>(buffer: &mut B) -> Result<(), CVPixelBufferError> {
+    let mut guard = buffer.lock_mut()?;
+    for (plane_index, mut plane) in guard.planes_mut()?.into_iter().enumerate() {
+        let row_bytes = plane.width() * if plane_index == 0 { 1 } else { 2 };
+        for y in 0..plane.height() {
+            for (x, byte) in plane.row_mut(y)[..row_bytes].iter_mut().enumerate() {
+                *byte = (plane_index * 100 + y * 16 + x) as u8;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_query_rounds_to_chroma_samples() -> Result<(), Box<dyn Error>> {
+    let buffer = SoftwarePixelBuffer::create(
+        10,
+        6,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        extended_attributes(3, 1, 2, 0),
+    )?;
+    assert_eq!(
+        buffer.get_extended_pixels(),
+        ExtendedPixels::new(4, 2, 2, 0)
+    );
+    assert_eq!(
+        (buffer.get_width(), buffer.get_height()),
+        (10, 6),
+        "the visible size excludes the padding"
+    );
+    assert_eq!(buffer.get_width_of_plane(1), 5);
+
+    let guard = buffer.lock()?;
+    let (luma, padded_luma) = (guard.plane(0)?, guard.padded_plane(0)?);
+    assert_eq!((luma.width(), luma.height()), (10, 6));
+    assert_eq!((padded_luma.width(), padded_luma.height()), (16, 8));
+    assert_eq!(luma.bytes_per_row(), padded_luma.bytes_per_row());
+    let padded_chroma = guard.padded_plane(1)?;
+    assert_eq!((padded_chroma.width(), padded_chroma.height()), (8, 4));
+    assert_eq!(
+        luma.as_slice().as_ptr(),
+        padded_luma.as_slice()[2 * luma.bytes_per_row() + 4..].as_ptr()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fill_replicates_edges() -> Result<(), Box<dyn Error>> {
+    let mut buffer = SoftwarePixelBuffer::create(
+        6,
+        4,
+        PixelFormat::YCbCr420_8BiPlanarFullRange,
+        extended_attributes(4, 2, 2, 4),
+    )?;
+    write_pattern(&mut buffer)?;
+    buffer.fill_extended_pixels()?;
+
+    let guard = buffer.lock()?;
+    for (plane_index, bytes_per_sample) in [(0, 1), (1, 2)] {
+        let plane = guard.plane(plane_index)?;
+        let padded = guard.padded_plane(plane_index)?;
+        let padding = buffer.get_extended_pixels().of_plane(
+            &PixelFormat::YCbCr420_8BiPlanarFullRange
+                .layout()
+                .unwrap()
+                .planes[plane_index],
+        );
+        for y in 0..padded.height() {
+            let source_y = y.clamp(padding.top, padding.top + plane.height() - 1) - padding.top;
+            for x in 0..padded.width() {
+                let source_x =
+                    x.clamp(padding.left, padding.left + plane.width() - 1) - padding.left;
+                let sample = |row: &[u8], x: usize| {
+                    row[x * bytes_per_sample..(x + 1) * bytes_per_sample].to_vec()
+                };
+                assert_eq!(
+                    sample(padded.row(y), x),
+                    sample(plane.row(source_y), source_x),
+                    "plane {plane_index} at ({x}, {y})"
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_fill_repeats_packed_blocks() -> Result<(), Box<dyn Error>> {
+    let mut buffer = SoftwarePixelBuffer::create(
+        4,
+        1,
+        PixelFormat::YCbCr422_8,
+        extended_attributes(1, 0, 0, 1),
+    )?;
+    assert_eq!(
+        buffer.get_extended_pixels(),
+        ExtendedPixels::new(2, 0, 0, 1)
+    );
+    {
+        let mut guard = buffer.lock_mut()?;
+        guard.plane_mut(0)?.row_mut(0)[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+    buffer.fill_extended_pixels()?;
+    let guard = buffer.lock()?;
+    let padded = guard.padded_plane(0)?;
+    assert_eq!(&padded.row(0)[..12], &[1, 2, 3, 4, 1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(&padded.row(1)[..12], &padded.row(0)[..12]);
+    Ok(())
+}
+
+#[test]
+fn test_visible_planes_stay_writable() -> Result<(), Box<dyn Error>> {
+    // Without bottom padding the last visible row ends past the padded plane, which the
+    // layout has to leave room for.
+    for pixel_format in [PixelFormat::BGRA32, PixelFormat::YCbCr420_8Planar] {
+        let mut buffer =
+            SoftwarePixelBuffer::create(16, 4, pixel_format, extended_attributes(16, 2, 0, 0))?;
+        let mut guard = buffer.lock_mut()?;
+        for mut plane in guard.planes_mut()? {
+            assert_eq!(
+                plane.as_slice().len(),
+                plane.bytes_per_row() * plane.height()
+            );
+            plane.as_mut_slice().fill(0xff);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_without_extended_pixels() -> Result<(), Box<dyn Error>> {
+    let mut buffer = SoftwarePixelBuffer::create(
+        8,
+        8,
+        PixelFormat::OneComponent8,
+        PixelBufferAttributes::default(),
+    )?;
+    assert!(buffer.get_extended_pixels().is_empty());
+    buffer.fill_extended_pixels()?;
+    {
+        let guard = buffer.lock()?;
+        assert_eq!(
+            guard.plane(0)?.as_slice(),
+            guard.padded_plane(0)?.as_slice()
+        );
+    }
+
+    let padded = SoftwarePixelBuffer::create(
+        8,
+        8,
+        PixelFormat::OneComponent8,
+        extended_attributes(8, 8, 8, 8),
+    )?;
+    let view = PixelBufferView::new(&padded, PixelRect::new(0, 0, 4, 4))?;
+    assert!(view.get_extended_pixels().is_empty());
+    let guard = view.lock()?;
+    assert_eq!(guard.padded_plane(0)?.width(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_replicate_edges_rejects_bad_padding() {
+    let format = PixelFormat::YCbCr422_8.layout().unwrap().planes[0];
+    let mut data = vec![0; 16 * 4];
+    let mut plane = PlaneMut::new(&mut data, 4, 4, 16).unwrap();
+    for extended in [
+        ExtendedPixels::new(1, 0, 0, 0),
+        ExtendedPixels::new(2, 0, 2, 0),
+        ExtendedPixels::new(0, 2, 0, 2),
+    ] {
+        assert!(matches!(
+            replicate_edges(&mut plane, extended, &format),
+            Err(CVPixelBufferError::InvalidArgument)
+        ));
+    }
+    assert!(replicate_edges(&mut plane, ExtendedPixels::new(2, 1, 0, 1), &format).is_ok());
+}
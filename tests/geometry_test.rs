@@ -0,0 +1,139 @@
+use core_video_rs::{
+    attachments::{
+        CVAttachmentMode, CleanAperture, DisplayDimensions, ImageBufferAttachment,
+        PixelAspectRatio, Rational, Rect, Size,
+    },
+    cv_pixel_buffer::{
+        attributes::PixelBufferAttributes, pixel_format::PixelFormat, PixelBufferLike, PixelRect,
+        SoftwarePixelBuffer,
+    },
+};
+
+#[test]
+fn test_rational() {
+    assert_eq!(Rational::new(2, 4), Rational::new(1, 2));
+    assert_eq!(Rational::new(3, -6).reduced(), Rational::new(-1, 2));
+    assert_eq!(Rational::new(3, -6).reduced().denominator, 2);
+    assert!(Rational::new(1, 3) < Rational::new(1, 2));
+    assert!(Rational::new(8, 4).is_integer());
+    assert_eq!(Rational::from_f64(0.5), Rational::new(1, 2));
+    assert_eq!(Rational::from_f64(-1.25), Rational::new(-5, 4));
+    assert_eq!(Rational::from_f64(1.0 / 3.0), Rational::new(1, 3));
+    assert_eq!(Rational::from_f64(1920.0), Rational::integer(1920));
+    assert_eq!(Rational::from_f64(f64::NAN), Rational::integer(0));
+    assert_eq!(Rational::new(-7, 2).to_f64(), -3.5);
+
+    // Zero denominators order as signed infinities, with 0/0 last.
+    let ordered = [
+        Rational::new(-3, 0),
+        Rational::new(i32::MIN, 1),
+        Rational::new(1, -2),
+        Rational::new(0, 5),
+        Rational::new(3, 2),
+        Rational::new(1, 0),
+        Rational::new(0, 0),
+    ];
+    for pair in ordered.windows(2) {
+        assert!(pair[0] < pair[1]);
+    }
+    assert_eq!(Rational::new(-1, 0), Rational::new(-7, 0));
+    assert_ne!(Rational::new(0, 0), Rational::integer(0));
+    assert_eq!(Rational::new(0, 0), Rational::new(0, 0));
+
+    assert_eq!(-Rational::new(1, 2), Rational::new(-1, 2));
+    assert_eq!(-Rational::new(i32::MIN, 3), Rational::new(i32::MIN, -3));
+    assert_eq!(-Rational::new(i32::MIN, i32::MIN), Rational::integer(-1));
+    assert_eq!(
+        Rational::new(1, i32::MIN).reduced(),
+        Rational::new(1, i32::MIN)
+    );
+    assert!(Rational::new(i32::MIN, -1).is_integer());
+    assert!(!Rational::new(1, 0).is_integer());
+}
+
+#[test]
+fn test_clean_aperture() {
+    let aperture = CleanAperture::centered(1920, 1080);
+    assert_eq!(
+        aperture.clean_rect(Size::new(1920.0, 1088.0)),
+        Rect::new(0.0, 4.0, 1920.0, 1080.0)
+    );
+    assert_eq!(
+        aperture.crop_rect(1920, 1088),
+        PixelRect::new(0, 4, 1920, 1080)
+    );
+
+    // 704x480 NTSC with an 8 pixel overscan on each side, shifted half a pixel left.
+    let aperture = CleanAperture {
+        width: Rational::integer(688),
+        height: Rational::integer(480),
+        horizontal_offset: Rational::new(-1, 2),
+        vertical_offset: Rational::integer(0),
+    };
+    assert_eq!(
+        aperture.clean_rect(Size::new(704.0, 480.0)),
+        Rect::new(7.5, 0.0, 688.0, 480.0)
+    );
+    assert_eq!(aperture.crop_rect(704, 480), PixelRect::new(7, 0, 689, 480));
+
+    // Apertures larger than the image are clamped to it.
+    assert_eq!(
+        CleanAperture::centered(800, 600).crop_rect(640, 480),
+        PixelRect::new(0, 0, 640, 480)
+    );
+}
+
+#[test]
+fn test_pixel_aspect_ratio() {
+    let ratio = PixelAspectRatio {
+        horizontal_spacing: 10,
+        vertical_spacing: 11,
+    };
+    assert!(!ratio.is_square());
+    assert_eq!(
+        ratio.display_size(Size::new(704.0, 480.0)),
+        Size::new(640.0, 480.0)
+    );
+    assert_eq!(
+        PixelAspectRatio::default().display_size(Size::new(3.0, 2.0)),
+        Size::new(3.0, 2.0)
+    );
+}
+
+#[test]
+fn test_buffer_geometry() -> Result<(), Box<dyn std::error::Error>> {
+    let buffer = SoftwarePixelBuffer::create(
+        720,
+        480,
+        PixelFormat::YCbCr422_8,
+        PixelBufferAttributes::default(),
+    )?;
+    assert_eq!(buffer.get_encoded_size(), Size::new(720.0, 480.0));
+    assert_eq!(buffer.get_clean_rect(), Rect::new(0.0, 0.0, 720.0, 480.0));
+    assert_eq!(buffer.get_display_size(), Size::new(720.0, 480.0));
+
+    buffer.set_attachment(
+        &ImageBufferAttachment::CleanAperture(CleanAperture::centered(704, 480)),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    buffer.set_attachment(
+        &ImageBufferAttachment::PixelAspectRatio(PixelAspectRatio {
+            horizontal_spacing: 10,
+            vertical_spacing: 11,
+        }),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    assert_eq!(buffer.get_encoded_size(), Size::new(720.0, 480.0));
+    assert_eq!(buffer.get_clean_rect(), Rect::new(8.0, 0.0, 704.0, 480.0));
+    assert_eq!(buffer.get_display_size(), Size::new(640.0, 480.0));
+
+    buffer.set_attachment(
+        &ImageBufferAttachment::DisplayDimensions(DisplayDimensions {
+            width: 654,
+            height: 480,
+        }),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    assert_eq!(buffer.get_display_size(), Size::new(654.0, 480.0));
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use core_video_rs::attachments::{
+    Chromaticity, ContentLightLevelInfo, MasteringDisplayColorVolume, St2086,
+};
+
+// BT.2020 primaries with a D65 white point on a 1000 nit display.
+fn bt2020_mdcv() -> MasteringDisplayColorVolume {
+    MasteringDisplayColorVolume {
+        red: Chromaticity { x: 35400, y: 14600 },
+        green: Chromaticity { x: 8500, y: 39850 },
+        blue: Chromaticity { x: 6550, y: 2300 },
+        white_point: Chromaticity { x: 15635, y: 16450 },
+        max_luminance: 10_000_000,
+        min_luminance: 50,
+    }
+}
+
+#[test]
+fn test_content_light_level_bytes() {
+    let cll = ContentLightLevelInfo {
+        max_cll: 1000,
+        max_fall: 400,
+    };
+    assert_eq!(cll.to_bytes(), [0x03, 0xe8, 0x01, 0x90]);
+    assert_eq!(
+        ContentLightLevelInfo::from_bytes(&cll.to_bytes()).unwrap(),
+        cll
+    );
+    assert_eq!(cll.to_hevc_sei_payload(), cll.to_av1_metadata_payload());
+    assert!(ContentLightLevelInfo::from_bytes(&[0x03, 0xe8, 0x01]).is_err());
+}
+
+#[test]
+fn test_mastering_display_bytes() {
+    let mdcv = bt2020_mdcv();
+    let bytes = mdcv.to_bytes();
+    assert_eq!(bytes.len(), MasteringDisplayColorVolume::SIZE);
+    // Green comes first in the CoreVideo and HEVC layout.
+    assert_eq!(&bytes[0..4], &[0x21, 0x34, 0x9b, 0xaa]);
+    assert_eq!(&bytes[16..20], &10_000_000u32.to_be_bytes());
+    assert_eq!(
+        MasteringDisplayColorVolume::from_bytes(&bytes).unwrap(),
+        mdcv
+    );
+    assert_eq!(
+        MasteringDisplayColorVolume::from_hevc_sei_payload(&mdcv.to_hevc_sei_payload()).unwrap(),
+        mdcv
+    );
+    assert!(MasteringDisplayColorVolume::from_bytes(&bytes[1..]).is_err());
+}
+
+#[test]
+fn test_mastering_display_av1() {
+    let mdcv = bt2020_mdcv();
+    let payload = mdcv.to_av1_metadata_payload();
+    // Red first, as 0.16 fixed point: 0.708 * 65536 = 46399.49.
+    assert_eq!(&payload[0..2], &46399u16.to_be_bytes());
+    // 1000 cd/m² as 24.8 and 0.005 cd/m² as 18.14.
+    assert_eq!(&payload[16..20], &256_000u32.to_be_bytes());
+    assert_eq!(&payload[20..24], &82u32.to_be_bytes());
+    let decoded = MasteringDisplayColorVolume::from_av1_metadata_payload(&payload).unwrap();
+    assert_eq!(decoded.red, mdcv.red);
+    assert_eq!(decoded.white_point, mdcv.white_point);
+    assert_eq!(decoded.max_luminance, mdcv.max_luminance);
+    assert!(decoded.min_luminance.abs_diff(mdcv.min_luminance) <= 1);
+}
+
+#[test]
+fn test_mastering_display_st2086() {
+    let mdcv = bt2020_mdcv();
+    let st2086 = mdcv.to_st2086();
+    assert_eq!(st2086.red, (0.708, 0.292));
+    assert_eq!(st2086.white_point, (0.3127, 0.329));
+    assert_eq!(st2086.max_luminance, 1000.0);
+    assert_eq!(st2086.min_luminance, 0.005);
+    assert_eq!(MasteringDisplayColorVolume::from(st2086), mdcv);
+    assert_eq!(St2086::from(mdcv), st2086);
+    assert_eq!(Chromaticity::from_xy(0.17, 0.797), mdcv.green);
+}
@@ -0,0 +1,16 @@
+#![cfg(target_vendor = "apple")]
+use core_video_rs::host_time::{
+    get_current_host_time, get_host_clock_frequency, HostClock, SystemHostClock,
+};
+
+#[test]
+fn test_system_host_clock() {
+    let clock = SystemHostClock;
+    assert!(get_host_clock_frequency() > 0.0);
+    assert!(clock.minimum_time_delta() >= 1);
+    let before = get_current_host_time();
+    let after = clock.now();
+    assert!(after >= before);
+    let frequency = clock.timebase().frequency();
+    assert!((frequency - get_host_clock_frequency()).abs() / frequency < 1e-9);
+}
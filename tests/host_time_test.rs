@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+
+use core_video_rs::{
+    host_time::{FakeHostClock, HostClock, Timebase},
+    types::CVTime,
+};
+
+/// Timebase of Apple silicon, 24 MHz.
+const APPLE_SILICON: Timebase = Timebase::new(125, 3);
+
+#[test]
+fn test_timebase_from_frequency() {
+    assert_eq!(Timebase::from_frequency(24_000_000.0), Some(APPLE_SILICON));
+    assert_eq!(
+        Timebase::from_frequency(1_000_000_000.0),
+        Some(Timebase::NANOSECONDS)
+    );
+    assert_eq!(Timebase::from_frequency(0.0), None);
+    assert_eq!(Timebase::from_frequency(f64::NAN), None);
+    assert_eq!(APPLE_SILICON.frequency(), 24_000_000.0);
+}
+
+#[test]
+fn test_tick_conversions() {
+    assert_eq!(APPLE_SILICON.ticks_to_nanos(24_000_000), 1_000_000_000);
+    assert_eq!(APPLE_SILICON.nanos_to_ticks(1_000_000_000), 24_000_000);
+    assert_eq!(APPLE_SILICON.ticks_to_nanos(1), 41);
+    assert_eq!(
+        APPLE_SILICON.ticks_to_duration(36_000_000),
+        Duration::from_millis(1500)
+    );
+    assert_eq!(
+        APPLE_SILICON.duration_to_ticks(Duration::from_secs(2)),
+        48_000_000
+    );
+    assert_eq!(
+        APPLE_SILICON.ticks_to_cv_time(24_000_000 * 10 + 400_000, 600),
+        Some(CVTime::new(6010, 600))
+    );
+    assert_eq!(APPLE_SILICON.ticks_to_cv_time(1, 0), None);
+    assert_eq!(
+        APPLE_SILICON.cv_time_to_ticks(CVTime::new(1, 60)),
+        Some(400_000)
+    );
+    assert_eq!(APPLE_SILICON.cv_time_to_ticks(CVTime::INDEFINITE), None);
+}
+
+#[test]
+fn test_scaling_does_not_overflow() {
+    // A naive u64 `ticks * 125` overflows after about 4.7 years of uptime.
+    let ticks = u64::MAX / 100;
+    assert_eq!(
+        APPLE_SILICON.ticks_to_nanos(ticks),
+        (ticks as u128 * 125 / 3) as u64
+    );
+    assert_eq!(APPLE_SILICON.ticks_to_nanos(u64::MAX), u64::MAX);
+    assert_eq!(
+        APPLE_SILICON.nanos_to_ticks(u64::MAX),
+        (u64::MAX as u128 * 3 / 125) as u64
+    );
+    assert_eq!(
+        Timebase::new(1, 1_000).duration_to_ticks(Duration::MAX),
+        u64::MAX
+    );
+}
+
+#[test]
+fn test_fake_clock() {
+    let clock = FakeHostClock::new(1_000, APPLE_SILICON);
+    let start = clock.now();
+    clock.advance_by(Duration::from_millis(500));
+    assert_eq!(clock.now(), 1_000 + 12_000_000);
+    assert_eq!(clock.elapsed_since(start), Duration::from_millis(500));
+    assert_eq!(clock.elapsed_since(clock.now() + 1), Duration::ZERO);
+    clock.set(24_000_000);
+    assert_eq!(clock.now_nanos(), 1_000_000_000);
+    assert_eq!(clock.minimum_time_delta(), 1);
+}
+
+#[test]
+fn test_instant_bridge() {
+    let clock = FakeHostClock::new(24_000_000, APPLE_SILICON);
+    let bridge = clock.instant_bridge();
+    let later = bridge.to_instant(24_000_000 + 2_400_000).unwrap();
+    assert_eq!(later - bridge.instant, Duration::from_millis(100));
+    let earlier = bridge.to_instant(24_000_000 - 24_000).unwrap();
+    assert_eq!(bridge.instant - earlier, Duration::from_millis(1));
+    assert_eq!(bridge.to_ticks(later), 26_400_000);
+    assert_eq!(bridge.to_ticks(earlier), 23_976_000);
+    assert!(bridge.to_ticks(Instant::now()) >= bridge.ticks);
+}
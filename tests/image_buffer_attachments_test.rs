@@ -0,0 +1,123 @@
+use core_video_rs::{
+    attachments::{
+        CVAttachmentMode, CleanAperture, ImageBufferAttachment, ImageBufferAttachmentKey,
+        ImageBufferAttachments, PixelAspectRatio,
+    },
+    constants::{ColorPrimaries, YCbCrMatrix},
+    cv_pixel_buffer::{
+        attributes::PixelBufferAttributes, pixel_format::PixelFormat, SoftwarePixelBuffer,
+    },
+};
+
+#[test]
+fn test_add_replaces_same_key() {
+    let mut attachments = ImageBufferAttachments::new(&[
+        ImageBufferAttachment::ColorPrimaries(ColorPrimaries::ItuR709_2),
+        ImageBufferAttachment::FieldCount(1),
+    ]);
+    let replaced = attachments.add(ImageBufferAttachment::ColorPrimaries(
+        ColorPrimaries::ItuR2020,
+    ));
+    assert_eq!(
+        replaced,
+        Some(ImageBufferAttachment::ColorPrimaries(
+            ColorPrimaries::ItuR709_2
+        ))
+    );
+    assert_eq!(attachments.len(), 2);
+    assert_eq!(
+        attachments.get(ImageBufferAttachmentKey::ColorPrimaries),
+        Some(&ImageBufferAttachment::ColorPrimaries(
+            ColorPrimaries::ItuR2020
+        ))
+    );
+}
+
+#[test]
+fn test_remove() {
+    let mut attachments: ImageBufferAttachments = [
+        ImageBufferAttachment::GammaLevel(2.2),
+        ImageBufferAttachment::AlphaChannelIsOpaque(true),
+    ]
+    .into_iter()
+    .collect();
+    assert!(attachments
+        .remove(ImageBufferAttachmentKey::GammaLevel)
+        .is_some());
+    assert!(attachments
+        .remove(ImageBufferAttachmentKey::GammaLevel)
+        .is_none());
+    assert_eq!(attachments.len(), 1);
+    attachments.clear();
+    assert!(attachments.is_empty());
+}
+
+#[test]
+fn test_keys() {
+    for attachment in [
+        ImageBufferAttachment::CleanAperture(CleanAperture::centered(1920, 1080)),
+        ImageBufferAttachment::PixelAspectRatio(PixelAspectRatio {
+            horizontal_spacing: 1,
+            vertical_spacing: 1,
+        }),
+        ImageBufferAttachment::ICCProfile(vec![1, 2, 3]),
+    ] {
+        assert!(ImageBufferAttachmentKey::ALL.contains(&attachment.key()));
+    }
+}
+
+#[test]
+fn test_software_pixel_buffer_attachments() {
+    let source = SoftwarePixelBuffer::create(
+        16,
+        16,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )
+    .unwrap();
+    let destination = SoftwarePixelBuffer::create(
+        16,
+        16,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )
+    .unwrap();
+    source.set_attachment(
+        &ImageBufferAttachment::YCbCrMatrix(YCbCrMatrix::ItuR709_2),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    source.set_attachment(
+        &ImageBufferAttachment::FieldCount(2),
+        CVAttachmentMode::ShouldNotPropagate,
+    );
+    assert_eq!(
+        source.get_attachment(ImageBufferAttachmentKey::FieldCount),
+        Some((
+            ImageBufferAttachment::FieldCount(2),
+            CVAttachmentMode::ShouldNotPropagate
+        ))
+    );
+    assert_eq!(
+        source
+            .get_attachments(CVAttachmentMode::ShouldPropagate)
+            .len(),
+        1
+    );
+
+    source.propagate_attachments(&destination);
+    assert!(destination
+        .get_attachment(ImageBufferAttachmentKey::YCbCrMatrix)
+        .is_some());
+    assert!(destination
+        .get_attachment(ImageBufferAttachmentKey::FieldCount)
+        .is_none());
+
+    source.remove_attachment(ImageBufferAttachmentKey::FieldCount);
+    assert!(source
+        .get_attachment(ImageBufferAttachmentKey::FieldCount)
+        .is_none());
+    source.remove_all_attachments();
+    assert!(source
+        .get_attachments(CVAttachmentMode::ShouldPropagate)
+        .is_empty());
+}
@@ -0,0 +1,5 @@
+#[test]
+fn test_lock_guard_lifetimes() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,226 @@
+#![cfg(target_vendor = "apple")]
+use core_foundation::{
+    base::{kCFAllocatorSystemDefault, CFType, TCFType},
+    number::CFNumber,
+    string::{CFString, CFStringRef},
+};
+use core_graphics::display::CFDictionary;
+use core_video_rs::{
+    cv_pixel_buffer::{
+        attributes::{PixelBufferAttribute, PixelBufferAttributeKey, PixelBufferAttributes},
+        error::CVPixelBufferError,
+        pixel_format::PixelFormat,
+        CVPixelBuffer,
+    },
+    dictionary::DictionaryValue,
+};
+
+extern "C" {
+    static kCVPixelBufferMemoryAllocatorKey: CFStringRef;
+    static kCVPixelBufferPixelFormatTypeKey: CFStringRef;
+    static kCVPixelBufferWidthKey: CFStringRef;
+    static kCVPixelBufferHeightKey: CFStringRef;
+    static kCVPixelBufferExtendedPixelsLeftKey: CFStringRef;
+    static kCVPixelBufferExtendedPixelsTopKey: CFStringRef;
+    static kCVPixelBufferExtendedPixelsRightKey: CFStringRef;
+    static kCVPixelBufferExtendedPixelsBottomKey: CFStringRef;
+    static kCVPixelBufferBytesPerRowAlignmentKey: CFStringRef;
+    static kCVPixelBufferCGBitmapContextCompatibilityKey: CFStringRef;
+    static kCVPixelBufferCGImageCompatibilityKey: CFStringRef;
+    static kCVPixelBufferOpenGLCompatibilityKey: CFStringRef;
+    static kCVPixelBufferPlaneAlignmentKey: CFStringRef;
+    static kCVPixelBufferIOSurfacePropertiesKey: CFStringRef;
+    static kCVPixelBufferOpenGLESCompatibilityKey: CFStringRef;
+    static kCVPixelBufferMetalCompatibilityKey: CFStringRef;
+    static kCVPixelBufferIOSurfaceCoreAnimationCompatibilityKey: CFStringRef;
+    static kCVPixelBufferIOSurfaceOpenGLFBOCompatibilityKey: CFStringRef;
+    static kCVPixelBufferIOSurfaceOpenGLESFBOCompatibilityKey: CFStringRef;
+    static kCVPixelBufferIOSurfaceOpenGLTextureCompatibilityKey: CFStringRef;
+    static kCVPixelBufferIOSurfaceOpenGLESTextureCompatibilityKey: CFStringRef;
+    static kCVPixelBufferOpenGLTextureCacheCompatibilityKey: CFStringRef;
+    static kCVPixelBufferOpenGLESTextureCacheCompatibilityKey: CFStringRef;
+}
+
+fn constant(key: PixelBufferAttributeKey) -> String {
+    let constant = unsafe {
+        match key {
+            PixelBufferAttributeKey::MemoryAllocator => kCVPixelBufferMemoryAllocatorKey,
+            PixelBufferAttributeKey::PixelFormat => kCVPixelBufferPixelFormatTypeKey,
+            PixelBufferAttributeKey::Width => kCVPixelBufferWidthKey,
+            PixelBufferAttributeKey::Height => kCVPixelBufferHeightKey,
+            PixelBufferAttributeKey::ExtendedPixelsLeft => kCVPixelBufferExtendedPixelsLeftKey,
+            PixelBufferAttributeKey::ExtendedPixelsTop => kCVPixelBufferExtendedPixelsTopKey,
+            PixelBufferAttributeKey::ExtendedPixelsRight => kCVPixelBufferExtendedPixelsRightKey,
+            PixelBufferAttributeKey::ExtendedPixelsBottom => kCVPixelBufferExtendedPixelsBottomKey,
+            PixelBufferAttributeKey::BytesPerRowAlignment => kCVPixelBufferBytesPerRowAlignmentKey,
+            PixelBufferAttributeKey::CGBitmapContextCompatibility => {
+                kCVPixelBufferCGBitmapContextCompatibilityKey
+            }
+            PixelBufferAttributeKey::CGImageCompatibility => kCVPixelBufferCGImageCompatibilityKey,
+            PixelBufferAttributeKey::OpenGLCompatibility => kCVPixelBufferOpenGLCompatibilityKey,
+            PixelBufferAttributeKey::PlaneAlignment => kCVPixelBufferPlaneAlignmentKey,
+            PixelBufferAttributeKey::IOSurfaceProperties => kCVPixelBufferIOSurfacePropertiesKey,
+            PixelBufferAttributeKey::OpenGLESCompatibility => {
+                kCVPixelBufferOpenGLESCompatibilityKey
+            }
+            PixelBufferAttributeKey::MetalCompatibility => kCVPixelBufferMetalCompatibilityKey,
+            PixelBufferAttributeKey::IOSurfaceCoreAnimationCompatibility => {
+                kCVPixelBufferIOSurfaceCoreAnimationCompatibilityKey
+            }
+            PixelBufferAttributeKey::IOSurfaceOpenGLFBOCompatibility => {
+                kCVPixelBufferIOSurfaceOpenGLFBOCompatibilityKey
+            }
+            PixelBufferAttributeKey::IOSurfaceOpenGLESFBOCompatibility => {
+                kCVPixelBufferIOSurfaceOpenGLESFBOCompatibilityKey
+            }
+            PixelBufferAttributeKey::IOSurfaceOpenGLTextureCompatibility => {
+                kCVPixelBufferIOSurfaceOpenGLTextureCompatibilityKey
+            }
+            PixelBufferAttributeKey::IOSurfaceOpenGLESTextureCompatibility => {
+                kCVPixelBufferIOSurfaceOpenGLESTextureCompatibilityKey
+            }
+            PixelBufferAttributeKey::OpenGLTextureCacheCompatibility => {
+                kCVPixelBufferOpenGLTextureCacheCompatibilityKey
+            }
+            PixelBufferAttributeKey::OpenGLESTextureCacheCompatibility => {
+                kCVPixelBufferOpenGLESTextureCacheCompatibilityKey
+            }
+        }
+    };
+    unsafe { CFString::wrap_under_get_rule(constant) }.to_string()
+}
+
+#[test]
+fn test_keys_match_core_video() {
+    for &key in PixelBufferAttributeKey::ALL {
+        assert_eq!(key.as_str(), constant(key), "{key:?}");
+    }
+}
+
+#[test]
+fn test_cf_dictionary_round_trip() -> Result<(), CVPixelBufferError> {
+    let mut attributes = PixelBufferAttributes::builder()
+        .pixel_formats([
+            PixelFormat::BGRA32,
+            PixelFormat::YCbCr420_8BiPlanarFullRange,
+        ])
+        .size(320, 240)
+        .bytes_per_row_alignment(64)
+        .metal_compatibility(true)
+        .build()?;
+    attributes.set_unknown("CustomKey", DictionaryValue::from("value"));
+
+    let dictionary: CFDictionary<CFString, CFType> = attributes.clone().into();
+    let parsed = PixelBufferAttributes::try_from(dictionary)?;
+    for attribute in attributes.effective() {
+        assert_eq!(parsed.get(attribute.key()), Some(attribute));
+    }
+    assert_eq!(parsed.unknown(), attributes.unknown());
+    Ok(())
+}
+
+#[test]
+fn test_cf_dictionary_skips_memory_allocator() -> Result<(), CVPixelBufferError> {
+    let dictionary = CFDictionary::from_CFType_pairs(&[
+        (
+            CFString::new(PixelBufferAttributeKey::MemoryAllocator.as_str()),
+            unsafe { CFType::wrap_under_get_rule(kCFAllocatorSystemDefault.cast()) },
+        ),
+        (
+            CFString::new(PixelBufferAttributeKey::Width.as_str()),
+            CFNumber::from(64).as_CFType(),
+        ),
+    ]);
+    let parsed = PixelBufferAttributes::try_from(dictionary)?;
+    assert!(parsed
+        .get(PixelBufferAttributeKey::MemoryAllocator)
+        .is_none());
+    assert_eq!(
+        parsed.get(PixelBufferAttributeKey::Width),
+        Some(&PixelBufferAttribute::Width(64))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_create_resolved_attributes() -> Result<(), CVPixelBufferError> {
+    let sets = [
+        PixelBufferAttributes::builder()
+            .pixel_format(PixelFormat::BGRA32)
+            .width(64)
+            .build()?,
+        PixelBufferAttributes::builder()
+            .metal_compatibility(true)
+            .build()?,
+    ];
+    let resolved = CVPixelBuffer::create_resolved_attributes(&sets)?;
+    let software = PixelBufferAttributes::resolve(&sets)?;
+    for key in [
+        PixelBufferAttributeKey::Width,
+        PixelBufferAttributeKey::MetalCompatibility,
+    ] {
+        assert_eq!(resolved.get(key), software.get(key));
+    }
+    assert!(matches!(
+        resolved.get(PixelBufferAttributeKey::PixelFormat),
+        Some(PixelBufferAttribute::PixelFormat(value))
+            if value.as_slice() == [PixelFormat::BGRA32]
+    ));
+    Ok(())
+}
+
+/// Resolves `sets` with CoreVideo and with the pure Rust rules and compares the results.
+fn assert_resolves_like_core_video(
+    sets: &[PixelBufferAttributes],
+) -> Result<(), CVPixelBufferError> {
+    let resolved = CVPixelBuffer::create_resolved_attributes(sets)?;
+    let software = PixelBufferAttributes::resolve(sets)?;
+    let pixel_formats = |attributes: &PixelBufferAttributes| match attributes
+        .get(PixelBufferAttributeKey::PixelFormat)
+    {
+        Some(PixelBufferAttribute::PixelFormat(value)) => value.as_slice().to_vec(),
+        _ => Vec::new(),
+    };
+    assert_eq!(pixel_formats(&resolved), pixel_formats(&software));
+    for key in [
+        PixelBufferAttributeKey::Width,
+        PixelBufferAttributeKey::Height,
+    ] {
+        assert_eq!(resolved.get(key), software.get(key), "{key:?}");
+    }
+    assert_eq!(
+        resolved
+            .get(PixelBufferAttributeKey::IOSurfaceProperties)
+            .is_some(),
+        software
+            .get(PixelBufferAttributeKey::IOSurfaceProperties)
+            .is_some()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_resolve_conflicts_like_core_video() -> Result<(), CVPixelBufferError> {
+    let narrow = PixelBufferAttributes::builder().size(64, 48).build()?;
+    let wide = PixelBufferAttributes::builder().size(128, 32).build()?;
+    assert_resolves_like_core_video(&[narrow.clone(), wide.clone()])?;
+    assert_resolves_like_core_video(&[wide, narrow])?;
+
+    let bgra = PixelBufferAttributes::builder()
+        .pixel_format(PixelFormat::BGRA32)
+        .build()?;
+    let yuv = PixelBufferAttributes::builder()
+        .pixel_format(PixelFormat::YCbCr420_8BiPlanarVideoRange)
+        .build()?;
+    assert_resolves_like_core_video(&[bgra.clone(), yuv.clone()])?;
+    assert_resolves_like_core_video(&[yuv, bgra.clone()])?;
+
+    let surface = PixelBufferAttributes::builder()
+        .io_surface_properties(
+            CFDictionary::<CFString, CFType>::from_CFType_pairs(&[]).to_untyped(),
+        )
+        .build()?;
+    assert_resolves_like_core_video(&[surface.clone(), bgra.clone()])?;
+    assert_resolves_like_core_video(&[bgra, surface])?;
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use core_video_rs::{
+    cv_pixel_buffer::{
+        attributes::{
+            PixelBufferAttribute, PixelBufferAttributeKey, PixelBufferAttributes, PixelFormatValue,
+        },
+        error::CVPixelBufferError,
+        pixel_format::PixelFormat,
+        ExtendedPixels, SoftwarePixelBuffer,
+    },
+    dictionary::{Dictionary, DictionaryValue},
+};
+
+#[test]
+fn test_from_dictionary_keeps_unknown_keys() -> Result<(), CVPixelBufferError> {
+    let dictionary = Dictionary::from([
+        (
+            "PixelFormatType".to_string(),
+            DictionaryValue::Array(vec![
+                PixelFormat::BGRA32.as_os_type().into(),
+                PixelFormat::YCbCr420_8BiPlanarVideoRange
+                    .as_os_type()
+                    .into(),
+            ]),
+        ),
+        ("Width".to_string(), 1920i64.into()),
+        ("Height".to_string(), 1080.0.into()),
+        ("MetalCompatibility".to_string(), 1i64.into()),
+        ("CacheMode".to_string(), 2i64.into()),
+    ]);
+    let attributes = PixelBufferAttributes::from_dictionary(&dictionary)?;
+    assert_eq!(
+        attributes.get(PixelBufferAttributeKey::PixelFormat),
+        Some(&PixelBufferAttribute::PixelFormat(PixelFormatValue::Many(
+            vec![
+                PixelFormat::BGRA32,
+                PixelFormat::YCbCr420_8BiPlanarVideoRange
+            ]
+        )))
+    );
+    assert_eq!(
+        attributes.get(PixelBufferAttributeKey::Height),
+        Some(&PixelBufferAttribute::Height(1080))
+    );
+    assert_eq!(
+        attributes.get(PixelBufferAttributeKey::MetalCompatibility),
+        Some(&PixelBufferAttribute::MetalCompatibility(true))
+    );
+    assert_eq!(
+        attributes.unknown(),
+        &Dictionary::from([("CacheMode".to_string(), 2i64.into())])
+    );
+    assert_eq!(attributes.len(), 5);
+
+    let mut rebuilt = PixelBufferAttributes::builder()
+        .merge(&attributes)
+        .build()?;
+    assert_eq!(rebuilt.unknown(), attributes.unknown());
+    assert_eq!(rebuilt.remove_unknown("CacheMode"), Some(2i64.into()));
+    assert!(rebuilt.unknown().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_from_dictionary_unknown_pixel_formats_and_bad_values() {
+    let unknown_format = DictionaryValue::Array(vec![
+        PixelFormat::BGRA32.as_os_type().into(),
+        0x7a7a7a7au32.into(),
+    ]);
+    let attributes = PixelBufferAttributes::from_dictionary(&Dictionary::from([(
+        "PixelFormatType".to_string(),
+        unknown_format.clone(),
+    )]))
+    .unwrap();
+    assert!(attributes
+        .get(PixelBufferAttributeKey::PixelFormat)
+        .is_none());
+    assert_eq!(
+        attributes.unknown().get("PixelFormatType"),
+        Some(&unknown_format)
+    );
+
+    for (key, value) in [
+        ("Width", DictionaryValue::from("wide")),
+        ("Height", DictionaryValue::from(-1i64)),
+        ("CGImageCompatibility", DictionaryValue::from(1.5)),
+        ("PixelFormatType", DictionaryValue::from("BGRA")),
+    ] {
+        let result =
+            PixelBufferAttributes::from_dictionary(&Dictionary::from([(key.to_string(), value)]));
+        assert!(
+            matches!(
+                result,
+                Err(CVPixelBufferError::InvalidPixelBufferAttributes)
+            ),
+            "{key}: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn test_resolve_combines_requirements() -> Result<(), CVPixelBufferError> {
+    let mut encoder = PixelBufferAttributes::builder()
+        .pixel_formats([
+            PixelFormat::YCbCr420_8BiPlanarVideoRange,
+            PixelFormat::YCbCr420_8BiPlanarFullRange,
+            PixelFormat::BGRA32,
+        ])
+        .size(1280, 720)
+        .bytes_per_row_alignment(16)
+        .extended_pixels(ExtendedPixels::new(2, 0, 8, 0))
+        .metal_compatibility(false)
+        .build()?;
+    encoder.set_unknown("CacheMode", 1i64.into());
+    let mut display = PixelBufferAttributes::builder()
+        .pixel_formats([
+            PixelFormat::BGRA32,
+            PixelFormat::YCbCr420_8BiPlanarFullRange,
+        ])
+        .width(1280)
+        .bytes_per_row_alignment(64)
+        .plane_alignment(32)
+        .extended_pixels(ExtendedPixels::new(0, 4, 2, 0))
+        .metal_compatibility(true)
+        .cg_image_compatibility(false)
+        .build()?;
+    display.set_unknown("CacheMode", 2i64.into());
+
+    let resolved = SoftwarePixelBuffer::create_resolved_attributes(&[encoder, display])?;
+    let get = |key| resolved.get(key).cloned();
+    assert_eq!(
+        get(PixelBufferAttributeKey::PixelFormat),
+        Some(PixelBufferAttribute::PixelFormat(PixelFormatValue::Many(
+            vec![
+                PixelFormat::YCbCr420_8BiPlanarFullRange,
+                PixelFormat::BGRA32
+            ]
+        )))
+    );
+    assert_eq!(
+        (
+            get(PixelBufferAttributeKey::Width),
+            get(PixelBufferAttributeKey::Height)
+        ),
+        (
+            Some(PixelBufferAttribute::Width(1280)),
+            Some(PixelBufferAttribute::Height(720))
+        )
+    );
+    assert_eq!(
+        get(PixelBufferAttributeKey::BytesPerRowAlignment),
+        Some(PixelBufferAttribute::BytesPerRowAlignment(64))
+    );
+    assert_eq!(
+        get(PixelBufferAttributeKey::PlaneAlignment),
+        Some(PixelBufferAttribute::PlaneAlignment(32))
+    );
+    assert_eq!(
+        ExtendedPixels::from_attributes(&resolved),
+        ExtendedPixels::new(2, 4, 8, 0)
+    );
+    assert_eq!(
+        get(PixelBufferAttributeKey::MetalCompatibility),
+        Some(PixelBufferAttribute::MetalCompatibility(true))
+    );
+    assert_eq!(
+        get(PixelBufferAttributeKey::CGImageCompatibility),
+        Some(PixelBufferAttribute::CGImageCompatibility(false))
+    );
+    assert_eq!(resolved.unknown().get("CacheMode"), Some(&1i64.into()));
+    Ok(())
+}
+
+#[test]
+fn test_resolve_conflicts() -> Result<(), CVPixelBufferError> {
+    let bgra = PixelBufferAttributes::builder()
+        .pixel_format(PixelFormat::BGRA32)
+        .width(640)
+        .build()?;
+    let yuv = PixelBufferAttributes::builder()
+        .pixel_format(PixelFormat::YCbCr420_8BiPlanarFullRange)
+        .height(480)
+        .build()?;
+    let wide = PixelBufferAttributes::builder().size(1280, 360).build()?;
+
+    // Without a common pixel format the earlier set's is kept.
+    let resolved = PixelBufferAttributes::resolve(&[bgra.clone(), yuv.clone()])?;
+    assert_eq!(
+        resolved.get(PixelBufferAttributeKey::PixelFormat),
+        Some(&PixelBufferAttribute::PixelFormat(
+            PixelFormatValue::Single(PixelFormat::BGRA32)
+        ))
+    );
+    let resolved = PixelBufferAttributes::resolve(&[yuv.clone(), bgra.clone()])?;
+    assert_eq!(
+        resolved.get(PixelBufferAttributeKey::PixelFormat),
+        Some(&PixelBufferAttribute::PixelFormat(
+            PixelFormatValue::Single(PixelFormat::YCbCr420_8BiPlanarFullRange)
+        ))
+    );
+
+    // Sizes take the largest value.
+    let resolved = PixelBufferAttributes::resolve(&[bgra.clone(), yuv, wide])?;
+    assert_eq!(
+        (
+            resolved.get(PixelBufferAttributeKey::Width),
+            resolved.get(PixelBufferAttributeKey::Height)
+        ),
+        (
+            Some(&PixelBufferAttribute::Width(1280)),
+            Some(&PixelBufferAttribute::Height(480))
+        )
+    );
+
+    assert_eq!(
+        PixelBufferAttributes::resolve(&[bgra.clone(), bgra.clone()])?,
+        bgra
+    );
+    assert!(PixelBufferAttributes::resolve(&[])?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "serde")]
+use core_video_rs::cv_pixel_buffer::{
+    attributes::{PixelBufferAttribute, PixelBufferAttributes},
+    pixel_format::PixelFormat,
+};
+
+#[test]
+fn test_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let attributes = PixelBufferAttributes::builder()
+        .pixel_formats([
+            PixelFormat::YCbCr420_8BiPlanarVideoRange,
+            PixelFormat::ARGB32,
+        ])
+        .size(1920, 1080)
+        .bytes_per_row_alignment(64)
+        .io_surface_core_animation_compatibility(true)
+        .build()?;
+    let json = serde_json::to_string(&attributes)?;
+    assert_eq!(
+        json,
+        r#"{"PixelFormatType":["420v",32],"Width":1920,"Height":1080,"BytesPerRowAlignment":64,"IOSurfaceCoreAnimationCompatibility":true}"#
+    );
+    let parsed: PixelBufferAttributes = serde_json::from_str(&json)?;
+    for attribute in attributes.as_slice() {
+        assert_eq!(parsed.get(attribute.key()), Some(attribute));
+    }
+    assert_eq!(parsed.len(), attributes.len());
+    Ok(())
+}
+
+#[test]
+fn test_only_effective_values_are_written() {
+    let mut attributes = PixelBufferAttributes::default();
+    attributes.add(PixelBufferAttribute::Width(1));
+    attributes.add(PixelBufferAttribute::Width(2));
+    assert_eq!(
+        serde_json::to_string(&attributes).unwrap(),
+        r#"{"Width":2}"#
+    );
+}
+
+#[test]
+fn test_invalid_config_is_rejected() {
+    for json in [
+        r#"{"Width":0}"#,
+        r#"{"BytesPerRowAlignment":48}"#,
+        r#"{"PixelFormatType":"????"}"#,
+        r#"{"PixelFormatType":[]}"#,
+        r#"{"MetalCompatibility":"yes"}"#,
+        r#"{"Colour":true}"#,
+    ] {
+        assert!(
+            serde_json::from_str::<PixelBufferAttributes>(json).is_err(),
+            "{json}"
+        );
+    }
+    let parsed: PixelBufferAttributes =
+        serde_json::from_str(r#"{"PixelFormatType":1111970369}"#).unwrap();
+    assert_eq!(
+        format!("{parsed:?}"),
+        format!("{{PixelFormat: Single({:?})}}", PixelFormat::BGRA32)
+    );
+    // Values are read like dictionary entries, where flags may be numbers.
+    let parsed: PixelBufferAttributes =
+        serde_json::from_str(r#"{"MetalCompatibility":1}"#).unwrap();
+    assert_eq!(format!("{parsed:?}"), "{MetalCompatibility: true}");
+}
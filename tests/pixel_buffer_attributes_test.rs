@@ -0,0 +1,181 @@
+use core_video_rs::cv_pixel_buffer::{
+    attributes::{
+        PixelBufferAttribute, PixelBufferAttributeKey, PixelBufferAttributes,
+        PixelBufferAttributesBuilder, PixelFormatValue,
+    },
+    error::CVPixelBufferError,
+    pixel_format::PixelFormat,
+    ExtendedPixels, SoftwarePixelBuffer,
+};
+
+#[test]
+fn test_builder_last_write_wins() -> Result<(), CVPixelBufferError> {
+    let attributes = PixelBufferAttributes::builder()
+        .pixel_format(PixelFormat::BGRA32)
+        .size(1920, 1080)
+        .width(1280)
+        .bytes_per_row_alignment(16)
+        .metal_compatibility(false)
+        .metal_compatibility(true)
+        .extended_pixels(ExtendedPixels::new(1, 2, 3, 4))
+        .build()?;
+    assert_eq!(
+        attributes.as_slice(),
+        [
+            PixelBufferAttribute::PixelFormat(PixelFormatValue::Single(PixelFormat::BGRA32)),
+            PixelBufferAttribute::Height(1080),
+            PixelBufferAttribute::Width(1280),
+            PixelBufferAttribute::BytesPerRowAlignment(16),
+            PixelBufferAttribute::MetalCompatibility(true),
+            PixelBufferAttribute::ExtendedPixelsLeft(1),
+            PixelBufferAttribute::ExtendedPixelsTop(2),
+            PixelBufferAttribute::ExtendedPixelsRight(3),
+            PixelBufferAttribute::ExtendedPixelsBottom(4),
+        ]
+    );
+    assert_eq!(
+        ExtendedPixels::from_attributes(&attributes),
+        ExtendedPixels::new(1, 2, 3, 4)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_builder_validation() {
+    let cases = [
+        (
+            PixelBufferAttributes::builder().bytes_per_row_alignment(48),
+            "InvalidPixelBufferAttributes",
+        ),
+        (
+            PixelBufferAttributes::builder().plane_alignment(0),
+            "InvalidPixelBufferAttributes",
+        ),
+        (
+            PixelBufferAttributes::builder().size(0, 1080),
+            "InvalidSize",
+        ),
+        (PixelBufferAttributes::builder().height(0), "InvalidSize"),
+        (
+            PixelBufferAttributes::builder().pixel_formats([]),
+            "InvalidPixelFormat",
+        ),
+        (
+            PixelBufferAttributes::builder().pixel_format_types([
+                PixelFormat::BGRA32.as_os_type(),
+                u32::from_be_bytes(*b"????"),
+            ]),
+            "InvalidPixelFormat",
+        ),
+    ];
+    for (builder, expected) in cases {
+        let error = builder.build().unwrap_err();
+        assert_eq!(format!("{error:?}"), expected);
+    }
+
+    // A later valid value replaces an invalid one.
+    assert!(PixelBufferAttributes::builder()
+        .bytes_per_row_alignment(48)
+        .bytes_per_row_alignment(64)
+        .pixel_format_types([u32::from_be_bytes(*b"????")])
+        .pixel_format(PixelFormat::BGRA32)
+        .build()
+        .is_ok());
+    let attributes = PixelBufferAttributes::builder()
+        .pixel_format_types([u32::from_be_bytes(*b"420v")])
+        .build()
+        .unwrap();
+    assert_eq!(
+        attributes.get(PixelBufferAttributeKey::PixelFormat),
+        Some(&PixelBufferAttribute::PixelFormat(
+            PixelFormatValue::Single(PixelFormat::YCbCr420_8BiPlanarVideoRange)
+        ))
+    );
+}
+
+#[test]
+fn test_raw_attributes_use_last_value() {
+    let mut attributes = PixelBufferAttributes::new(&[
+        PixelBufferAttribute::BytesPerRowAlignment(3),
+        PixelBufferAttribute::Width(64),
+        PixelBufferAttribute::BytesPerRowAlignment(32),
+    ]);
+    assert!(attributes.validate().is_ok());
+    assert_eq!(attributes.effective().count(), 2);
+    assert_eq!(
+        attributes.get(PixelBufferAttributeKey::BytesPerRowAlignment),
+        Some(&PixelBufferAttribute::BytesPerRowAlignment(32))
+    );
+    assert_eq!(
+        attributes.set(PixelBufferAttribute::BytesPerRowAlignment(16)),
+        Some(PixelBufferAttribute::BytesPerRowAlignment(32))
+    );
+    assert_eq!(attributes.len(), 2);
+    assert_eq!(
+        attributes.remove(PixelBufferAttributeKey::Width),
+        Some(PixelBufferAttribute::Width(64))
+    );
+    assert_eq!(attributes.get(PixelBufferAttributeKey::Width), None);
+}
+
+#[test]
+fn test_merge() -> Result<(), CVPixelBufferError> {
+    let defaults = PixelBufferAttributes::builder()
+        .pixel_format(PixelFormat::BGRA32)
+        .bytes_per_row_alignment(64)
+        .cg_image_compatibility(true)
+        .build()?;
+    let overrides = PixelBufferAttributes::new(&[
+        PixelBufferAttribute::BytesPerRowAlignment(7),
+        PixelBufferAttribute::BytesPerRowAlignment(16),
+        PixelBufferAttribute::Width(640),
+    ]);
+
+    let mut merged = defaults.clone();
+    merged.merge(&overrides);
+    assert_eq!(
+        merged.as_slice(),
+        [
+            PixelBufferAttribute::PixelFormat(PixelFormatValue::Single(PixelFormat::BGRA32)),
+            PixelBufferAttribute::CGImageCompatibility(true),
+            PixelBufferAttribute::BytesPerRowAlignment(16),
+            PixelBufferAttribute::Width(640),
+        ]
+    );
+    assert_eq!(
+        PixelBufferAttributesBuilder::from(defaults)
+            .merge(&overrides)
+            .build()?,
+        merged
+    );
+    Ok(())
+}
+
+#[test]
+fn test_debug_prints_a_map() -> Result<(), CVPixelBufferError> {
+    let attributes = PixelBufferAttributes::builder()
+        .pixel_format(PixelFormat::BGRA32)
+        .width(640)
+        .opengl_compatibility(true)
+        .build()?;
+    assert_eq!(
+        format!("{attributes:?}"),
+        "{PixelFormat: Single(BGRA32), Width: 640, OpenGLCompatibility: true}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_built_attributes_create_buffers() -> Result<(), CVPixelBufferError> {
+    let attributes = PixelBufferAttributes::builder()
+        .bytes_per_row_alignment(16)
+        .extended_pixels(ExtendedPixels::new(2, 2, 2, 2))
+        .build()?;
+    let buffer = SoftwarePixelBuffer::create(10, 10, PixelFormat::BGRA32, attributes)?;
+    assert_eq!(buffer.get_bytes_per_row(), 64);
+    assert_eq!(
+        buffer.get_extended_pixels(),
+        ExtendedPixels::new(2, 2, 2, 2)
+    );
+    Ok(())
+}
@@ -0,0 +1,271 @@
+use std::error::Error;
+
+use core_video_rs::{
+    attachments::{
+        CVAttachmentMode, CleanAperture, DisplayDimensions, ImageBufferAttachment,
+        ImageBufferAttachmentKey, Rational, Rect,
+    },
+    convert::{self, ChromaFormat, PlanarYCbCr16},
+    cv_pixel_buffer::{
+        attributes::PixelBufferAttributes, error::CVPixelBufferError, pixel_format::PixelFormat,
+        PixelBufferLike, PixelBufferView, PixelRect, SoftwarePixelBuffer,
+    },
+    scale,
+};
+
+fn gray_buffer(
+    width: usize,
+    height: usize,
+) -> Result<SoftwarePixelBuffer<'static>, Box<dyn Error>> {
+    let mut buffer = SoftwarePixelBuffer::create(
+        width,
+        height,
+        PixelFormat::OneComponent8,
+        PixelBufferAttributes::default(),
+    )?;
+    {
+        let mut guard = buffer.lock_mut()?;
+        let mut plane = guard.plane_mut(0)?;
+        for y in 0..height {
+            for (x, sample) in plane.row_mut(y)[..width].iter_mut().enumerate() {
+                *sample = (y * 16 + x) as u8;
+            }
+        }
+    }
+    Ok(buffer)
+}
+
+fn rows(
+    view: &PixelBufferView<'_, SoftwarePixelBuffer<'_>>,
+) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+    let guard = view.lock()?;
+    let plane = guard.plane(0)?;
+    Ok(plane
+        .rows()
+        .map(|row| row[..plane.width()].to_vec())
+        .collect())
+}
+
+#[test]
+fn test_view_rows() -> Result<(), Box<dyn Error>> {
+    let buffer = gray_buffer(8, 6)?;
+    let view = PixelBufferView::new(&buffer, PixelRect::new(2, 1, 3, 2))?;
+    assert_eq!((view.get_width(), view.get_height()), (3, 2));
+    assert_eq!(rows(&view)?, [[0x12, 0x13, 0x14], [0x22, 0x23, 0x24]]);
+
+    // The view points into the buffer rather than at a copy.
+    let guard = view.lock()?;
+    let offset = guard.plane(0)?.as_slice().as_ptr() as usize
+        - buffer.lock()?.plane(0)?.as_slice().as_ptr() as usize;
+    assert_eq!(offset, buffer.get_bytes_per_row() + 2);
+    Ok(())
+}
+
+#[test]
+fn test_view_at_bottom_right_corner() -> Result<(), Box<dyn Error>> {
+    let buffer = gray_buffer(8, 6)?;
+    let view = PixelBufferView::new(&buffer, PixelRect::new(5, 4, 3, 2))?;
+    assert_eq!(rows(&view)?, [[0x45, 0x46, 0x47], [0x55, 0x56, 0x57]]);
+    let guard = view.lock()?;
+    let plane = guard.plane(0)?;
+    assert_eq!(plane.row(1).len(), buffer.get_bytes_per_row() - 5);
+    Ok(())
+}
+
+#[test]
+fn test_chroma_alignment() -> Result<(), Box<dyn Error>> {
+    let buffer = SoftwarePixelBuffer::create(
+        8,
+        6,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PixelBufferAttributes::default(),
+    )?;
+    let view = PixelBufferView::new(&buffer, PixelRect::new(1, 3, 4, 2))?;
+    assert_eq!(view.rect(), PixelRect::new(0, 2, 6, 4));
+    assert_eq!(view.get_width_of_plane(1), 3);
+    assert_eq!(view.get_height_of_plane(1), 2);
+    let guard = view.lock()?;
+    let (y_plane, uv_plane) = (guard.plane(0)?, guard.plane(1)?);
+    assert_eq!((y_plane.width(), y_plane.height()), (6, 4));
+    assert_eq!((uv_plane.width(), uv_plane.height()), (3, 2));
+
+    let v210 = SoftwarePixelBuffer::create(
+        24,
+        2,
+        PixelFormat::YCbCr422_10,
+        PixelBufferAttributes::default(),
+    )?;
+    let view = PixelBufferView::new(&v210, PixelRect::new(7, 0, 4, 1))?;
+    assert_eq!(view.rect(), PixelRect::new(6, 0, 6, 1));
+    Ok(())
+}
+
+#[test]
+fn test_clean_aperture() -> Result<(), Box<dyn Error>> {
+    let buffer = gray_buffer(8, 6)?;
+    let view = PixelBufferView::clean_aperture(&buffer)?;
+    assert_eq!(view.rect(), PixelRect::new(0, 0, 8, 6));
+
+    buffer.set_attachment(
+        &ImageBufferAttachment::CleanAperture(CleanAperture {
+            width: Rational::integer(4),
+            height: Rational::integer(2),
+            horizontal_offset: Rational::integer(1),
+            vertical_offset: Rational::new(-1, 2),
+        }),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    let view = PixelBufferView::clean_aperture(&buffer)?;
+    assert_eq!(view.rect(), PixelRect::new(3, 1, 4, 3));
+    Ok(())
+}
+
+#[test]
+fn test_attachments_follow_the_view() -> Result<(), Box<dyn Error>> {
+    let buffer = gray_buffer(8, 6)?;
+    // Clean rect (3, 1.5, 4, 2) in buffer coordinates.
+    buffer.set_attachment(
+        &ImageBufferAttachment::CleanAperture(CleanAperture {
+            width: Rational::integer(4),
+            height: Rational::integer(2),
+            horizontal_offset: Rational::integer(1),
+            vertical_offset: Rational::new(-1, 2),
+        }),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    buffer.set_attachment(
+        &ImageBufferAttachment::DisplayDimensions(DisplayDimensions {
+            width: 16,
+            height: 12,
+        }),
+        CVAttachmentMode::ShouldPropagate,
+    );
+
+    let view = PixelBufferView::clean_aperture(&buffer)?;
+    assert_eq!(view.get_clean_rect(), Rect::new(0.0, 0.5, 4.0, 2.0));
+    assert_eq!(
+        view.get_attachment(ImageBufferAttachmentKey::CleanAperture),
+        Some((
+            ImageBufferAttachment::CleanAperture(CleanAperture::centered(4, 2)),
+            CVAttachmentMode::ShouldPropagate
+        ))
+    );
+    assert_eq!(view.get_display_size(), buffer.get_display_size());
+
+    // Only the left half of the aperture is inside this view.
+    let view = PixelBufferView::new(&buffer, PixelRect::new(5, 0, 3, 6))?;
+    assert_eq!(view.get_clean_rect(), Rect::new(0.0, 1.5, 2.0, 2.0));
+    let attachments = view.get_attachments(CVAttachmentMode::ShouldPropagate);
+    assert_eq!(
+        attachments.get(ImageBufferAttachmentKey::CleanAperture),
+        Some(&ImageBufferAttachment::CleanAperture(CleanAperture {
+            width: Rational::integer(2),
+            height: Rational::integer(2),
+            horizontal_offset: Rational::new(-1, 2),
+            vertical_offset: Rational::new(-1, 2),
+        }))
+    );
+    assert_eq!(
+        attachments.get(ImageBufferAttachmentKey::DisplayDimensions),
+        Some(&ImageBufferAttachment::DisplayDimensions(
+            DisplayDimensions {
+                width: 8,
+                height: 12,
+            }
+        ))
+    );
+
+    // The aperture lies entirely above this view.
+    let view = PixelBufferView::new(&buffer, PixelRect::new(0, 4, 8, 2))?;
+    assert!(view
+        .get_attachment(ImageBufferAttachmentKey::CleanAperture)
+        .is_none());
+    assert!(view
+        .get_attachments(CVAttachmentMode::ShouldPropagate)
+        .get(ImageBufferAttachmentKey::CleanAperture)
+        .is_none());
+    assert_eq!(view.get_clean_rect(), Rect::new(0.0, 0.0, 8.0, 2.0));
+    Ok(())
+}
+
+#[test]
+fn test_nested_views() -> Result<(), Box<dyn Error>> {
+    let buffer = gray_buffer(8, 6)?;
+    let outer = PixelBufferView::new(&buffer, PixelRect::new(1, 1, 6, 4))?;
+    let inner = outer.view(PixelRect::new(2, 1, 2, 2))?;
+    assert_eq!(inner.rect(), PixelRect::new(3, 2, 2, 2));
+    assert_eq!(rows(&inner)?, [[0x23, 0x24], [0x33, 0x34]]);
+
+    let nested = PixelBufferView::new(&outer, PixelRect::new(2, 1, 2, 2))?;
+    let guard = nested.lock()?;
+    let plane = guard.plane(0)?;
+    let nested_rows: Vec<_> = plane.rows().map(|row| row[..2].to_vec()).collect();
+    assert_eq!(nested_rows, rows(&inner)?);
+    Ok(())
+}
+
+#[test]
+fn test_view_as_source() -> Result<(), Box<dyn Error>> {
+    let mut image = PlanarYCbCr16::new(8, 4, ChromaFormat::Yuv420, 8)?;
+    for (i, sample) in image.y.iter_mut().enumerate() {
+        *sample = i as u16;
+    }
+    image.cb = (0..8).collect();
+    image.cr = (100..108).collect();
+    let mut buffer = SoftwarePixelBuffer::create(
+        8,
+        4,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PixelBufferAttributes::default(),
+    )?;
+    convert::pack(&image, &mut buffer)?;
+
+    let view = PixelBufferView::new(&buffer, PixelRect::new(2, 2, 4, 2))?;
+    let mut planar = SoftwarePixelBuffer::create(
+        4,
+        2,
+        PixelFormat::YCbCr420_8Planar,
+        PixelBufferAttributes::default(),
+    )?;
+    convert::convert(&view, &mut planar)?;
+    let cropped = convert::unpack(&planar)?;
+    assert_eq!(cropped.y, [18, 19, 20, 21, 26, 27, 28, 29]);
+    assert_eq!(cropped.cb, [5, 6]);
+    assert_eq!(cropped.cr, [105, 106]);
+
+    let mut scaled = SoftwarePixelBuffer::create(
+        2,
+        2,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PixelBufferAttributes::default(),
+    )?;
+    scale::scale(&view, &mut scaled)?;
+    Ok(())
+}
+
+#[test]
+fn test_errors() -> Result<(), Box<dyn Error>> {
+    let buffer = gray_buffer(8, 6)?;
+    for rect in [
+        PixelRect::new(0, 0, 0, 1),
+        PixelRect::new(6, 0, 3, 1),
+        PixelRect::new(0, 5, 1, 2),
+        PixelRect::new(usize::MAX, 0, 1, 1),
+    ] {
+        assert!(matches!(
+            PixelBufferView::new(&buffer, rect),
+            Err(CVPixelBufferError::InvalidArgument)
+        ));
+    }
+    let view = PixelBufferView::new(&buffer, PixelRect::new(0, 0, 4, 4))?;
+    assert!(matches!(
+        view.view(PixelRect::new(2, 2, 3, 1)),
+        Err(CVPixelBufferError::InvalidArgument)
+    ));
+    let mut view = view;
+    assert!(matches!(
+        view.lock_mut(),
+        Err(CVPixelBufferError::Unsupported)
+    ));
+    Ok(())
+}
@@ -0,0 +1,168 @@
+#![cfg(target_vendor = "apple")]
+use core_foundation::{
+    base::TCFType,
+    string::{CFString, CFStringRef},
+};
+use core_video_rs::{
+    constants::ComponentRange,
+    cv_pixel_buffer::{
+        error::CVPixelBufferError,
+        pixel_format::PixelFormat,
+        pixel_format_description::{PixelFormatDescription, PlaneDescription},
+    },
+    dictionary::{Dictionary, DictionaryValue},
+};
+
+extern "C" {
+    static kCVPixelFormatConstant: CFStringRef;
+    static kCVPixelFormatName: CFStringRef;
+    static kCVPixelFormatCodecType: CFStringRef;
+    static kCVPixelFormatPlanes: CFStringRef;
+    static kCVPixelFormatBitsPerBlock: CFStringRef;
+    static kCVPixelFormatBlockWidth: CFStringRef;
+    static kCVPixelFormatBlockHeight: CFStringRef;
+    static kCVPixelFormatBlockHorizontalAlignment: CFStringRef;
+    static kCVPixelFormatBlockVerticalAlignment: CFStringRef;
+    static kCVPixelFormatHorizontalSubsampling: CFStringRef;
+    static kCVPixelFormatVerticalSubsampling: CFStringRef;
+    static kCVPixelFormatBlackBlock: CFStringRef;
+    static kCVPixelFormatComponentRange: CFStringRef;
+    static kCVPixelFormatContainsAlpha: CFStringRef;
+    static kCVPixelFormatContainsYCbCr: CFStringRef;
+    static kCVPixelFormatContainsRGB: CFStringRef;
+    static kCVPixelFormatContainsGrayscale: CFStringRef;
+    static kCVPixelFormatContainsSenselArray: CFStringRef;
+    static kCVPixelFormatFillExtendedPixelsCallback: CFStringRef;
+    static kCVPixelFormatOpenGLCompatibility: CFStringRef;
+    #[cfg(target_os = "ios")]
+    static kCVPixelFormatOpenGLESCompatibility: CFStringRef;
+    static kCVPixelFormatCGBitmapContextCompatibility: CFStringRef;
+    static kCVPixelFormatCGImageCompatibility: CFStringRef;
+}
+
+fn value(constant: CFStringRef) -> String {
+    unsafe { CFString::wrap_under_get_rule(constant) }.to_string()
+}
+
+fn dictionary(entries: Vec<(CFStringRef, DictionaryValue)>) -> Dictionary {
+    entries
+        .into_iter()
+        .map(|(key, entry)| (value(key), entry))
+        .collect()
+}
+
+#[test]
+fn test_keys_match_core_video() -> Result<(), CVPixelBufferError> {
+    let plane = |subsampling: u32| unsafe {
+        DictionaryValue::from(dictionary(vec![
+            (kCVPixelFormatBitsPerBlock, 16u32.into()),
+            (kCVPixelFormatBlockWidth, 2u32.into()),
+            (kCVPixelFormatBlockHeight, 2u32.into()),
+            (kCVPixelFormatBlockHorizontalAlignment, 64u32.into()),
+            (kCVPixelFormatBlockVerticalAlignment, 4u32.into()),
+            (kCVPixelFormatHorizontalSubsampling, subsampling.into()),
+            (kCVPixelFormatVerticalSubsampling, subsampling.into()),
+            (kCVPixelFormatBlackBlock, vec![0x10, 0x80].into()),
+        ]))
+    };
+    let entries = unsafe {
+        vec![
+            (kCVPixelFormatConstant, u32::from_be_bytes(*b"Xrs2").into()),
+            (kCVPixelFormatName, "Keys".into()),
+            (kCVPixelFormatCodecType, u32::from_be_bytes(*b"Xrs3").into()),
+            (kCVPixelFormatPlanes, vec![plane(1), plane(2)].into()),
+            (kCVPixelFormatComponentRange, "FullRange".into()),
+            (kCVPixelFormatContainsAlpha, true.into()),
+            (kCVPixelFormatContainsYCbCr, true.into()),
+            (kCVPixelFormatContainsRGB, true.into()),
+            (kCVPixelFormatContainsGrayscale, true.into()),
+            (kCVPixelFormatContainsSenselArray, true.into()),
+            (
+                kCVPixelFormatFillExtendedPixelsCallback,
+                vec![0u8; 24].into(),
+            ),
+            (kCVPixelFormatOpenGLCompatibility, true.into()),
+            (kCVPixelFormatCGBitmapContextCompatibility, false.into()),
+            (kCVPixelFormatCGImageCompatibility, true.into()),
+        ]
+    };
+    let description = PixelFormatDescription::from_dictionary(&dictionary(entries))?;
+    assert_eq!(description.pixel_format, u32::from_be_bytes(*b"Xrs2"));
+    assert_eq!(description.name.as_deref(), Some("Keys"));
+    assert_eq!(description.codec_type, Some(u32::from_be_bytes(*b"Xrs3")));
+    assert_eq!(
+        description.planes,
+        [1, 2].map(|subsampling| PlaneDescription {
+            block_width: 2,
+            block_height: 2,
+            block_horizontal_alignment: Some(64),
+            block_vertical_alignment: Some(4),
+            horizontal_subsampling: subsampling,
+            vertical_subsampling: subsampling,
+            black_block: Some(vec![0x10, 0x80]),
+            ..PlaneDescription::new(16)
+        })
+    );
+    assert_eq!(description.component_range, Some(ComponentRange::FullRange));
+    assert!(
+        description.contains_alpha
+            && description.contains_ycbcr
+            && description.contains_rgb
+            && description.contains_grayscale
+            && description.contains_sensel_array
+            && description.has_fill_extended_pixels_callback
+    );
+    assert_eq!(description.opengl_compatibility, Some(true));
+    #[cfg(target_os = "ios")]
+    {
+        let opengles = PixelFormatDescription::from_dictionary(&dictionary(unsafe {
+            vec![
+                (kCVPixelFormatConstant, u32::from_be_bytes(*b"Xrs2").into()),
+                (kCVPixelFormatOpenGLESCompatibility, true.into()),
+            ]
+        }))?;
+        assert_eq!(opengles.opengles_compatibility, Some(true));
+    }
+    assert_eq!(description.cg_bitmap_context_compatibility, Some(false));
+    assert_eq!(description.cg_image_compatibility, Some(true));
+
+    // CoreVideo exports no constant for the Metal key, so check it is the one its own
+    // descriptions use.
+    let bgra = PixelFormatDescription::load(PixelFormat::BGRA32.as_os_type())?;
+    assert!(bgra.metal_compatibility.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_load_matches_built_in_table() -> Result<(), CVPixelBufferError> {
+    let pixel_format = PixelFormat::YCbCr420_8BiPlanarFullRange;
+    let loaded = PixelFormatDescription::load(pixel_format.as_os_type())?;
+    let built_in = PixelFormatDescription::for_format(pixel_format).unwrap();
+    assert_eq!(loaded.planes.len(), built_in.planes.len());
+    for (loaded, built_in) in loaded.planes.iter().zip(&built_in.planes) {
+        assert_eq!(loaded.bits_per_block, built_in.bits_per_block);
+        assert_eq!(
+            loaded.horizontal_subsampling,
+            built_in.horizontal_subsampling
+        );
+        assert_eq!(loaded.vertical_subsampling, built_in.vertical_subsampling);
+    }
+    assert!(loaded.contains_ycbcr);
+    assert_eq!(loaded.component_range, Some(ComponentRange::FullRange));
+    assert!(PixelFormatDescription::all_pixel_formats().contains(&PixelFormat::BGRA32.as_os_type()));
+    Ok(())
+}
+
+#[test]
+fn test_register_custom_format() -> Result<(), CVPixelBufferError> {
+    let pixel_format = u32::from_be_bytes(*b"Xrs1");
+    let mut description =
+        PixelFormatDescription::new(pixel_format, vec![PlaneDescription::new(64)]);
+    description.name = Some("core-video-rs test".into());
+    description.contains_rgb = true;
+    description.register()?;
+    let loaded = PixelFormatDescription::load(pixel_format)?;
+    assert_eq!(loaded.planes, description.planes);
+    assert_eq!(loaded.name, description.name);
+    Ok(())
+}
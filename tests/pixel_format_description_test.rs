@@ -0,0 +1,237 @@
+use core_video_rs::{
+    constants::ComponentRange,
+    cv_pixel_buffer::{
+        error::CVPixelBufferError,
+        pixel_format::PixelFormat,
+        pixel_format_description::{PixelFormatDescription, PlaneDescription},
+    },
+    dictionary::{Dictionary, DictionaryValue},
+};
+
+fn dictionary(entries: &[(&str, DictionaryValue)]) -> Dictionary {
+    entries
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect()
+}
+
+/// The description CoreVideo returns for 420v, trimmed to the keys the crate reads.
+fn nv12_fixture() -> Dictionary {
+    dictionary(&[
+        ("PixelFormat", DictionaryValue::Integer(0x3432_3076)),
+        ("ContainsYCbCr", true.into()),
+        ("ContainsRGB", false.into()),
+        ("ContainsAlpha", false.into()),
+        ("ComponentRange", "VideoRange".into()),
+        ("OpenGLCompatibility", true.into()),
+        ("MetalCompatibility", DictionaryValue::Integer(1)),
+        ("CGBitmapContextCompatibility", false.into()),
+        ("FillExtendedPixelsCallback", vec![0u8; 24].into()),
+        ("IOSurfaceCoreAnimationCompatibility", true.into()),
+        (
+            "Planes",
+            DictionaryValue::Array(vec![
+                dictionary(&[
+                    ("BitsPerBlock", DictionaryValue::Integer(8)),
+                    ("BlackBlock", vec![0x10].into()),
+                ])
+                .into(),
+                dictionary(&[
+                    ("BitsPerBlock", DictionaryValue::Integer(16)),
+                    ("BlackBlock", vec![0x80, 0x80].into()),
+                    ("HorizontalSubsampling", DictionaryValue::Integer(2)),
+                    ("VerticalSubsampling", DictionaryValue::Float(2.0)),
+                ])
+                .into(),
+            ]),
+        ),
+    ])
+}
+
+#[test]
+fn test_parse_planar_description() -> Result<(), CVPixelBufferError> {
+    let description = PixelFormatDescription::from_dictionary(&nv12_fixture())?;
+    assert_eq!(
+        description.get_pixel_format(),
+        Some(PixelFormat::YCbCr420_8BiPlanarVideoRange)
+    );
+    assert!(description.is_planar());
+    assert_eq!(
+        description.planes,
+        [
+            PlaneDescription {
+                black_block: Some(vec![0x10]),
+                ..PlaneDescription::new(8)
+            },
+            PlaneDescription {
+                horizontal_subsampling: 2,
+                vertical_subsampling: 2,
+                black_block: Some(vec![0x80, 0x80]),
+                ..PlaneDescription::new(16)
+            },
+        ]
+    );
+    assert_eq!(
+        description.component_range,
+        Some(ComponentRange::VideoRange)
+    );
+    assert!(description.contains_ycbcr && !description.contains_rgb);
+    assert!(description.has_fill_extended_pixels_callback);
+    assert_eq!(description.opengl_compatibility, Some(true));
+    assert_eq!(description.metal_compatibility, Some(true));
+    assert_eq!(description.cg_bitmap_context_compatibility, Some(false));
+    assert_eq!(description.cg_image_compatibility, None);
+    Ok(())
+}
+
+#[test]
+fn test_parse_single_plane_at_top_level() -> Result<(), CVPixelBufferError> {
+    let description = PixelFormatDescription::from_dictionary(&dictionary(&[
+        ("PixelFormat", DictionaryValue::Integer(0x7632_3130)),
+        ("Name", "v210".into()),
+        ("BitsPerBlock", DictionaryValue::Integer(128)),
+        ("BlockWidth", DictionaryValue::Integer(6)),
+        ("BlockHorizontalAlignment", DictionaryValue::Integer(128)),
+        ("ContainsYCbCr", true.into()),
+        ("ComponentRange", "SomethingNew".into()),
+    ]))?;
+    assert_eq!(description.name.as_deref(), Some("v210"));
+    assert_eq!(
+        description.planes,
+        [PlaneDescription {
+            block_width: 6,
+            block_horizontal_alignment: Some(128),
+            ..PlaneDescription::new(128)
+        }]
+    );
+    assert_eq!(
+        description.component_range,
+        Some(ComponentRange::Unknown("SomethingNew".into()))
+    );
+    assert!(!description.has_fill_extended_pixels_callback);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_descriptions() {
+    let mut missing_format = nv12_fixture();
+    missing_format.remove("PixelFormat");
+    let mut bad_flag = nv12_fixture();
+    bad_flag.insert("ContainsAlpha".into(), "yes".into());
+    let mut bad_plane = nv12_fixture();
+    bad_plane.insert(
+        "Planes".into(),
+        DictionaryValue::Array(vec![dictionary(&[(
+            "BlockWidth",
+            DictionaryValue::Integer(2),
+        )])
+        .into()]),
+    );
+    let mut zero_subsampling = nv12_fixture();
+    zero_subsampling.insert(
+        "Planes".into(),
+        DictionaryValue::Array(vec![dictionary(&[
+            ("BitsPerBlock", DictionaryValue::Integer(8)),
+            ("VerticalSubsampling", DictionaryValue::Integer(0)),
+        ])
+        .into()]),
+    );
+    for fixture in [missing_format, bad_flag, bad_plane, zero_subsampling] {
+        assert!(matches!(
+            PixelFormatDescription::from_dictionary(&fixture),
+            Err(CVPixelBufferError::InvalidArgument)
+        ));
+    }
+}
+
+#[test]
+fn test_dictionary_round_trip() -> Result<(), CVPixelBufferError> {
+    let mut description = PixelFormatDescription::new(
+        u32::from_be_bytes(*b"Xcst"),
+        vec![
+            PlaneDescription {
+                block_height: 2,
+                black_block: Some(vec![0; 4]),
+                ..PlaneDescription::new(32)
+            },
+            PlaneDescription {
+                horizontal_subsampling: 2,
+                block_vertical_alignment: Some(2),
+                ..PlaneDescription::new(16)
+            },
+        ],
+    );
+    description.name = Some("Custom".into());
+    description.contains_rgb = true;
+    description.metal_compatibility = Some(false);
+    let dictionary = description.to_dictionary();
+    assert!(dictionary.contains_key("Planes"));
+    assert_eq!(
+        PixelFormatDescription::from_dictionary(&dictionary)?,
+        description
+    );
+
+    description.planes.truncate(1);
+    let dictionary = description.to_dictionary();
+    assert_eq!(
+        dictionary.get("BitsPerBlock"),
+        Some(&DictionaryValue::Integer(32))
+    );
+    assert_eq!(
+        PixelFormatDescription::from_dictionary(&dictionary)?,
+        description
+    );
+    Ok(())
+}
+
+#[test]
+fn test_built_in_table() -> Result<(), CVPixelBufferError> {
+    let nv12 = PixelFormatDescription::for_format(PixelFormat::YCbCr420_8BiPlanarVideoRange)
+        .expect("420v has a layout");
+    let parsed = PixelFormatDescription::from_dictionary(&nv12_fixture())?;
+    let without_black = |planes: &[PlaneDescription]| -> Vec<PlaneDescription> {
+        planes
+            .iter()
+            .map(|plane| PlaneDescription {
+                black_block: None,
+                ..plane.clone()
+            })
+            .collect()
+    };
+    assert_eq!(nv12.planes, without_black(&parsed.planes));
+    assert_eq!(nv12.component_range, parsed.component_range);
+    assert_eq!(
+        (nv12.contains_ycbcr, nv12.contains_rgb, nv12.contains_alpha),
+        (true, false, false)
+    );
+    assert_eq!(nv12.metal_compatibility, None);
+
+    let v210 = PixelFormatDescription::for_format(PixelFormat::YCbCr422_10).unwrap();
+    assert_eq!(
+        (v210.planes[0].bits_per_block, v210.planes[0].block_width),
+        (128, 6)
+    );
+    assert_eq!(v210.planes[0].block_horizontal_alignment, Some(128));
+    assert_eq!(nv12.planes[0].block_horizontal_alignment, None);
+    let bgra = PixelFormatDescription::for_format(PixelFormat::BGRA32).unwrap();
+    assert!(bgra.contains_alpha && bgra.contains_rgb && bgra.component_range.is_none());
+    let full = PixelFormatDescription::for_format(PixelFormat::YCbCr420_8BiPlanarFullRange);
+    assert_eq!(
+        full.unwrap().component_range,
+        Some(ComponentRange::FullRange)
+    );
+    assert!(
+        PixelFormatDescription::for_format(PixelFormat::Bayer14RGGB)
+            .unwrap()
+            .contains_sensel_array
+    );
+    assert!(PixelFormatDescription::for_format(PixelFormat::LossyBGRA32).is_none());
+
+    for pixel_format in PixelFormat::ALL {
+        if let Some(description) = PixelFormatDescription::for_format(*pixel_format) {
+            let round_trip = PixelFormatDescription::from_dictionary(&description.to_dictionary())?;
+            assert_eq!(round_trip, description, "{pixel_format}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use core_video_rs::cv_pixel_buffer::{
+    error::CVPixelBufferError,
+    extended_pixels::ExtendedPixels,
+    pixel_format::PixelFormat,
+    pixel_format_layout::{PixelFormatLayout, PlaneLayout},
+    planar_data::PlanarDataPointer,
+};
+
+fn plane(width: usize, height: usize, bytes_per_row: usize, offset: usize) -> PlaneLayout {
+    PlaneLayout {
+        width,
+        height,
+        bytes_per_row,
+        offset,
+        size: bytes_per_row * height,
+    }
+}
+
+#[test]
+fn test_nv12_layout() {
+    let layout = PixelFormat::YCbCr420_8BiPlanarVideoRange
+        .layout()
+        .unwrap()
+        .compute(1920, 1080, 64)
+        .unwrap();
+    assert_eq!(
+        layout.planes,
+        vec![plane(1920, 1080, 1920, 0), plane(960, 540, 1920, 2_073_600)]
+    );
+    assert_eq!(layout.data_size, 3_110_400);
+    assert!(layout.is_planar());
+}
+
+#[test]
+fn test_nv12_odd_size_layout() {
+    let layout = PixelFormat::YCbCr420_8BiPlanarFullRange
+        .layout()
+        .unwrap()
+        .compute(1921, 1081, 16)
+        .unwrap();
+    assert_eq!(
+        layout.planes,
+        vec![plane(1921, 1081, 1936, 0), plane(961, 541, 1936, 2_092_816)]
+    );
+}
+
+#[test]
+fn test_p010_layout() {
+    let layout = PixelFormat::YCbCr420_10BiPlanarVideoRange
+        .layout()
+        .unwrap()
+        .compute(1920, 1080, 64)
+        .unwrap();
+    assert_eq!(layout.planes[0], plane(1920, 1080, 3840, 0));
+    assert_eq!(layout.planes[1], plane(960, 540, 3840, 4_147_200));
+    assert_eq!(layout.data_size, 6_220_800);
+}
+
+#[test]
+fn test_v210_layout() {
+    let format = PixelFormat::YCbCr422_10.layout().unwrap();
+    assert_eq!(format.bits_per_component, 10);
+    assert_eq!(format.planes[0].block_width, 6);
+    assert_eq!(format.planes[0].bytes_per_block, 16);
+    assert_eq!(
+        format.compute(1920, 1080, 16).unwrap().planes,
+        vec![plane(1920, 1080, 5120, 0)]
+    );
+    // Rows are padded to a multiple of 48 pixels (128 bytes).
+    assert_eq!(
+        format.compute(1280, 720, 16).unwrap().planes,
+        vec![plane(1280, 720, 3456, 0)]
+    );
+}
+
+#[test]
+fn test_2vuy_layout() {
+    let layout = PixelFormat::YCbCr422_8.layout().unwrap();
+    assert_eq!(
+        layout.compute(1920, 1080, 16).unwrap().planes,
+        vec![plane(1920, 1080, 3840, 0)]
+    );
+    assert_eq!(
+        layout.compute(11, 3, 1).unwrap().planes,
+        vec![plane(11, 3, 24, 0)]
+    );
+}
+
+#[test]
+fn test_bgra_layout() {
+    let layout = PixelFormat::BGRA32.layout().unwrap();
+    assert_eq!(layout.plane_count(), 1);
+    assert_eq!(
+        layout.compute_default(10, 10).unwrap().planes,
+        vec![plane(10, 10, 64, 0)]
+    );
+    assert_eq!(
+        layout.compute(1920, 1080, 64).unwrap().data_size,
+        1920 * 1080 * 4
+    );
+}
+
+#[test]
+fn test_invalid_layout_arguments() {
+    let layout = PixelFormat::BGRA32.layout().unwrap();
+    assert!(matches!(
+        layout.compute(0, 10, 16),
+        Err(CVPixelBufferError::InvalidSize)
+    ));
+    assert!(matches!(
+        layout.compute(10, 10, 24),
+        Err(CVPixelBufferError::InvalidArgument)
+    ));
+    assert!(PixelFormatLayout::for_format(PixelFormat::LosslessBGRA32).is_none());
+}
+
+#[test]
+fn test_extended_layout_overflow() {
+    let layout = PixelFormat::BGRA32.layout().unwrap();
+    for extended in [
+        ExtendedPixels::new(0, usize::MAX / 8, 0, 0),
+        ExtendedPixels::new(usize::MAX / 8, 0, 0, 0),
+        ExtendedPixels::new(0, 0, 0, usize::MAX / 8),
+    ] {
+        assert!(matches!(
+            layout.compute_extended(16, 16, 16, extended),
+            Err(CVPixelBufferError::InvalidSize)
+        ));
+    }
+}
+
+#[test]
+fn test_all_uncompressed_formats_have_layouts() {
+    for format in PixelFormat::ALL {
+        if let Some(layout) = format.layout() {
+            let computed = layout.compute_default(33, 17).unwrap();
+            assert_eq!(computed.planes.len(), layout.plane_count());
+        }
+    }
+}
+
+#[test]
+fn test_planar_data_for_format() -> Result<(), CVPixelBufferError> {
+    let data = PlanarDataPointer::for_format(PixelFormat::YCbCr420_8BiPlanarVideoRange, 64, 48)?;
+    assert_eq!(data.number_of_planes(), 2);
+    assert_eq!(data.plane_width, vec![64, 32]);
+    assert_eq!(data.plane_height, vec![48, 24]);
+    assert_eq!(data.plane_bytes_per_row, vec![64, 64]);
+    assert_eq!(data.base_addresses[0].len(), 64 * 48);
+    assert_eq!(data.base_addresses[1].len(), 64 * 24);
+    data.validate()
+}
+
+#[test]
+fn test_planar_data_validation() {
+    let data = PlanarDataPointer::new(
+        None,
+        vec![64, 64],
+        vec![64, 32],
+        vec![48, 24],
+        vec![vec![0; 64 * 48], vec![0; 64 * 23]],
+    );
+    assert!(matches!(
+        data.validate(),
+        Err(CVPixelBufferError::InvalidSize)
+    ));
+    let data = PlanarDataPointer::new(
+        None,
+        vec![64],
+        vec![64, 32],
+        vec![48, 24],
+        vec![vec![0; 64 * 48], vec![0; 64 * 24]],
+    );
+    assert!(matches!(
+        data.validate(),
+        Err(CVPixelBufferError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn test_planar_data_validation_for_format() -> Result<(), CVPixelBufferError> {
+    let format = PixelFormat::YCbCr420_8BiPlanarVideoRange;
+    let data = PlanarDataPointer::for_format(format, 64, 48)?;
+    data.validate_for(format, 64, 48)?;
+    assert!(matches!(
+        data.validate_for(format, 64, 50),
+        Err(CVPixelBufferError::InvalidSize)
+    ));
+    assert!(matches!(
+        data.validate_for(PixelFormat::OneComponent8, 64, 48),
+        Err(CVPixelBufferError::InvalidArgument)
+    ));
+    // Plane widths that fit the image but not the stride.
+    let data = PlanarDataPointer::new(
+        None,
+        vec![32, 32],
+        vec![64, 32],
+        vec![48, 24],
+        vec![vec![0; 64 * 48], vec![0; 64 * 24]],
+    );
+    assert!(matches!(
+        data.validate_for(format, 64, 48),
+        Err(CVPixelBufferError::InvalidArgument)
+    ));
+    assert_eq!(
+        data.raw_base_addresses(),
+        [
+            data.base_addresses[0].as_ptr(),
+            data.base_addresses[1].as_ptr()
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+use core_utils_rs::four_char_code::FourCharCode;
+use core_video_rs::{
+    cv_pixel_buffer::{error::CVPixelBufferError, pixel_format::PixelFormat},
+    types::OSType,
+};
+
+#[test]
+fn test_os_type_round_trip() {
+    for format in PixelFormat::ALL {
+        let os_type = OSType::from(*format);
+        assert_eq!(PixelFormat::try_from(os_type).unwrap(), *format);
+    }
+}
+
+#[test]
+fn test_codes_are_unique() {
+    let codes: HashSet<OSType> = PixelFormat::ALL.iter().map(|f| f.as_os_type()).collect();
+    assert_eq!(codes.len(), PixelFormat::ALL.len());
+}
+
+#[test]
+fn test_known_codes() {
+    assert_eq!(PixelFormat::BGRA32.as_os_type(), 0x4247_5241);
+    assert_eq!(PixelFormat::ARGB32.as_os_type(), 0x0000_0020);
+    assert_eq!(PixelFormat::RGB24.as_os_type(), 0x0000_0018);
+    assert_eq!(
+        PixelFormat::YCbCr420_8BiPlanarVideoRange.as_os_type(),
+        u32::from_be_bytes(*b"420v")
+    );
+    assert_eq!(
+        PixelFormat::YCbCr420_10BiPlanarVideoRange.as_os_type(),
+        u32::from_be_bytes(*b"x420")
+    );
+    assert_eq!(
+        PixelFormat::LosslessBGRA32.as_os_type(),
+        u32::from_be_bytes(*b"&BGA")
+    );
+}
+
+#[test]
+fn test_four_char_code_conversion() {
+    let code = FourCharCode::from_str("BGRA").unwrap();
+    assert_eq!(PixelFormat::try_from(code).unwrap(), PixelFormat::BGRA32);
+    assert_eq!(
+        FourCharCode::try_from(PixelFormat::YCbCr422_8).unwrap(),
+        FourCharCode::from_str("2vuy").unwrap()
+    );
+    for format in PixelFormat::ALL {
+        if let Ok(code) = FourCharCode::try_from(*format) {
+            assert_eq!(code.as_u32(), format.as_os_type());
+            assert_eq!(PixelFormat::try_from(code).unwrap(), *format);
+        }
+    }
+}
+
+#[test]
+fn test_invalid_codes() {
+    assert!(matches!(
+        PixelFormat::try_from(u32::from_be_bytes(*b"BRGA")),
+        Err(CVPixelBufferError::InvalidPixelFormat)
+    ));
+    assert!(PixelFormat::from_os_type(0).is_none());
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(PixelFormat::BGRA32.to_string(), "'BGRA'");
+    assert_eq!(PixelFormat::ARGB32.to_string(), "0x00000020");
+}
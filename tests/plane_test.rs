@@ -0,0 +1,40 @@
+use core_video_rs::cv_pixel_buffer::{
+    error::CVPixelBufferError,
+    plane::{Plane, PlaneMut},
+};
+
+#[test]
+fn test_plane_rows() -> Result<(), CVPixelBufferError> {
+    let data: Vec<u8> = (0..24).collect();
+    let plane = Plane::new(&data, 3, 3, 8)?;
+    assert_eq!(plane.row(1), &data[8..16]);
+    assert_eq!(plane.rows().count(), 3);
+    assert_eq!(plane.as_slice().len(), 24);
+    Ok(())
+}
+
+#[test]
+fn test_plane_too_small() {
+    let data = vec![0u8; 23];
+    assert!(matches!(
+        Plane::new(&data, 3, 3, 8),
+        Err(CVPixelBufferError::InvalidSize)
+    ));
+}
+
+#[test]
+fn test_plane_mut_rows() -> Result<(), CVPixelBufferError> {
+    let mut data = vec![0u8; 32];
+    let mut plane = PlaneMut::new(&mut data, 2, 3, 8)?;
+    for (y, row) in plane.rows_mut().enumerate() {
+        row[..2].fill(y as u8 + 1);
+    }
+    plane.row_mut(2)[7] = 9;
+    assert_eq!(plane.as_plane().row(1)[..2], [2, 2]);
+    assert_eq!(
+        data[..24],
+        [1, 1, 0, 0, 0, 0, 0, 0, 2, 2, 0, 0, 0, 0, 0, 0, 3, 3, 0, 0, 0, 0, 0, 9]
+    );
+    assert_eq!(data[24..], [0; 8]);
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use std::error::Error;
+
+use core_video_rs::{
+    attachments::{CVAttachmentMode, ImageBufferAttachment, ImageBufferAttachmentKey},
+    constants::TransferFunction,
+    convert::{self, ChromaFormat, ChromaSiting, PlanarYCbCr16},
+    cv_pixel_buffer::{error::CVPixelBufferError, pixel_format::PixelFormat, SoftwarePixelBuffer},
+    scale::{self, ScaleFilter, ScaleOptions},
+};
+
+use common::{buffer, gray};
+
+mod common;
+
+const FILTERS: [ScaleFilter; 5] = [
+    ScaleFilter::Nearest,
+    ScaleFilter::Bilinear,
+    ScaleFilter::Bicubic,
+    ScaleFilter::Lanczos3,
+    ScaleFilter::Area,
+];
+
+fn scaled_gray(
+    src: &SoftwarePixelBuffer<'_>,
+    width: usize,
+    height: usize,
+    filter: ScaleFilter,
+) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+    let mut dst = buffer(width, height, PixelFormat::OneComponent8)?;
+    let options = ScaleOptions {
+        filter,
+        ..Default::default()
+    };
+    scale::scale_with_options(src, &mut dst, &options)?;
+    let guard = dst.lock()?;
+    let plane = guard.plane(0)?;
+    Ok((0..height)
+        .map(|y| plane.row(y)[..width].to_vec())
+        .collect())
+}
+
+#[test]
+fn test_constant_color_is_preserved() -> Result<(), Box<dyn Error>> {
+    let mut src = buffer(8, 6, PixelFormat::BGRA32)?;
+    {
+        let mut guard = src.lock_mut()?;
+        let mut plane = guard.plane_mut(0)?;
+        for row in plane.rows_mut() {
+            for pixel in row[..32].chunks_exact_mut(4) {
+                pixel.copy_from_slice(&[10, 120, 230, 255]);
+            }
+        }
+    }
+    for filter in FILTERS {
+        for (width, height) in [(3, 2), (16, 13), (8, 6)] {
+            let mut dst = buffer(width, height, PixelFormat::BGRA32)?;
+            let options = ScaleOptions {
+                filter,
+                ..Default::default()
+            };
+            scale::scale_with_options(&src, &mut dst, &options)?;
+            let guard = dst.lock()?;
+            let plane = guard.plane(0)?;
+            for y in 0..height {
+                for pixel in plane.row(y)[..width * 4].chunks_exact(4) {
+                    assert_eq!(pixel, [10, 120, 230, 255], "{filter:?}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_identity() -> Result<(), Box<dyn Error>> {
+    let src = gray(4, &[&[0, 255, 10, 200], &[90, 30, 255, 0]])?;
+    for filter in FILTERS {
+        assert_eq!(
+            scaled_gray(&src, 4, 2, filter)?,
+            [[0, 255, 10, 200], [90, 30, 255, 0]],
+            "{filter:?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_upscale() -> Result<(), Box<dyn Error>> {
+    let src = gray(2, &[&[0, 255]])?;
+    assert_eq!(
+        scaled_gray(&src, 4, 1, ScaleFilter::Nearest)?,
+        [[0, 0, 255, 255]]
+    );
+    assert_eq!(
+        scaled_gray(&src, 4, 1, ScaleFilter::Bilinear)?,
+        [[0, 64, 191, 255]]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_area_keeps_thin_lines() -> Result<(), Box<dyn Error>> {
+    // A one pixel wide vertical line, as in small text.
+    let row: &[u8] = &[0, 255, 0, 0, 0, 0, 0, 0];
+    let src = gray(8, &[row; 8])?;
+    assert_eq!(
+        scaled_gray(&src, 2, 2, ScaleFilter::Area)?,
+        [[64, 0], [64, 0]]
+    );
+    assert_eq!(
+        scaled_gray(&src, 2, 2, ScaleFilter::Nearest)?,
+        [[0, 0], [0, 0]]
+    );
+    let bilinear = scaled_gray(&src, 2, 2, ScaleFilter::Bilinear)?;
+    assert!(bilinear[0][0] > 0);
+    Ok(())
+}
+
+#[test]
+fn test_16_bit_big_endian() -> Result<(), Box<dyn Error>> {
+    let mut src = buffer(2, 1, PixelFormat::Gray16)?;
+    {
+        let mut guard = src.lock_mut()?;
+        guard.plane_mut(0)?.row_mut(0)[..4].copy_from_slice(&[0x00, 0x00, 0xff, 0xff]);
+    }
+    let mut dst = buffer(1, 1, PixelFormat::Gray16)?;
+    let options = ScaleOptions {
+        filter: ScaleFilter::Area,
+        ..Default::default()
+    };
+    scale::scale_with_options(&src, &mut dst, &options)?;
+    let guard = dst.lock()?;
+    assert_eq!(guard.plane(0)?.row(0)[..2], [0x80, 0x00]);
+    Ok(())
+}
+
+#[test]
+fn test_chroma_siting() -> Result<(), Box<dyn Error>> {
+    let mut image = PlanarYCbCr16::new(4, 2, ChromaFormat::Yuv420, 8)?;
+    image.y.fill(128);
+    image.cb = vec![0, 200];
+    image.cr = vec![100, 100];
+    let cb = |siting: ChromaSiting| -> Result<u16, Box<dyn Error>> {
+        let options = ScaleOptions {
+            filter: ScaleFilter::Bilinear,
+            chroma_siting: Some(siting),
+        };
+        let scaled = scale::scale_image(&image, 2, 2, &options)?;
+        assert_eq!(scaled.y, [128; 4]);
+        assert_eq!(scaled.cr, [100]);
+        Ok(scaled.cb[0])
+    };
+    // Co-sited chroma at luma 0 and 2 weighs the first sample more for the output sample at
+    // luma 0.5.
+    assert_eq!(cb(ChromaSiting::Center)?, 100);
+    assert_eq!(cb(ChromaSiting::Left)?, 75);
+    Ok(())
+}
+
+#[test]
+fn test_ycbcr_buffers() -> Result<(), Box<dyn Error>> {
+    let mut image = PlanarYCbCr16::new(6, 4, ChromaFormat::Yuv420, 10)?;
+    image.y.fill(940);
+    image.cb.fill(300);
+    image.cr.fill(700);
+    let mut src = buffer(6, 4, PixelFormat::YCbCr420_10BiPlanarVideoRange)?;
+    convert::pack(&image, &mut src)?;
+    src.set_attachment(
+        &ImageBufferAttachment::TransferFunction(TransferFunction::SmpteSt2084Pq),
+        CVAttachmentMode::ShouldPropagate,
+    );
+    let mut dst = buffer(3, 2, PixelFormat::YCbCr420_10BiPlanarVideoRange)?;
+    scale::scale(&src, &mut dst)?;
+    let scaled = convert::unpack(&dst)?;
+    assert_eq!(scaled.y, [940; 6]);
+    assert_eq!(scaled.cb, [300; 2]);
+    assert_eq!(scaled.cr, [700; 2]);
+    assert_eq!(
+        dst.get_attachment(ImageBufferAttachmentKey::TransferFunction),
+        Some((
+            ImageBufferAttachment::TransferFunction(TransferFunction::SmpteSt2084Pq),
+            CVAttachmentMode::ShouldPropagate
+        ))
+    );
+
+    let mut v210 = buffer(12, 2, PixelFormat::YCbCr422_10)?;
+    let mut v210_small = buffer(6, 1, PixelFormat::YCbCr422_10)?;
+    scale::scale(&v210, &mut v210_small)?;
+    scale::scale(&v210_small, &mut v210)?;
+    Ok(())
+}
+
+#[test]
+fn test_errors() -> Result<(), Box<dyn Error>> {
+    let src = buffer(4, 4, PixelFormat::BGRA32)?;
+    let mut rgba = buffer(2, 2, PixelFormat::RGBA32)?;
+    assert!(matches!(
+        scale::scale(&src, &mut rgba),
+        Err(CVPixelBufferError::InvalidPixelFormat)
+    ));
+    let bayer = buffer(4, 4, PixelFormat::Bayer14RGGB)?;
+    let mut bayer_small = buffer(2, 2, PixelFormat::Bayer14RGGB)?;
+    assert!(matches!(
+        scale::scale(&bayer, &mut bayer_small),
+        Err(CVPixelBufferError::InvalidPixelFormat)
+    ));
+    Ok(())
+}
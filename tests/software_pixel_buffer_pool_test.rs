@@ -0,0 +1,187 @@
+use std::{error::Error, thread, time::Duration};
+
+use core_video_rs::{
+    cv_pixel_buffer::{
+        attributes::{PixelBufferAttribute, PixelBufferAttributes, PixelFormatValue},
+        error::CVPixelBufferError,
+        pixel_format::PixelFormat,
+        PixelBufferLike,
+    },
+    cv_pixel_buffer_pool::{
+        attributes::{
+            PixelBufferPoolAttribute, PixelBufferPoolAttributes, PixelBufferPoolAuxAttribute,
+            PixelBufferPoolAuxAttributes,
+        },
+        CVPixelBufferPoolFlushFlags, PixelBufferPoolLike, SoftwarePixelBufferPool,
+    },
+    dictionary::{Dictionary, DictionaryValue},
+};
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 10;
+
+fn pixel_buffer_attributes() -> PixelBufferAttributes {
+    PixelBufferAttributes::new(&[
+        PixelBufferAttribute::Width(WIDTH),
+        PixelBufferAttribute::Height(HEIGHT),
+        PixelBufferAttribute::PixelFormat(PixelFormatValue::Single(
+            PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        )),
+    ])
+}
+
+#[test]
+fn test_create_pixel_buffer() -> Result<(), Box<dyn Error>> {
+    let pool = SoftwarePixelBufferPool::create(
+        PixelBufferPoolAttributes::default(),
+        pixel_buffer_attributes(),
+    )?;
+    let buffer = pool.create_pixel_buffer()?;
+    assert_eq!(buffer.get_width(), WIDTH);
+    assert_eq!(buffer.get_height(), HEIGHT);
+    assert_eq!(
+        buffer.get_pixel_format()?,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange
+    );
+    assert_eq!(buffer.get_plane_count(), 2);
+    assert_eq!(pool.in_use_count(), 1);
+    drop(buffer);
+    assert_eq!(pool.in_use_count(), 0);
+    assert_eq!(pool.free_count(), 1);
+    assert_eq!(pool.get_pixel_buffer_attributes().as_slice().len(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_buffers_are_recycled() -> Result<(), Box<dyn Error>> {
+    let pool = SoftwarePixelBufferPool::create(
+        PixelBufferPoolAttributes::default(),
+        pixel_buffer_attributes(),
+    )?;
+    {
+        let mut buffer = pool.create_pixel_buffer()?;
+        buffer.lock_mut()?.fill(42);
+    }
+    let buffer = pool.create_pixel_buffer()?;
+    assert_eq!(pool.free_count(), 0);
+    assert!(buffer.lock()?.iter().all(|&b| b == 42));
+    Ok(())
+}
+
+#[test]
+fn test_missing_attributes() {
+    let result = SoftwarePixelBufferPool::create(
+        PixelBufferPoolAttributes::default(),
+        PixelBufferAttributes::new(&[PixelBufferAttribute::Width(WIDTH)]),
+    );
+    assert!(matches!(
+        result,
+        Err(CVPixelBufferError::InvalidPixelBufferAttributes)
+    ));
+}
+
+#[test]
+fn test_allocation_threshold() -> Result<(), Box<dyn Error>> {
+    let pool = SoftwarePixelBufferPool::create(
+        PixelBufferPoolAttributes::default(),
+        pixel_buffer_attributes(),
+    )?;
+    let aux_attributes =
+        PixelBufferPoolAuxAttributes::new(&[PixelBufferPoolAuxAttribute::AllocationThreshold(2)]);
+    let first = pool.create_pixel_buffer_with_aux_attributes(aux_attributes.clone())?;
+    let _second = pool.create_pixel_buffer_with_aux_attributes(aux_attributes.clone())?;
+    let result = pool.create_pixel_buffer_with_aux_attributes(aux_attributes.clone());
+    assert!(matches!(
+        result,
+        Err(CVPixelBufferError::WouldExceedAllocationThreshold)
+    ));
+    drop(first);
+    pool.create_pixel_buffer_with_aux_attributes(aux_attributes)?;
+    Ok(())
+}
+
+#[test]
+fn test_flush() -> Result<(), Box<dyn Error>> {
+    let pool = SoftwarePixelBufferPool::create(
+        PixelBufferPoolAttributes::new(&[
+            PixelBufferPoolAttribute::MinimumBufferCount(1),
+            PixelBufferPoolAttribute::MaximumBufferAge(Duration::from_millis(10)),
+        ]),
+        pixel_buffer_attributes(),
+    )?;
+    let buffers = (0..3)
+        .map(|_| pool.create_pixel_buffer())
+        .collect::<Result<Vec<_>, _>>()?;
+    drop(buffers);
+    assert_eq!(pool.free_count(), 3);
+    pool.flush(CVPixelBufferPoolFlushFlags::AgedBuffers);
+    assert_eq!(pool.free_count(), 3);
+    thread::sleep(Duration::from_millis(20));
+    pool.flush(CVPixelBufferPoolFlushFlags::AgedBuffers);
+    assert_eq!(pool.free_count(), 1);
+
+    let _buffer = pool.create_pixel_buffer()?;
+    pool.create_pixel_buffer()?;
+    pool.flush(CVPixelBufferPoolFlushFlags::ExcessBuffers);
+    assert_eq!(pool.free_count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_buffers_outlive_pool() -> Result<(), Box<dyn Error>> {
+    let pool = SoftwarePixelBufferPool::create(
+        PixelBufferPoolAttributes::default(),
+        pixel_buffer_attributes(),
+    )?;
+    let buffer = pool.create_pixel_buffer()?;
+    drop(pool);
+    assert_eq!(buffer.get_width(), WIDTH);
+    Ok(())
+}
+
+fn first_frame_size<P: PixelBufferPoolLike>(
+    pool: &P,
+) -> Result<(usize, usize), CVPixelBufferError> {
+    let buffer = pool.create_pixel_buffer()?;
+    Ok((buffer.get_width(), buffer.get_height()))
+}
+
+#[test]
+fn test_generic_over_pool_backend() -> Result<(), Box<dyn Error>> {
+    let pool = SoftwarePixelBufferPool::create(
+        PixelBufferPoolAttributes::new(&[PixelBufferPoolAttribute::MinimumBufferCount(2)]),
+        pixel_buffer_attributes(),
+    )?;
+    assert_eq!(first_frame_size(&pool)?, (WIDTH, HEIGHT));
+    let attributes = PixelBufferPoolLike::get_attributes(&pool)?;
+    assert_eq!(attributes.minimum_buffer_count(), Some(2));
+    Ok(())
+}
+
+#[test]
+fn test_pool_attributes_from_dictionary() -> Result<(), CVPixelBufferError> {
+    let attributes = PixelBufferPoolAttributes::from_dictionary(&Dictionary::from([
+        ("MinimumBufferCount".to_string(), 3i64.into()),
+        ("MaximumBufferAge".to_string(), 0.5.into()),
+        ("Other".to_string(), true.into()),
+    ]))?;
+    assert_eq!(attributes.minimum_buffer_count(), Some(3));
+    assert_eq!(
+        attributes.maximum_buffer_age(),
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(attributes.as_slice().len(), 2);
+    for (key, value) in [
+        ("MinimumBufferCount", DictionaryValue::from(-1i64)),
+        ("MaximumBufferAge", DictionaryValue::from("old")),
+    ] {
+        assert!(matches!(
+            PixelBufferPoolAttributes::from_dictionary(&Dictionary::from([(
+                key.to_string(),
+                value
+            )])),
+            Err(CVPixelBufferError::InvalidPoolAttributes)
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use std::{
+    error::Error,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use core_video_rs::cv_pixel_buffer::{
+    attributes::{PixelBufferAttribute, PixelBufferAttributes},
+    error::CVPixelBufferError,
+    pixel_format::PixelFormat,
+    planar_data::PlanarDataPointer,
+    PixelBufferLike, SoftwarePixelBuffer,
+};
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 10;
+
+fn fill_and_sum<B: PixelBufferLike>(buffer: &mut B, value: u8) -> Result<usize, Box<dyn Error>> {
+    {
+        let mut guard = buffer.lock_mut()?;
+        for mut plane in guard.planes_mut()? {
+            plane.as_mut_slice().fill(value);
+        }
+    }
+    let guard = buffer.lock()?;
+    let mut sum = 0;
+    for plane in guard.planes()? {
+        sum += plane.as_slice().iter().map(|&b| b as usize).sum::<usize>();
+    }
+    Ok(sum)
+}
+
+#[test]
+fn test_create() -> Result<(), Box<dyn Error>> {
+    let buffer = SoftwarePixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )?;
+    assert_eq!(buffer.get_width(), WIDTH);
+    assert_eq!(buffer.get_height(), HEIGHT);
+    assert_eq!(buffer.get_bytes_per_row(), 64);
+    assert_eq!(buffer.get_pixel_format()?, PixelFormat::BGRA32);
+    assert!(!buffer.is_planar());
+    assert_eq!(buffer.get_plane_count(), 0);
+    assert_eq!(buffer.get_width_of_plane(0), 0);
+    Ok(())
+}
+
+#[test]
+fn test_create_bi_planar() -> Result<(), Box<dyn Error>> {
+    let mut buffer = SoftwarePixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        PixelBufferAttributes::default(),
+    )?;
+    assert!(buffer.is_planar());
+    assert_eq!(buffer.get_plane_count(), 2);
+    assert_eq!(buffer.get_width_of_plane(1), 5);
+    assert_eq!(buffer.get_height_of_plane(1), 5);
+    assert_eq!(buffer.get_bytes_per_row_of_plane(1), 64);
+    assert_eq!(fill_and_sum(&mut buffer, 1)?, 64 * HEIGHT + 64 * 5);
+    Ok(())
+}
+
+#[test]
+fn test_bytes_per_row_alignment() -> Result<(), Box<dyn Error>> {
+    let buffer = SoftwarePixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::new(&[PixelBufferAttribute::BytesPerRowAlignment(16)]),
+    )?;
+    assert_eq!(buffer.get_bytes_per_row(), 48);
+
+    let result = SoftwarePixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::new(&[PixelBufferAttribute::BytesPerRowAlignment(24)]),
+    );
+    assert!(matches!(
+        result,
+        Err(CVPixelBufferError::InvalidPixelBufferAttributes)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_create_invalid() {
+    let result = SoftwarePixelBuffer::create(
+        0,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(result, Err(CVPixelBufferError::InvalidSize)));
+
+    let result = SoftwarePixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::LosslessYCbCr420_8BiPlanarVideoRange,
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(CVPixelBufferError::InvalidPixelFormat)
+    ));
+}
+
+#[test]
+fn test_create_with_bytes() -> Result<(), Box<dyn Error>> {
+    let released = Arc::new(AtomicBool::new(false));
+    {
+        let released_cb = released.clone();
+        let buffer = SoftwarePixelBuffer::create_with_bytes_release_cb(
+            WIDTH,
+            HEIGHT,
+            PixelFormat::BGRA32,
+            vec![7; WIDTH * 4 * HEIGHT],
+            WIDTH * 4,
+            move |data| {
+                assert_eq!(data.len(), WIDTH * 4 * HEIGHT);
+                released_cb.store(true, Ordering::SeqCst);
+            },
+            PixelBufferAttributes::default(),
+        )?;
+        assert_eq!(buffer.get_bytes_per_row(), WIDTH * 4);
+        let guard = buffer.lock()?;
+        assert!(guard.iter().all(|&b| b == 7));
+        assert!(!released.load(Ordering::SeqCst));
+    }
+    assert!(released.load(Ordering::SeqCst));
+    Ok(())
+}
+
+#[test]
+fn test_create_with_bytes_invalid() {
+    let result = SoftwarePixelBuffer::create_with_bytes(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        vec![0; WIDTH * 4 * HEIGHT - 1],
+        WIDTH * 4,
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(result, Err(CVPixelBufferError::InvalidSize)));
+
+    let result = SoftwarePixelBuffer::create_with_bytes(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        vec![0; WIDTH * 4 * HEIGHT],
+        WIDTH * 4 - 1,
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(result, Err(CVPixelBufferError::InvalidArgument)));
+
+    let result = SoftwarePixelBuffer::create_with_bytes(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::YCbCr420_8BiPlanarVideoRange,
+        vec![0; WIDTH * HEIGHT * 2],
+        WIDTH,
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(CVPixelBufferError::InvalidPixelFormat)
+    ));
+}
+
+#[test]
+fn test_create_with_planar_bytes() -> Result<(), Box<dyn Error>> {
+    let released = Arc::new(AtomicBool::new(false));
+    {
+        let released_cb = released.clone();
+        let mut buffer = SoftwarePixelBuffer::create_with_planar_bytes_release_cb(
+            WIDTH,
+            HEIGHT,
+            PixelFormat::YCbCr420_8BiPlanarFullRange,
+            PlanarDataPointer::for_format(PixelFormat::YCbCr420_8BiPlanarFullRange, WIDTH, HEIGHT)?,
+            move |data| {
+                assert_eq!(data.base_addresses[0][0], 3);
+                released_cb.store(true, Ordering::SeqCst);
+            },
+            PixelBufferAttributes::default(),
+        )?;
+        assert!(buffer.is_planar());
+        assert_eq!(buffer.get_plane_count(), 2);
+        assert_eq!(fill_and_sum(&mut buffer, 3)?, 3 * (64 * HEIGHT + 64 * 5));
+    }
+    assert!(released.load(Ordering::SeqCst));
+    Ok(())
+}
+
+#[test]
+fn test_create_with_planar_bytes_invalid() -> Result<(), Box<dyn Error>> {
+    let mut data_pointer =
+        PlanarDataPointer::for_format(PixelFormat::YCbCr420_8BiPlanarFullRange, WIDTH, HEIGHT)?;
+    data_pointer.base_addresses[1].truncate(10);
+    let result = SoftwarePixelBuffer::create_with_planar_bytes(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::YCbCr420_8BiPlanarFullRange,
+        data_pointer,
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(result, Err(CVPixelBufferError::InvalidSize)));
+
+    let data_pointer = PlanarDataPointer::for_format(PixelFormat::BGRA32, WIDTH, HEIGHT)?;
+    let result = SoftwarePixelBuffer::create_with_planar_bytes(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::YCbCr420_8BiPlanarFullRange,
+        data_pointer,
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(result, Err(CVPixelBufferError::InvalidArgument)));
+
+    // A plane wider than its stride would let row readers run past each row.
+    let mut data_pointer =
+        PlanarDataPointer::for_format(PixelFormat::YCbCr420_8BiPlanarFullRange, WIDTH, HEIGHT)?;
+    data_pointer.plane_bytes_per_row[0] = WIDTH / 2;
+    let result = SoftwarePixelBuffer::create_with_planar_bytes(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::YCbCr420_8BiPlanarFullRange,
+        data_pointer,
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(result, Err(CVPixelBufferError::InvalidArgument)));
+
+    let mut data_pointer =
+        PlanarDataPointer::for_format(PixelFormat::YCbCr420_8BiPlanarFullRange, WIDTH, HEIGHT)?;
+    data_pointer.plane_width[1] *= 2;
+    let result = SoftwarePixelBuffer::create_with_planar_bytes(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::YCbCr420_8BiPlanarFullRange,
+        data_pointer,
+        PixelBufferAttributes::default(),
+    );
+    assert!(matches!(result, Err(CVPixelBufferError::InvalidSize)));
+    Ok(())
+}
+
+#[test]
+fn test_lock_read_only_shared() -> Result<(), Box<dyn Error>> {
+    let buffer = SoftwarePixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )?;
+    let first = buffer.lock()?;
+    let second = buffer.lock()?;
+    assert_eq!(first.as_slice(), second.as_slice());
+    first.unlock()?;
+    second.unlock()?;
+    Ok(())
+}
+
+#[test]
+fn test_lock_write_read() -> Result<(), Box<dyn Error>> {
+    let mut buffer = SoftwarePixelBuffer::create(
+        WIDTH,
+        HEIGHT,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )?;
+    {
+        let mut guard = buffer.lock_mut()?;
+        guard.fill(123);
+        guard.unlock()?;
+    }
+    let guard = buffer.lock()?;
+    assert_eq!(guard.plane_count(), 1);
+    assert_eq!(guard.len(), 64 * HEIGHT);
+    assert!(guard.iter().all(|&b| b == 123));
+    Ok(())
+}
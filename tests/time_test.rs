@@ -0,0 +1,165 @@
+use std::{mem, time::Duration};
+
+use core_video_rs::{
+    cv_pixel_buffer::error::CVPixelBufferError,
+    types::{
+        CVSMPTETime, CVSMPTETimeFlags, CVSMPTETimeType, CVTime, CVTimeFlags, CVTimeStamp,
+        CVTimeStampFlags,
+    },
+};
+
+#[test]
+fn test_layout_matches_core_video() {
+    assert_eq!(mem::size_of::<CVTime>(), 16);
+    assert_eq!(mem::offset_of!(CVTime, time_scale), 8);
+    assert_eq!(mem::offset_of!(CVTime, flags), 12);
+
+    assert_eq!(mem::size_of::<CVSMPTETime>(), 24);
+    assert_eq!(mem::offset_of!(CVSMPTETime, counter), 4);
+    assert_eq!(mem::offset_of!(CVSMPTETime, time_type), 8);
+    assert_eq!(mem::offset_of!(CVSMPTETime, flags), 12);
+    assert_eq!(mem::offset_of!(CVSMPTETime, hours), 16);
+    assert_eq!(mem::offset_of!(CVSMPTETime, frames), 22);
+
+    assert_eq!(mem::size_of::<CVTimeStamp>(), 80);
+    assert_eq!(mem::align_of::<CVTimeStamp>(), 8);
+    assert_eq!(mem::offset_of!(CVTimeStamp, video_time), 8);
+    assert_eq!(mem::offset_of!(CVTimeStamp, host_time), 16);
+    assert_eq!(mem::offset_of!(CVTimeStamp, rate_scalar), 24);
+    assert_eq!(mem::offset_of!(CVTimeStamp, video_refresh_period), 32);
+    assert_eq!(mem::offset_of!(CVTimeStamp, smpte_time), 40);
+    assert_eq!(mem::offset_of!(CVTimeStamp, flags), 64);
+    assert_eq!(mem::offset_of!(CVTimeStamp, reserved), 72);
+}
+
+#[test]
+fn test_time_conversions() -> Result<(), CVPixelBufferError> {
+    let frame = CVTime::new(1001, 30000);
+    assert_eq!(Duration::try_from(frame)?, Duration::from_nanos(33_366_666));
+    assert_eq!(frame.as_secs_f64(), Some(1001.0 / 30000.0));
+    assert_eq!(
+        CVTime::new(90_000 * 3 + 45_000, 90_000).to_duration(),
+        Some(Duration::from_millis(3500))
+    );
+    assert_eq!(CVTime::new(6000, 600).reduced(), CVTime::new(10, 1));
+    assert_eq!(CVTime::new(1 << 32, 0).reduced(), CVTime::new(1 << 32, 0));
+    assert_eq!(CVTime::new(6, 0).reduced(), CVTime::new(6, 0));
+    assert_eq!(
+        CVTime::new(1 << 31, i32::MIN).reduced(),
+        CVTime::new(1 << 31, i32::MIN)
+    );
+    assert_eq!(
+        CVTime::from_duration(Duration::from_millis(1500), 600),
+        Some(CVTime::new(900, 600))
+    );
+    assert_eq!(CVTime::from_duration(Duration::from_secs(1), 0), None);
+
+    assert!(CVTime::INDEFINITE.is_indefinite());
+    assert_eq!(CVTime::INDEFINITE.to_duration(), None);
+    assert!(Duration::try_from(CVTime::new(-1, 600)).is_err());
+    assert_eq!(CVTime::ZERO.to_duration(), Some(Duration::ZERO));
+    Ok(())
+}
+
+#[test]
+fn test_flags() {
+    let flags = CVTimeStampFlags::VIDEO_TIME_VALID | CVTimeStampFlags::HOST_TIME_VALID;
+    assert_eq!(flags, CVTimeStampFlags::VIDEO_HOST_TIME_VALID);
+    assert!(flags.contains(CVTimeStampFlags::HOST_TIME_VALID));
+    assert!(!flags.intersects(CVTimeStampFlags::IS_INTERLACED));
+    assert_eq!(
+        (flags & !CVTimeStampFlags::HOST_TIME_VALID).bits(),
+        CVTimeStampFlags::VIDEO_TIME_VALID.bits()
+    );
+    assert!(CVTimeFlags::default().is_empty());
+
+    let stamp = CVTimeStamp {
+        video_time_scale: 600,
+        video_time: 1200,
+        host_time: 42,
+        video_refresh_period: 10,
+        flags: flags | CVTimeStampFlags::VIDEO_REFRESH_PERIOD_VALID,
+        ..Default::default()
+    };
+    assert_eq!(stamp.get_video_time(), Some(CVTime::new(1200, 600)));
+    assert_eq!(stamp.get_host_time(), Some(42));
+    assert_eq!(stamp.get_video_refresh_period(), Some(CVTime::new(10, 600)));
+    assert_eq!(stamp.get_rate_scalar(), None);
+    assert_eq!(stamp.get_smpte_time(), None);
+}
+
+#[test]
+fn test_non_drop_timecode() -> Result<(), CVPixelBufferError> {
+    for (time_type, frame_count, timecode) in [
+        (CVSMPTETimeType::Type24, 24 * 3661 + 5, "01:01:01:05"),
+        (CVSMPTETimeType::Type25, 25 * 60 - 1, "00:00:59:24"),
+        (CVSMPTETimeType::Type30, 30 * 600, "00:10:00:00"),
+        (CVSMPTETimeType::Type50, 50 * 3600 + 49, "01:00:00:49"),
+        (CVSMPTETimeType::Type60, 60 * 61 + 7, "00:01:01:07"),
+        (CVSMPTETimeType::Type5994, 60 * 60, "00:01:00:00"),
+    ] {
+        let time = CVSMPTETime::from_frame_count(frame_count, time_type);
+        assert_eq!(time.to_string(), timecode);
+        assert!(time.flags.contains(CVSMPTETimeFlags::VALID));
+        assert_eq!(time.to_frame_count()?, frame_count);
+        assert_eq!(CVSMPTETime::parse(timecode, time_type)?, time);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_drop_frame_timecode() -> Result<(), CVPixelBufferError> {
+    for (time_type, frame_count, timecode) in [
+        (CVSMPTETimeType::Type2997Drop, 1799, "00:00:59;29"),
+        (CVSMPTETimeType::Type2997Drop, 1800, "00:01:00;02"),
+        (CVSMPTETimeType::Type2997Drop, 17982, "00:10:00;00"),
+        (CVSMPTETimeType::Type2997Drop, 107892, "01:00:00;00"),
+        (CVSMPTETimeType::Type5994Drop, 3600, "00:01:00;04"),
+        (CVSMPTETimeType::Type5994Drop, 35964, "00:10:00;00"),
+    ] {
+        let time = CVSMPTETime::from_frame_count(frame_count, time_type);
+        assert_eq!(time.to_string(), timecode);
+        assert_eq!(time.to_frame_count()?, frame_count);
+        assert_eq!(CVSMPTETime::parse(timecode, time_type)?, time);
+    }
+    // Every frame count round-trips through the first two hours.
+    for frame_count in 0..2 * 107892 {
+        let time = CVSMPTETime::from_frame_count(frame_count, CVSMPTETimeType::Type2997Drop);
+        assert_eq!(time.to_frame_count()?, frame_count);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_timecode_wraps_and_rejects_invalid() {
+    let day = 24 * 3600 * 25;
+    assert_eq!(
+        CVSMPTETime::from_frame_count(day + 1, CVSMPTETimeType::Type25).to_string(),
+        "00:00:00:01"
+    );
+    for timecode in [
+        "00:01:00;00",
+        "00:01:00;01",
+        "24:00:00;00",
+        "00:60:00;00",
+        "00:00:00;30",
+        "00:00:00",
+        "00:00:+1:00",
+        "a0:00:00:00",
+    ] {
+        assert!(
+            CVSMPTETime::parse(timecode, CVSMPTETimeType::Type2997Drop).is_err(),
+            "{timecode}"
+        );
+    }
+    assert!(CVSMPTETime::parse("00:01:00:00", CVSMPTETimeType::Type30).is_ok());
+
+    let unknown = CVSMPTETime {
+        time_type: 99,
+        ..Default::default()
+    };
+    assert!(unknown.get_time_type().is_err());
+    assert!(unknown.to_frame_count().is_err());
+    assert_eq!(CVSMPTETimeType::Type2997Drop.frame_rate(), (30000, 1001));
+    assert!(CVSMPTETimeType::Type5994Drop.is_drop_frame());
+}
@@ -0,0 +1,271 @@
+use std::error::Error;
+
+use core_video_rs::{
+    attachments::{
+        CVAttachmentMode, CleanAperture, ImageBufferAttachment, ImageBufferAttachmentKey,
+        PixelAspectRatio, Rational,
+    },
+    constants::ChromaLocation,
+    convert,
+    cv_pixel_buffer::{
+        error::CVPixelBufferError, pixel_format::PixelFormat, PixelBufferView, PixelRect,
+        SoftwarePixelBuffer,
+    },
+    transform::{self, Orientation},
+};
+
+use common::{buffer, gray};
+
+mod common;
+
+fn gray_rows(buffer: &SoftwarePixelBuffer<'_>) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+    let width = buffer.get_width();
+    let guard = buffer.lock()?;
+    let plane = guard.plane(0)?;
+    Ok((0..buffer.get_height())
+        .map(|y| plane.row(y)[..width].to_vec())
+        .collect())
+}
+
+fn transformed(
+    src: &SoftwarePixelBuffer<'_>,
+    orientation: Orientation,
+) -> Result<SoftwarePixelBuffer<'static>, Box<dyn Error>> {
+    let (width, height) = orientation.transformed_size(src.get_width(), src.get_height());
+    let mut dst = buffer(width, height, src.get_pixel_format()?)?;
+    transform::transform(src, &mut dst, orientation)?;
+    Ok(dst)
+}
+
+#[test]
+fn test_all_orientations() -> Result<(), Box<dyn Error>> {
+    let src = gray(3, &[b"abc", b"def"])?;
+    let expected: [(Orientation, &[&[u8]]); 8] = [
+        (Orientation::Up, &[b"abc", b"def"]),
+        (Orientation::UpMirrored, &[b"cba", b"fed"]),
+        (Orientation::Down, &[b"fed", b"cba"]),
+        (Orientation::DownMirrored, &[b"def", b"abc"]),
+        (Orientation::LeftMirrored, &[b"ad", b"be", b"cf"]),
+        (Orientation::Right, &[b"da", b"eb", b"fc"]),
+        (Orientation::RightMirrored, &[b"fc", b"eb", b"da"]),
+        (Orientation::Left, &[b"cf", b"be", b"ad"]),
+    ];
+    for (orientation, rows) in expected {
+        assert_eq!(gray_rows(&transformed(&src, orientation)?)?, rows);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_inverse_round_trips_across_tiles() -> Result<(), Box<dyn Error>> {
+    let (width, height) = (70, 131);
+    let mut src = buffer(width, height, PixelFormat::BGRA32)?;
+    {
+        let mut guard = src.lock_mut()?;
+        let mut plane = guard.plane_mut(0)?;
+        for y in 0..height {
+            for (x, pixel) in plane.row_mut(y)[..width * 4]
+                .chunks_exact_mut(4)
+                .enumerate()
+            {
+                pixel.copy_from_slice(&[x as u8, y as u8, (x ^ y) as u8, 255]);
+            }
+        }
+    }
+    for orientation in Orientation::ALL {
+        let back = transformed(&transformed(&src, orientation)?, orientation.inverse())?;
+        let (src_guard, back_guard) = (src.lock()?, back.lock()?);
+        let (src_plane, back_plane) = (src_guard.plane(0)?, back_guard.plane(0)?);
+        for y in 0..height {
+            assert_eq!(
+                src_plane.row(y)[..width * 4],
+                back_plane.row(y)[..width * 4],
+                "{orientation:?} row {y}"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_biplanar_chroma_is_rotated() -> Result<(), Box<dyn Error>> {
+    let mut src = buffer(4, 2, PixelFormat::YCbCr420_8BiPlanarVideoRange)?;
+    {
+        let mut guard = src.lock_mut()?;
+        guard.plane_mut(0)?.row_mut(0)[..4].copy_from_slice(&[16, 32, 48, 64]);
+        guard.plane_mut(0)?.row_mut(1)[..4].copy_from_slice(&[80, 96, 112, 128]);
+        guard.plane_mut(1)?.row_mut(0)[..4].copy_from_slice(&[1, 2, 3, 4]);
+    }
+    let dst = transformed(&src, Orientation::Right)?;
+    assert_eq!((dst.get_width(), dst.get_height()), (2, 4));
+    let guard = dst.lock()?;
+    let luma = guard.plane(0)?;
+    let rows: Vec<&[u8]> = (0..4).map(|y| &luma.row(y)[..2]).collect();
+    assert_eq!(rows, [[80, 16], [96, 32], [112, 48], [128, 64]]);
+    let chroma = guard.plane(1)?;
+    assert_eq!(chroma.row(0)[..2], [1, 2]);
+    assert_eq!(chroma.row(1)[..2], [3, 4]);
+    Ok(())
+}
+
+#[test]
+fn test_packed_422_is_rotated() -> Result<(), Box<dyn Error>> {
+    let mut src = buffer(4, 2, PixelFormat::YCbCr422_8)?;
+    {
+        let mut guard = src.lock_mut()?;
+        let mut plane = guard.plane_mut(0)?;
+        // 2vuy stores Cb Y0 Cr Y1.
+        plane.row_mut(0)[..8].copy_from_slice(&[128, 20, 128, 40, 128, 60, 128, 80]);
+        plane.row_mut(1)[..8].copy_from_slice(&[128, 100, 128, 120, 128, 140, 128, 160]);
+    }
+    let dst = transformed(&src, Orientation::Left)?;
+    assert_eq!((dst.get_width(), dst.get_height()), (2, 4));
+    let image = convert::unpack(&dst)?;
+    assert_eq!(image.bit_depth, 8);
+    assert_eq!(image.y, [80, 160, 60, 140, 40, 120, 20, 100]);
+    assert!(image.cb.iter().chain(&image.cr).all(|&c| c == 128));
+    Ok(())
+}
+
+#[test]
+fn test_attachments_follow_orientation() -> Result<(), Box<dyn Error>> {
+    let src = gray(4, &[b"abcd", b"efgh"])?;
+    let mode = CVAttachmentMode::ShouldPropagate;
+    src.set_attachment(
+        &ImageBufferAttachment::CleanAperture(CleanAperture {
+            width: Rational::integer(2),
+            height: Rational::integer(1),
+            horizontal_offset: Rational::new(1, 2),
+            vertical_offset: Rational::integer(0),
+        }),
+        mode,
+    );
+    src.set_attachment(
+        &ImageBufferAttachment::PixelAspectRatio(PixelAspectRatio {
+            horizontal_spacing: 4,
+            vertical_spacing: 3,
+        }),
+        mode,
+    );
+    src.set_attachment(
+        &ImageBufferAttachment::ChromaLocationTopField(ChromaLocation::Left),
+        mode,
+    );
+    let dst = transformed(&src, Orientation::Right)?;
+    assert_eq!(
+        dst.get_attachment(ImageBufferAttachmentKey::CleanAperture),
+        Some((
+            ImageBufferAttachment::CleanAperture(CleanAperture {
+                width: Rational::integer(1),
+                height: Rational::integer(2),
+                horizontal_offset: Rational::integer(0),
+                vertical_offset: Rational::new(1, 2),
+            }),
+            mode
+        ))
+    );
+    assert_eq!(
+        dst.get_attachment(ImageBufferAttachmentKey::PixelAspectRatio),
+        Some((
+            ImageBufferAttachment::PixelAspectRatio(PixelAspectRatio {
+                horizontal_spacing: 3,
+                vertical_spacing: 4,
+            }),
+            mode
+        ))
+    );
+    assert_eq!(
+        dst.get_attachment(ImageBufferAttachmentKey::ChromaLocationTopField),
+        Some((
+            ImageBufferAttachment::ChromaLocationTopField(ChromaLocation::Top),
+            mode
+        ))
+    );
+
+    let mirrored = transformed(&src, Orientation::UpMirrored)?;
+    assert_eq!(
+        mirrored.get_attachment(ImageBufferAttachmentKey::ChromaLocationTopField),
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn test_view_attachments_are_relative_to_the_view() -> Result<(), Box<dyn Error>> {
+    let src = buffer(8, 6, PixelFormat::OneComponent8)?;
+    let mode = CVAttachmentMode::ShouldPropagate;
+    // Clean rect (3, 1.5, 4, 2) in buffer coordinates.
+    src.set_attachment(
+        &ImageBufferAttachment::CleanAperture(CleanAperture {
+            width: Rational::integer(4),
+            height: Rational::integer(2),
+            horizontal_offset: Rational::integer(1),
+            vertical_offset: Rational::new(-1, 2),
+        }),
+        mode,
+    );
+    let view = PixelBufferView::new(&src, PixelRect::new(5, 0, 3, 6))?;
+    let mut dst = buffer(3, 6, PixelFormat::OneComponent8)?;
+    transform::transform(&view, &mut dst, Orientation::Down)?;
+    assert_eq!(
+        dst.get_attachment(ImageBufferAttachmentKey::CleanAperture),
+        Some((
+            ImageBufferAttachment::CleanAperture(CleanAperture {
+                width: Rational::integer(2),
+                height: Rational::integer(2),
+                horizontal_offset: Rational::new(1, 2),
+                vertical_offset: Rational::new(1, 2),
+            }),
+            mode
+        ))
+    );
+
+    // The aperture is outside this view, so the one left on `dst` is removed.
+    let view = PixelBufferView::new(&src, PixelRect::new(0, 4, 3, 2))?;
+    let mut dst = buffer(3, 2, PixelFormat::OneComponent8)?;
+    dst.set_attachment(
+        &ImageBufferAttachment::CleanAperture(CleanAperture::centered(2, 2)),
+        mode,
+    );
+    transform::transform(&view, &mut dst, Orientation::Up)?;
+    assert_eq!(
+        dst.get_attachment(ImageBufferAttachmentKey::CleanAperture),
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn test_orientation_values() -> Result<(), Box<dyn Error>> {
+    for orientation in Orientation::ALL {
+        assert_eq!(Orientation::try_from(u32::from(orientation))?, orientation);
+        assert_eq!(orientation.inverse().inverse(), orientation);
+    }
+    assert!(matches!(
+        Orientation::try_from(0),
+        Err(CVPixelBufferError::InvalidArgument)
+    ));
+    assert!(matches!(
+        Orientation::try_from(9),
+        Err(CVPixelBufferError::InvalidArgument)
+    ));
+    assert_eq!(Orientation::Right.transformed_size(4, 2), (2, 4));
+    assert_eq!(Orientation::Down.transformed_size(4, 2), (4, 2));
+    Ok(())
+}
+
+#[test]
+fn test_errors() -> Result<(), Box<dyn Error>> {
+    let src = gray(4, &[b"abcd", b"efgh"])?;
+    let mut wrong_size = buffer(4, 2, PixelFormat::OneComponent8)?;
+    assert!(matches!(
+        transform::transform(&src, &mut wrong_size, Orientation::Left),
+        Err(CVPixelBufferError::InvalidSize)
+    ));
+    let mut wrong_format = buffer(4, 2, PixelFormat::BGRA32)?;
+    assert!(matches!(
+        transform::transform(&src, &mut wrong_format, Orientation::Down),
+        Err(CVPixelBufferError::InvalidPixelFormat)
+    ));
+    Ok(())
+}
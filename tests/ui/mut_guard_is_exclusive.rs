@@ -0,0 +1,17 @@
+use core_video_rs::cv_pixel_buffer::{
+    attributes::PixelBufferAttributes, pixel_format::PixelFormat, SoftwarePixelBuffer,
+};
+
+fn main() {
+    let mut buffer = SoftwarePixelBuffer::create(
+        16,
+        16,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )
+    .unwrap();
+    let mut first = buffer.lock_mut().unwrap();
+    let second = buffer.lock().unwrap();
+    first.fill(0);
+    println!("{}", second[0]);
+}
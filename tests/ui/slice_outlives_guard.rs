@@ -0,0 +1,18 @@
+use core_video_rs::cv_pixel_buffer::{
+    attributes::PixelBufferAttributes, pixel_format::PixelFormat, SoftwarePixelBuffer,
+};
+
+fn main() {
+    let buffer = SoftwarePixelBuffer::create(
+        16,
+        16,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )
+    .unwrap();
+    let slice = {
+        let guard = buffer.lock().unwrap();
+        guard.as_slice()
+    };
+    println!("{}", slice[0]);
+}
@@ -0,0 +1,17 @@
+use core_video_rs::cv_pixel_buffer::{
+    attributes::PixelBufferAttributes, pixel_format::PixelFormat, SoftwarePixelBuffer,
+};
+
+fn main() {
+    let buffer = SoftwarePixelBuffer::create(
+        16,
+        16,
+        PixelFormat::BGRA32,
+        PixelBufferAttributes::default(),
+    )
+    .unwrap();
+    let guard = buffer.lock().unwrap();
+    let plane = guard.plane(0).unwrap();
+    guard.unlock().unwrap();
+    println!("{}", plane.row(0)[0]);
+}